@@ -201,11 +201,11 @@ if args.check:
                 "cargo",
                 "run",
                 "--bin",
-                "qsc_formatter",
+                "qsfmt",
                 "--",
+                "--check",
                 "./library/",
                 "./samples/",
-                "-r",
             ],
             check=True,
             text=True,
@@ -7,7 +7,12 @@ use clap::{crate_version, ArgGroup, Parser, ValueEnum};
 use log::info;
 use miette::{Context, IntoDiagnostic, Report};
 use qsc::hir::PackageId;
-use qsc::{compile::compile, PassContext};
+use qsc::{
+    compile::compile,
+    generate_docs::{generate_docs_for_packages, DocFormat},
+    interpret::{CircuitEntryPoint, Interpreter},
+    PassContext,
+};
 use qsc_codegen::qir::fir_to_qir;
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_frontend::{
@@ -24,8 +29,12 @@ use std::{
     path::{Path, PathBuf},
     process::ExitCode,
     string::String,
+    sync::Arc,
 };
 
+#[cfg(test)]
+mod tests;
+
 #[derive(clap::ValueEnum, Clone, Debug, Default, PartialEq)]
 pub enum Profile {
     /// This is the default profile, which allows all operations.
@@ -87,12 +96,19 @@ struct Cli {
     /// Language features to compile with
     #[arg(short, long)]
     features: Vec<String>,
+
+    /// The number of levels of operation calls to show as groups in an emitted circuit.
+    #[arg(long, value_name = "DEPTH", default_value_t = 0)]
+    circuit_group_depth: usize,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 enum Emit {
     Hir,
     Qir,
+    CircuitSvg,
+    /// A static HTML documentation site for the compiled package, in the `docs` directory.
+    DocsHtml,
 }
 
 fn main() -> miette::Result<ExitCode> {
@@ -102,7 +118,7 @@ fn main() -> miette::Result<ExitCode> {
     let mut dependencies = Vec::new();
     let profile: qsc::target::Profile = cli.profile.unwrap_or_default().into();
     let capabilities = profile.into();
-    let package_type = if cli.emit.contains(&Emit::Qir) {
+    let package_type = if cli.emit.contains(&Emit::Qir) || cli.emit.contains(&Emit::CircuitSvg) {
         PackageType::Exe
     } else {
         PackageType::Lib
@@ -135,8 +151,10 @@ fn main() -> miette::Result<ExitCode> {
         }
     }
 
-    let entry = cli.entry.unwrap_or_default();
-    let sources = SourceMap::new(sources, Some(entry.into()));
+    let entry: Arc<str> = cli.entry.unwrap_or_default().into();
+    // Circuit generation compiles the program again in an interpreter.
+    let circuit_sources = sources.clone();
+    let sources = SourceMap::new(sources, Some(entry.clone()));
     let (unit, errors) = compile(
         &store,
         &dependencies,
@@ -170,6 +188,29 @@ fn main() -> miette::Result<ExitCode> {
                     }
                 }
             }
+            Emit::CircuitSvg => {
+                if errors.is_empty() {
+                    let sources = SourceMap::new(circuit_sources.clone(), Some(entry.clone()));
+                    if let Err(reports) = emit_circuit_svg(
+                        out_dir,
+                        sources,
+                        !cli.nostdlib,
+                        capabilities,
+                        features,
+                        cli.circuit_group_depth,
+                    ) {
+                        for report in reports {
+                            eprintln!("{report:?}");
+                        }
+                        return Ok(ExitCode::FAILURE);
+                    }
+                }
+            }
+            Emit::DocsHtml => {
+                if errors.is_empty() {
+                    emit_docs_html(out_dir, &store, package_id)?;
+                }
+            }
         }
     }
 
@@ -268,3 +309,62 @@ fn emit_qir(
         }
     }
 }
+
+fn emit_circuit_svg(
+    out_dir: &Path,
+    sources: SourceMap,
+    std: bool,
+    capabilities: TargetCapabilityFlags,
+    language_features: LanguageFeatures,
+    group_depth: usize,
+) -> Result<(), Vec<Report>> {
+    let into_reports = |errors: Vec<qsc::interpret::Error>| {
+        errors.into_iter().map(Report::new).collect::<Vec<_>>()
+    };
+    let mut interpreter = Interpreter::new(
+        std,
+        sources,
+        PackageType::Exe,
+        capabilities,
+        language_features,
+    )
+    .map_err(into_reports)?;
+    interpreter.set_circuit_group_depth(group_depth);
+    let circuit = interpreter
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .map_err(into_reports)?;
+
+    let path = out_dir.join("circuit.svg");
+    info!(
+        "Writing circuit output file to: {}",
+        path.to_str().unwrap_or_default()
+    );
+    fs::write(&path, circuit.to_svg())
+        .into_diagnostic()
+        .with_context(|| format!("could not emit circuit file `{}`", path.display()))
+        .map_err(|err| vec![err])
+}
+
+fn emit_docs_html(
+    out_dir: &Path,
+    store: &PackageStore,
+    package_id: PackageId,
+) -> miette::Result<()> {
+    let dir = out_dir.join("docs");
+    info!(
+        "Writing HTML documentation to: {}",
+        dir.to_str().unwrap_or_default()
+    );
+    for (name, _, contents) in generate_docs_for_packages(store, &[package_id], DocFormat::Html) {
+        let path = dir.join(name.as_ref());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .into_diagnostic()
+                .with_context(|| format!("could not create directory `{}`", parent.display()))?;
+        }
+        fs::write(&path, contents.as_ref())
+            .into_diagnostic()
+            .with_context(|| format!("could not emit documentation file `{}`", path.display()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::emit_docs_html;
+use indoc::indoc;
+use qsc::{compile::compile, hir::PackageId};
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_passes::PackageType;
+use std::{fs, path::PathBuf};
+
+/// A directory under the system temp directory that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("qsc-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("temp dir should be created");
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn compile_lib(source: &str) -> (PackageStore, PackageId) {
+    let mut store = PackageStore::new(qsc::compile::core());
+    let capabilities = TargetCapabilityFlags::all();
+    let std = store.insert(qsc::compile::std(&store, capabilities));
+    let sources = SourceMap::new([("Geometry.qs".into(), source.into())], None);
+    let (unit, errors) = compile(
+        &store,
+        &[std],
+        sources,
+        PackageType::Lib,
+        capabilities,
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+    let package_id = store.insert(unit);
+    (store, package_id)
+}
+
+#[test]
+fn docs_html_writes_site_for_package() {
+    let (store, package_id) = compile_lib(indoc! {"
+        namespace Geometry {
+            /// # Summary
+            /// Returns the square of `x`.
+            function Square(x : Double) : Double {
+                x * x
+            }
+        }
+    "});
+    let dir = TempDir::new("docs-html");
+
+    emit_docs_html(&dir.0, &store, package_id).expect("docs should be emitted");
+
+    let docs = dir.0.join("docs");
+    for file in [
+        "index.html",
+        "search.json",
+        "styles.css",
+        "Geometry/index.html",
+        "Geometry/Square.html",
+    ] {
+        assert!(docs.join(file).is_file(), "{file} should be emitted");
+    }
+    let page = fs::read_to_string(docs.join("Geometry/Square.html")).expect("page should be read");
+    assert!(page.contains("<p>Returns the square of <code>x</code>.</p>"));
+    let search = fs::read_to_string(docs.join("search.json")).expect("index should be read");
+    assert!(search.contains("\"url\": \"Geometry/Square.html\""));
+    assert!(
+        !search.contains("Std."),
+        "only the compiled package should be documented"
+    );
+}
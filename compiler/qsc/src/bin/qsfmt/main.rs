@@ -0,0 +1,317 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+allocator::assign_global!();
+
+use clap::{crate_version, Parser};
+use qsc::formatter::{apply_edits, calculate_format_edits_with_options, FormatOptions, TextEdit};
+use qsc_project::Manifest;
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// The number of unchanged lines shown around each change in a diff.
+const CONTEXT_LINES: usize = 3;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Parser)]
+#[command(name = "qsfmt", version = concat!(crate_version!(), " (", env!("QSHARP_GIT_HASH"), ")"))]
+#[command(author, about, next_line_help = true)]
+struct Cli {
+    /// Q# files to format, or directories to search recursively for Q# files.
+    #[arg(required = true, num_args = 1..)]
+    paths: Vec<PathBuf>,
+
+    /// Do not write any files. Print a diff for each file that is not formatted
+    /// and exit with a non-zero status if there are any.
+    #[arg(long)]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    run(&Cli::parse())
+}
+
+fn run(cli: &Cli) -> ExitCode {
+    let mut formatter = FileFormatter::new(cli.check);
+
+    for path in &cli.paths {
+        if path.is_dir() {
+            formatter.format_dir(path);
+        } else if path.is_file() {
+            formatter.format_file(path);
+        } else {
+            eprintln!("error: path {} does not exist", path.display());
+            formatter.failed = true;
+        }
+    }
+
+    if formatter.check {
+        if formatter.unformatted.is_empty() {
+            println!("{} files are correctly formatted.", formatter.file_count);
+        } else {
+            println!(
+                "{} of {} files are not formatted. Run qsfmt without `--check` to format them.",
+                formatter.unformatted.len(),
+                formatter.file_count
+            );
+        }
+    } else {
+        println!(
+            "Formatted {} of {} files.",
+            formatter.unformatted.len(),
+            formatter.file_count
+        );
+    }
+
+    if formatter.failed || (formatter.check && !formatter.unformatted.is_empty()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+struct FileFormatter {
+    check: bool,
+    /// The format options of the project each visited directory belongs to.
+    options: FxHashMap<PathBuf, Option<FormatOptions>>,
+    file_count: usize,
+    unformatted: Vec<PathBuf>,
+    failed: bool,
+}
+
+impl FileFormatter {
+    fn new(check: bool) -> Self {
+        Self {
+            check,
+            options: FxHashMap::default(),
+            file_count: 0,
+            unformatted: Vec::new(),
+            failed: false,
+        }
+    }
+
+    fn format_dir(&mut self, dir: &Path) {
+        let mut entries = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|entry| entry.path())
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                eprintln!("error: could not read directory {}: {e}", dir.display());
+                self.failed = true;
+                return;
+            }
+        };
+        entries.sort();
+        for path in entries {
+            if is_hidden(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                self.format_dir(&path);
+            } else if path.extension().is_some_and(|ext| ext == "qs") {
+                self.format_file(&path);
+            }
+        }
+    }
+
+    fn format_file(&mut self, path: &Path) {
+        let Some(options) = self.options_for(path) else {
+            self.failed = true;
+            return;
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                eprintln!("error: could not read file {}: {e}", path.display());
+                self.failed = true;
+                return;
+            }
+        };
+        self.file_count += 1;
+
+        let edits = calculate_format_edits_with_options(&contents, &options);
+        if edits.is_empty() {
+            return;
+        }
+        self.unformatted.push(path.to_path_buf());
+
+        if self.check {
+            print!("{}", unified_diff(path, &contents, &edits));
+        } else if let Err(e) = fs::write(path, apply_edits(&contents, &edits)) {
+            eprintln!("error: could not write file {}: {e}", path.display());
+            self.failed = true;
+        }
+    }
+
+    /// Returns the format options from the manifest of the project containing `path`,
+    /// or the default options if the file is not part of a project.
+    /// Returns `None` if the manifest could not be loaded.
+    fn options_for(&mut self, path: &Path) -> Option<FormatOptions> {
+        let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        if let Some(options) = self.options.get(&dir) {
+            return options.clone();
+        }
+        let manifest = fs::canonicalize(&dir)
+            .map_err(Into::into)
+            .and_then(Manifest::load_from_path);
+        let options = match manifest {
+            Ok(descriptor) => Some(
+                descriptor
+                    .map(|descriptor| descriptor.manifest.format)
+                    .unwrap_or_default(),
+            ),
+            Err(e) => {
+                eprintln!(
+                    "error: could not load the project manifest for {}: {e}",
+                    path.display()
+                );
+                None
+            }
+        };
+        self.options.insert(dir, options.clone());
+        options
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// A run of original lines, `start..end`, replaced by `new_lines` in the formatted file.
+struct Change<'a> {
+    start: usize,
+    end: usize,
+    new_lines: Vec<String>,
+    old_lines: &'a [&'a str],
+}
+
+/// Renders the formatting `edits` to `code` as a unified diff.
+fn unified_diff(path: &Path, code: &str, edits: &[TextEdit]) -> String {
+    let lines = code.split_inclusive('\n').collect::<Vec<_>>();
+    let mut line_starts = Vec::with_capacity(lines.len() + 1);
+    let mut offset = 0;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    line_starts.push(offset);
+    let line_of = |offset: usize| {
+        line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1)
+            .min(lines.len().saturating_sub(1))
+    };
+
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| edit.span.lo);
+
+    // Expand each edit to the whole lines it touches, merging edits that share lines.
+    let mut regions: Vec<(usize, usize, Vec<&TextEdit>)> = Vec::new();
+    for edit in edits {
+        let lo = edit.span.lo as usize;
+        let hi = edit.span.hi as usize;
+        let first = line_of(lo);
+        let last = if hi > lo { line_of(hi - 1) } else { first };
+        match regions.last_mut() {
+            Some((_, end, region_edits)) if first < *end => {
+                *end = (*end).max(last + 1);
+                region_edits.push(edit);
+            }
+            _ => regions.push((first, last + 1, vec![edit])),
+        }
+    }
+
+    let changes = regions
+        .into_iter()
+        .map(|(start, end, region_edits)| {
+            let region_start = line_starts[start];
+            let mut new_text = code[region_start..line_starts[end]].to_string();
+            for edit in region_edits.iter().rev() {
+                new_text.replace_range(
+                    edit.span.lo as usize - region_start..edit.span.hi as usize - region_start,
+                    &edit.new_text,
+                );
+            }
+            Change {
+                start,
+                end,
+                new_lines: new_text.split_inclusive('\n').map(String::from).collect(),
+                old_lines: &lines[start..end],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut diff = String::new();
+    let _ = writeln!(diff, "--- {}", path.display());
+    let _ = writeln!(diff, "+++ {}", path.display());
+
+    // Changes close enough that their context would overlap share a hunk.
+    let (mut old_changed, mut new_changed) = (0, 0);
+    let mut i = 0;
+    while i < changes.len() {
+        let mut j = i + 1;
+        while j < changes.len() && changes[j].start - changes[j - 1].end <= 2 * CONTEXT_LINES {
+            j += 1;
+        }
+        let hunk = &changes[i..j];
+        let old_start = hunk[0].start.saturating_sub(CONTEXT_LINES);
+        let old_end = (hunk[hunk.len() - 1].end + CONTEXT_LINES).min(lines.len());
+        let old_len = old_end - old_start;
+        let new_len = hunk.iter().fold(old_len, |len, change| {
+            len + change.new_lines.len() - change.old_lines.len()
+        });
+        let new_start = old_start - old_changed + new_changed;
+        let _ = writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            hunk_range(old_start, old_len),
+            hunk_range(new_start, new_len)
+        );
+
+        let mut line = old_start;
+        for change in hunk {
+            for context in &lines[line..change.start] {
+                push_line(&mut diff, ' ', context);
+            }
+            for old in change.old_lines {
+                push_line(&mut diff, '-', old);
+            }
+            for new in &change.new_lines {
+                push_line(&mut diff, '+', new);
+            }
+            line = change.end;
+            old_changed += change.old_lines.len();
+            new_changed += change.new_lines.len();
+        }
+        for context in &lines[line..old_end] {
+            push_line(&mut diff, ' ', context);
+        }
+        i = j;
+    }
+    diff
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+fn push_line(diff: &mut String, prefix: char, line: &str) {
+    diff.push(prefix);
+    diff.push_str(line);
+    if !line.ends_with('\n') {
+        diff.push_str("\n\\ No newline at end of file\n");
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{run, unified_diff, Cli};
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc::formatter::calculate_format_edits;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// A directory under the system temp directory that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("qsfmt-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("temp dir should be created");
+        Self(path)
+    }
+
+    fn write(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.0.join(name);
+        fs::write(&path, contents).expect("file should be written");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn check_diff(code: &str, expect: &Expect) {
+    let edits = calculate_format_edits(code);
+    expect.assert_eq(&unified_diff(Path::new("Main.qs"), code, &edits));
+}
+
+fn cli(paths: &[&Path], check: bool) -> Cli {
+    Cli {
+        paths: paths.iter().map(|path| path.to_path_buf()).collect(),
+        check,
+    }
+}
+
+const UNFORMATTED: &str =
+    "namespace Foo {\n    operation Main() : Unit {\n    let x = 1 ;\n    }\n}\n";
+
+#[test]
+fn diff_single_change_has_context() {
+    check_diff(
+        indoc! {"
+            namespace Foo {
+                function A() : Unit {}
+                function B() : Unit {}
+                function C() : Unit {}
+                function D() : Int { 1+2 }
+                function E() : Unit {}
+                function F() : Unit {}
+                function G() : Unit {}
+                function H() : Unit {}
+            }
+        "},
+        &expect![[r#"
+            --- Main.qs
+            +++ Main.qs
+            @@ -2,7 +2,7 @@
+                 function A() : Unit {}
+                 function B() : Unit {}
+                 function C() : Unit {}
+            -    function D() : Int { 1+2 }
+            +    function D() : Int { 1 + 2 }
+                 function E() : Unit {}
+                 function F() : Unit {}
+                 function G() : Unit {}
+        "#]],
+    );
+}
+
+#[test]
+fn diff_distant_changes_are_separate_hunks() {
+    check_diff(
+        indoc! {"
+            namespace Foo {
+                function A() : Int { 1+2 }
+                function B() : Unit {}
+                function C() : Unit {}
+                function D() : Unit {}
+                function E() : Unit {}
+                function F() : Unit {}
+                function G() : Unit {}
+                function H() : Unit {}
+                function I() : Int { 3+4 }
+            }
+        "},
+        &expect![[r#"
+            --- Main.qs
+            +++ Main.qs
+            @@ -1,5 +1,5 @@
+             namespace Foo {
+            -    function A() : Int { 1+2 }
+            +    function A() : Int { 1 + 2 }
+                 function B() : Unit {}
+                 function C() : Unit {}
+                 function D() : Unit {}
+            @@ -7,5 +7,5 @@
+                 function F() : Unit {}
+                 function G() : Unit {}
+                 function H() : Unit {}
+            -    function I() : Int { 3+4 }
+            +    function I() : Int { 3 + 4 }
+             }
+        "#]],
+    );
+}
+
+#[test]
+fn diff_nearby_changes_share_a_hunk() {
+    check_diff(
+        indoc! {"
+            namespace Foo {
+                function A() : Int { 1+2 }
+                function B() : Unit {}
+                function C() : Int { 3+4 }
+            }
+        "},
+        &expect![[r#"
+            --- Main.qs
+            +++ Main.qs
+            @@ -1,5 +1,5 @@
+             namespace Foo {
+            -    function A() : Int { 1+2 }
+            +    function A() : Int { 1 + 2 }
+                 function B() : Unit {}
+            -    function C() : Int { 3+4 }
+            +    function C() : Int { 3 + 4 }
+             }
+        "#]],
+    );
+}
+
+#[test]
+fn diff_change_without_trailing_newline() {
+    check_diff(
+        "namespace Foo {\n    function A() : Int { 1+2 }\n}",
+        &expect![[r#"
+            --- Main.qs
+            +++ Main.qs
+            @@ -1,3 +1,3 @@
+             namespace Foo {
+            -    function A() : Int { 1+2 }
+            +    function A() : Int { 1 + 2 }
+             }
+            \ No newline at end of file
+        "#]],
+    );
+}
+
+#[test]
+fn check_fails_for_unformatted_file_and_leaves_it_unchanged() {
+    let dir = TempDir::new("check-unformatted");
+    let file = dir.write("Main.qs", UNFORMATTED);
+
+    assert_eq!(run(&cli(&[&file], true)), ExitCode::FAILURE);
+    assert_eq!(
+        fs::read_to_string(&file).expect("file should be read"),
+        UNFORMATTED
+    );
+}
+
+#[test]
+fn check_succeeds_for_formatted_files() {
+    let dir = TempDir::new("check-formatted");
+    dir.write("Main.qs", &qsc::formatter::format_str(UNFORMATTED));
+
+    assert_eq!(run(&cli(&[&dir.0], true)), ExitCode::SUCCESS);
+}
+
+#[test]
+fn check_fails_for_missing_path() {
+    let dir = TempDir::new("check-missing");
+
+    assert_eq!(
+        run(&cli(&[&dir.0.join("Missing.qs")], true)),
+        ExitCode::FAILURE
+    );
+}
+
+#[test]
+fn format_writes_file_and_succeeds() {
+    let dir = TempDir::new("format");
+    let file = dir.write("Main.qs", UNFORMATTED);
+
+    assert_eq!(run(&cli(&[&dir.0], false)), ExitCode::SUCCESS);
+    expect![[r#"
+        namespace Foo {
+            operation Main() : Unit {
+                let x = 1;
+            }
+        }
+    "#]]
+    .assert_eq(&fs::read_to_string(&file).expect("file should be read"));
+    assert_eq!(run(&cli(&[&file], true)), ExitCode::SUCCESS);
+}
+
+#[test]
+fn format_uses_options_from_manifest() {
+    let dir = TempDir::new("manifest");
+    dir.write("qsharp.json", r#"{ "format": { "indentWidth": 2 } }"#);
+    fs::create_dir(dir.0.join("src")).expect("src dir should be created");
+    let file = dir.write("src/Main.qs", UNFORMATTED);
+
+    assert_eq!(run(&cli(&[&dir.0], false)), ExitCode::SUCCESS);
+    expect![[r#"
+        namespace Foo {
+          operation Main() : Unit {
+            let x = 1;
+          }
+        }
+    "#]]
+    .assert_eq(&fs::read_to_string(&file).expect("file should be read"));
+}
+
+#[test]
+fn invalid_manifest_fails() {
+    let dir = TempDir::new("invalid-manifest");
+    dir.write("qsharp.json", "{ not json");
+    let file = dir.write("Main.qs", UNFORMATTED);
+
+    assert_eq!(run(&cli(&[&file], true)), ExitCode::FAILURE);
+}
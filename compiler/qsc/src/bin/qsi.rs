@@ -7,7 +7,10 @@ use clap::{crate_version, Parser};
 use miette::{Context, IntoDiagnostic, Report, Result};
 use num_bigint::BigUint;
 use num_complex::Complex64;
-use qsc::interpret::{self, InterpretResult, Interpreter};
+use qsc::{
+    doctest,
+    interpret::{self, InterpretResult, Interpreter},
+};
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
 use qsc_eval::{
     output::{self, Receiver},
@@ -28,6 +31,7 @@ use std::{
 #[derive(Debug, Parser)]
 #[command(name = "qsi", version = concat!(crate_version!(), " (", env!("QSHARP_GIT_HASH"), ")"))]
 #[command(author, about, next_line_help = true)]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
     /// Use the given file on startup as initial session input.
     #[arg(long = "use")]
@@ -56,6 +60,10 @@ struct Cli {
     /// Compile the given files and interactive snippets in debug mode.
     #[arg(long)]
     debug: bool,
+
+    /// Run the Q# examples in the doc comments of the given files or project, then exit.
+    #[arg(long)]
+    doctest: bool,
 }
 
 struct TerminalReceiver;
@@ -105,6 +113,9 @@ fn main() -> miette::Result<ExitCode> {
             ));
         }
     }
+    if cli.doctest {
+        return Ok(run_doctests(SourceMap::new(sources, None), features));
+    }
     if cli.exec {
         let mut interpreter = match (if cli.debug {
             Interpreter::new_with_debug
@@ -159,6 +170,28 @@ fn main() -> miette::Result<ExitCode> {
     Ok(ExitCode::SUCCESS)
 }
 
+fn run_doctests(sources: SourceMap, features: LanguageFeatures) -> ExitCode {
+    match doctest::run_project_doctests(sources, TargetCapabilityFlags::all(), features) {
+        Ok(failures) if failures.is_empty() => {
+            println!("all doc examples passed");
+            ExitCode::SUCCESS
+        }
+        Ok(failures) => {
+            for failure in &failures {
+                eprintln!("error: {:?}", Report::new(failure.clone()));
+            }
+            eprintln!("{} doc examples failed", failures.len());
+            ExitCode::FAILURE
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("error: {:?}", Report::new(error));
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
 fn repl(interpreter: &mut Interpreter, receiver: &mut impl Receiver) -> io::Result<()> {
     print_prompt(false);
 
@@ -0,0 +1,219 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::interpret::{self, GenericReceiver, Interpreter};
+use miette::Diagnostic;
+use qsc_data_structures::{
+    language_features::LanguageFeatures, span::Span, target::TargetCapabilityFlags,
+};
+use qsc_doc_gen::display::parse_doc_for_examples;
+use qsc_frontend::{
+    compile::{CompileUnit, PackageStore, SourceMap},
+    error::WithSource,
+};
+use qsc_hir::hir::{Item, ItemKind, Package, Visibility};
+use qsc_passes::PackageType;
+use std::{ops::Range, rc::Rc};
+use thiserror::Error;
+
+pub type Error = WithSource<DocTestFailure>;
+
+/// A doctest that failed to compile or run.
+#[derive(Clone, Debug, Diagnostic, Error)]
+#[error("example in the documentation of `{name}` failed")]
+#[diagnostic(code("Qsc.DocTest.Failed"))]
+pub struct DocTestFailure {
+    pub name: String,
+    #[label("example")]
+    pub span: Span,
+    #[related]
+    pub errors: Vec<interpret::Error>,
+}
+
+/// A fenced Q# code block from the `# Example` section of the doc comment of an item.
+#[derive(Clone, Debug)]
+pub struct DocTest {
+    /// The fully qualified name of the documented item.
+    pub name: String,
+    /// The namespace of the documented item, which is opened before the example runs.
+    pub namespace: Rc<str>,
+    /// The code of the example.
+    pub code: String,
+    /// The span of the example within the doc comment.
+    pub span: Span,
+}
+
+/// Collects the doctests from the doc comments of the items in `unit`.
+#[must_use]
+pub fn collect_doctests(unit: &CompileUnit) -> Vec<DocTest> {
+    let mut tests = Vec::new();
+    for (_, item) in &unit.package.items {
+        let Some((namespace, name)) = item_name(&unit.package, item) else {
+            continue;
+        };
+        for example in parse_doc_for_examples(&item.doc) {
+            tests.push(DocTest {
+                name: format!("{namespace}.{name}"),
+                namespace: namespace.clone(),
+                span: example_span(&unit.sources, item.span, &example.lines),
+                code: example.code,
+            });
+        }
+    }
+    tests
+}
+
+/// Runs the doctests of the standard library, returning the ones that failed.
+#[must_use]
+pub fn run_std_doctests(capabilities: TargetCapabilityFlags) -> Vec<Error> {
+    let store = PackageStore::new(crate::compile::core());
+    let std = crate::compile::std(&store, capabilities);
+    run_doctests(
+        &std,
+        SourceMap::default,
+        capabilities,
+        LanguageFeatures::default(),
+    )
+}
+
+/// Runs the doctests of the project made up of `sources`, returning the ones that failed.
+/// Each example runs in a fresh interpreter, with the project and the standard library loaded.
+/// # Errors
+/// If the project does not compile, the compilation errors are returned.
+pub fn run_project_doctests(
+    sources: SourceMap,
+    capabilities: TargetCapabilityFlags,
+    language_features: LanguageFeatures,
+) -> Result<Vec<Error>, Vec<crate::compile::Error>> {
+    let mut store = PackageStore::new(crate::compile::core());
+    let std_id = store.insert(crate::compile::std(&store, capabilities));
+    let (unit, errors) = crate::compile::compile(
+        &store,
+        &[std_id],
+        sources,
+        PackageType::Lib,
+        capabilities,
+        language_features,
+    );
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let project_sources = || {
+        SourceMap::new(
+            unit.sources
+                .iter()
+                .map(|source| (source.name.clone(), source.contents.clone())),
+            None,
+        )
+    };
+    Ok(run_doctests(
+        &unit,
+        project_sources,
+        capabilities,
+        language_features,
+    ))
+}
+
+/// Runs the doctests of `unit`, each in a new interpreter compiled from `sources`.
+fn run_doctests(
+    unit: &CompileUnit,
+    sources: impl Fn() -> SourceMap,
+    capabilities: TargetCapabilityFlags,
+    language_features: LanguageFeatures,
+) -> Vec<Error> {
+    collect_doctests(unit)
+        .into_iter()
+        .filter_map(|test| {
+            run_doctest(&test, sources(), capabilities, language_features)
+                .err()
+                .map(|errors| {
+                    WithSource::from_map(
+                        &unit.sources,
+                        DocTestFailure {
+                            name: test.name,
+                            span: test.span,
+                            errors,
+                        },
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Runs `test` in a new interpreter compiled from `sources` and the standard library.
+/// # Errors
+/// If the interpreter cannot be created or the example fails to compile or run,
+/// the errors are returned.
+pub fn run_doctest(
+    test: &DocTest,
+    sources: SourceMap,
+    capabilities: TargetCapabilityFlags,
+    language_features: LanguageFeatures,
+) -> Result<(), Vec<interpret::Error>> {
+    let mut interpreter = Interpreter::new(
+        true,
+        sources,
+        PackageType::Lib,
+        capabilities,
+        language_features,
+    )?;
+    let mut output = std::io::sink();
+    let mut receiver = GenericReceiver::new(&mut output);
+    interpreter.eval_fragments(
+        &mut receiver,
+        &format!("open {};\n{}", test.namespace, test.code),
+    )?;
+    Ok(())
+}
+
+/// Returns the namespace and name of `item` if it is a documented callable or type.
+fn item_name(package: &Package, item: &Item) -> Option<(Rc<str>, Rc<str>)> {
+    if item.visibility == Visibility::Internal {
+        return None;
+    }
+    let name = match &item.kind {
+        ItemKind::Callable(decl) => decl.name.name.clone(),
+        ItemKind::Ty(ident, _) => ident.name.clone(),
+        ItemKind::Namespace(_, _) => return None,
+    };
+    let parent = package.items.get(item.parent?)?;
+    match &parent.kind {
+        ItemKind::Namespace(namespace, _) => Some((namespace.name(), name)),
+        _ => None,
+    }
+}
+
+/// Returns the span of the doc comment `lines` of the item at `item_span`.
+/// Item spans start at their doc comments, which have one `///` comment per doc line.
+fn example_span(sources: &SourceMap, item_span: Span, lines: &Range<usize>) -> Span {
+    let Some(source) = sources.find_by_offset(item_span.lo) else {
+        return item_span;
+    };
+    let start = (item_span.lo - source.offset) as usize;
+    let end = (item_span.hi - source.offset) as usize;
+    let mut offset = item_span.lo;
+    let mut span = None::<Span>;
+    for (index, line) in source.contents[start..end]
+        .split_inclusive('\n')
+        .enumerate()
+    {
+        let comment = line.trim_start();
+        if !comment.starts_with("///") || index >= lines.end {
+            break;
+        }
+        if index >= lines.start {
+            let lo = offset + u32::try_from(line.len() - comment.len()).unwrap_or_default();
+            let hi = lo + u32::try_from(comment.trim_end().len()).unwrap_or_default();
+            span = Some(span.map_or(Span { lo, hi }, |span| Span { lo: span.lo, hi }));
+        }
+        offset += u32::try_from(line.len()).unwrap_or_default();
+    }
+    span.unwrap_or(Span {
+        lo: item_span.lo,
+        hi: item_span.lo,
+    })
+}
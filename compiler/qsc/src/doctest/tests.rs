@@ -0,0 +1,168 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{collect_doctests, run_project_doctests};
+use crate::compile;
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{PackageStore, SourceMap};
+use qsc_passes::PackageType;
+
+fn check_collected(source: &str, expect: &Expect) {
+    let mut store = PackageStore::new(compile::core());
+    let std_id = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+    let (unit, errors) = compile::compile(
+        &store,
+        &[std_id],
+        sources,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+
+    let tests = collect_doctests(&unit)
+        .into_iter()
+        .map(|test| {
+            let span = &source[test.span.lo as usize..test.span.hi as usize];
+            format!("{}:\n{}span: {span}", test.name, test.code)
+        })
+        .collect::<Vec<_>>();
+    expect.assert_eq(&tests.join("\n\n"));
+}
+
+fn check_failures(source: &str, expect: &Expect) {
+    let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+    let failures = run_project_doctests(
+        sources,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    )
+    .expect("project should compile");
+    let failures = failures
+        .iter()
+        .map(|failure| {
+            let failure = failure.error();
+            let errors = failure
+                .errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            format!("{} {}: {errors:?}", failure.name, failure.span)
+        })
+        .collect::<Vec<_>>();
+    expect.assert_debug_eq(&failures);
+}
+
+#[test]
+fn examples_are_collected_with_doc_spans() {
+    check_collected(
+        indoc! {r#"
+            namespace Test {
+                /// # Summary
+                /// Adds one.
+                ///
+                /// # Example
+                /// ```qsharp
+                /// let two = AddOne(1);
+                /// Message($"{two}");
+                /// ```
+                function AddOne(x : Int) : Int { x + 1 }
+            }
+        "#},
+        &expect![[r#"
+            Test.AddOne:
+            let two = AddOne(1);
+            Message($"{two}");
+            span: /// let two = AddOne(1);
+                /// Message($"{two}");"#]],
+    );
+}
+
+#[test]
+fn code_outside_example_sections_is_not_collected() {
+    check_collected(
+        indoc! {r#"
+            namespace Test {
+                /// # Summary
+                /// ```qsharp
+                /// let x = AddOne(1);
+                /// ```
+                ///
+                /// # Example
+                /// ```python
+                /// # Example
+                /// print(1)
+                /// ```
+                /// ```qsharp
+                /// let y = AddOne(2);
+                /// ```
+                function AddOne(x : Int) : Int { x + 1 }
+
+                /// # Example
+                /// ```qsharp
+                /// Hidden();
+                /// ```
+                internal function Hidden() : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            Test.AddOne:
+            let y = AddOne(2);
+            span: /// let y = AddOne(2);"#]],
+    );
+}
+
+#[test]
+fn passing_examples_report_no_failures() {
+    check_failures(
+        indoc! {r#"
+            namespace Test {
+                /// # Example
+                /// ```qsharp
+                /// use q = Qubit();
+                /// Flip(q);
+                /// if M(q) != One { fail "expected One"; }
+                /// Reset(q);
+                /// ```
+                operation Flip(q : Qubit) : Unit { X(q); }
+            }
+        "#},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn failing_examples_report_errors_at_doc_span() {
+    check_failures(
+        indoc! {r#"
+            namespace Test {
+                /// # Example
+                /// ```qsharp
+                /// let x = AddOne(true);
+                /// ```
+                function AddOne(x : Int) : Int { x + 1 }
+
+                /// # Example
+                /// ```qsharp
+                /// Check(1);
+                /// ```
+                function Check(x : Int) : Unit {
+                    if x != 0 { fail "x must be zero"; }
+                }
+            }
+        "#},
+        &expect![[r#"
+            [
+                "Test.AddOne [57-82]: [\"type error\"]",
+                "Test.Check [181-194]: [\"runtime error\"]",
+            ]
+        "#]],
+    );
+}
@@ -8,6 +8,14 @@ use thiserror::Error;
 
 pub use qsc_frontend::error::WithSource;
 
+/// Returns the documentation link for `error`. Only diagnostics whose error
+/// code is documented on a page of its own, such as the capability errors
+/// reported for the target profile, specify a URL.
+#[must_use]
+pub fn docs_url(error: &dyn Diagnostic) -> Option<String> {
+    error.url().map(|url| url.to_string())
+}
+
 #[derive(Clone, Debug, Error)]
 pub struct WithStack<E> {
     error: E,
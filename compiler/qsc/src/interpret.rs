@@ -37,10 +37,10 @@ use miette::Diagnostic;
 use num_bigint::BigUint;
 use num_complex::Complex;
 use qsc_circuit::{
-    operations::entry_expr_for_qubit_operation, Builder as CircuitBuilder, Circuit,
-    Config as CircuitConfig,
+    operations::entry_expr_for_qubit_operation, rir_to_circuit::rir_to_circuit,
+    Builder as CircuitBuilder, Circuit, Config as CircuitConfig,
 };
-use qsc_codegen::qir::fir_to_qir;
+use qsc_codegen::qir::{fir_to_qir, fir_to_rir};
 use qsc_data_structures::{
     functors::FunctorApp,
     language_features::LanguageFeatures,
@@ -91,6 +91,9 @@ pub enum Error {
     #[error("circuit error")]
     #[diagnostic(transparent)]
     Circuit(#[from] qsc_circuit::Error),
+    #[error("circuit generation error")]
+    #[diagnostic(transparent)]
+    CircuitGeneration(#[from] qsc_circuit::rir_to_circuit::Error),
     #[error("entry point not found")]
     #[diagnostic(code("Qsc.Interpret.NoEntryPoint"))]
     NoEntryPoint,
@@ -101,6 +104,12 @@ pub enum Error {
     #[diagnostic(code("Qsc.Interpret.NotAnOperation"))]
     #[diagnostic(help("provide the name of a callable or a lambda expression"))]
     NotAnOperation,
+    #[error("operation calls cannot be grouped in circuits of programs that branch on measurement results")]
+    #[diagnostic(code("Qsc.Interpret.UnsupportedCircuitGroups"))]
+    #[diagnostic(help(
+        "set the circuit group depth to 0, or simulate the program to generate the circuit"
+    ))]
+    UnsupportedCircuitGroups,
     #[error("partial evaluation error")]
     #[diagnostic(transparent)]
     PartialEvaluation(#[from] WithSource<qsc_partial_eval::Error>),
@@ -135,6 +144,8 @@ pub struct Interpreter {
     /// The classical seed, if any. This needs to be passed to the evaluator for use in intrinsic
     /// calls that produce classical random numbers.
     classical_seed: Option<u64>,
+    /// The number of levels of operation calls shown as groups in generated circuits.
+    circuit_group_depth: usize,
     /// The evaluator environment.
     env: Env,
 }
@@ -233,9 +244,10 @@ impl Interpreter {
             fir_store,
             lowerer: qsc_lowerer::Lowerer::new().with_debug(dbg),
             env: Env::default(),
-            sim: sim_circuit_backend(),
+            sim: sim_circuit_backend(0),
             quantum_seed: None,
             classical_seed: None,
+            circuit_group_depth: 0,
             package,
             source_package: map_hir_package_to_fir(source_package_id),
         })
@@ -269,9 +281,10 @@ impl Interpreter {
             fir_store,
             lowerer: qsc_lowerer::Lowerer::new(),
             env: Env::default(),
-            sim: sim_circuit_backend(),
+            sim: sim_circuit_backend(0),
             quantum_seed: None,
             classical_seed: None,
+            circuit_group_depth: 0,
             package: map_hir_package_to_fir(package_id),
             source_package: map_hir_package_to_fir(source_package_id),
         })
@@ -282,6 +295,14 @@ impl Interpreter {
         self.sim.set_seed(seed);
     }
 
+    /// Sets the number of levels of operation calls, not counting the entry point,
+    /// that are shown as groups in circuits generated with [`Interpreter::circuit`].
+    /// Circuits generated for the Adaptive profile without simulation have no
+    /// operation calls left to group, so they can only be generated with a depth of 0.
+    pub fn set_circuit_group_depth(&mut self, depth: usize) {
+        self.circuit_group_depth = depth;
+    }
+
     pub fn set_classical_seed(&mut self, seed: Option<u64>) {
         self.classical_seed = seed;
     }
@@ -458,21 +479,20 @@ impl Interpreter {
             Some(compute_properties),
             &entry,
         )
-        .map_err(|e| {
-            let hir_package_id = match e.span() {
-                Some(span) => span.package,
-                None => map_fir_package_to_hir(self.package),
-            };
-            let source_package = self
-                .compiler
-                .package_store()
-                .get(hir_package_id)
-                .expect("package should exist in the package store");
-            vec![Error::PartialEvaluation(WithSource::from_map(
-                &source_package.sources,
-                e,
-            ))]
-        })
+        .map_err(|e| vec![self.partial_evaluation_error(e)])
+    }
+
+    fn partial_evaluation_error(&self, e: qsc_partial_eval::Error) -> Error {
+        let hir_package_id = match e.span() {
+            Some(span) => span.package,
+            None => map_fir_package_to_hir(self.package),
+        };
+        let source_package = self
+            .compiler
+            .package_store()
+            .get(hir_package_id)
+            .expect("package should exist in the package store");
+        Error::PartialEvaluation(WithSource::from_map(&source_package.sources, e))
     }
 
     /// Generates a circuit representation for the program.
@@ -486,7 +506,10 @@ impl Interpreter {
     /// If `simulate` is specified, the program is simulated and the resulting
     /// circuit is returned (a.k.a. trace mode). Otherwise, the circuit is generated without
     /// simulation. In this case circuit generation may fail if the program contains dynamic
-    /// behavior (quantum operations that are dependent on measurement results).
+    /// behavior (quantum operations that are dependent on measurement results), unless the
+    /// target supports adaptive execution. For such programs the circuit is then generated
+    /// from the partially evaluated program, and operations that depend on measurement
+    /// results are shown as groups controlled by those results.
     pub fn circuit(
         &mut self,
         entry: CircuitEntryPoint,
@@ -504,7 +527,7 @@ impl Interpreter {
         };
 
         let circuit = if simulate {
-            let mut sim = sim_circuit_backend();
+            let mut sim = sim_circuit_backend(self.circuit_group_depth);
 
             self.run_with_sim_no_output(entry_expr, &mut sim)?;
 
@@ -512,16 +535,63 @@ impl Interpreter {
         } else {
             let mut sim = CircuitBuilder::new(CircuitConfig {
                 base_profile: self.capabilities.is_empty(),
+                group_depth: self.circuit_group_depth,
             });
 
-            self.run_with_sim_no_output(entry_expr, &mut sim)?;
-
-            sim.finish()
+            match self.run_with_sim_no_output(entry_expr.clone(), &mut sim) {
+                Ok(_) => sim.finish(),
+                Err(errors)
+                    if self.capabilities.contains(TargetCapabilityFlags::Adaptive)
+                        && self.capabilities != TargetCapabilityFlags::all()
+                        && errors.iter().any(is_result_comparison) =>
+                {
+                    // The program branches on measurement results, which the target
+                    // can execute, so the circuit comes from the partially evaluated program.
+                    if self.circuit_group_depth > 0 {
+                        return Err(vec![Error::UnsupportedCircuitGroups]);
+                    }
+                    self.rir_circuit(entry_expr)?
+                }
+                Err(errors) => return Err(errors),
+            }
         };
 
         Ok(circuit)
     }
 
+    /// Generates the circuit for the given entry expression, or the entry point if there is none,
+    /// from the partially evaluated program.
+    fn rir_circuit(
+        &mut self,
+        entry_expr: Option<String>,
+    ) -> std::result::Result<Circuit, Vec<Error>> {
+        let (package_id, exec_graph, compute_properties) = if let Some(entry_expr) = entry_expr {
+            let (graph, compute_properties) = self.compile_entry_expr(&entry_expr)?;
+            (self.package, graph.into(), compute_properties)
+        } else {
+            (self.source_package, self.get_entry_exec_graph()?, None)
+        };
+        let package = self.fir_store.get(package_id);
+        let entry = ProgramEntry {
+            exec_graph,
+            expr: (
+                package_id,
+                package
+                    .entry
+                    .expect("package must have an entry expression"),
+            )
+                .into(),
+        };
+        let program = fir_to_rir(
+            &self.fir_store,
+            self.capabilities,
+            compute_properties,
+            &entry,
+        )
+        .map_err(|e| vec![self.partial_evaluation_error(e)])?;
+        rir_to_circuit(&program).map_err(|e| vec![e.into()])
+    }
+
     /// Runs the given entry expression on the given simulator with a new instance of the environment
     /// but using the current compilation.
     pub fn run_with_sim(
@@ -696,7 +766,17 @@ impl Interpreter {
     }
 }
 
-fn sim_circuit_backend() -> BackendChain<SparseSim, CircuitBuilder> {
+/// Whether the error comes from comparing a measurement result while generating a circuit
+/// without simulating the program.
+fn is_result_comparison(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Eval(error)
+            if matches!(error.error().error(), qsc_eval::Error::ResultComparisonUnsupported(_))
+    )
+}
+
+fn sim_circuit_backend(group_depth: usize) -> BackendChain<SparseSim, CircuitBuilder> {
     BackendChain::new(
         SparseSim::new(),
         CircuitBuilder::new(CircuitConfig {
@@ -708,6 +788,7 @@ fn sim_circuit_backend() -> BackendChain<SparseSim, CircuitBuilder> {
             // will still respect the selected profile. This also
             // matches the behavior of the simulator.
             base_profile: false,
+            group_depth,
         }),
     )
 }
@@ -3,7 +3,7 @@
 
 #![allow(clippy::unicode_not_nfc)]
 
-use super::{CircuitEntryPoint, Debugger, Interpreter};
+use super::{CircuitEntryPoint, Debugger, Error, Interpreter};
 use crate::target::Profile;
 use expect_test::expect;
 use miette::Diagnostic;
@@ -11,6 +11,7 @@ use qsc_data_structures::language_features::LanguageFeatures;
 use qsc_eval::output::GenericReceiver;
 use qsc_frontend::compile::SourceMap;
 use qsc_passes::PackageType;
+use std::fmt::Write;
 
 fn interpreter(code: &str, profile: Profile) -> Interpreter {
     let sources = SourceMap::new([("test.qs".into(), code.into())], None);
@@ -942,3 +943,404 @@ mod debugger_stepping {
         .assert_eq(&circs);
     }
 }
+
+/// Formats the operations of a circuit as a tree, one operation per line.
+fn operation_tree(operations: &[crate::circuit::Operation], depth: usize, out: &mut String) {
+    for op in operations {
+        let registers = |regs: &[crate::circuit::Register]| {
+            regs.iter()
+                .map(|reg| match reg.c_id {
+                    Some(c_id) => format!("c{}_{c_id}", reg.q_id),
+                    None => format!("q{}", reg.q_id),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let _ = writeln!(
+            out,
+            "{:indent$}{}{}{} controls: [{}] targets: [{}]",
+            "",
+            op.gate,
+            if op.is_adjoint { "'" } else { "" },
+            op.display_args
+                .as_ref()
+                .map_or(String::new(), |args| format!("({args})")),
+            registers(&op.controls),
+            registers(&op.targets),
+            indent = depth * 4
+        );
+        operation_tree(&op.children, depth + 1, out);
+    }
+}
+
+fn grouped_circuit(code: &str, group_depth: usize) -> String {
+    let mut interpreter = interpreter(code, Profile::Unrestricted);
+    interpreter.set_circuit_group_depth(group_depth);
+    let circ = interpreter
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed");
+    let mut tree = String::new();
+    operation_tree(&circ.operations, 0, &mut tree);
+    tree
+}
+
+const NESTED_CALLS: &str = r"
+    namespace Test {
+        operation PrepareBell(q0 : Qubit, q1 : Qubit) : Unit is Adj + Ctl {
+            H(q0);
+            CNOT(q0, q1);
+        }
+
+        operation PrepareBellPairs(qs : Qubit[], angle : Double) : Unit {
+            PrepareBell(qs[0], qs[1]);
+            PrepareBell(qs[2], qs[3]);
+            Rx(angle, qs[0]);
+        }
+
+        @EntryPoint()
+        operation Main() : Result[] {
+            use qs = Qubit[4];
+            PrepareBellPairs(qs, 1.0);
+            Adjoint PrepareBell(qs[1], qs[2]);
+            Controlled PrepareBell([qs[0]], (qs[2], qs[3]));
+            MResetEachZ(qs)
+        }
+    }
+";
+
+#[test]
+fn calls_are_grouped() {
+    expect![[r"
+        PrepareBellPairs(1.0) controls: [] targets: [q0, q1, q2, q3]
+            PrepareBell controls: [] targets: [q0, q1]
+                H controls: [] targets: [q0]
+                X controls: [q0] targets: [q1]
+            PrepareBell controls: [] targets: [q2, q3]
+                H controls: [] targets: [q2]
+                X controls: [q2] targets: [q3]
+            rx(1.0000) controls: [] targets: [q0]
+        PrepareBell' controls: [] targets: [q1, q2]
+            X controls: [q1] targets: [q2]
+            H controls: [] targets: [q1]
+        PrepareBell controls: [q0] targets: [q2, q3]
+            H controls: [q0] targets: [q2]
+                S controls: [] targets: [q2]
+                H controls: [] targets: [q2]
+                T controls: [] targets: [q2]
+                X controls: [q0] targets: [q2]
+                T' controls: [] targets: [q2]
+                H controls: [] targets: [q2]
+                S' controls: [] targets: [q2]
+            CX controls: [q0, q2] targets: [q3]
+        MResetEachZ controls: [] targets: [q0, q1, q2, q3]
+            MResetZ controls: [] targets: [q0]
+                Measure controls: [q0] targets: [c0_0]
+                |0〉 controls: [] targets: [q0]
+            MResetZ controls: [] targets: [q1]
+                Measure controls: [q1] targets: [c1_0]
+                |0〉 controls: [] targets: [q1]
+            MResetZ controls: [] targets: [q2]
+                Measure controls: [q2] targets: [c2_0]
+                |0〉 controls: [] targets: [q2]
+            MResetZ controls: [] targets: [q3]
+                Measure controls: [q3] targets: [c3_0]
+                |0〉 controls: [] targets: [q3]
+    "]]
+    .assert_eq(&grouped_circuit(NESTED_CALLS, 2));
+}
+
+#[test]
+fn calls_below_group_depth_are_flattened() {
+    expect![[r"
+        PrepareBellPairs(1.0) controls: [] targets: [q0, q1, q2, q3]
+            H controls: [] targets: [q0]
+            X controls: [q0] targets: [q1]
+            H controls: [] targets: [q2]
+            X controls: [q2] targets: [q3]
+            rx(1.0000) controls: [] targets: [q0]
+        PrepareBell' controls: [] targets: [q1, q2]
+            X controls: [q1] targets: [q2]
+            H controls: [] targets: [q1]
+        PrepareBell controls: [q0] targets: [q2, q3]
+            S controls: [] targets: [q2]
+            H controls: [] targets: [q2]
+            T controls: [] targets: [q2]
+            X controls: [q0] targets: [q2]
+            T' controls: [] targets: [q2]
+            H controls: [] targets: [q2]
+            S' controls: [] targets: [q2]
+            CX controls: [q0, q2] targets: [q3]
+        MResetEachZ controls: [] targets: [q0, q1, q2, q3]
+            Measure controls: [q0] targets: [c0_0]
+            |0〉 controls: [] targets: [q0]
+            Measure controls: [q1] targets: [c1_0]
+            |0〉 controls: [] targets: [q1]
+            Measure controls: [q2] targets: [c2_0]
+            |0〉 controls: [] targets: [q2]
+            Measure controls: [q3] targets: [c3_0]
+            |0〉 controls: [] targets: [q3]
+    "]]
+    .assert_eq(&grouped_circuit(NESTED_CALLS, 1));
+}
+
+#[test]
+fn group_depth_zero_is_flat() {
+    expect![[r"
+        H controls: [] targets: [q0]
+        X controls: [q0] targets: [q1]
+        H controls: [] targets: [q2]
+        X controls: [q2] targets: [q3]
+        rx(1.0000) controls: [] targets: [q0]
+        X controls: [q1] targets: [q2]
+        H controls: [] targets: [q1]
+        S controls: [] targets: [q2]
+        H controls: [] targets: [q2]
+        T controls: [] targets: [q2]
+        X controls: [q0] targets: [q2]
+        T' controls: [] targets: [q2]
+        H controls: [] targets: [q2]
+        S' controls: [] targets: [q2]
+        CX controls: [q0, q2] targets: [q3]
+        Measure controls: [q0] targets: [c0_0]
+        |0〉 controls: [] targets: [q0]
+        Measure controls: [q1] targets: [c1_0]
+        |0〉 controls: [] targets: [q1]
+        Measure controls: [q2] targets: [c2_0]
+        |0〉 controls: [] targets: [q2]
+        Measure controls: [q3] targets: [c3_0]
+        |0〉 controls: [] targets: [q3]
+    "]]
+    .assert_eq(&grouped_circuit(NESTED_CALLS, 0));
+}
+
+#[test]
+fn grouped_circuit_display_shows_groups_collapsed() {
+    let mut interpreter = interpreter(NESTED_CALLS, Profile::Unrestricted);
+    interpreter.set_circuit_group_depth(1);
+    let circ = interpreter
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed");
+
+    expect![[r"
+        q_0     PrepareBellPairs(1.0) ───────── ● ── MResetEachZ
+                  ┆             │      ┆
+        q_1     PrepareBellPairs(1.0)  PrepareBell' ───┼─── MResetEachZ
+                  ┆      ┆      │      ┆
+        q_2     PrepareBellPairs(1.0)  PrepareBell'  PrepareBell  MResetEachZ
+                  ┆             │      ┆
+        q_3     PrepareBellPairs(1.0) ─────── PrepareBell  MResetEachZ
+
+    "]]
+    .assert_eq(&circ.to_string());
+}
+
+fn adaptive_circuit(code: &str) -> crate::circuit::Circuit {
+    let mut interpreter = interpreter(code, Profile::AdaptiveRI);
+    interpreter
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed")
+}
+
+#[test]
+fn adaptive_profile_branch_on_measurement() {
+    let circ = adaptive_circuit(
+        r"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Result {
+                    use (q, r) = (Qubit(), Qubit());
+                    H(q);
+                    if M(q) == One {
+                        X(r);
+                    }
+                    M(r)
+                }
+            }
+        ",
+    );
+
+    let mut tree = String::new();
+    operation_tree(&circ.operations, 0, &mut tree);
+    expect![[r"
+        H controls: [] targets: [q0]
+        Measure controls: [q0] targets: [c0_0]
+        if(c0_0 = One) controls: [c0_0] targets: [q1]
+            X controls: [] targets: [q1]
+        Measure controls: [q1] targets: [c1_0]
+    "]]
+    .assert_eq(&tree);
+    expect![[r"
+        q_0    ── H ──── M ────────────────
+                         ╘═════ ● ═════════
+        q_1    ────────────── if(c0_0 = One) ── M ──
+                                       ╘═══
+    "]]
+    .assert_eq(&circ.to_string());
+}
+
+#[test]
+fn adaptive_profile_branch_with_else() {
+    let circ = adaptive_circuit(
+        r"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Result[] {
+                    use qs = Qubit[3];
+                    H(qs[0]);
+                    H(qs[1]);
+                    if M(qs[0]) == Zero and M(qs[1]) == One {
+                        X(qs[2]);
+                    } else {
+                        Z(qs[2]);
+                    }
+                    MResetEachZ(qs)
+                }
+            }
+        ",
+    );
+
+    let mut tree = String::new();
+    operation_tree(&circ.operations, 0, &mut tree);
+    expect![[r"
+        H controls: [] targets: [q0]
+        H controls: [] targets: [q1]
+        Measure controls: [q0] targets: [c0_0]
+        if(c0_0 = Zero) controls: [c0_0] targets: [q1]
+            Measure controls: [q1] targets: [c1_0]
+        if((c0_0 = Zero) and (c1_0 = One)) controls: [c0_0, c1_0] targets: [q2]
+            X controls: [] targets: [q2]
+        if(not ((c0_0 = Zero) and (c1_0 = One))) controls: [c0_0, c1_0] targets: [q2]
+            Z controls: [] targets: [q2]
+        Measure controls: [q0] targets: [c0_1]
+        |0〉 controls: [] targets: [q0]
+        Measure controls: [q1] targets: [c1_1]
+        |0〉 controls: [] targets: [q1]
+        Measure controls: [q2] targets: [c2_0]
+        |0〉 controls: [] targets: [q2]
+    "]]
+    .assert_eq(&tree);
+}
+
+#[test]
+fn adaptive_profile_mutable_set_in_branch() {
+    let circ = adaptive_circuit(
+        r"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Result {
+                    use (q, r) = (Qubit(), Qubit());
+                    H(q);
+                    mutable count = 0;
+                    if M(q) == One {
+                        set count += 1;
+                    }
+                    if count > 0 {
+                        Rx(1.0, r);
+                    }
+                    MResetZ(r)
+                }
+            }
+        ",
+    );
+
+    let mut tree = String::new();
+    operation_tree(&circ.operations, 0, &mut tree);
+    expect![[r"
+        H controls: [] targets: [q0]
+        Measure controls: [q0] targets: [c0_0]
+        if(((c0_0 = One) ? 1 | 0) > 0) controls: [c0_0] targets: [q1]
+            rx(1.0000) controls: [] targets: [q1]
+        Measure controls: [q1] targets: [c1_0]
+        |0〉 controls: [] targets: [q1]
+    "]]
+    .assert_eq(&tree);
+}
+
+#[test]
+fn adaptive_profile_without_result_branches_matches_unrestricted() {
+    let mut adaptive = interpreter(NESTED_CALLS, Profile::AdaptiveRI);
+    adaptive.set_circuit_group_depth(1);
+    let circ = adaptive
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed");
+
+    let mut tree = String::new();
+    operation_tree(&circ.operations, 0, &mut tree);
+    assert_eq!(tree, grouped_circuit(NESTED_CALLS, 1));
+
+    let mut unrestricted = interpreter(NESTED_CALLS, Profile::Unrestricted);
+    let unrestricted_circ = unrestricted
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed");
+    let mut adaptive = interpreter(NESTED_CALLS, Profile::AdaptiveRI);
+    let adaptive_circ = adaptive
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect("circuit generation should succeed");
+    assert_eq!(adaptive_circ.to_string(), unrestricted_circ.to_string());
+}
+
+#[test]
+fn adaptive_profile_group_depth_with_result_branch_is_error() {
+    let mut interpreter = interpreter(
+        r"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Result {
+                    use (q, r) = (Qubit(), Qubit());
+                    H(q);
+                    if M(q) == One {
+                        X(r);
+                    }
+                    M(r)
+                }
+            }
+        ",
+        Profile::AdaptiveRI,
+    );
+    interpreter.set_circuit_group_depth(1);
+    let errors = interpreter
+        .circuit(CircuitEntryPoint::EntryPoint, false)
+        .expect_err("circuit generation should fail");
+
+    assert!(
+        matches!(errors.as_slice(), [Error::UnsupportedCircuitGroups]),
+        "unexpected errors: {errors:?}"
+    );
+}
+
+#[test]
+fn adaptive_profile_operation_entry() {
+    let mut interpreter = interpreter(
+        r"
+            namespace Test {
+                operation Teleport(msg : Qubit, target : Qubit) : Unit {
+                    use here = Qubit();
+                    H(here);
+                    CNOT(here, target);
+                    CNOT(msg, here);
+                    H(msg);
+                    if M(msg) == One { Z(target); }
+                    if M(here) == One { X(target); }
+                    Reset(here);
+                }
+
+                @EntryPoint()
+                operation Main() : Unit {}
+            }
+        ",
+        Profile::AdaptiveRI,
+    );
+
+    let circ = interpreter
+        .circuit(CircuitEntryPoint::Operation("Test.Teleport".into()), false)
+        .expect("circuit generation should succeed");
+
+    expect![[r"
+        q_0    ──────────────── ● ──── H ──── M ───────────────────────
+                                │             ╘═════ ● ════════════════
+        q_1    ───────── X ─────┼───────────────── if(c0_0 = One)  if(c2_0 = One) ───────
+        q_2    ── H ──── ● ──── X ──── M ───────────────────┼──── |0〉 ─
+                                       ╘═══════════════════ ● ═════════
+    "]]
+    .assert_eq(&circ.to_string());
+}
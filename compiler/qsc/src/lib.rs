@@ -3,6 +3,7 @@
 
 pub mod codegen;
 pub mod compile;
+pub mod doctest;
 pub mod error;
 pub mod incremental;
 pub mod interpret;
@@ -21,6 +22,10 @@ pub mod fir {
     pub use qsc_fir::{fir::*, *};
 }
 
+pub mod rir {
+    pub use qsc_rir::{rir::*, *};
+}
+
 pub mod hir {
     pub use qsc_hir::{hir::*, *};
 }
@@ -34,7 +39,8 @@ pub mod project {
 }
 
 pub use qsc_data_structures::{
-    language_features::LanguageFeatures, namespaces::*, span::Span, target::TargetCapabilityFlags,
+    functors::FunctorApp, language_features::LanguageFeatures, namespaces::*, span::Span,
+    target::TargetCapabilityFlags,
 };
 
 pub use qsc_passes::{lower_hir_to_fir, PackageType, PassContext};
@@ -49,11 +55,14 @@ pub use qsc_eval::{
 };
 
 pub mod linter {
-    pub use qsc_linter::{run_lints, LintConfig, LintKind, LintLevel};
+    pub use qsc_linter::{
+        run_lints, run_lints_with_dependencies, run_profile_lints, AstLint, HirLint, LintConfig,
+        LintKind, LintLevel, ProfileLint,
+    };
 }
 
 pub use qsc_doc_gen::{display, generate_docs};
 
 pub mod circuit {
-    pub use qsc_circuit::{operations::*, Circuit, Operation};
+    pub use qsc_circuit::{operations::*, Circuit, Operation, Qubit, Register};
 }
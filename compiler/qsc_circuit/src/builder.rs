@@ -8,7 +8,7 @@ use crate::{
 use num_bigint::BigUint;
 use num_complex::Complex;
 use qsc_codegen::remapper::{HardwareId, Remapper};
-use qsc_data_structures::index_map::IndexMap;
+use qsc_data_structures::{functors::FunctorApp, index_map::IndexMap};
 use qsc_eval::{backend::Backend, val::Value};
 use std::{fmt::Write, mem::take, rc::Rc};
 
@@ -17,6 +17,20 @@ pub struct Builder {
     circuit: Circuit,
     config: Config,
     remapper: Remapper,
+    /// The calls being recorded as groups, innermost last.
+    call_stack: Vec<CallFrame>,
+    /// The number of callables that have been entered and not yet left.
+    call_depth: usize,
+}
+
+/// An operation call being recorded as a group, with the operations applied so far.
+#[derive(Clone)]
+struct CallFrame {
+    name: Rc<str>,
+    functor: FunctorApp,
+    display_args: Option<String>,
+    controls: Vec<Register>,
+    operations: Vec<Operation>,
 }
 
 impl Backend for Builder {
@@ -214,6 +228,48 @@ impl Backend for Builder {
             _ => Some(Ok(Value::unit())),
         }
     }
+
+    fn enter_callable(&mut self, name: &str, functor: FunctorApp, arg: &Value) {
+        let depth = self.call_depth;
+        self.call_depth += 1;
+        if !self.is_group_depth(depth) {
+            return;
+        }
+
+        // Each `Controlled` functor wraps the argument in a tuple with the control qubits.
+        let mut arg = arg.clone();
+        let mut controls = Vec::new();
+        for _ in 0..functor.controlled {
+            let Value::Tuple(items) = &arg else {
+                break;
+            };
+            let [ctls, inner] = &**items else {
+                break;
+            };
+            let (ctls, inner) = (ctls.clone(), inner.clone());
+            let (ctl_qubits, _) = self.split_qubit_args(ctls);
+            controls.extend(ctl_qubits.iter().map(|q| Register::quantum(q.0)));
+            arg = inner;
+        }
+        let (_, classical_args) = self.split_qubit_args(arg);
+
+        self.call_stack.push(CallFrame {
+            name: name.into(),
+            functor,
+            display_args: (!classical_args.is_empty()).then_some(classical_args),
+            controls,
+            operations: Vec::new(),
+        });
+    }
+
+    fn leave_callable(&mut self) {
+        self.call_depth = self.call_depth.saturating_sub(1);
+        if self.is_group_depth(self.call_depth) {
+            if let Some(frame) = self.call_stack.pop() {
+                push_group(&mut self.call_stack, &mut self.circuit, frame);
+            }
+        }
+    }
 }
 
 impl Builder {
@@ -223,18 +279,23 @@ impl Builder {
             circuit: Circuit::default(),
             config,
             remapper: Remapper::default(),
+            call_stack: Vec::new(),
+            call_depth: 0,
         }
     }
 
     #[must_use]
     pub fn snapshot(&self) -> Circuit {
-        let circuit = self.circuit.clone();
+        let mut circuit = self.circuit.clone();
+        close_groups(self.call_stack.clone(), &mut circuit);
         self.finish_circuit(circuit)
     }
 
     #[must_use]
     pub fn finish(mut self) -> Circuit {
-        let circuit = take(&mut self.circuit);
+        let mut circuit = take(&mut self.circuit);
+        // Calls are left open if evaluation stopped early.
+        close_groups(take(&mut self.call_stack), &mut circuit);
         self.finish_circuit(circuit)
     }
 
@@ -243,7 +304,16 @@ impl Builder {
     }
 
     fn push_gate(&mut self, gate: Operation) {
-        self.circuit.operations.push(gate);
+        match self.call_stack.last_mut() {
+            Some(frame) => frame.operations.push(gate),
+            None => self.circuit.operations.push(gate),
+        }
+    }
+
+    /// Whether a call made with `depth` other calls in progress is recorded as a group.
+    /// The outermost call is the entry point of the circuit, so it is never a group.
+    fn is_group_depth(&self, depth: usize) -> bool {
+        (1..=self.config.group_depth).contains(&depth)
     }
 
     fn num_measurements_by_qubit(&self) -> IndexMap<usize, usize> {
@@ -370,8 +440,55 @@ impl Builder {
     }
 }
 
+/// Adds the group recorded in `frame` to the innermost open call, or to the circuit.
+fn push_group(call_stack: &mut [CallFrame], circuit: &mut Circuit, frame: CallFrame) {
+    let Some(group) = group(frame) else {
+        return;
+    };
+    match call_stack.last_mut() {
+        Some(parent) => parent.operations.push(group),
+        None => circuit.operations.push(group),
+    }
+}
+
+/// Closes the calls in `call_stack`, innermost first.
+fn close_groups(mut call_stack: Vec<CallFrame>, circuit: &mut Circuit) {
+    while let Some(frame) = call_stack.pop() {
+        push_group(&mut call_stack, circuit, frame);
+    }
+}
+
+/// Creates a group for the operations applied by a call. Calls that apply no operations
+/// are dropped, and calls that apply a single operation are replaced by that operation.
+fn group(frame: CallFrame) -> Option<Operation> {
+    let mut children = frame.operations;
+    if children.len() <= 1 {
+        return children.pop();
+    }
+
+    let mut targets = children
+        .iter()
+        .flat_map(|child| child.targets.iter().chain(&child.controls))
+        .filter(|reg| reg.c_id.is_none() && !frame.controls.contains(reg))
+        .cloned()
+        .collect::<Vec<_>>();
+    targets.sort_unstable_by_key(|reg| reg.q_id);
+    targets.dedup();
+
+    Some(Operation {
+        gate: frame.name.to_string(),
+        display_args: frame.display_args,
+        is_controlled: !frame.controls.is_empty(),
+        is_adjoint: frame.functor.adjoint,
+        is_measurement: false,
+        controls: frame.controls,
+        targets,
+        children,
+    })
+}
+
 #[allow(clippy::unicode_not_nfc)]
-static KET_ZERO: &str = "|0〉";
+pub(crate) static KET_ZERO: &str = "|0〉";
 
 fn gate<const N: usize>(name: &str, targets: [HardwareId; N]) -> Operation {
     Operation {
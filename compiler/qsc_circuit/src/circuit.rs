@@ -5,18 +5,19 @@
 mod tests;
 
 use rustc_hash::FxHashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, fmt::Write, ops::Not, vec};
 
 /// Representation of a quantum circuit.
 /// Implementation of <https://github.com/microsoft/quantum-viz.js/wiki/API-schema-reference>
-#[derive(Clone, Serialize, Default, Debug, PartialEq)]
+/// Circuits can be read back from the same JSON, for example with `serde_json::from_str`.
+#[derive(Clone, Serialize, Deserialize, Default, Debug, PartialEq)]
 pub struct Circuit {
     pub operations: Vec<Operation>,
     pub qubits: Vec<Qubit>,
 }
 
-#[derive(Clone, Serialize, Debug, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct Operation {
     #[allow(clippy::struct_field_names)]
     pub gate: String,
@@ -24,25 +25,25 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_args: Option<String>,
     #[serde(rename = "isControlled")]
-    #[serde(skip_serializing_if = "Not::not")]
+    #[serde(default, skip_serializing_if = "Not::not")]
     pub is_controlled: bool,
     #[serde(rename = "isAdjoint")]
-    #[serde(skip_serializing_if = "Not::not")]
+    #[serde(default, skip_serializing_if = "Not::not")]
     pub is_adjoint: bool,
     #[serde(rename = "isMeasurement")]
-    #[serde(skip_serializing_if = "Not::not")]
+    #[serde(default, skip_serializing_if = "Not::not")]
     pub is_measurement: bool,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub controls: Vec<Register>,
     pub targets: Vec<Register>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<Operation>,
 }
 
 const QUANTUM_REGISTER: usize = 0;
 const CLASSICAL_REGISTER: usize = 1;
 
-#[derive(Serialize, Debug, Eq, Hash, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, Eq, Hash, PartialEq, Clone)]
 pub struct Register {
     #[serde(rename = "qId")]
     pub q_id: usize,
@@ -70,10 +71,11 @@ impl Register {
     }
 }
 
-#[derive(PartialEq, Clone, Serialize, Debug)]
+#[derive(PartialEq, Clone, Serialize, Deserialize, Debug)]
 pub struct Qubit {
     pub id: usize,
     #[serde(rename = "numChildren")]
+    #[serde(default)]
     pub num_children: usize,
 }
 
@@ -81,6 +83,10 @@ pub struct Qubit {
 pub struct Config {
     /// Perform Base Profile decompositions
     pub base_profile: bool,
+    /// The number of levels of nested operation calls to show as groups,
+    /// not counting the entry point. Calls nested more deeply are shown as
+    /// the gates they apply. Zero produces a flat list of gates.
+    pub group_depth: usize,
 }
 
 type ObjectsByColumn = FxHashMap<usize, String>;
@@ -267,3 +267,88 @@ fn two_targets() {
     "]]
     .assert_eq(&c.to_string());
 }
+
+#[test]
+fn deserialize_quantum_viz_json() {
+    let json = r#"{
+        "qubits": [{ "id": 0, "numChildren": 1 }, { "id": 1 }],
+        "operations": [
+            { "gate": "H", "targets": [{ "qId": 0, "type": 0 }] },
+            {
+                "gate": "rx",
+                "displayArgs": "1.5708",
+                "isAdjoint": true,
+                "isControlled": true,
+                "controls": [{ "qId": 0, "type": 0 }],
+                "targets": [{ "qId": 1, "type": 0 }],
+                "conditionalRender": 1
+            },
+            {
+                "gate": "Measure",
+                "isMeasurement": true,
+                "controls": [{ "qId": 0, "type": 0 }],
+                "targets": [{ "qId": 0, "type": 1, "cId": 0 }]
+            }
+        ]
+    }"#;
+
+    let c: Circuit = serde_json::from_str(json).expect("circuit should deserialize");
+
+    expect![[r"
+        q_0    ── H ──── ● ──── M ──
+                         │      ╘═══
+        q_1    ─────── rx'(1.5708) ───────
+    "]]
+    .assert_eq(&c.to_string());
+}
+
+#[test]
+fn json_round_trip() {
+    let c = Circuit {
+        operations: vec![Operation {
+            gate: "Group".to_string(),
+            display_args: Some("1, 2".to_string()),
+            is_controlled: false,
+            is_adjoint: true,
+            is_measurement: false,
+            controls: vec![],
+            targets: vec![Register::quantum(0), Register::quantum(1)],
+            children: vec![
+                Operation {
+                    gate: "Measure".to_string(),
+                    display_args: None,
+                    is_controlled: false,
+                    is_adjoint: false,
+                    is_measurement: true,
+                    controls: vec![Register::quantum(0)],
+                    targets: vec![Register::classical(0, 0)],
+                    children: vec![],
+                },
+                Operation {
+                    gate: "X".to_string(),
+                    display_args: None,
+                    is_controlled: true,
+                    is_adjoint: false,
+                    is_measurement: false,
+                    controls: vec![Register::classical(0, 0)],
+                    targets: vec![Register::quantum(1)],
+                    children: vec![],
+                },
+            ],
+        }],
+        qubits: vec![
+            Qubit {
+                id: 0,
+                num_children: 1,
+            },
+            Qubit {
+                id: 1,
+                num_children: 0,
+            },
+        ],
+    };
+
+    let json = serde_json::to_string(&c).expect("circuit should serialize");
+    let round_trip: Circuit = serde_json::from_str(&json).expect("circuit should deserialize");
+    assert_eq!(c, round_trip);
+}
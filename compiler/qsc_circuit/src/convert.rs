@@ -0,0 +1,549 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Conversions from a [`Circuit`] to source code that applies the same gates.
+
+#[cfg(test)]
+mod tests;
+
+use crate::circuit::{Circuit, Operation, Register};
+use miette::Diagnostic;
+use qsc_ast::ast::{
+    self, BinOp, Block, CallableBody, CallableDecl, CallableKind, Expr, ExprKind, Functor, Ident,
+    Item, ItemKind, Lit, Mutability, NodeId, Package, Pat, PatKind, Path, Stmt, StmtKind,
+    TopLevelNode, Ty, TyKind, UnOp,
+};
+use qsc_codegen::qsharp::write_package_string;
+use qsc_data_structures::language_features::LanguageFeatures;
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Diagnostic, Error, PartialEq)]
+pub enum Error {
+    #[error("gate `{0}` cannot be converted to {1}")]
+    #[diagnostic(code("Qsc.Circuit.UnsupportedGate"))]
+    UnsupportedGate(String, &'static str),
+    #[error("gate `{0}` has invalid arguments `{1}`")]
+    #[diagnostic(code("Qsc.Circuit.InvalidArgs"))]
+    InvalidArgs(String, String),
+    #[error("gate `{0}` uses a register that is not declared in the circuit")]
+    #[diagnostic(code("Qsc.Circuit.UnknownRegister"))]
+    UnknownRegister(String),
+}
+
+/// OpenQASM definitions for the two-qubit rotations, which are not in `stdgates.inc`.
+const QASM_ROTATIONS: [(&str, &str); 3] = [
+    (
+        "rxx",
+        "gate rxx(theta) a, b { h a; h b; cx a, b; rz(theta) b; cx a, b; h a; h b; }",
+    ),
+    (
+        "ryy",
+        "gate ryy(theta) a, b { rx(pi/2) a; rx(pi/2) b; cx a, b; rz(theta) b; cx a, b; rx(-pi/2) a; rx(-pi/2) b; }",
+    ),
+    (
+        "rzz",
+        "gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }",
+    ),
+];
+
+/// A gate from the circuit builder, with the names used for it in Q# and OpenQASM.
+struct KnownGate {
+    qsharp: &'static str,
+    qasm: &'static str,
+    /// Whether the gate takes a rotation angle from the display arguments.
+    rotation: bool,
+}
+
+fn known_gate(gate: &str) -> Option<KnownGate> {
+    let (qsharp, qasm, rotation) = match gate {
+        "H" => ("H", "h", false),
+        // Doubly controlled X is named `CX` by the circuit builder.
+        "X" | "CX" => ("X", "x", false),
+        "Y" => ("Y", "y", false),
+        "Z" => ("Z", "z", false),
+        "S" => ("S", "s", false),
+        "T" => ("T", "t", false),
+        "SWAP" => ("SWAP", "swap", false),
+        "rx" => ("Rx", "rx", true),
+        "ry" => ("Ry", "ry", true),
+        "rz" => ("Rz", "rz", true),
+        "rxx" => ("Rxx", "rxx", true),
+        "ryy" => ("Ryy", "ryy", true),
+        "rzz" => ("Rzz", "rzz", true),
+        "|0〉" => ("Reset", "reset", false),
+        _ => return None,
+    };
+    Some(KnownGate {
+        qsharp,
+        qasm,
+        rotation,
+    })
+}
+
+/// The qubits and measurement results of a circuit, numbered in declaration order.
+struct Registers {
+    qubits: FxHashMap<usize, usize>,
+    results: FxHashMap<(usize, usize), usize>,
+}
+
+impl Registers {
+    fn new(circuit: &Circuit) -> Self {
+        let mut qubits = FxHashMap::default();
+        let mut results = FxHashMap::default();
+        for (index, qubit) in circuit.qubits.iter().enumerate() {
+            qubits.insert(qubit.id, index);
+            for c_id in 0..qubit.num_children {
+                results.insert((qubit.id, c_id), results.len());
+            }
+        }
+        Self { qubits, results }
+    }
+
+    /// Splits the registers of `op` into quantum controls, classical controls and targets,
+    /// given as qubit and result indices.
+    fn operands(&self, op: &Operation) -> Result<Operands, Error> {
+        let unknown = || Error::UnknownRegister(op.gate.clone());
+        let mut operands = Operands::default();
+        for reg in &op.controls {
+            match reg.c_id {
+                Some(c_id) => operands
+                    .conditions
+                    .push(*self.results.get(&(reg.q_id, c_id)).ok_or_else(unknown)?),
+                None => operands
+                    .controls
+                    .push(*self.qubits.get(&reg.q_id).ok_or_else(unknown)?),
+            }
+        }
+        for reg in &op.targets {
+            operands.targets.push(self.index(reg).ok_or_else(unknown)?);
+        }
+        Ok(operands)
+    }
+
+    fn index(&self, reg: &Register) -> Option<usize> {
+        match reg.c_id {
+            Some(c_id) => self.results.get(&(reg.q_id, c_id)).copied(),
+            None => self.qubits.get(&reg.q_id).copied(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Operands {
+    controls: Vec<usize>,
+    conditions: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+/// Returns the leaf operations of `ops`, replacing each group by its children.
+fn flatten<'a>(ops: &'a [Operation], leaves: &mut Vec<&'a Operation>) {
+    for op in ops {
+        if op.children.is_empty() {
+            leaves.push(op);
+        } else {
+            flatten(&op.children, leaves);
+        }
+    }
+}
+
+impl Circuit {
+    /// Converts the circuit into an OpenQASM 3 program over a qubit register `q`,
+    /// writing measurement results to a bit register `c`.
+    /// Groups are replaced by the gates they contain.
+    /// # Errors
+    /// Returns an error if the circuit contains a gate that has no OpenQASM equivalent.
+    pub fn to_openqasm(&self) -> Result<String, Error> {
+        let registers = Registers::new(self);
+        let mut leaves = Vec::new();
+        flatten(&self.operations, &mut leaves);
+
+        let mut body = String::new();
+        let mut used_gates = Vec::new();
+        for op in leaves {
+            let operands = registers.operands(op)?;
+            let mut indent = "";
+            if !operands.conditions.is_empty() {
+                let condition = operands
+                    .conditions
+                    .iter()
+                    .map(|c| format!("c[{c}] == 1"))
+                    .collect::<Vec<_>>()
+                    .join(" && ");
+                let _ = writeln!(body, "if ({condition}) {{");
+                indent = "    ";
+            }
+            let qubits = |indices: &[usize]| {
+                indices
+                    .iter()
+                    .map(|q| format!("q[{q}]"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            if op.is_measurement {
+                for (qubit, result) in operands.controls.iter().zip(&operands.targets) {
+                    let _ = writeln!(body, "{indent}c[{result}] = measure q[{qubit}];");
+                }
+            } else {
+                let gate = qasm_gate(op, &operands)?;
+                if !used_gates.contains(&gate.name) {
+                    used_gates.push(gate.name);
+                }
+                let mut operands_list = operands.controls.clone();
+                operands_list.extend(&operands.targets);
+                let _ = writeln!(body, "{indent}{gate} {};", qubits(&operands_list));
+            }
+
+            if !operands.conditions.is_empty() {
+                body.push_str("}\n");
+            }
+        }
+
+        let mut program = String::from("OPENQASM 3.0;\ninclude \"stdgates.inc\";\n");
+        for (name, definition) in QASM_ROTATIONS {
+            if used_gates.contains(&name) {
+                let _ = writeln!(program, "{definition}");
+            }
+        }
+        if !registers.qubits.is_empty() {
+            let _ = writeln!(program, "qubit[{}] q;", registers.qubits.len());
+        }
+        if !registers.results.is_empty() {
+            let _ = writeln!(program, "bit[{}] c;", registers.results.len());
+        }
+        program.push('\n');
+        program.push_str(&body);
+        Ok(program)
+    }
+
+    /// Converts the circuit into the source of a Q# operation with the given name,
+    /// which takes the qubits of the circuit as an array and returns its measurement results.
+    /// Groups are replaced by the gates they contain, and gates that are not intrinsic
+    /// are called by name with their display arguments followed by their target qubits.
+    /// # Errors
+    /// Returns an error if the circuit contains a gate whose name is not a Q# identifier,
+    /// or whose display arguments are not Q# expressions.
+    pub fn to_qsharp(&self, operation_name: &str) -> Result<String, Error> {
+        let registers = Registers::new(self);
+        let mut leaves = Vec::new();
+        flatten(&self.operations, &mut leaves);
+
+        let mut results = registers.results.values().copied().collect::<Vec<_>>();
+        results.sort_unstable();
+
+        // Results are declared up front, so that measurements in conditional
+        // blocks are still in scope for later conditions and the output.
+        let mut stmts = results
+            .iter()
+            .map(|&result| {
+                stmt(StmtKind::Local(
+                    Mutability::Mutable,
+                    Box::new(Pat {
+                        kind: Box::new(PatKind::Bind(ident(&result_name(result)), None)),
+                        ..Pat::default()
+                    }),
+                    expr(ExprKind::Lit(Box::new(Lit::Result(ast::Result::Zero)))),
+                ))
+            })
+            .collect::<Vec<_>>();
+        for op in leaves {
+            let operands = registers.operands(op)?;
+            let op_stmts = if op.is_measurement {
+                operands
+                    .controls
+                    .iter()
+                    .zip(&operands.targets)
+                    .map(|(&qubit, &result)| {
+                        stmt(StmtKind::Semi(expr(ExprKind::Assign(
+                            path_expr(&result_name(result)),
+                            call(path_expr("M"), vec![qubit_expr(qubit)]),
+                        ))))
+                    })
+                    .collect()
+            } else {
+                vec![stmt(StmtKind::Semi(qsharp_call(op, &operands)?))]
+            };
+
+            if operands.conditions.is_empty() {
+                stmts.extend(op_stmts);
+            } else {
+                let condition = operands
+                    .conditions
+                    .iter()
+                    .map(|&result| {
+                        expr(ExprKind::BinOp(
+                            BinOp::Eq,
+                            path_expr(&result_name(result)),
+                            expr(ExprKind::Lit(Box::new(Lit::Result(ast::Result::One)))),
+                        ))
+                    })
+                    .reduce(|lhs, rhs| expr(ExprKind::BinOp(BinOp::AndL, lhs, rhs)))
+                    .expect("conditions should not be empty");
+                stmts.push(Box::new(Stmt {
+                    kind: Box::new(StmtKind::Expr(expr(ExprKind::If(
+                        condition,
+                        block(op_stmts),
+                        None,
+                    )))),
+                    ..Stmt::default()
+                }));
+            }
+        }
+
+        let output = if results.is_empty() {
+            ty_path("Unit")
+        } else {
+            stmts.push(Box::new(Stmt {
+                kind: Box::new(StmtKind::Expr(expr(ExprKind::Array(
+                    results
+                        .into_iter()
+                        .map(|result| path_expr(&result_name(result)))
+                        .collect(),
+                )))),
+                ..Stmt::default()
+            }));
+            array_ty(ty_path("Result"))
+        };
+
+        let decl = CallableDecl {
+            id: NodeId::default(),
+            span: Default::default(),
+            kind: CallableKind::Operation,
+            name: ident(operation_name),
+            generics: Box::default(),
+            input: Box::new(Pat {
+                kind: Box::new(PatKind::Paren(Box::new(Pat {
+                    kind: Box::new(PatKind::Bind(
+                        ident("qs"),
+                        Some(Box::new(array_ty(ty_path("Qubit")))),
+                    )),
+                    ..Pat::default()
+                }))),
+                ..Pat::default()
+            }),
+            output: Box::new(output),
+            functors: None,
+            body: Box::new(CallableBody::Block(block(stmts))),
+        };
+        let package = Package {
+            nodes: Box::new([TopLevelNode::Stmt(stmt(StmtKind::Item(Box::new(Item {
+                kind: Box::new(ItemKind::Callable(Box::new(decl))),
+                ..Item::default()
+            }))))]),
+            ..Package::default()
+        };
+        Ok(write_package_string(&package))
+    }
+}
+
+/// A gate application in OpenQASM, without its operands.
+struct QasmGate {
+    name: &'static str,
+    modifiers: String,
+    args: Option<String>,
+}
+
+impl std::fmt::Display for QasmGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.modifiers, self.name)?;
+        if let Some(args) = &self.args {
+            write!(f, "({args})")?;
+        }
+        Ok(())
+    }
+}
+
+fn qasm_gate(op: &Operation, operands: &Operands) -> Result<QasmGate, Error> {
+    let unsupported = || Error::UnsupportedGate(op.gate.clone(), "OpenQASM");
+    let gate = known_gate(&op.gate).ok_or_else(unsupported)?;
+    let args = if gate.rotation {
+        Some(
+            op.display_args
+                .clone()
+                .ok_or_else(|| Error::InvalidArgs(op.gate.clone(), String::new()))?,
+        )
+    } else {
+        None
+    };
+
+    let (name, inverse) = match (gate.qasm, op.is_adjoint) {
+        ("reset", _) if op.is_adjoint || !operands.controls.is_empty() => {
+            return Err(unsupported());
+        }
+        ("s", true) => ("sdg", false),
+        ("t", true) => ("tdg", false),
+        (name, inverse) => (name, inverse),
+    };
+    let (name, controls) = match (name, operands.controls.len()) {
+        ("x", 1) => ("cx", 0),
+        ("x", 2) => ("ccx", 0),
+        ("y", 1) => ("cy", 0),
+        ("z", 1) => ("cz", 0),
+        (name, controls) => (name, controls),
+    };
+
+    let mut modifiers = String::new();
+    match controls {
+        0 => {}
+        1 => modifiers.push_str("ctrl @ "),
+        n => {
+            let _ = write!(modifiers, "ctrl({n}) @ ");
+        }
+    }
+    if inverse {
+        modifiers.push_str("inv @ ");
+    }
+    Ok(QasmGate {
+        name,
+        modifiers,
+        args,
+    })
+}
+
+/// Builds the Q# call for a gate: `Controlled Adjoint G(controls, (args, targets))`.
+fn qsharp_call(op: &Operation, operands: &Operands) -> Result<Box<Expr>, Error> {
+    let name = match known_gate(&op.gate) {
+        Some(gate) => gate.qsharp,
+        None if is_qsharp_path(&op.gate) => &op.gate,
+        None => return Err(Error::UnsupportedGate(op.gate.clone(), "Q#")),
+    };
+    let mut callee = path_expr(name);
+    if op.is_adjoint {
+        callee = expr(ExprKind::UnOp(UnOp::Functor(Functor::Adj), callee));
+    }
+
+    let mut args = match &op.display_args {
+        Some(display_args) => parse_args(&op.gate, display_args)?,
+        None => Vec::new(),
+    };
+    args.extend(operands.targets.iter().map(|&q| qubit_expr(q)));
+
+    if operands.controls.is_empty() {
+        Ok(call(callee, args))
+    } else {
+        let controls = expr(ExprKind::Array(
+            operands.controls.iter().map(|&q| qubit_expr(q)).collect(),
+        ));
+        let inner = if args.len() == 1 {
+            args.pop().expect("args should have one element")
+        } else {
+            expr(ExprKind::Tuple(args.into_boxed_slice()))
+        };
+        Ok(call(
+            expr(ExprKind::UnOp(UnOp::Functor(Functor::Ctl), callee)),
+            vec![controls, inner],
+        ))
+    }
+}
+
+/// Parses comma-separated display arguments into Q# expressions.
+#[allow(clippy::vec_box)]
+fn parse_args(gate: &str, display_args: &str) -> Result<Vec<Box<Expr>>, Error> {
+    let (args, errors) = qsc_parse::expr(&format!("({display_args})"), LanguageFeatures::default());
+    if !errors.is_empty() {
+        return Err(Error::InvalidArgs(
+            gate.to_string(),
+            display_args.to_string(),
+        ));
+    }
+    Ok(match *args.kind {
+        ExprKind::Paren(arg) => vec![arg],
+        ExprKind::Tuple(args) => args.into_vec(),
+        _ => vec![args],
+    })
+}
+
+fn is_qsharp_path(name: &str) -> bool {
+    name.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+fn result_name(result: usize) -> String {
+    format!("c{result}")
+}
+
+fn ident(name: &str) -> Box<Ident> {
+    Box::new(Ident {
+        name: name.into(),
+        ..Ident::default()
+    })
+}
+
+fn expr(kind: ExprKind) -> Box<Expr> {
+    Box::new(Expr {
+        kind: Box::new(kind),
+        ..Expr::default()
+    })
+}
+
+fn stmt(kind: StmtKind) -> Box<Stmt> {
+    Box::new(Stmt {
+        kind: Box::new(kind),
+        ..Stmt::default()
+    })
+}
+
+#[allow(clippy::vec_box)]
+fn block(stmts: Vec<Box<Stmt>>) -> Box<Block> {
+    Box::new(Block {
+        id: NodeId::default(),
+        span: Default::default(),
+        stmts: stmts.into_boxed_slice(),
+    })
+}
+
+fn path(name: &str) -> Box<Path> {
+    let mut idents = name.split('.').map(|part| *ident(part)).collect::<Vec<_>>();
+    let name = idents.pop().expect("path should not be empty");
+    Box::new(Path {
+        namespace: (!idents.is_empty()).then(|| idents.into()),
+        name: Box::new(name),
+        ..Path::default()
+    })
+}
+
+fn path_expr(name: &str) -> Box<Expr> {
+    expr(ExprKind::Path(path(name)))
+}
+
+fn ty_path(name: &str) -> Ty {
+    Ty {
+        kind: Box::new(TyKind::Path(path(name))),
+        ..Ty::default()
+    }
+}
+
+fn array_ty(item: Ty) -> Ty {
+    Ty {
+        kind: Box::new(TyKind::Array(Box::new(item))),
+        ..Ty::default()
+    }
+}
+
+/// `qs[index]`
+fn qubit_expr(index: usize) -> Box<Expr> {
+    expr(ExprKind::Index(
+        path_expr("qs"),
+        expr(ExprKind::Lit(Box::new(Lit::Int(
+            i64::try_from(index).expect("qubit index should fit in an Int"),
+        )))),
+    ))
+}
+
+/// A call with the given arguments, which are wrapped in a tuple unless there is exactly one.
+#[allow(clippy::vec_box)]
+fn call(callee: Box<Expr>, mut args: Vec<Box<Expr>>) -> Box<Expr> {
+    let args = if args.len() == 1 {
+        expr(ExprKind::Paren(
+            args.pop().expect("args should have one element"),
+        ))
+    } else {
+        expr(ExprKind::Tuple(args.into_boxed_slice()))
+    };
+    expr(ExprKind::Call(callee, args))
+}
@@ -0,0 +1,300 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::Error;
+use crate::circuit::{Circuit, Operation, Qubit, Register};
+use expect_test::expect;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{compile, core, std, PackageStore, SourceMap};
+
+fn gate(name: &str, controls: Vec<Register>, targets: Vec<Register>) -> Operation {
+    Operation {
+        gate: name.to_string(),
+        display_args: None,
+        is_controlled: !controls.is_empty(),
+        is_adjoint: false,
+        is_measurement: false,
+        controls,
+        targets,
+        children: vec![],
+    }
+}
+
+fn measurement(q_id: usize, c_id: usize) -> Operation {
+    Operation {
+        is_measurement: true,
+        ..gate(
+            "Measure",
+            vec![Register::quantum(q_id)],
+            vec![Register::classical(q_id, c_id)],
+        )
+    }
+}
+
+fn qubits(num_children: &[usize]) -> Vec<Qubit> {
+    num_children
+        .iter()
+        .enumerate()
+        .map(|(id, &num_children)| Qubit { id, num_children })
+        .collect()
+}
+
+/// Compiles the Q# generated for `circuit` against the standard library and
+/// asserts that it has no errors.
+fn assert_qsharp_compiles(circuit: &Circuit) {
+    let source = circuit
+        .to_qsharp("Circuit")
+        .expect("conversion should succeed");
+    let mut store = PackageStore::new(core());
+    let std = store.insert(std(&store, TargetCapabilityFlags::all()));
+    let unit = compile(
+        &store,
+        &[std],
+        SourceMap::new([("circuit.qs".into(), source.clone().into())], None),
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(
+        unit.errors.is_empty(),
+        "generated Q# should compile: {:?}\n{source}",
+        unit.errors
+    );
+}
+
+/// A circuit using each kind of gate the circuit builder produces.
+fn sample() -> Circuit {
+    Circuit {
+        operations: vec![
+            gate("H", vec![], vec![Register::quantum(0)]),
+            gate("X", vec![Register::quantum(0)], vec![Register::quantum(1)]),
+            gate(
+                "CX",
+                vec![Register::quantum(0), Register::quantum(1)],
+                vec![Register::quantum(2)],
+            ),
+            Operation {
+                is_adjoint: true,
+                ..gate("S", vec![], vec![Register::quantum(2)])
+            },
+            Operation {
+                display_args: Some("1.5708".to_string()),
+                ..gate("rx", vec![], vec![Register::quantum(1)])
+            },
+            Operation {
+                display_args: Some("0.5000".to_string()),
+                is_adjoint: true,
+                ..gate(
+                    "rzz",
+                    vec![],
+                    vec![Register::quantum(0), Register::quantum(2)],
+                )
+            },
+            measurement(0, 0),
+            gate("|0〉", vec![], vec![Register::quantum(0)]),
+            gate(
+                "Z",
+                vec![Register::classical(0, 0)],
+                vec![Register::quantum(1)],
+            ),
+            measurement(1, 0),
+            measurement(2, 0),
+        ],
+        qubits: qubits(&[1, 1, 1]),
+    }
+}
+
+#[test]
+fn sample_to_openqasm() {
+    expect![[r#"
+        OPENQASM 3.0;
+        include "stdgates.inc";
+        gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }
+        qubit[3] q;
+        bit[3] c;
+
+        h q[0];
+        cx q[0], q[1];
+        ccx q[0], q[1], q[2];
+        sdg q[2];
+        rx(1.5708) q[1];
+        inv @ rzz(0.5000) q[0], q[2];
+        c[0] = measure q[0];
+        reset q[0];
+        if (c[0] == 1) {
+            z q[1];
+        }
+        c[1] = measure q[1];
+        c[2] = measure q[2];
+    "#]]
+    .assert_eq(&sample().to_openqasm().expect("conversion should succeed"));
+}
+
+#[test]
+fn sample_to_qsharp() {
+    expect![[r#"
+        operation Sample(qs : Qubit[]) : Result[] {
+            mutable c0 = Zero;
+            mutable c1 = Zero;
+            mutable c2 = Zero;
+            H(qs[0]);
+            Controlled X([qs[0]], qs[1]);
+            Controlled X([qs[0], qs[1]], qs[2]);
+            Adjoint S(qs[2]);
+            Rx(1.5708, qs[1]);
+            Adjoint Rzz(0.5, qs[0], qs[2]);
+            set c0 = M(qs[0]);
+            Reset(qs[0]);
+            if c0 == One {
+                Z(qs[1]);
+            }
+            set c1 = M(qs[1]);
+            set c2 = M(qs[2]);
+            [c0, c1, c2]
+        }
+    "#]]
+    .assert_eq(
+        &sample()
+            .to_qsharp("Sample")
+            .expect("conversion should succeed"),
+    );
+}
+
+#[test]
+fn sample_to_qsharp_compiles() {
+    assert_qsharp_compiles(&sample());
+}
+
+#[test]
+fn conditional_measurement_to_qsharp() {
+    let c = Circuit {
+        operations: vec![
+            gate("H", vec![], vec![Register::quantum(0)]),
+            measurement(0, 0),
+            Operation {
+                controls: vec![Register::quantum(1), Register::classical(0, 0)],
+                ..measurement(1, 0)
+            },
+            gate(
+                "X",
+                vec![Register::classical(1, 0)],
+                vec![Register::quantum(0)],
+            ),
+        ],
+        qubits: qubits(&[1, 1]),
+    };
+
+    expect![[r#"
+        operation Circuit(qs : Qubit[]) : Result[] {
+            mutable c0 = Zero;
+            mutable c1 = Zero;
+            H(qs[0]);
+            set c0 = M(qs[0]);
+            if c0 == One {
+                set c1 = M(qs[1]);
+            }
+            if c1 == One {
+                X(qs[0]);
+            }
+            [c0, c1]
+        }
+    "#]]
+    .assert_eq(&c.to_qsharp("Circuit").expect("conversion should succeed"));
+    assert_qsharp_compiles(&c);
+}
+
+#[test]
+fn groups_are_flattened() {
+    let c = Circuit {
+        operations: vec![Operation {
+            children: vec![
+                gate("H", vec![], vec![Register::quantum(0)]),
+                gate("X", vec![Register::quantum(0)], vec![Register::quantum(1)]),
+            ],
+            ..gate(
+                "PrepareBell",
+                vec![],
+                vec![Register::quantum(0), Register::quantum(1)],
+            )
+        }],
+        qubits: qubits(&[0, 0]),
+    };
+
+    expect![[r#"
+        OPENQASM 3.0;
+        include "stdgates.inc";
+        qubit[2] q;
+
+        h q[0];
+        cx q[0], q[1];
+    "#]]
+    .assert_eq(&c.to_openqasm().expect("conversion should succeed"));
+    expect![[r#"
+        operation Bell(qs : Qubit[]) : Unit {
+            H(qs[0]);
+            Controlled X([qs[0]], qs[1]);
+        }
+    "#]]
+    .assert_eq(&c.to_qsharp("Bell").expect("conversion should succeed"));
+}
+
+#[test]
+fn custom_gates_are_called_by_name_in_qsharp() {
+    let c = Circuit {
+        operations: vec![Operation {
+            display_args: Some("PauliX, [1, 2]".to_string()),
+            ..gate(
+                "ApplyCustom",
+                vec![Register::quantum(1)],
+                vec![Register::quantum(0)],
+            )
+        }],
+        qubits: qubits(&[0, 0]),
+    };
+
+    expect![[r#"
+        operation Custom(qs : Qubit[]) : Unit {
+            Controlled ApplyCustom([qs[1]], (PauliX, [1, 2], qs[0]));
+        }
+    "#]]
+    .assert_eq(&c.to_qsharp("Custom").expect("conversion should succeed"));
+    assert_eq!(
+        c.to_openqasm(),
+        Err(Error::UnsupportedGate(
+            "ApplyCustom".to_string(),
+            "OpenQASM"
+        ))
+    );
+}
+
+#[test]
+fn invalid_gates_are_errors() {
+    let c = Circuit {
+        operations: vec![gate("my gate", vec![], vec![Register::quantum(0)])],
+        qubits: qubits(&[0]),
+    };
+    assert_eq!(
+        c.to_qsharp("Invalid"),
+        Err(Error::UnsupportedGate("my gate".to_string(), "Q#"))
+    );
+
+    let c = Circuit {
+        operations: vec![Operation {
+            display_args: Some("1.0 +".to_string()),
+            ..gate("rx", vec![], vec![Register::quantum(0)])
+        }],
+        qubits: qubits(&[0]),
+    };
+    assert_eq!(
+        c.to_qsharp("Invalid"),
+        Err(Error::InvalidArgs("rx".to_string(), "1.0 +".to_string()))
+    );
+
+    let c = Circuit {
+        operations: vec![gate("H", vec![], vec![Register::quantum(3)])],
+        qubits: qubits(&[0]),
+    };
+    assert_eq!(
+        c.to_openqasm(),
+        Err(Error::UnknownRegister("H".to_string()))
+    );
+}
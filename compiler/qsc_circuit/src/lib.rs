@@ -3,8 +3,11 @@
 
 mod builder;
 mod circuit;
+pub mod convert;
 pub mod operations;
+pub mod rir_to_circuit;
+mod svg;
 
 pub use builder::Builder;
-pub use circuit::{Circuit, Config, Operation};
+pub use circuit::{Circuit, Config, Operation, Qubit, Register};
 pub use operations::Error;
@@ -0,0 +1,590 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Generation of a [`Circuit`] from a partially evaluated program.
+//!
+//! Unlike the circuit [`Builder`](crate::Builder), which follows a single path through the
+//! program, the RIR keeps the branches that depend on measurement results. Each branch is drawn
+//! as a group of the operations it applies, controlled by the classical wires of the results
+//! its condition depends on.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builder::KET_ZERO,
+    circuit::{Circuit, Operation, Qubit, Register},
+};
+use miette::Diagnostic;
+use qsc_rir::{
+    rir::{
+        BlockId, CallableType, ConditionCode, Instruction, Literal, Operand, Program, Variable,
+        VariableId,
+    },
+    utils::get_all_block_successors,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::{self, Display, Formatter};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Diagnostic, Error, PartialEq)]
+pub enum Error {
+    #[error("call to `{0}` has unsupported operands `{1}`")]
+    #[diagnostic(code("Qsc.Circuit.UnsupportedOperand"))]
+    UnsupportedOperand(String, String),
+    #[error("result {0} is used before it is measured")]
+    #[diagnostic(code("Qsc.Circuit.UnmeasuredResult"))]
+    UnmeasuredResult(u32),
+    #[error("variable {0} is used before it is set")]
+    #[diagnostic(code("Qsc.Circuit.UnsetVariable"))]
+    UnsetVariable(u32),
+}
+
+/// Generates the circuit for `program`, which should be the output of partial evaluation
+/// before any RIR passes are run.
+/// Branches on measurement results become groups named `if`, with the branch condition as their
+/// argument and the classical registers of the results it reads as their controls.
+/// # Errors
+/// Returns an error if a qubit or result operand is not known when the program is generated.
+pub fn rir_to_circuit(program: &Program) -> Result<Circuit, Error> {
+    let mut generator = Generator {
+        program,
+        results: FxHashMap::default(),
+        num_results: vec![0; program.num_qubits as usize],
+        vars: FxHashMap::default(),
+    };
+    let mut operations = Vec::new();
+    if let Some(body) = program.get_callable(program.entry).body {
+        generator.block(body, None, &mut operations)?;
+    }
+    Ok(Circuit {
+        operations,
+        qubits: generator
+            .num_results
+            .iter()
+            .enumerate()
+            .map(|(id, &num_children)| Qubit { id, num_children })
+            .collect(),
+    })
+}
+
+/// A classical value computed by the program, tracked so branch conditions can be displayed.
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    /// Whether the result measured into this classical register is `One`.
+    Result(Register),
+    Literal(String),
+    /// The value returned by a call to the named callable.
+    Call(String, Vec<Expr>),
+    /// A value that depends on the branch taken, `cond ? if_true | if_false`.
+    Cond(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// A value chosen by a phi instruction from the values of its predecessor blocks.
+    Phi(Vec<Expr>),
+    Not(Box<Expr>),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn not(self) -> Self {
+        match self {
+            Expr::Not(expr) => *expr,
+            Expr::Literal(lit) if lit == "true" => Expr::Literal("false".into()),
+            Expr::Literal(lit) if lit == "false" => Expr::Literal("true".into()),
+            expr => Expr::Not(Box::new(expr)),
+        }
+    }
+
+    fn binary(op: &'static str, lhs: Self, rhs: Self) -> Self {
+        Expr::Binary(op, Box::new(lhs), Box::new(rhs))
+    }
+
+    /// The value that is `if_true` when `cond` holds and `if_false` otherwise.
+    /// Conditions with a boolean literal branch, as short-circuiting operators
+    /// produce, are shown as the operator.
+    fn cond(cond: Self, if_true: Self, if_false: Self) -> Self {
+        let is_lit = |expr: &Expr, value: &str| matches!(expr, Expr::Literal(lit) if lit == value);
+        if if_true == if_false {
+            if_true
+        } else if is_lit(&if_false, "false") {
+            Expr::binary("and", cond, if_true)
+        } else if is_lit(&if_true, "true") {
+            Expr::binary("or", cond, if_false)
+        } else {
+            Expr::Cond(Box::new(cond), Box::new(if_true), Box::new(if_false))
+        }
+    }
+
+    /// Adds the classical registers of the results this value depends on to `deps`.
+    fn deps(&self, deps: &mut Vec<Register>) {
+        match self {
+            Expr::Result(reg) => deps.push(reg.clone()),
+            Expr::Literal(_) => {}
+            Expr::Call(_, exprs) | Expr::Phi(exprs) => {
+                for expr in exprs {
+                    expr.deps(deps);
+                }
+            }
+            Expr::Cond(cond, if_true, if_false) => {
+                cond.deps(deps);
+                if_true.deps(deps);
+                if_false.deps(deps);
+            }
+            Expr::Not(expr) | Expr::Unary(_, expr) => expr.deps(deps),
+            Expr::Binary(_, lhs, rhs) => {
+                lhs.deps(deps);
+                rhs.deps(deps);
+            }
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Expr::Result(_) | Expr::Binary(..) | Expr::Cond(..) => write!(f, "({self})"),
+            Expr::Not(expr) if matches!(**expr, Expr::Result(_)) => write!(f, "({self})"),
+            _ => write!(f, "{self}"),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Expr::Result(reg) => write!(f, "{} = One", register_name(reg)),
+            Expr::Literal(lit) => write!(f, "{lit}"),
+            Expr::Call(name, args) => {
+                write!(f, "{name}(")?;
+                fmt_list(f, args)?;
+                write!(f, ")")
+            }
+            Expr::Cond(cond, if_true, if_false) => {
+                cond.fmt_operand(f)?;
+                write!(f, " ? ")?;
+                if_true.fmt_operand(f)?;
+                write!(f, " | ")?;
+                if_false.fmt_operand(f)
+            }
+            Expr::Phi(values) => {
+                write!(f, "phi(")?;
+                fmt_list(f, values)?;
+                write!(f, ")")
+            }
+            Expr::Not(expr) => match &**expr {
+                Expr::Result(reg) => write!(f, "{} = Zero", register_name(reg)),
+                expr => {
+                    write!(f, "not ")?;
+                    expr.fmt_operand(f)
+                }
+            },
+            Expr::Unary(op, expr) => {
+                write!(f, "{op}")?;
+                expr.fmt_operand(f)
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                lhs.fmt_operand(f)?;
+                write!(f, " {op} ")?;
+                rhs.fmt_operand(f)
+            }
+        }
+    }
+}
+
+fn fmt_list(f: &mut Formatter, exprs: &[Expr]) -> fmt::Result {
+    for (i, expr) in exprs.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{expr}")?;
+    }
+    Ok(())
+}
+
+/// The name of a classical register as it is shown in the circuit, `c{q_id}_{c_id}`.
+fn register_name(reg: &Register) -> String {
+    format!("c{}_{}", reg.q_id, reg.c_id.unwrap_or_default())
+}
+
+struct Generator<'a> {
+    program: &'a Program,
+    /// The classical register written by each measured result.
+    results: FxHashMap<u32, Register>,
+    /// The number of measurements of each qubit.
+    num_results: Vec<usize>,
+    vars: FxHashMap<VariableId, Expr>,
+}
+
+impl Generator<'_> {
+    /// Adds the operations of the blocks starting at `block_id` to `operations`,
+    /// following jumps until `stop` or the end of the program is reached.
+    fn block(
+        &mut self,
+        mut block_id: BlockId,
+        stop: Option<BlockId>,
+        operations: &mut Vec<Operation>,
+    ) -> Result<(), Error> {
+        loop {
+            if Some(block_id) == stop {
+                return Ok(());
+            }
+            let mut next = None;
+            for instr in &self.program.get_block(block_id).0 {
+                match instr {
+                    Instruction::Call(id, args, var) => self.call(*id, args, *var, operations)?,
+                    Instruction::Jump(target) => next = Some(*target),
+                    Instruction::Branch(cond, if_true, if_false) => {
+                        next = self.branch(*cond, *if_true, *if_false, operations)?;
+                        if next.is_none() {
+                            return Ok(());
+                        }
+                    }
+                    Instruction::Return => return Ok(()),
+                    instr => self.classical(instr)?,
+                }
+            }
+            match next {
+                Some(target) => block_id = target,
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Adds the operations of both branches as conditional groups and returns
+    /// the block where the branches meet, if any.
+    fn branch(
+        &mut self,
+        cond: Variable,
+        if_true: BlockId,
+        if_false: BlockId,
+        operations: &mut Vec<Operation>,
+    ) -> Result<Option<BlockId>, Error> {
+        let cond = self.expr(&Operand::Variable(cond))?;
+        let merge = self.merge_block(if_true, if_false);
+        let vars = self.vars.clone();
+
+        let mut true_ops = Vec::new();
+        self.block(if_true, merge, &mut true_ops)?;
+        let true_vars = std::mem::replace(&mut self.vars, vars.clone());
+        let mut false_ops = Vec::new();
+        self.block(if_false, merge, &mut false_ops)?;
+
+        // Variables set in either branch depend on the condition after the branches meet.
+        let changed = true_vars
+            .iter()
+            .chain(&self.vars)
+            .filter(|(id, expr)| vars.get(id) != Some(expr))
+            .map(|(id, _)| *id)
+            .collect::<FxHashSet<_>>();
+        for id in changed {
+            let if_true = true_vars.get(&id).or(vars.get(&id));
+            let if_false = self.vars.get(&id).or(vars.get(&id));
+            let expr = match (if_true, if_false) {
+                (Some(if_true), Some(if_false)) => {
+                    Expr::cond(cond.clone(), if_true.clone(), if_false.clone())
+                }
+                (Some(expr), None) | (None, Some(expr)) => expr.clone(),
+                (None, None) => continue,
+            };
+            self.vars.insert(id, expr);
+        }
+
+        let not_cond = cond.clone().not();
+        for (cond, children) in [(cond, true_ops), (not_cond, false_ops)] {
+            if !children.is_empty() {
+                operations.push(Self::conditional_group(&cond, children));
+            }
+        }
+        Ok(merge)
+    }
+
+    /// The first block reached by both branches, from which every other
+    /// block reached by both branches can be reached.
+    fn merge_block(&self, if_true: BlockId, if_false: BlockId) -> Option<BlockId> {
+        let reachable = |block| {
+            let mut blocks = get_all_block_successors(block, self.program);
+            blocks.push(block);
+            blocks.into_iter().collect::<FxHashSet<_>>()
+        };
+        let from_false = reachable(if_false);
+        let mut common = reachable(if_true)
+            .into_iter()
+            .filter(|block| from_false.contains(block))
+            .collect::<Vec<_>>();
+        common.sort_unstable();
+        common.iter().copied().find(|&block| {
+            let from_block = reachable(block);
+            common.iter().all(|other| from_block.contains(other))
+        })
+    }
+
+    fn conditional_group(cond: &Expr, children: Vec<Operation>) -> Operation {
+        let mut controls = Vec::new();
+        cond.deps(&mut controls);
+        controls.sort_unstable_by_key(|reg| (reg.q_id, reg.c_id));
+        controls.dedup();
+
+        let mut targets = Vec::new();
+        collect_qubits(&children, &mut targets);
+        targets.sort_unstable_by_key(|reg: &Register| reg.q_id);
+        targets.dedup();
+
+        Operation {
+            gate: "if".into(),
+            display_args: Some(cond.to_string()),
+            is_controlled: !controls.is_empty(),
+            is_adjoint: false,
+            is_measurement: false,
+            controls,
+            targets,
+            children,
+        }
+    }
+
+    fn call(
+        &mut self,
+        callable_id: qsc_rir::rir::CallableId,
+        args: &[Operand],
+        var: Option<Variable>,
+        operations: &mut Vec<Operation>,
+    ) -> Result<(), Error> {
+        let callable = self.program.get_callable(callable_id);
+        let name = callable.name.as_str();
+        match callable.call_type {
+            CallableType::Measurement => {
+                let (q, result) = match args {
+                    [Operand::Literal(Literal::Qubit(q)), Operand::Literal(Literal::Result(r))] => {
+                        (*q as usize, *r)
+                    }
+                    _ => return Err(unsupported(name, args)),
+                };
+                if self.num_results.len() <= q {
+                    self.num_results.resize(q + 1, 0);
+                }
+                let reg = Register::classical(q, self.num_results[q]);
+                self.num_results[q] += 1;
+                self.results.insert(result, reg.clone());
+                operations.push(Operation {
+                    is_measurement: true,
+                    ..gate("Measure", vec![Register::quantum(q)], vec![reg])
+                });
+                if name == "__quantum__qis__mresetz__body" {
+                    operations.push(gate(KET_ZERO, vec![], vec![Register::quantum(q)]));
+                }
+            }
+            CallableType::Reset => {
+                let qubits = self.qubits(name, args)?;
+                operations.push(gate(KET_ZERO, vec![], qubits));
+            }
+            CallableType::Readout => {
+                if let (Some(var), [Operand::Literal(Literal::Result(id))]) = (var, args) {
+                    let reg = self.result(*id)?;
+                    self.vars.insert(var.variable_id, Expr::Result(reg));
+                }
+            }
+            CallableType::OutputRecording => {}
+            CallableType::Regular => {
+                if let Some(var) = var {
+                    let args = args
+                        .iter()
+                        .map(|arg| self.expr(arg))
+                        .collect::<Result<_, _>>()?;
+                    self.vars
+                        .insert(var.variable_id, Expr::Call(name.into(), args));
+                }
+                if !name.starts_with("__quantum__rt__") {
+                    operations.push(self.gate(name, args)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The operation applied by a call to the intrinsic `name`.
+    fn gate(&self, name: &str, args: &[Operand]) -> Result<Operation, Error> {
+        let qubits = self.qubits(name, args)?;
+        let theta = args
+            .iter()
+            .find_map(|arg| match arg {
+                Operand::Literal(Literal::Double(theta)) => Some(Ok(format!("{theta:.4}"))),
+                Operand::Variable(_) => Some(self.expr(arg).map(|expr| expr.to_string())),
+                _ => None,
+            })
+            .transpose()?;
+        let controlled = |name: &str, n: usize| {
+            let mut controls = qubits.clone();
+            let targets = controls.split_off(n);
+            gate(name, controls, targets)
+        };
+        let op = match name.trim_start_matches("__quantum__qis__") {
+            "ccx__body" => controlled("X", 2),
+            "cx__body" => controlled("X", 1),
+            "cy__body" => controlled("Y", 1),
+            "cz__body" => controlled("Z", 1),
+            "h__body" => gate("H", vec![], qubits),
+            "s__body" => gate("S", vec![], qubits),
+            "t__body" => gate("T", vec![], qubits),
+            "x__body" => gate("X", vec![], qubits),
+            "y__body" => gate("Y", vec![], qubits),
+            "z__body" => gate("Z", vec![], qubits),
+            "swap__body" => gate("SWAP", vec![], qubits),
+            "s__adj" => Operation {
+                is_adjoint: true,
+                ..gate("S", vec![], qubits)
+            },
+            "t__adj" => Operation {
+                is_adjoint: true,
+                ..gate("T", vec![], qubits)
+            },
+            gate_name @ ("rx__body" | "ry__body" | "rz__body" | "rxx__body" | "ryy__body"
+            | "rzz__body") => Operation {
+                display_args: theta,
+                ..gate(gate_name.trim_end_matches("__body"), vec![], qubits)
+            },
+            _ => {
+                let display_args = args
+                    .iter()
+                    .filter(|arg| !matches!(arg, Operand::Literal(Literal::Qubit(_))))
+                    .map(|arg| self.expr(arg).map(|expr| expr.to_string()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Operation {
+                    display_args: (!display_args.is_empty()).then(|| display_args.join(", ")),
+                    ..gate(name, vec![], qubits)
+                }
+            }
+        };
+        Ok(op)
+    }
+
+    fn qubits(&self, name: &str, args: &[Operand]) -> Result<Vec<Register>, Error> {
+        let mut qubits = Vec::new();
+        for arg in args {
+            match arg {
+                Operand::Literal(Literal::Qubit(q)) => qubits.push(Register::quantum(*q as usize)),
+                Operand::Variable(var) if var.ty == qsc_rir::rir::Ty::Qubit => {
+                    return Err(unsupported(name, args));
+                }
+                _ => {}
+            }
+        }
+        Ok(qubits)
+    }
+
+    fn result(&self, id: u32) -> Result<Register, Error> {
+        self.results
+            .get(&id)
+            .cloned()
+            .ok_or(Error::UnmeasuredResult(id))
+    }
+
+    /// Tracks the value of the variable set by a classical instruction.
+    fn classical(&mut self, instr: &Instruction) -> Result<(), Error> {
+        let (var, expr) = match instr {
+            Instruction::Store(value, var) => (var, self.expr(value)?),
+            Instruction::Add(lhs, rhs, var) => (var, self.binary("+", lhs, rhs)?),
+            Instruction::Sub(lhs, rhs, var) => (var, self.binary("-", lhs, rhs)?),
+            Instruction::Mul(lhs, rhs, var) => (var, self.binary("*", lhs, rhs)?),
+            Instruction::Sdiv(lhs, rhs, var) => (var, self.binary("/", lhs, rhs)?),
+            Instruction::Srem(lhs, rhs, var) => (var, self.binary("%", lhs, rhs)?),
+            Instruction::Shl(lhs, rhs, var) => (var, self.binary("<<<", lhs, rhs)?),
+            Instruction::Ashr(lhs, rhs, var) => (var, self.binary(">>>", lhs, rhs)?),
+            Instruction::LogicalAnd(lhs, rhs, var) => (var, self.binary("and", lhs, rhs)?),
+            Instruction::LogicalOr(lhs, rhs, var) => (var, self.binary("or", lhs, rhs)?),
+            Instruction::BitwiseAnd(lhs, rhs, var) => (var, self.binary("&&&", lhs, rhs)?),
+            Instruction::BitwiseOr(lhs, rhs, var) => (var, self.binary("|||", lhs, rhs)?),
+            Instruction::BitwiseXor(lhs, rhs, var) => (var, self.binary("^^^", lhs, rhs)?),
+            Instruction::LogicalNot(value, var) => (var, self.expr(value)?.not()),
+            Instruction::BitwiseNot(value, var) => {
+                (var, Expr::Unary("~~~", Box::new(self.expr(value)?)))
+            }
+            Instruction::Icmp(code, lhs, rhs, var) => (var, self.compare(*code, lhs, rhs)?),
+            // Values from blocks that are not visited yet, such as loop back edges, are left out.
+            Instruction::Phi(values, var) => (
+                var,
+                Expr::Phi(
+                    values
+                        .iter()
+                        .filter_map(|(value, _)| self.expr(value).ok())
+                        .collect(),
+                ),
+            ),
+            Instruction::Call(..)
+            | Instruction::Jump(_)
+            | Instruction::Branch(..)
+            | Instruction::Return => return Ok(()),
+        };
+        self.vars.insert(var.variable_id, expr);
+        Ok(())
+    }
+
+    fn compare(&self, code: ConditionCode, lhs: &Operand, rhs: &Operand) -> Result<Expr, Error> {
+        let (lhs, rhs) = (self.expr(lhs)?, self.expr(rhs)?);
+        // Comparisons of a condition with a boolean literal are the condition or its negation.
+        let is_true = |expr: &Expr| matches!(expr, Expr::Literal(lit) if lit == "true");
+        let is_false = |expr: &Expr| matches!(expr, Expr::Literal(lit) if lit == "false");
+        Ok(match code {
+            ConditionCode::Eq if is_true(&rhs) => lhs,
+            ConditionCode::Eq if is_false(&rhs) => lhs.not(),
+            ConditionCode::Ne if is_true(&rhs) => lhs.not(),
+            ConditionCode::Ne if is_false(&rhs) => lhs,
+            ConditionCode::Eq => Expr::binary("==", lhs, rhs),
+            ConditionCode::Ne => Expr::binary("!=", lhs, rhs),
+            ConditionCode::Slt => Expr::binary("<", lhs, rhs),
+            ConditionCode::Sle => Expr::binary("<=", lhs, rhs),
+            ConditionCode::Sgt => Expr::binary(">", lhs, rhs),
+            ConditionCode::Sge => Expr::binary(">=", lhs, rhs),
+        })
+    }
+
+    fn binary(&self, op: &'static str, lhs: &Operand, rhs: &Operand) -> Result<Expr, Error> {
+        Ok(Expr::binary(op, self.expr(lhs)?, self.expr(rhs)?))
+    }
+
+    fn expr(&self, operand: &Operand) -> Result<Expr, Error> {
+        Ok(match operand {
+            Operand::Literal(Literal::Bool(b)) => Expr::Literal(b.to_string()),
+            Operand::Literal(Literal::Integer(i)) => Expr::Literal(i.to_string()),
+            Operand::Literal(Literal::Double(d)) => Expr::Literal(format!("{d:.4}")),
+            Operand::Literal(lit) => Expr::Literal(lit.to_string()),
+            Operand::Variable(var) => self
+                .vars
+                .get(&var.variable_id)
+                .cloned()
+                .ok_or(Error::UnsetVariable(var.variable_id.0))?,
+        })
+    }
+}
+
+/// Adds the quantum registers used by `ops` and their children to `qubits`.
+fn collect_qubits(ops: &[Operation], qubits: &mut Vec<Register>) {
+    for op in ops {
+        qubits.extend(
+            op.controls
+                .iter()
+                .chain(&op.targets)
+                .filter(|reg| reg.c_id.is_none())
+                .cloned(),
+        );
+        collect_qubits(&op.children, qubits);
+    }
+}
+
+fn gate(name: &str, controls: Vec<Register>, targets: Vec<Register>) -> Operation {
+    Operation {
+        gate: name.into(),
+        display_args: None,
+        is_controlled: !controls.is_empty(),
+        is_adjoint: false,
+        is_measurement: false,
+        controls,
+        targets,
+        children: vec![],
+    }
+}
+
+fn unsupported(name: &str, args: &[Operand]) -> Error {
+    let args = args
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Error::UnsupportedOperand(name.to_string(), args)
+}
@@ -0,0 +1,246 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{rir_to_circuit, Error};
+use crate::circuit::Register;
+use expect_test::expect;
+use qsc_rir::{
+    builder::{bell_program, m_decl, new_program, read_result_decl, teleport_program, x_decl},
+    rir::{Block, BlockId, CallableId, Instruction, Literal, Operand, Ty, Variable, VariableId},
+};
+
+fn bool_var(id: u32) -> Variable {
+    Variable {
+        variable_id: VariableId(id),
+        ty: Ty::Boolean,
+    }
+}
+
+#[test]
+fn program_without_branches() {
+    let circuit = rir_to_circuit(&bell_program()).expect("circuit should be generated");
+
+    expect![[r"
+        q_0    ── H ──── ● ──── M ──
+                         │      ╘═══
+        q_1    ───────── X ──── M ──
+                                ╘═══
+    "]]
+    .assert_eq(&circuit.to_string());
+}
+
+#[test]
+fn branches_on_results_are_conditional_groups() {
+    let circuit = rir_to_circuit(&teleport_program()).expect("circuit should be generated");
+
+    expect![[r"
+        q_0    ── X ─────────── ● ──── H ──── M ─── |0〉 ──────────────────────
+                                │             ╘═════ ● ═══════════════════════
+        q_1    ───────── X ─────┼───────────────── if(c0_0 = One)  if(c2_0 = One) ── M ─── |0〉 ─
+                         │      │                           │      ╘══════════
+        q_2    ── H ──── ● ──── X ──── M ─── |0〉 ───────────┼─────────────────
+                                       ╘═══════════════════ ● ════════════════
+    "]]
+    .assert_eq(&circuit.to_string());
+
+    let group = &circuit.operations[7];
+    assert_eq!(group.gate, "if");
+    assert_eq!(group.controls, vec![Register::classical(0, 0)]);
+    assert_eq!(group.targets, vec![Register::quantum(1)]);
+    assert_eq!(group.children.len(), 1);
+}
+
+#[test]
+fn else_branch_is_conditioned_on_negation() {
+    let mut program = new_program();
+    program.num_qubits = 2;
+    program.callables.insert(CallableId(1), x_decl());
+    program.callables.insert(CallableId(2), m_decl());
+    program.callables.insert(CallableId(3), read_result_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(2),
+                vec![
+                    Operand::Literal(Literal::Qubit(0)),
+                    Operand::Literal(Literal::Result(0)),
+                ],
+                None,
+            ),
+            Instruction::Call(
+                CallableId(3),
+                vec![Operand::Literal(Literal::Result(0))],
+                Some(bool_var(0)),
+            ),
+            Instruction::LogicalNot(Operand::Variable(bool_var(0)), bool_var(1)),
+            Instruction::Branch(bool_var(1), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Qubit(1))],
+                None,
+            ),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Qubit(0))],
+                None,
+            ),
+            Instruction::Jump(BlockId(3)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(3), Block(vec![Instruction::Return]));
+
+    let circuit = rir_to_circuit(&program).expect("circuit should be generated");
+
+    expect![[r"
+        q_0    ── M ───────── if(c0_0 = One)
+                  ╘═════ ● ════ ● ══
+        q_1    ─────── if(c0_0 = Zero) ───────
+    "]]
+    .assert_eq(&circuit.to_string());
+}
+
+#[test]
+fn reading_unmeasured_result_is_error() {
+    let mut program = new_program();
+    program.num_qubits = 1;
+    program.callables.insert(CallableId(1), x_decl());
+    program.callables.insert(CallableId(2), read_result_decl());
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            Instruction::Call(
+                CallableId(2),
+                vec![Operand::Literal(Literal::Result(0))],
+                Some(bool_var(0)),
+            ),
+            Instruction::Branch(bool_var(0), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Qubit(0))],
+                None,
+            ),
+            Instruction::Jump(BlockId(2)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(2), Block(vec![Instruction::Return]));
+
+    assert_eq!(rir_to_circuit(&program), Err(Error::UnmeasuredResult(0)));
+}
+
+#[test]
+fn variable_set_in_branch_is_shown_as_expression() {
+    let mut program = new_program();
+    program.num_qubits = 2;
+    program.callables.insert(CallableId(1), x_decl());
+    program.callables.insert(CallableId(2), m_decl());
+    program.callables.insert(CallableId(3), read_result_decl());
+    let measure = |q, r| {
+        Instruction::Call(
+            CallableId(2),
+            vec![
+                Operand::Literal(Literal::Qubit(q)),
+                Operand::Literal(Literal::Result(r)),
+            ],
+            None,
+        )
+    };
+    let read = |r, var| {
+        Instruction::Call(
+            CallableId(3),
+            vec![Operand::Literal(Literal::Result(r))],
+            Some(bool_var(var)),
+        )
+    };
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![
+            measure(0, 0),
+            measure(1, 1),
+            measure(1, 2),
+            read(0, 0),
+            Instruction::Store(Operand::Literal(Literal::Bool(false)), bool_var(2)),
+            Instruction::Branch(bool_var(0), BlockId(1), BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(1),
+        Block(vec![
+            read(2, 1),
+            Instruction::Store(Operand::Variable(bool_var(1)), bool_var(2)),
+            Instruction::Jump(BlockId(2)),
+        ]),
+    );
+    program.blocks.insert(
+        BlockId(2),
+        Block(vec![Instruction::Branch(
+            bool_var(2),
+            BlockId(3),
+            BlockId(4),
+        )]),
+    );
+    program.blocks.insert(
+        BlockId(3),
+        Block(vec![
+            Instruction::Call(
+                CallableId(1),
+                vec![Operand::Literal(Literal::Qubit(0))],
+                None,
+            ),
+            Instruction::Jump(BlockId(4)),
+        ]),
+    );
+    program
+        .blocks
+        .insert(BlockId(4), Block(vec![Instruction::Return]));
+
+    let circuit = rir_to_circuit(&program).expect("circuit should be generated");
+
+    let group = &circuit.operations[3];
+    assert_eq!(
+        group.display_args.as_deref(),
+        Some("(c0_0 = One) and (c1_1 = One)")
+    );
+    assert_eq!(
+        group.controls,
+        vec![Register::classical(0, 0), Register::classical(1, 1)]
+    );
+}
+
+#[test]
+fn reading_unset_variable_is_error() {
+    let mut program = new_program();
+    program.blocks.insert(
+        BlockId(0),
+        Block(vec![Instruction::Branch(
+            bool_var(0),
+            BlockId(1),
+            BlockId(1),
+        )]),
+    );
+    program
+        .blocks
+        .insert(BlockId(1), Block(vec![Instruction::Return]));
+
+    assert_eq!(rir_to_circuit(&program), Err(Error::UnsetVariable(0)));
+}
@@ -0,0 +1,533 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::circuit::{Circuit, Operation, Register};
+use rustc_hash::FxHashMap;
+use std::fmt::{self, Display, Write};
+
+/// Space around the whole diagram.
+const PADDING: usize = 10;
+/// Height of a gate box, and the height of the band around each wire that gates occupy.
+const GATE_HEIGHT: usize = 40;
+/// Minimum width of a gate box.
+const MIN_GATE_WIDTH: usize = 40;
+/// Space between a gate label and the sides of its box.
+const GATE_PADDING: usize = 10;
+/// Vertical space between the gate bands of two adjacent wires.
+const ROW_GAP: usize = 20;
+/// Horizontal space between two adjacent columns.
+const COLUMN_GAP: usize = 10;
+/// Space between a group box and the boxes it contains.
+const GROUP_PADDING: usize = 6;
+/// Space reserved at the top of a group box for its label.
+const GROUP_LABEL_HEIGHT: usize = 12;
+/// Approximate width of a character in the gate label font.
+const CHAR_WIDTH: usize = 9;
+/// Approximate width of a character in the argument and group label fonts.
+const SMALL_CHAR_WIDTH: usize = 7;
+/// Radius of a control dot.
+const CONTROL_RADIUS: usize = 5;
+/// Radius of the circle drawn for the target of a controlled X.
+const OPLUS_RADIUS: usize = 12;
+/// Distance between the two lines of a classical wire.
+const CLASSICAL_WIRE_SPACING: usize = 2;
+
+impl Circuit {
+    /// Renders the circuit as a standalone SVG document.
+    /// Each qubit is drawn as a wire, followed by a double wire for each of its measurement results.
+    /// Operations with children are drawn as a dashed box around their children, with their
+    /// controls marked on the edge of the box.
+    #[must_use]
+    pub fn to_svg(&self) -> String {
+        Layout::new(self).to_string()
+    }
+}
+
+enum Wire {
+    Qubit { q_id: usize },
+    Classical,
+}
+
+/// An operation without children and the column it occupies.
+struct Gate<'a> {
+    op: &'a Operation,
+    column: usize,
+}
+
+/// An operation with children and the columns its children occupy.
+struct Group<'a> {
+    op: &'a Operation,
+    start_column: usize,
+    end_column: usize,
+    first_row: usize,
+    last_row: usize,
+    /// The number of nested group levels inside this group, including itself.
+    height: usize,
+}
+
+/// The placement of operations into rows and columns, following the same rules as
+/// the text rendering of a circuit: each operation goes in the first column that is
+/// free in all the rows it spans.
+struct Layout<'a> {
+    wires: Vec<Wire>,
+    register_to_row: FxHashMap<(usize, Option<usize>), usize>,
+    next_column: Vec<usize>,
+    column_widths: Vec<usize>,
+    gates: Vec<Gate<'a>>,
+    groups: Vec<Group<'a>>,
+    /// The deepest nesting of groups in the circuit.
+    max_height: usize,
+}
+
+impl<'a> Layout<'a> {
+    fn new(circuit: &'a Circuit) -> Self {
+        let mut wires = Vec::new();
+        let mut register_to_row = FxHashMap::default();
+        for q in &circuit.qubits {
+            register_to_row.insert((q.id, None), wires.len());
+            wires.push(Wire::Qubit { q_id: q.id });
+            for i in 0..q.num_children {
+                register_to_row.insert((q.id, Some(i)), wires.len());
+                wires.push(Wire::Classical);
+            }
+        }
+
+        let mut layout = Self {
+            next_column: vec![0; wires.len()],
+            wires,
+            register_to_row,
+            column_widths: Vec::new(),
+            gates: Vec::new(),
+            groups: Vec::new(),
+            max_height: 0,
+        };
+        for op in &circuit.operations {
+            let height = layout.place(op);
+            layout.max_height = layout.max_height.max(height);
+        }
+        layout
+    }
+
+    /// Places `op` and returns the number of nested group levels it contains.
+    fn place(&mut self, op: &'a Operation) -> usize {
+        let mut rows = Vec::new();
+        self.collect_rows(op, &mut rows);
+        let (Some(&first_row), Some(&last_row)) = (rows.iter().min(), rows.iter().max()) else {
+            // The operation does not touch any known register.
+            return 0;
+        };
+        let span = first_row..=last_row;
+        let start_column = self.next_column[span.clone()]
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or_default();
+
+        if op.children.is_empty() {
+            self.widen_column(start_column, gate_width(op));
+            self.next_column[span].fill(start_column + 1);
+            self.gates.push(Gate {
+                op,
+                column: start_column,
+            });
+            return 0;
+        }
+
+        // Children start together in the first column of the group, and the group
+        // reserves all the rows it spans until its last child.
+        self.next_column[span.clone()].fill(start_column);
+        let height = 1 + op
+            .children
+            .iter()
+            .map(|child| self.place(child))
+            .max()
+            .unwrap_or_default();
+        let end_column = self.next_column[span.clone()]
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or_default()
+            .max(start_column + 1);
+        self.next_column[span].fill(end_column);
+
+        // Make sure the label fits inside the box.
+        self.widen_column(end_column - 1, MIN_GATE_WIDTH);
+        let inner_width = self.column_x(end_column - 1) + self.column_widths[end_column - 1]
+            - self.column_x(start_column);
+        let label_width = gate_label(op).chars().count() * SMALL_CHAR_WIDTH;
+        self.widen_column(
+            end_column - 1,
+            self.column_widths[end_column - 1] + label_width.saturating_sub(inner_width),
+        );
+
+        self.groups.push(Group {
+            op,
+            start_column,
+            end_column,
+            first_row,
+            last_row,
+            height,
+        });
+        height
+    }
+
+    /// Collects the rows of the registers used by `op` and its children.
+    fn collect_rows(&self, op: &Operation, rows: &mut Vec<usize>) {
+        rows.extend(
+            op.targets
+                .iter()
+                .chain(&op.controls)
+                .filter_map(|reg| self.row(reg)),
+        );
+        for child in &op.children {
+            self.collect_rows(child, rows);
+        }
+    }
+
+    fn row(&self, reg: &Register) -> Option<usize> {
+        self.register_to_row.get(&(reg.q_id, reg.c_id)).copied()
+    }
+
+    fn widen_column(&mut self, column: usize, width: usize) {
+        if self.column_widths.len() <= column {
+            self.column_widths.resize(column + 1, MIN_GATE_WIDTH);
+        }
+        self.column_widths[column] = self.column_widths[column].max(width);
+    }
+
+    /// Horizontal space between columns, leaving room for the boxes of nested groups.
+    fn column_gap(&self) -> usize {
+        COLUMN_GAP + 2 * self.max_height * GROUP_PADDING
+    }
+
+    /// Width of the space on the left reserved for qubit labels.
+    fn label_width(&self) -> usize {
+        self.wires
+            .iter()
+            .map(|wire| match wire {
+                Wire::Qubit { q_id } => qubit_label(*q_id).chars().count() * CHAR_WIDTH,
+                Wire::Classical => 0,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Left edge of `column`.
+    fn column_x(&self, column: usize) -> usize {
+        PADDING
+            + self.label_width()
+            + (column + 1) * self.column_gap()
+            + self.column_widths[..column].iter().sum::<usize>()
+    }
+
+    fn column_center(&self, column: usize) -> usize {
+        self.column_x(column) + self.column_widths[column] / 2
+    }
+
+    /// Vertical distance between adjacent wires, leaving room for the boxes and labels of nested groups.
+    fn row_height(&self) -> usize {
+        GATE_HEIGHT + ROW_GAP + self.max_height * (2 * GROUP_PADDING + GROUP_LABEL_HEIGHT)
+    }
+
+    /// Vertical position of the wire of `row`.
+    fn row_y(&self, row: usize) -> usize {
+        PADDING
+            + self.max_height * (GROUP_PADDING + GROUP_LABEL_HEIGHT)
+            + row * self.row_height()
+            + GATE_HEIGHT / 2
+    }
+
+    fn width(&self) -> usize {
+        self.column_x(self.column_widths.len()) + PADDING
+    }
+
+    fn height(&self) -> usize {
+        match self.wires.len() {
+            0 => 2 * PADDING,
+            rows => {
+                self.row_y(rows - 1) + GATE_HEIGHT / 2 + self.max_height * GROUP_PADDING + PADDING
+            }
+        }
+    }
+
+    fn fmt_wires(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Classical wires start at the measurement that writes them.
+        let mut classical_starts = FxHashMap::default();
+        for gate in self.gates.iter().filter(|gate| gate.op.is_measurement) {
+            for row in gate.op.targets.iter().filter_map(|reg| self.row(reg)) {
+                let start = classical_starts.entry(row).or_insert(gate.column);
+                *start = (*start).min(gate.column);
+            }
+        }
+
+        let wire_start = PADDING + self.label_width() + self.column_gap() / 2;
+        let wire_end = self.width() - PADDING;
+        for (row, wire) in self.wires.iter().enumerate() {
+            let y = self.row_y(row);
+            match wire {
+                Wire::Qubit { q_id } => {
+                    writeln!(
+                        f,
+                        r#"<text x="{PADDING}" y="{y}" dominant-baseline="middle">{}</text>"#,
+                        qubit_label(*q_id)
+                    )?;
+                    fmt_line(f, wire_start, y, wire_end, y)?;
+                }
+                Wire::Classical => {
+                    if let Some(&column) = classical_starts.get(&row) {
+                        let x = self.column_center(column);
+                        fmt_line(
+                            f,
+                            x,
+                            y - CLASSICAL_WIRE_SPACING,
+                            wire_end,
+                            y - CLASSICAL_WIRE_SPACING,
+                        )?;
+                        fmt_line(
+                            f,
+                            x,
+                            y + CLASSICAL_WIRE_SPACING,
+                            wire_end,
+                            y + CLASSICAL_WIRE_SPACING,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_group(&self, f: &mut fmt::Formatter<'_>, group: &Group) -> fmt::Result {
+        let x = self.column_x(group.start_column) - group.height * GROUP_PADDING;
+        let width = self.column_x(group.end_column - 1)
+            + self.column_widths[group.end_column - 1]
+            + group.height * GROUP_PADDING
+            - x;
+        let y = self.row_y(group.first_row)
+            - GATE_HEIGHT / 2
+            - group.height * (GROUP_PADDING + GROUP_LABEL_HEIGHT);
+        let height =
+            self.row_y(group.last_row) + GATE_HEIGHT / 2 + group.height * GROUP_PADDING - y;
+        writeln!(
+            f,
+            r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="none" stroke="gray" stroke-dasharray="4 2"/>"#
+        )?;
+        writeln!(
+            f,
+            r#"<text x="{}" y="{}" font-size="11">{}</text>"#,
+            x + GROUP_PADDING / 2,
+            y + GROUP_LABEL_HEIGHT,
+            escape(&gate_label(group.op))
+        )?;
+        // Controls of a group, such as the results a conditional group depends on,
+        // are drawn on the edge of its box.
+        if group.op.is_controlled {
+            for row in rows_of(self, &group.op.controls) {
+                writeln!(
+                    f,
+                    r#"<circle cx="{x}" cy="{}" r="{CONTROL_RADIUS}" fill="black"/>"#,
+                    self.row_y(row)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_gate(&self, f: &mut fmt::Formatter<'_>, gate: &Gate) -> fmt::Result {
+        let op = gate.op;
+        let x = self.column_center(gate.column);
+        let targets = rows_of(self, &op.targets);
+        let controls = rows_of(self, &op.controls);
+        if op.is_measurement {
+            // Measurements have the measured qubits as controls and the results as targets.
+            for &row in &controls {
+                for &target in &targets {
+                    let y = self.row_y(row) + GATE_HEIGHT / 2;
+                    let target_y = self.row_y(target);
+                    fmt_line(
+                        f,
+                        x - CLASSICAL_WIRE_SPACING,
+                        y,
+                        x - CLASSICAL_WIRE_SPACING,
+                        target_y,
+                    )?;
+                    fmt_line(
+                        f,
+                        x + CLASSICAL_WIRE_SPACING,
+                        y,
+                        x + CLASSICAL_WIRE_SPACING,
+                        target_y,
+                    )?;
+                }
+                fmt_measurement(f, x, self.row_y(row))?;
+            }
+            return Ok(());
+        }
+
+        let (Some(&first_target), Some(&last_target)) =
+            (targets.iter().min(), targets.iter().max())
+        else {
+            return Ok(());
+        };
+
+        if op.is_controlled {
+            let first = controls.iter().copied().fold(first_target, usize::min);
+            let last = controls.iter().copied().fold(last_target, usize::max);
+            fmt_line(f, x, self.row_y(first), x, self.row_y(last))?;
+            for &row in &controls {
+                writeln!(
+                    f,
+                    r#"<circle cx="{x}" cy="{}" r="{CONTROL_RADIUS}" fill="black"/>"#,
+                    self.row_y(row)
+                )?;
+            }
+            if op.gate == "X" && !op.is_adjoint && op.display_args.is_none() {
+                for &row in &targets {
+                    fmt_oplus(f, x, self.row_y(row))?;
+                }
+                return Ok(());
+            }
+        }
+
+        // Multiple targets share a single box.
+        let width = gate_width(op);
+        let y = self.row_y(first_target) - GATE_HEIGHT / 2;
+        let height = self.row_y(last_target) + GATE_HEIGHT / 2 - y;
+        writeln!(
+            f,
+            r#"<rect x="{}" y="{y}" width="{width}" height="{height}" fill="white" stroke="black"/>"#,
+            x - width / 2,
+        )?;
+        let center = y + height / 2;
+        let mut label = escape(&op.gate);
+        if op.is_adjoint {
+            label.push('†');
+        }
+        match &op.display_args {
+            Some(args) => {
+                writeln!(
+                    f,
+                    r#"<text x="{x}" y="{}" text-anchor="middle" dominant-baseline="middle">{label}</text>"#,
+                    center - 6
+                )?;
+                writeln!(
+                    f,
+                    r#"<text x="{x}" y="{}" text-anchor="middle" dominant-baseline="middle" font-size="11">({})</text>"#,
+                    center + 10,
+                    escape(args)
+                )
+            }
+            None => writeln!(
+                f,
+                r#"<text x="{x}" y="{center}" text-anchor="middle" dominant-baseline="middle">{label}</text>"#
+            ),
+        }
+    }
+}
+
+impl Display for Layout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width();
+        let height = self.height();
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="14">"#
+        )?;
+        writeln!(
+            f,
+            r#"<rect width="{width}" height="{height}" fill="white"/>"#
+        )?;
+        self.fmt_wires(f)?;
+        // Outer groups are placed after their children, so draw groups in reverse
+        // to keep inner boxes on top.
+        for group in self.groups.iter().rev() {
+            self.fmt_group(f, group)?;
+        }
+        for gate in &self.gates {
+            self.fmt_gate(f, gate)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+fn rows_of(layout: &Layout, registers: &[Register]) -> Vec<usize> {
+    registers.iter().filter_map(|reg| layout.row(reg)).collect()
+}
+
+fn qubit_label(q_id: usize) -> String {
+    format!("q_{q_id}")
+}
+
+/// The label of a group box: the gate name with its adjoint marker and arguments.
+fn gate_label(op: &Operation) -> String {
+    let mut label = op.gate.clone();
+    if op.is_adjoint {
+        label.push('†');
+    }
+    if let Some(args) = &op.display_args {
+        let _ = write!(label, "({args})");
+    }
+    label
+}
+
+fn gate_width(op: &Operation) -> usize {
+    let gate = (op.gate.chars().count() + usize::from(op.is_adjoint)) * CHAR_WIDTH;
+    let args = op
+        .display_args
+        .as_ref()
+        .map_or(0, |args| (args.chars().count() + 2) * SMALL_CHAR_WIDTH);
+    (gate.max(args) + 2 * GATE_PADDING).max(MIN_GATE_WIDTH)
+}
+
+fn fmt_line(f: &mut fmt::Formatter<'_>, x1: usize, y1: usize, x2: usize, y2: usize) -> fmt::Result {
+    writeln!(
+        f,
+        r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="black"/>"#
+    )
+}
+
+/// A box with a meter.
+fn fmt_measurement(f: &mut fmt::Formatter<'_>, x: usize, y: usize) -> fmt::Result {
+    writeln!(
+        f,
+        r#"<rect x="{}" y="{}" width="{MIN_GATE_WIDTH}" height="{GATE_HEIGHT}" fill="white" stroke="black"/>"#,
+        x - MIN_GATE_WIDTH / 2,
+        y - GATE_HEIGHT / 2
+    )?;
+    writeln!(
+        f,
+        r#"<path d="M {} {} A 12 12 0 0 1 {} {}" fill="none" stroke="black"/>"#,
+        x - 12,
+        y + 6,
+        x + 12,
+        y + 6
+    )?;
+    fmt_line(f, x, y + 6, x + 8, y - 10)
+}
+
+/// The target of a controlled X: a circle with a cross.
+fn fmt_oplus(f: &mut fmt::Formatter<'_>, x: usize, y: usize) -> fmt::Result {
+    writeln!(
+        f,
+        r#"<circle cx="{x}" cy="{y}" r="{OPLUS_RADIUS}" fill="white" stroke="black"/>"#
+    )?;
+    fmt_line(f, x - OPLUS_RADIUS, y, x + OPLUS_RADIUS, y)?;
+    fmt_line(f, x, y - OPLUS_RADIUS, x, y + OPLUS_RADIUS)
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,283 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::circuit::{Circuit, Operation, Qubit, Register};
+use expect_test::expect;
+
+fn gate(name: &str, controls: Vec<Register>, targets: Vec<Register>) -> Operation {
+    Operation {
+        gate: name.to_string(),
+        display_args: None,
+        is_controlled: !controls.is_empty(),
+        is_adjoint: false,
+        is_measurement: false,
+        controls,
+        targets,
+        children: vec![],
+    }
+}
+
+fn measurement(q_id: usize, c_id: usize) -> Operation {
+    Operation {
+        gate: "Measure".to_string(),
+        display_args: None,
+        is_controlled: false,
+        is_adjoint: false,
+        is_measurement: true,
+        controls: vec![Register::quantum(q_id)],
+        targets: vec![Register::classical(q_id, c_id)],
+        children: vec![],
+    }
+}
+
+fn qubits(num_children: &[usize]) -> Vec<Qubit> {
+    num_children
+        .iter()
+        .enumerate()
+        .map(|(id, &num_children)| Qubit { id, num_children })
+        .collect()
+}
+
+#[test]
+fn empty() {
+    let c = Circuit::default();
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 30 20" font-family="monospace" font-size="14">
+        <rect width="30" height="20" fill="white"/>
+        </svg>
+    "#]]
+    .assert_eq(&c.to_svg());
+}
+
+#[test]
+fn bell() {
+    let c = Circuit {
+        operations: vec![
+            gate("H", vec![], vec![Register::quantum(0)]),
+            gate("X", vec![Register::quantum(0)], vec![Register::quantum(1)]),
+            measurement(0, 0),
+            measurement(1, 0),
+        ],
+        qubits: qubits(&[1, 1]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="250" height="240" viewBox="0 0 250 240" font-family="monospace" font-size="14">
+        <rect width="250" height="240" fill="white"/>
+        <text x="10" y="30" dominant-baseline="middle">q_0</text>
+        <line x1="42" y1="30" x2="240" y2="30" stroke="black"/>
+        <line x1="188" y1="88" x2="240" y2="88" stroke="black"/>
+        <line x1="188" y1="92" x2="240" y2="92" stroke="black"/>
+        <text x="10" y="150" dominant-baseline="middle">q_1</text>
+        <line x1="42" y1="150" x2="240" y2="150" stroke="black"/>
+        <line x1="188" y1="208" x2="240" y2="208" stroke="black"/>
+        <line x1="188" y1="212" x2="240" y2="212" stroke="black"/>
+        <rect x="47" y="10" width="40" height="40" fill="white" stroke="black"/>
+        <text x="67" y="30" text-anchor="middle" dominant-baseline="middle">H</text>
+        <line x1="117" y1="30" x2="117" y2="150" stroke="black"/>
+        <circle cx="117" cy="30" r="5" fill="black"/>
+        <circle cx="117" cy="150" r="12" fill="white" stroke="black"/>
+        <line x1="105" y1="150" x2="129" y2="150" stroke="black"/>
+        <line x1="117" y1="138" x2="117" y2="162" stroke="black"/>
+        <line x1="186" y1="50" x2="186" y2="90" stroke="black"/>
+        <line x1="190" y1="50" x2="190" y2="90" stroke="black"/>
+        <rect x="168" y="10" width="40" height="40" fill="white" stroke="black"/>
+        <path d="M 176 36 A 12 12 0 0 1 200 36" fill="none" stroke="black"/>
+        <line x1="188" y1="36" x2="196" y2="20" stroke="black"/>
+        <line x1="186" y1="170" x2="186" y2="210" stroke="black"/>
+        <line x1="190" y1="170" x2="190" y2="210" stroke="black"/>
+        <rect x="168" y="130" width="40" height="40" fill="white" stroke="black"/>
+        <path d="M 176 156 A 12 12 0 0 1 200 156" fill="none" stroke="black"/>
+        <line x1="188" y1="156" x2="196" y2="140" stroke="black"/>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
+
+#[test]
+fn adjoint_with_args() {
+    let c = Circuit {
+        operations: vec![Operation {
+            is_adjoint: true,
+            display_args: Some("1.5708".to_string()),
+            ..gate("rx", vec![], vec![Register::quantum(0)])
+        }],
+        qubits: qubits(&[0]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="143" height="60" viewBox="0 0 143 60" font-family="monospace" font-size="14">
+        <rect width="143" height="60" fill="white"/>
+        <text x="10" y="30" dominant-baseline="middle">q_0</text>
+        <line x1="42" y1="30" x2="133" y2="30" stroke="black"/>
+        <rect x="47" y="10" width="76" height="40" fill="white" stroke="black"/>
+        <text x="85" y="24" text-anchor="middle" dominant-baseline="middle">rx†</text>
+        <text x="85" y="40" text-anchor="middle" dominant-baseline="middle" font-size="11">(1.5708)</text>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
+
+#[test]
+fn controlled_gate_with_two_targets() {
+    let c = Circuit {
+        operations: vec![Operation {
+            display_args: Some("1.0000".to_string()),
+            ..gate(
+                "rzz",
+                vec![Register::quantum(0)],
+                vec![Register::quantum(1), Register::quantum(2)],
+            )
+        }],
+        qubits: qubits(&[0, 0, 0]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="143" height="180" viewBox="0 0 143 180" font-family="monospace" font-size="14">
+        <rect width="143" height="180" fill="white"/>
+        <text x="10" y="30" dominant-baseline="middle">q_0</text>
+        <line x1="42" y1="30" x2="133" y2="30" stroke="black"/>
+        <text x="10" y="90" dominant-baseline="middle">q_1</text>
+        <line x1="42" y1="90" x2="133" y2="90" stroke="black"/>
+        <text x="10" y="150" dominant-baseline="middle">q_2</text>
+        <line x1="42" y1="150" x2="133" y2="150" stroke="black"/>
+        <line x1="85" y1="30" x2="85" y2="150" stroke="black"/>
+        <circle cx="85" cy="30" r="5" fill="black"/>
+        <rect x="47" y="70" width="76" height="100" fill="white" stroke="black"/>
+        <text x="85" y="114" text-anchor="middle" dominant-baseline="middle">rzz</text>
+        <text x="85" y="130" text-anchor="middle" dominant-baseline="middle" font-size="11">(1.0000)</text>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
+
+#[test]
+fn classically_controlled() {
+    let c = Circuit {
+        operations: vec![
+            measurement(0, 0),
+            gate(
+                "X",
+                vec![Register::classical(0, 0)],
+                vec![Register::quantum(1)],
+            ),
+        ],
+        qubits: qubits(&[1, 0]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="200" height="180" viewBox="0 0 200 180" font-family="monospace" font-size="14">
+        <rect width="200" height="180" fill="white"/>
+        <text x="10" y="30" dominant-baseline="middle">q_0</text>
+        <line x1="42" y1="30" x2="190" y2="30" stroke="black"/>
+        <line x1="88" y1="88" x2="190" y2="88" stroke="black"/>
+        <line x1="88" y1="92" x2="190" y2="92" stroke="black"/>
+        <text x="10" y="150" dominant-baseline="middle">q_1</text>
+        <line x1="42" y1="150" x2="190" y2="150" stroke="black"/>
+        <line x1="86" y1="50" x2="86" y2="90" stroke="black"/>
+        <line x1="90" y1="50" x2="90" y2="90" stroke="black"/>
+        <rect x="68" y="10" width="40" height="40" fill="white" stroke="black"/>
+        <path d="M 76 36 A 12 12 0 0 1 100 36" fill="none" stroke="black"/>
+        <line x1="88" y1="36" x2="96" y2="20" stroke="black"/>
+        <line x1="160" y1="90" x2="160" y2="150" stroke="black"/>
+        <circle cx="160" cy="90" r="5" fill="black"/>
+        <circle cx="160" cy="150" r="12" fill="white" stroke="black"/>
+        <line x1="148" y1="150" x2="172" y2="150" stroke="black"/>
+        <line x1="160" y1="138" x2="160" y2="162" stroke="black"/>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
+
+#[test]
+fn nested_groups() {
+    let c = Circuit {
+        operations: vec![
+            Operation {
+                children: vec![
+                    gate("H", vec![], vec![Register::quantum(0)]),
+                    Operation {
+                        children: vec![gate(
+                            "X",
+                            vec![Register::quantum(0)],
+                            vec![Register::quantum(1)],
+                        )],
+                        ..gate(
+                            "Entangle",
+                            vec![],
+                            vec![Register::quantum(0), Register::quantum(1)],
+                        )
+                    },
+                ],
+                ..gate(
+                    "PrepareBell",
+                    vec![],
+                    vec![Register::quantum(0), Register::quantum(1)],
+                )
+            },
+            gate("Z", vec![], vec![Register::quantum(1)]),
+        ],
+        qubits: qubits(&[0, 0]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="319" height="216" viewBox="0 0 319 216" font-family="monospace" font-size="14">
+        <rect width="319" height="216" fill="white"/>
+        <text x="10" y="66" dominant-baseline="middle">q_0</text>
+        <line x1="54" y1="66" x2="309" y2="66" stroke="black"/>
+        <text x="10" y="174" dominant-baseline="middle">q_1</text>
+        <line x1="54" y1="174" x2="309" y2="174" stroke="black"/>
+        <rect x="59" y="10" width="154" height="196" fill="none" stroke="gray" stroke-dasharray="4 2"/>
+        <text x="62" y="22" font-size="11">PrepareBell</text>
+        <rect x="139" y="28" width="68" height="172" fill="none" stroke="gray" stroke-dasharray="4 2"/>
+        <text x="142" y="40" font-size="11">Entangle</text>
+        <rect x="71" y="46" width="40" height="40" fill="white" stroke="black"/>
+        <text x="91" y="66" text-anchor="middle" dominant-baseline="middle">H</text>
+        <line x1="173" y1="66" x2="173" y2="174" stroke="black"/>
+        <circle cx="173" cy="66" r="5" fill="black"/>
+        <circle cx="173" cy="174" r="12" fill="white" stroke="black"/>
+        <line x1="161" y1="174" x2="185" y2="174" stroke="black"/>
+        <line x1="173" y1="162" x2="173" y2="186" stroke="black"/>
+        <rect x="235" y="154" width="40" height="40" fill="white" stroke="black"/>
+        <text x="255" y="174" text-anchor="middle" dominant-baseline="middle">Z</text>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
+
+#[test]
+fn conditional_group() {
+    let c = Circuit {
+        operations: vec![
+            measurement(0, 0),
+            Operation {
+                display_args: Some("c_0 = One".to_string()),
+                children: vec![gate("X", vec![], vec![Register::quantum(1)])],
+                ..gate(
+                    "if",
+                    vec![Register::classical(0, 0)],
+                    vec![Register::quantum(1)],
+                )
+            },
+        ],
+        qubits: qubits(&[1, 0]),
+    };
+
+    expect![[r#"
+        <svg xmlns="http://www.w3.org/2000/svg" width="287" height="252" viewBox="0 0 287 252" font-family="monospace" font-size="14">
+        <rect width="287" height="252" fill="white"/>
+        <text x="10" y="48" dominant-baseline="middle">q_0</text>
+        <line x1="48" y1="48" x2="277" y2="48" stroke="black"/>
+        <line x1="100" y1="130" x2="277" y2="130" stroke="black"/>
+        <line x1="100" y1="134" x2="277" y2="134" stroke="black"/>
+        <text x="10" y="216" dominant-baseline="middle">q_1</text>
+        <line x1="48" y1="216" x2="277" y2="216" stroke="black"/>
+        <rect x="158" y="94" width="103" height="148" fill="none" stroke="gray" stroke-dasharray="4 2"/>
+        <text x="161" y="106" font-size="11">if(c_0 = One)</text>
+        <circle cx="158" cy="132" r="5" fill="black"/>
+        <line x1="98" y1="68" x2="98" y2="132" stroke="black"/>
+        <line x1="102" y1="68" x2="102" y2="132" stroke="black"/>
+        <rect x="80" y="28" width="40" height="40" fill="white" stroke="black"/>
+        <path d="M 88 54 A 12 12 0 0 1 112 54" fill="none" stroke="black"/>
+        <line x1="100" y1="54" x2="108" y2="38" stroke="black"/>
+        <rect x="189" y="196" width="40" height="40" fill="white" stroke="black"/>
+        <text x="209" y="216" text-anchor="middle" dominant-baseline="middle">X</text>
+        </svg>
+    "#]].assert_eq(&c.to_svg());
+}
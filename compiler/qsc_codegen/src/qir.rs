@@ -48,6 +48,16 @@ pub fn fir_to_qir(
     Ok(ToQir::<String>::to_qir(&program, &program))
 }
 
+/// Partially evaluates the program starting at `entry` into RIR, without running any RIR passes.
+pub fn fir_to_rir(
+    fir_store: &qsc_fir::fir::PackageStore,
+    capabilities: TargetCapabilityFlags,
+    compute_properties: Option<PackageStoreComputeProperties>,
+    entry: &ProgramEntry,
+) -> Result<rir::Program, qsc_partial_eval::Error> {
+    get_rir_from_compilation(fir_store, compute_properties, entry, capabilities)
+}
+
 fn get_rir_from_compilation(
     fir_store: &qsc_fir::fir::PackageStore,
     compute_properties: Option<PackageStoreComputeProperties>,
@@ -10,6 +10,7 @@ use qsc_hir::{
 use regex_lite::Regex;
 use std::{
     fmt::{Display, Formatter, Result},
+    ops::Range,
     rc::Rc,
 };
 
@@ -54,11 +55,33 @@ pub struct CodeDisplay<'a> {
     pub compilation: &'a dyn Lookup,
 }
 
+/// Links user-defined types to their documentation when displaying HIR items as HTML.
+pub struct HtmlLinks<'a> {
+    /// The package the displayed item belongs to.
+    pub package_id: PackageId,
+    /// Returns the link target for the item with the given id, or `None`
+    /// if the item has no page to link to.
+    pub link: &'a dyn Fn(hir::ItemId) -> Option<String>,
+}
+
 #[allow(clippy::unused_self)]
 impl<'a> CodeDisplay<'a> {
     #[must_use]
     pub fn hir_callable_decl(&self, decl: &'a hir::CallableDecl) -> impl Display + '_ {
-        HirCallableDecl { decl }
+        HirCallableDecl { decl, html: None }
+    }
+
+    /// Displays a callable declaration as HTML, with user-defined types linked using `links`.
+    #[must_use]
+    pub fn hir_callable_decl_html(
+        &self,
+        decl: &'a hir::CallableDecl,
+        links: &'a HtmlLinks<'a>,
+    ) -> impl Display + 'a {
+        HirCallableDecl {
+            decl,
+            html: Some(links),
+        }
     }
 
     #[must_use]
@@ -100,17 +123,23 @@ impl<'a> CodeDisplay<'a> {
 
     #[must_use]
     pub fn hir_udt(&self, udt: &'a ty::Udt) -> impl Display + '_ {
-        HirUdt::new(udt)
+        HirUdt::new(udt, None)
+    }
+
+    /// Displays a user-defined type as HTML, with user-defined types linked using `links`.
+    #[must_use]
+    pub fn hir_udt_html(&self, udt: &'a ty::Udt, links: &'a HtmlLinks<'a>) -> impl Display + 'a {
+        HirUdt::new(udt, Some(links))
     }
 
     #[must_use]
     pub fn hir_pat(&self, pat: &'a hir::Pat) -> impl Display + '_ {
-        HirPat { pat }
+        HirPat { pat, html: None }
     }
 
     #[must_use]
     pub fn get_param_offset(&self, decl: &hir::CallableDecl) -> u32 {
-        HirCallableDecl { decl }.get_param_offset()
+        HirCallableDecl { decl, html: None }.get_param_offset()
     }
 
     // The rest of the display implementations are not made public b/c they're not used,
@@ -152,6 +181,7 @@ impl<'a> Display for NameTyId<'a> {
 
 struct HirCallableDecl<'a> {
     decl: &'a hir::CallableDecl,
+    html: Option<&'a HtmlLinks<'a>>,
 }
 
 impl HirCallableDecl<'_> {
@@ -177,9 +207,14 @@ impl Display for HirCallableDecl<'_> {
 
         write!(f, "{} {}", kind, self.decl.name.name)?;
         let type_params = display_type_params(&self.decl.generics);
-        write!(f, "{type_params}")?;
+        if self.html.is_some() {
+            write!(f, "{}", escape_html(&type_params))?;
+        } else {
+            write!(f, "{type_params}")?;
+        }
         let input = HirPat {
             pat: &self.decl.input,
+            html: self.html,
         };
         if matches!(self.decl.input.kind, hir::PatKind::Tuple(_)) {
             write!(f, "{input}")?;
@@ -189,7 +224,10 @@ impl Display for HirCallableDecl<'_> {
         write!(
             f,
             " : {}{}",
-            self.decl.output.display(),
+            HirTy {
+                ty: &self.decl.output,
+                html: self.html,
+            },
             FunctorSetValue {
                 functors: self.decl.functors,
             },
@@ -245,19 +283,28 @@ impl<'a> Display for AstCallableDecl<'a> {
 
 struct HirPat<'a> {
     pat: &'a hir::Pat,
+    html: Option<&'a HtmlLinks<'a>>,
 }
 
 impl<'a> Display for HirPat<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let ty = HirTy {
+            ty: &self.pat.ty,
+            html: self.html,
+        };
         match &self.pat.kind {
-            hir::PatKind::Bind(name) => write!(f, "{} : {}", name.name, self.pat.ty.display()),
-            hir::PatKind::Discard => write!(f, "_ : {}", self.pat.ty.display()),
+            hir::PatKind::Bind(name) => write!(f, "{} : {ty}", name.name),
+            hir::PatKind::Discard => write!(f, "_ : {ty}"),
             hir::PatKind::Tuple(items) => {
                 let mut elements = items.iter();
+                let pat = |pat| HirPat {
+                    pat,
+                    html: self.html,
+                };
                 if let Some(elem) = elements.next() {
-                    write!(f, "({}", HirPat { pat: elem })?;
+                    write!(f, "({}", pat(elem))?;
                     for elem in elements {
-                        write!(f, ", {}", HirPat { pat: elem })?;
+                        write!(f, ", {}", pat(elem))?;
                     }
                     write!(f, ")")
                 } else {
@@ -381,13 +428,15 @@ impl Display for StructDecl<'_> {
 struct HirUdt<'a> {
     udt: &'a ty::Udt,
     is_struct: bool,
+    html: Option<&'a HtmlLinks<'a>>,
 }
 
 impl<'a> HirUdt<'a> {
-    fn new(udt: &'a ty::Udt) -> Self {
+    fn new(udt: &'a ty::Udt, html: Option<&'a HtmlLinks<'a>>) -> Self {
         HirUdt {
             udt,
             is_struct: udt.is_struct(),
+            html,
         }
     }
 }
@@ -398,13 +447,13 @@ impl<'a> Display for HirUdt<'a> {
             match &self.udt.definition.kind {
                 ty::UdtDefKind::Tuple(fields) => {
                     write!(f, "struct {} ", self.udt.name)?;
-                    fmt_brace_seq(f, fields, UdtDef::new)?;
+                    fmt_brace_seq(f, fields, |field| UdtDef::new(field, self.html))?;
                 }
                 ty::UdtDefKind::Field(_) => {}
             }
             Ok(())
         } else {
-            let udt_def = UdtDef::new(&self.udt.definition);
+            let udt_def = UdtDef::new(&self.udt.definition, self.html);
             write!(f, "newtype {} = {}", self.udt.name, udt_def)
         }
     }
@@ -413,6 +462,7 @@ impl<'a> Display for HirUdt<'a> {
 struct UdtDef<'a> {
     name: Option<Rc<str>>,
     kind: UdtDefKind<'a>,
+    html: Option<&'a HtmlLinks<'a>>,
 }
 
 enum UdtDefKind<'a> {
@@ -421,15 +471,17 @@ enum UdtDefKind<'a> {
 }
 
 impl<'a> UdtDef<'a> {
-    pub fn new(def: &'a ty::UdtDef) -> Self {
+    pub fn new(def: &'a ty::UdtDef, html: Option<&'a HtmlLinks<'a>>) -> Self {
         match &def.kind {
             ty::UdtDefKind::Field(field) => UdtDef {
                 name: field.name.clone(),
                 kind: UdtDefKind::SingleTy(&field.ty),
+                html,
             },
             ty::UdtDefKind::Tuple(defs) => UdtDef {
                 name: None,
-                kind: UdtDefKind::TupleTy(defs.iter().map(UdtDef::new).collect()),
+                kind: UdtDefKind::TupleTy(defs.iter().map(|def| UdtDef::new(def, html)).collect()),
+                html,
             },
         }
     }
@@ -442,9 +494,14 @@ impl Display for UdtDef<'_> {
         }
 
         match &self.kind {
-            UdtDefKind::SingleTy(ty) => {
-                write!(f, "{}", ty.display())
-            }
+            UdtDefKind::SingleTy(ty) => write!(
+                f,
+                "{}",
+                HirTy {
+                    ty,
+                    html: self.html
+                }
+            ),
             UdtDefKind::TupleTy(defs) => fmt_tuple(f, defs, |def| def),
         }
     }
@@ -463,6 +520,58 @@ impl Display for HirUdtField<'_> {
     }
 }
 
+struct HirTy<'a> {
+    ty: &'a ty::Ty,
+    html: Option<&'a HtmlLinks<'a>>,
+}
+
+impl Display for HirTy<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Some(links) = self.html else {
+            return write!(f, "{}", self.ty.display());
+        };
+        let ty = |ty| HirTy {
+            ty,
+            html: self.html,
+        };
+        match self.ty {
+            ty::Ty::Array(item) => write!(f, "{}[]", ty(item)),
+            ty::Ty::Arrow(arrow) => {
+                let arrow_symbol = match arrow.kind {
+                    hir::CallableKind::Function => "-&gt;",
+                    hir::CallableKind::Operation => "=&gt;",
+                };
+                let functors = match arrow.functors {
+                    ty::FunctorSet::Value(ty::FunctorSetValue::Empty)
+                    | ty::FunctorSet::Param(_, ty::FunctorSetValue::Empty) => String::new(),
+                    ty::FunctorSet::Value(_) | ty::FunctorSet::Infer(_) => {
+                        format!(" is {}", arrow.functors)
+                    }
+                    ty::FunctorSet::Param(_, functors) => format!(" is {functors}"),
+                };
+                write!(
+                    f,
+                    "({} {arrow_symbol} {}{functors})",
+                    ty(&arrow.input),
+                    ty(&arrow.output)
+                )
+            }
+            ty::Ty::Tuple(items) => fmt_tuple(f, items, ty),
+            ty::Ty::Udt(name, hir::Res::Item(item_id)) => {
+                let item_id = hir::ItemId {
+                    package: Some(item_id.package.unwrap_or(links.package_id)),
+                    item: item_id.item,
+                };
+                match (links.link)(item_id) {
+                    Some(link) => write!(f, "<a href=\"{}\">{name}</a>", escape_html(&link)),
+                    None => write!(f, "{name}"),
+                }
+            }
+            _ => write!(f, "{}", escape_html(&self.ty.display())),
+        }
+    }
+}
+
 struct FunctorSetValue {
     functors: ty::FunctorSetValue,
 }
@@ -630,6 +739,23 @@ fn display_type_params(generics: &[GenericParam]) -> String {
     }
 }
 
+/// Escapes the characters that have a special meaning in HTML text and attribute values.
+#[must_use]
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 //
 // helpers that don't manipulate any strings
 //
@@ -766,3 +892,55 @@ pub fn parse_doc_for_param(doc: &str, param: &str) -> String {
     .trim()
     .to_string()
 }
+
+/// A fenced Q# code block found in the `# Example` section of a doc string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocExample {
+    /// The code in the block.
+    pub code: String,
+    /// The zero-based lines of the doc string that the code occupies.
+    pub lines: Range<usize>,
+}
+
+/// Takes a doc string from Q# and returns the `qsharp` code blocks in its
+/// `# Example` and `# Examples` sections. Code blocks in other sections are ignored.
+#[must_use]
+pub fn parse_doc_for_examples(doc: &str) -> Vec<DocExample> {
+    let mut examples = Vec::new();
+    let mut in_example_section = false;
+    // While inside a fenced block, the first line and the code of the block if it is an example.
+    let mut fenced: Option<Option<(usize, String)>> = None;
+    for (index, line) in doc.lines().enumerate() {
+        let fence = line.trim_start().strip_prefix("```");
+        match (&mut fenced, fence) {
+            (Some(example), Some(_)) => {
+                if let Some((start, code)) = example.take() {
+                    examples.push(DocExample {
+                        code,
+                        lines: start..index,
+                    });
+                }
+                fenced = None;
+            }
+            (Some(example), None) => {
+                if let Some((_, code)) = example {
+                    code.push_str(line);
+                    code.push('\n');
+                }
+            }
+            (None, Some(lang)) => {
+                let is_example = in_example_section && lang.trim() == "qsharp";
+                fenced = Some(is_example.then(|| (index + 1, String::new())));
+            }
+            (None, None) => {
+                if let Some(heading) = line.strip_prefix("# ") {
+                    in_example_section = matches!(
+                        heading.trim().to_lowercase().as_str(),
+                        "example" | "examples"
+                    );
+                }
+            }
+        }
+    }
+    examples
+}
@@ -1,17 +1,18 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+mod html;
 #[cfg(test)]
 mod tests;
 
 use crate::display::{increase_header_level, parse_doc_for_summary};
-use crate::display::{CodeDisplay, Lookup};
+use crate::display::{CodeDisplay, HtmlLinks, Lookup};
 use qsc_ast::ast;
 use qsc_data_structures::language_features::LanguageFeatures;
 use qsc_data_structures::target::TargetCapabilityFlags;
 use qsc_frontend::compile::{self, compile, PackageStore, SourceMap};
 use qsc_frontend::resolve;
-use qsc_hir::hir::{CallableKind, Item, ItemKind, Package, PackageId, Visibility};
+use qsc_hir::hir::{CallableKind, Item, ItemId, ItemKind, Package, PackageId, Visibility};
 use qsc_hir::{hir, ty};
 use rustc_hash::FxHashMap;
 use std::fmt::{Display, Formatter, Result};
@@ -20,21 +21,31 @@ use std::sync::Arc;
 
 type Files = Vec<(Arc<str>, Arc<str>, Arc<str>)>;
 
+/// The format documentation files are generated in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DocFormat {
+    /// Markdown files with YAML metadata, along with a `toc.yml` table of contents.
+    #[default]
+    Markdown,
+    /// A static HTML site with a namespace index and a `search.json` search index.
+    Html,
+}
+
 /// Represents an immutable compilation state.
 #[derive(Debug)]
-struct Compilation {
-    /// Package store, containing the current package and all its dependencies.
-    package_store: PackageStore,
+struct Compilation<'a> {
+    /// Package store, containing the documented packages and all their dependencies.
+    package_store: &'a PackageStore,
 }
 
-impl Compilation {
-    /// Creates a new `Compilation` by compiling standard library
+impl Compilation<'_> {
+    /// Creates a package store by compiling standard library
     /// and additional sources.
-    pub(crate) fn new(
+    fn compile(
         additional_sources: Option<SourceMap>,
         capabilities: Option<TargetCapabilityFlags>,
         language_features: Option<LanguageFeatures>,
-    ) -> Self {
+    ) -> PackageStore {
         let mut package_store = PackageStore::new(compile::core());
         let actual_capabilities = capabilities.unwrap_or_default();
         let std_unit = compile::std(&package_store, actual_capabilities);
@@ -57,11 +68,37 @@ impl Compilation {
             package_store.insert(unit);
         }
 
-        Self { package_store }
+        package_store
+    }
+
+    /// Returns the path of the documentation page for the item with the given id,
+    /// relative to the root of the generated files, if the item is documented.
+    fn item_path(
+        &self,
+        packages: &[PackageId],
+        item_id: ItemId,
+        extension: &str,
+    ) -> Option<String> {
+        let package_id = item_id.package?;
+        if !packages.contains(&package_id) {
+            return None;
+        }
+        let package = &self.package_store.get(package_id)?.package;
+        let item = package.items.get(item_id.item)?;
+        if item.visibility == Visibility::Internal {
+            return None;
+        }
+        let name = match &item.kind {
+            ItemKind::Callable(decl) => &decl.name.name,
+            ItemKind::Ty(ident, _) => &ident.name,
+            ItemKind::Namespace(_, _) => return None,
+        };
+        let ns = get_namespace(package, item)?;
+        Some(format!("{ns}/{name}.{extension}"))
     }
 }
 
-impl Lookup for Compilation {
+impl Lookup for Compilation<'_> {
     fn get_ty(&self, _: ast::NodeId) -> Option<&ty::Ty> {
         unimplemented!("Not needed for docs generation")
     }
@@ -136,41 +173,91 @@ pub fn generate_docs(
     capabilities: Option<TargetCapabilityFlags>,
     language_features: Option<LanguageFeatures>,
 ) -> Files {
-    let compilation = Compilation::new(additional_sources, capabilities, language_features);
+    let package_store = Compilation::compile(additional_sources, capabilities, language_features);
+    let packages = package_store.iter().map(|(id, _)| id).collect::<Vec<_>>();
+    generate_docs_for_packages(&package_store, &packages, DocFormat::Markdown)
+}
+
+/// Generates and returns documentation files in the given `format` for the
+/// `packages` of `package_store`, such as a user project and the packages it
+/// depends on. The store must also contain every dependency of those packages.
+/// Types from packages that are not documented are not linked.
+#[must_use]
+pub fn generate_docs_for_packages(
+    package_store: &PackageStore,
+    packages: &[PackageId],
+    format: DocFormat,
+) -> Files {
+    let compilation = Compilation { package_store };
     let mut files: Files = vec![];
 
     let display = &CodeDisplay {
         compilation: &compilation,
     };
 
-    let mut toc: FxHashMap<Rc<str>, Vec<String>> = FxHashMap::default();
-    for (_, unit) in &compilation.package_store {
-        let package = &unit.package;
-        for (_, item) in &package.items {
-            if let Some((ns, line)) = generate_doc_for_item(package, item, display, &mut files) {
-                toc.entry(ns).or_default().push(line);
+    match format {
+        DocFormat::Markdown => {
+            let mut toc: FxHashMap<Rc<str>, Vec<String>> = FxHashMap::default();
+            for package in documented_packages(package_store, packages) {
+                for (_, item) in &package.items {
+                    if let Some((ns, line)) =
+                        generate_doc_for_item(package, item, display, &mut files)
+                    {
+                        toc.entry(ns).or_default().push(line);
+                    }
+                }
             }
+
+            generate_toc(&mut toc, &mut files);
+        }
+        DocFormat::Html => {
+            let link = |item_id| {
+                compilation
+                    .item_path(packages, item_id, "html")
+                    .map(|path| format!("../{path}"))
+            };
+            let mut pages = Vec::new();
+            for (package_id, package) in packages
+                .iter()
+                .copied()
+                .zip(documented_packages(package_store, packages))
+            {
+                let links = HtmlLinks {
+                    package_id,
+                    link: &link,
+                };
+                for (_, item) in &package.items {
+                    if let Some(page) = html::generate_page(package, item, display, &links) {
+                        pages.push(page);
+                    }
+                }
+            }
+            html::generate_site(pages, &mut files);
         }
     }
 
-    generate_toc(&mut toc, &mut files);
-
     files
 }
 
+fn documented_packages<'a>(
+    package_store: &'a PackageStore,
+    packages: &'a [PackageId],
+) -> impl Iterator<Item = &'a Package> {
+    packages.iter().map(|id| {
+        &package_store
+            .get(*id)
+            .expect("documented package should exist in store")
+            .package
+    })
+}
+
 fn generate_doc_for_item<'a>(
     package: &'a Package,
     item: &'a Item,
     display: &'a CodeDisplay,
     files: &mut Files,
 ) -> Option<(Rc<str>, String)> {
-    // Filter items
-    if item.visibility == Visibility::Internal || matches!(item.kind, ItemKind::Namespace(_, _)) {
-        return None;
-    }
-
-    // Get namespace for item
-    let ns = get_namespace(package, item)?;
+    let ns = get_documented_namespace(package, item)?;
 
     // Add file
     let (metadata, content) = generate_file(&ns, item, display)?;
@@ -186,6 +273,16 @@ fn generate_doc_for_item<'a>(
     Some((ns.clone(), line))
 }
 
+/// Returns the namespace of `item` if it should be documented.
+fn get_documented_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
+    // Filter items
+    if item.visibility == Visibility::Internal || matches!(item.kind, ItemKind::Namespace(_, _)) {
+        return None;
+    }
+
+    get_namespace(package, item)
+}
+
 fn get_namespace(package: &Package, item: &Item) -> Option<Rc<str>> {
     match item.parent {
         Some(local_id) => {
@@ -0,0 +1,402 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{get_documented_namespace, get_metadata, Files, MetadataKind};
+use crate::display::{escape_html, CodeDisplay, HtmlLinks};
+use qsc_hir::hir::{Item, ItemKind, Package};
+use serde::Serialize;
+use std::{fmt::Write, rc::Rc, sync::Arc};
+
+#[cfg(test)]
+mod tests;
+
+/// The documentation page generated for an item.
+pub(super) struct Page {
+    namespace: Rc<str>,
+    name: Rc<str>,
+    kind: &'static str,
+    summary: String,
+    contents: String,
+}
+
+/// An entry of the search index `search.json`.
+#[derive(Serialize)]
+struct SearchEntry<'a> {
+    name: &'a str,
+    namespace: &'a str,
+    kind: &'static str,
+    summary: &'a str,
+    url: String,
+}
+
+/// Generates the page for `item`, or `None` if the item is not documented.
+pub(super) fn generate_page(
+    package: &Package,
+    item: &Item,
+    display: &CodeDisplay,
+    links: &HtmlLinks,
+) -> Option<Page> {
+    let ns = get_documented_namespace(package, item)?;
+    let metadata = get_metadata(ns.clone(), item, display)?;
+    let signature = match &item.kind {
+        ItemKind::Callable(decl) => display.hir_callable_decl_html(decl, links).to_string(),
+        ItemKind::Ty(_, udt) => display.hir_udt_html(udt, links).to_string(),
+        ItemKind::Namespace(_, _) => return None,
+    };
+
+    let title = escape_html(&metadata.title);
+    let body = format!(
+        "<h1>{title}</h1>
+<p class=\"namespace\">Namespace: <a href=\"index.html\">{ns}</a></p>
+<pre><code class=\"language-qsharp\">{signature}</code></pre>
+{}",
+        render_doc(&item.doc)
+    );
+    let breadcrumbs = format!("<a href=\"index.html\">{ns}</a>");
+
+    Some(Page {
+        kind: match metadata.kind {
+            MetadataKind::Function => "function",
+            MetadataKind::Operation => "operation",
+            MetadataKind::Udt => "udt",
+        },
+        contents: html_page(&title, "../", &breadcrumbs, &body),
+        namespace: ns,
+        name: metadata.name,
+        summary: metadata.summary,
+    })
+}
+
+/// Adds the item `pages` to `files`, along with the namespace index pages,
+/// the search index `search.json` and the stylesheet shared by all pages.
+pub(super) fn generate_site(mut pages: Vec<Page>, files: &mut Files) {
+    pages.sort_unstable_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    pages.dedup_by(|a, b| a.namespace == b.namespace && a.name == b.name);
+
+    let mut namespaces = String::new();
+    let mut search = Vec::new();
+    for namespace_pages in pages.chunk_by(|a, b| a.namespace == b.namespace) {
+        let ns = &namespace_pages[0].namespace;
+        let _ = writeln!(namespaces, "<li><a href=\"{ns}/index.html\">{ns}</a></li>");
+
+        let mut rows = String::new();
+        for page in namespace_pages {
+            let name = &page.name;
+            let _ = writeln!(
+                rows,
+                "<tr><td><a href=\"{name}.html\">{name}</a></td><td>{}</td><td>{}</td></tr>",
+                page.kind,
+                render_inline(&page.summary)
+            );
+            search.push(SearchEntry {
+                name,
+                namespace: ns,
+                kind: page.kind,
+                summary: &page.summary,
+                url: format!("{ns}/{name}.html"),
+            });
+            add_file(files, format!("{ns}/{name}.html"), page.contents.clone());
+        }
+
+        let body = format!("<h1>{ns}</h1>\n<table class=\"items\">\n{rows}</table>\n");
+        add_file(
+            files,
+            format!("{ns}/index.html"),
+            html_page(ns, "../", "", &body),
+        );
+    }
+
+    let body = format!(
+        "<h1>Namespaces</h1>
+<input id=\"search\" type=\"search\" placeholder=\"Search\">
+<ul id=\"results\"></ul>
+<ul class=\"namespaces\">
+{namespaces}</ul>
+<script>{SEARCH_SCRIPT}</script>
+"
+    );
+    add_file(
+        files,
+        "index.html".to_string(),
+        html_page("Namespaces", "", "", &body),
+    );
+    add_file(
+        files,
+        "search.json".to_string(),
+        serde_json::to_string_pretty(&search).expect("search index should serialize") + "\n",
+    );
+    add_file(files, "styles.css".to_string(), STYLES.to_string());
+}
+
+fn add_file(files: &mut Files, name: String, contents: String) {
+    files.push((Arc::from(name), Arc::from(""), Arc::from(contents)));
+}
+
+/// Wraps `body` in an HTML document, where `root` is the relative path to the site root.
+fn html_page(title: &str, root: &str, breadcrumbs: &str, body: &str) -> String {
+    let separator = if breadcrumbs.is_empty() { "" } else { " / " };
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<link rel=\"stylesheet\" href=\"{root}styles.css\">
+</head>
+<body>
+<nav><a href=\"{root}index.html\">Namespaces</a>{separator}{breadcrumbs}</nav>
+<main>
+{body}</main>
+</body>
+</html>
+"
+    )
+}
+
+/// Renders each section of a doc comment, such as `# Summary`, `# Input` or `# Example`,
+/// as a `<section>` whose class is the lower case section name.
+fn render_doc(doc: &str) -> String {
+    let mut html = String::new();
+    let mut title = None;
+    let mut body = Vec::new();
+    let mut in_code = false;
+    for line in doc.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code {
+            if let Some(heading) = line.strip_prefix("# ") {
+                render_section(&mut html, title, &body);
+                title = Some(heading.trim());
+                body.clear();
+                continue;
+            }
+        }
+        body.push(line);
+    }
+    render_section(&mut html, title, &body);
+    html
+}
+
+fn render_section(html: &mut String, title: Option<&str>, body: &[&str]) {
+    match title {
+        Some(title) => {
+            let class = title
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        '-'
+                    }
+                })
+                .collect::<String>();
+            let _ = write!(
+                html,
+                "<section class=\"{class}\">\n<h2>{}</h2>\n{}</section>\n",
+                escape_html(title),
+                render_markdown(body)
+            );
+        }
+        None if body.iter().all(|line| line.trim().is_empty()) => {}
+        None => {
+            let _ = write!(html, "<section>\n{}</section>\n", render_markdown(body));
+        }
+    }
+}
+
+/// Renders the Markdown used in doc comments: headings, paragraphs, lists and fenced code blocks.
+fn render_markdown(lines: &[&str]) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Option<(&str, Vec<String>)> = None;
+    let mut lines = lines.iter();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            flush(&mut html, &mut paragraph, &mut list);
+            let mut code = String::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push_str(line);
+                code.push('\n');
+            }
+            let class = if lang.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(lang))
+            };
+            let _ = writeln!(
+                html,
+                "<pre><code{class}>{}</code></pre>",
+                escape_html(&code)
+            );
+        } else if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph, &mut list);
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush(&mut html, &mut paragraph, &mut list);
+            // Sections are rendered as `<h2>`, so headings within them start at `<h3>`.
+            let level = (level + 1).min(6);
+            let _ = writeln!(html, "<h{level}>{}</h{level}>", render_inline(heading));
+        } else if let Some((tag, item)) = list_item(trimmed) {
+            if !paragraph.is_empty() || list.as_ref().is_some_and(|(open, _)| *open != tag) {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            list.get_or_insert_with(|| (tag, Vec::new()))
+                .1
+                .push(item.to_string());
+        } else if let (Some((_, items)), true) = (&mut list, line.starts_with(char::is_whitespace))
+        {
+            let item = items.last_mut().expect("open list should have an item");
+            item.push(' ');
+            item.push_str(trimmed);
+        } else {
+            if list.is_some() {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            paragraph.push(trimmed);
+        }
+    }
+    flush(&mut html, &mut paragraph, &mut list);
+    html
+}
+
+fn flush<'a>(
+    html: &mut String,
+    paragraph: &mut Vec<&'a str>,
+    list: &mut Option<(&'a str, Vec<String>)>,
+) {
+    if !paragraph.is_empty() {
+        let _ = writeln!(html, "<p>{}</p>", render_inline(&paragraph.join(" ")));
+        paragraph.clear();
+    }
+    if let Some((tag, items)) = list.take() {
+        let _ = writeln!(html, "<{tag}>");
+        for item in items {
+            let _ = writeln!(html, "<li>{}</li>", render_inline(&item));
+        }
+        let _ = writeln!(html, "</{tag}>");
+    }
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let heading = line[level..].strip_prefix(' ')?;
+    (level > 0).then_some((level, heading.trim()))
+}
+
+/// Returns the list tag and the contents of a list item line.
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(("ul", item));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let item = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then_some(("ol", item))
+}
+
+/// Renders the inline Markdown in `text`: code spans and links.
+fn render_inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                let _ = write!(html, "<code>{}</code>", escape_html(&rest[1..=end]));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        } else if c == '[' {
+            if let Some((label, target, len)) = link(rest) {
+                let _ = write!(
+                    html,
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(target),
+                    render_inline(label)
+                );
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        html.push_str(&escape_html(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+    html
+}
+
+/// Parses a `[label](target)` link at the start of `text`, returning the label,
+/// the target and the length of the link. Targets may contain balanced parentheses.
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let label = &text[1..label_end];
+    if label.contains(['[', ']']) {
+        return None;
+    }
+    let target_start = label_end + 2;
+    let mut depth = 1;
+    for (i, c) in text[target_start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let target_end = target_start + i;
+                    return Some((label, &text[target_start..target_end], target_end + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+const SEARCH_SCRIPT: &str = r#"
+const input = document.getElementById("search");
+const results = document.getElementById("results");
+fetch("search.json").then((response) => response.json()).then((items) => {
+  input.addEventListener("input", () => {
+    const query = input.value.trim().toLowerCase();
+    results.replaceChildren();
+    if (!query) return;
+    for (const item of items) {
+      if (!`${item.namespace}.${item.name}`.toLowerCase().includes(query)) continue;
+      const link = document.createElement("a");
+      link.href = item.url;
+      link.textContent = `${item.namespace}.${item.name}`;
+      const entry = document.createElement("li");
+      entry.append(link, ` - ${item.summary}`);
+      results.append(entry);
+    }
+  });
+});
+"#;
+
+const STYLES: &str = "body {
+  font-family: system-ui, sans-serif;
+  line-height: 1.5;
+  margin: 0 auto;
+  max-width: 960px;
+  padding: 0 1em;
+}
+
+nav {
+  border-bottom: 1px solid #ddd;
+  padding: 1em 0;
+}
+
+pre {
+  background: #f6f8fa;
+  overflow-x: auto;
+  padding: 1em;
+}
+
+code {
+  font-family: ui-monospace, monospace;
+}
+
+table.items td {
+  padding: 0.25em 1em 0.25em 0;
+  vertical-align: top;
+}
+";
@@ -0,0 +1,216 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::{render_doc, render_inline};
+use crate::generate_docs::{generate_docs_for_packages, Compilation, DocFormat};
+use expect_test::{expect, Expect};
+use indoc::indoc;
+
+fn check_doc(doc: &str, expect: &Expect) {
+    expect.assert_eq(&render_doc(doc));
+}
+
+fn check_inline(text: &str, expect: &Expect) {
+    expect.assert_eq(&render_inline(text));
+}
+
+#[test]
+fn sections_get_class_from_heading() {
+    check_doc(
+        indoc! {"
+            # Summary
+            Applies the gate.
+
+            # Type Parameters
+            ## 'T
+            The type of the value.
+        "},
+        &expect![[r#"
+            <section class="summary">
+            <h2>Summary</h2>
+            <p>Applies the gate.</p>
+            </section>
+            <section class="type-parameters">
+            <h2>Type Parameters</h2>
+            <h3>&#39;T</h3>
+            <p>The type of the value.</p>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn text_before_first_section_is_kept() {
+    check_doc(
+        indoc! {"
+            Some text.
+            # Summary
+            More text.
+        "},
+        &expect![[r#"
+            <section>
+            <p>Some text.</p>
+            </section>
+            <section class="summary">
+            <h2>Summary</h2>
+            <p>More text.</p>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn paragraph_lines_are_joined_and_blank_lines_split_them() {
+    check_doc(
+        indoc! {"
+            First line
+            second line.
+
+            Next paragraph.
+        "},
+        &expect![[r#"
+            <section>
+            <p>First line second line.</p>
+            <p>Next paragraph.</p>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn lists_with_continuation_lines() {
+    check_doc(
+        indoc! {"
+            - first item
+              continued
+            * second item
+            1. one
+            2. two
+            After the list.
+        "},
+        &expect![[r#"
+            <section>
+            <ul>
+            <li>first item continued</li>
+            <li>second item</li>
+            </ul>
+            <ol>
+            <li>one</li>
+            <li>two</li>
+            </ol>
+            <p>After the list.</p>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn code_blocks_are_escaped_and_keep_headings() {
+    check_doc(
+        indoc! {"
+            # Example
+            ```qsharp
+            # not a section
+            let x = a < b && c;
+            ```
+        "},
+        &expect![[r#"
+            <section class="example">
+            <h2>Example</h2>
+            <pre><code class="language-qsharp"># not a section
+            let x = a &lt; b &amp;&amp; c;
+            </code></pre>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn unterminated_code_block_runs_to_end() {
+    check_doc(
+        indoc! {"
+            ```
+            H(q);
+        "},
+        &expect![[r#"
+            <section>
+            <pre><code>H(q);
+            </code></pre>
+            </section>
+        "#]],
+    );
+}
+
+#[test]
+fn inline_code_is_escaped() {
+    check_inline(
+        "Use `a <= b` & more.",
+        &expect!["Use <code>a &lt;= b</code> &amp; more."],
+    );
+}
+
+#[test]
+fn unmatched_backtick_is_text() {
+    check_inline("a ` b", &expect!["a ` b"]);
+}
+
+#[test]
+fn links_allow_balanced_parentheses_in_target() {
+    check_inline(
+        "See [Measure `q`](https://example.com/a_(b)) now.",
+        &expect![[r#"See <a href="https://example.com/a_(b)">Measure <code>q</code></a> now."#]],
+    );
+}
+
+#[test]
+fn link_target_is_escaped() {
+    check_inline(r#"[x](a"b)"#, &expect![[r#"<a href="a&quot;b">x</a>"#]]);
+}
+
+#[test]
+fn incomplete_links_are_text() {
+    check_inline("[x](y", &expect!["[x](y"]);
+    check_inline("[x] (y)", &expect!["[x] (y)"]);
+    check_inline("[[x]](y)", &expect!["[[x]](y)"]);
+}
+
+/// Checks that every element opened in `html` is closed in order.
+fn assert_balanced(name: &str, html: &str) {
+    const VOID: [&str; 4] = ["!DOCTYPE", "meta", "link", "input"];
+    let mut open: Vec<&str> = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let end = start + rest[start..].find('>').expect("tag should be closed");
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        if let Some(closed) = tag.strip_prefix('/') {
+            assert_eq!(open.pop(), Some(closed), "mismatched tag in {name}");
+        } else {
+            let tag_name = tag.split_whitespace().next().unwrap_or_default();
+            if tag_name == "script" {
+                let script_end = rest.find("</script>").expect("script should be closed");
+                rest = &rest[script_end + "</script>".len()..];
+            } else if !VOID.contains(&tag_name) {
+                open.push(tag_name);
+            }
+        }
+    }
+    assert!(open.is_empty(), "unclosed tags {open:?} in {name}");
+}
+
+#[test]
+fn std_pages_are_well_formed() {
+    let package_store = Compilation::compile(None, None, None);
+    let packages = package_store.iter().map(|(id, _)| id).collect::<Vec<_>>();
+    let files = generate_docs_for_packages(&package_store, &packages, DocFormat::Html);
+    let pages = files
+        .iter()
+        .filter(|(name, _, _)| name.ends_with(".html"))
+        .collect::<Vec<_>>();
+    assert!(pages.len() > 100);
+    for (name, _, contents) in pages {
+        assert_balanced(name, contents);
+    }
+}
@@ -3,8 +3,12 @@
 
 #![allow(clippy::needless_raw_string_hashes)]
 
-use super::generate_docs;
+use super::{generate_docs, generate_docs_for_packages, DocFormat};
 use expect_test::expect;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_frontend::compile::{self, PackageStore, SourceMap};
+use qsc_hir::hir::PackageId;
+use std::sync::Arc;
 
 #[test]
 fn docs_generation() {
@@ -52,3 +56,220 @@ fn docs_generation() {
     "#]]
     .assert_eq(full_contents.as_str());
 }
+
+fn compile_project(source: &str) -> (PackageStore, PackageId) {
+    let mut package_store = PackageStore::new(compile::core());
+    let std_package_id =
+        package_store.insert(compile::std(&package_store, TargetCapabilityFlags::all()));
+    let sources = SourceMap::new([("project.qs".into(), source.into())], None);
+    let unit = compile::compile(
+        &package_store,
+        &[std_package_id],
+        sources,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(unit.errors.is_empty(), "{:?}", unit.errors);
+    let package_id = package_store.insert(unit);
+    (package_store, package_id)
+}
+
+fn get_file<'a>(files: &'a [(Arc<str>, Arc<str>, Arc<str>)], name: &str) -> &'a str {
+    let (_, _, contents) = files
+        .iter()
+        .find(|(file_name, _, _)| &**file_name == name)
+        .unwrap_or_else(|| panic!("Could not find doc file {name}"));
+    contents
+}
+
+const PROJECT: &str = "
+namespace Geometry {
+    /// # Summary
+    /// A point in the plane.
+    newtype Point = (X : Double, Y : Double);
+
+    /// # Summary
+    /// Moves `point` by the given offsets.
+    ///
+    /// # Input
+    /// ## point
+    /// The point to move.
+    /// ## dx
+    /// - The horizontal offset.
+    ///
+    /// # Example
+    /// ```qsharp
+    /// let p = Translate(Point(0.0, 0.0), 1.0);
+    /// ```
+    function Translate(point : Point, dx : Double) : Point {
+        Point(point::X + dx, point::Y)
+    }
+
+    internal function Hidden() : Unit {}
+}
+";
+
+#[test]
+fn markdown_docs_for_project_only() {
+    let (package_store, package_id) = compile_project(PROJECT);
+    let files = generate_docs_for_packages(&package_store, &[package_id], DocFormat::Markdown);
+    let names = files
+        .iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "Geometry/Point.md",
+            "Geometry/Translate.md",
+            "toc.yml",
+        ]
+    "#]]
+    .assert_debug_eq(&names);
+}
+
+#[test]
+fn html_item_page_links_types_and_renders_sections() {
+    let (package_store, package_id) = compile_project(PROJECT);
+    let files = generate_docs_for_packages(&package_store, &[package_id], DocFormat::Html);
+    expect![[r#"
+        <!DOCTYPE html>
+        <html lang="en">
+        <head>
+        <meta charset="utf-8">
+        <title>Translate function</title>
+        <link rel="stylesheet" href="../styles.css">
+        </head>
+        <body>
+        <nav><a href="../index.html">Namespaces</a> / <a href="index.html">Geometry</a></nav>
+        <main>
+        <h1>Translate function</h1>
+        <p class="namespace">Namespace: <a href="index.html">Geometry</a></p>
+        <pre><code class="language-qsharp">function Translate(point : <a href="../Geometry/Point.html">Point</a>, dx : Double) : <a href="../Geometry/Point.html">Point</a></code></pre>
+        <section class="summary">
+        <h2>Summary</h2>
+        <p>Moves <code>point</code> by the given offsets.</p>
+        </section>
+        <section class="input">
+        <h2>Input</h2>
+        <h3>point</h3>
+        <p>The point to move.</p>
+        <h3>dx</h3>
+        <ul>
+        <li>The horizontal offset.</li>
+        </ul>
+        </section>
+        <section class="example">
+        <h2>Example</h2>
+        <pre><code class="language-qsharp">let p = Translate(Point(0.0, 0.0), 1.0);
+        </code></pre>
+        </section>
+        </main>
+        </body>
+        </html>
+    "#]].assert_eq(get_file(&files, "Geometry/Translate.html"));
+}
+
+#[test]
+fn html_namespace_index_and_search_metadata() {
+    let (package_store, package_id) = compile_project(PROJECT);
+    let files = generate_docs_for_packages(&package_store, &[package_id], DocFormat::Html);
+    let names = files
+        .iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "Geometry/Point.html",
+            "Geometry/Translate.html",
+            "Geometry/index.html",
+            "index.html",
+            "search.json",
+            "styles.css",
+        ]
+    "#]]
+    .assert_debug_eq(&names);
+    expect![[r#"
+        <!DOCTYPE html>
+        <html lang="en">
+        <head>
+        <meta charset="utf-8">
+        <title>Geometry</title>
+        <link rel="stylesheet" href="../styles.css">
+        </head>
+        <body>
+        <nav><a href="../index.html">Namespaces</a></nav>
+        <main>
+        <h1>Geometry</h1>
+        <table class="items">
+        <tr><td><a href="Point.html">Point</a></td><td>udt</td><td>A point in the plane.</td></tr>
+        <tr><td><a href="Translate.html">Translate</a></td><td>function</td><td>Moves <code>point</code> by the given offsets.</td></tr>
+        </table>
+        </main>
+        </body>
+        </html>
+    "#]].assert_eq(get_file(&files, "Geometry/index.html"));
+    expect![[r#"
+        [
+          {
+            "name": "Point",
+            "namespace": "Geometry",
+            "kind": "udt",
+            "summary": "A point in the plane.",
+            "url": "Geometry/Point.html"
+          },
+          {
+            "name": "Translate",
+            "namespace": "Geometry",
+            "kind": "function",
+            "summary": "Moves `point` by the given offsets.",
+            "url": "Geometry/Translate.html"
+          }
+        ]
+    "#]]
+    .assert_eq(get_file(&files, "search.json"));
+}
+
+#[test]
+fn html_types_from_undocumented_packages_are_not_linked() {
+    let (package_store, package_id) = compile_project(
+        "
+namespace Test {
+    open Microsoft.Quantum.Math;
+    function Magnitude(c : Complex) : Double { AbsComplex(c) }
+}",
+    );
+    let files = generate_docs_for_packages(&package_store, &[package_id], DocFormat::Html);
+    let page = get_file(&files, "Test/Magnitude.html");
+    expect![[r#"<pre><code class="language-qsharp">function Magnitude(c : Complex) : Double</code></pre>"#]].assert_eq(
+        page.lines()
+            .find(|line| line.starts_with("<pre>"))
+            .expect("page should have a signature"),
+    );
+}
+
+#[test]
+fn html_types_from_documented_dependencies_are_linked() {
+    let (package_store, package_id) = compile_project(
+        "
+namespace Test {
+    open Microsoft.Quantum.Math;
+    function Magnitude(c : Complex) : Double { AbsComplex(c) }
+}",
+    );
+    let std_package_id = package_store
+        .iter()
+        .map(|(id, _)| id)
+        .nth(1)
+        .expect("store should contain the standard library");
+    let files = generate_docs_for_packages(
+        &package_store,
+        &[std_package_id, package_id],
+        DocFormat::Html,
+    );
+    let page = get_file(&files, "Test/Magnitude.html");
+    expect![[r#"<pre><code class="language-qsharp">function Magnitude(c : <a href="../Microsoft.Quantum.Math/Complex.html">Complex</a>) : Double</code></pre>"#]].assert_eq(
+        page.lines()
+            .find(|line| line.starts_with("<pre>"))
+            .expect("page should have a signature"),
+    );
+}
@@ -3,6 +3,7 @@
 
 use num_bigint::BigUint;
 use num_complex::Complex;
+use qsc_data_structures::functors::FunctorApp;
 use quantum_sparse_sim::QuantumSim;
 use rand::RngCore;
 
@@ -97,6 +98,13 @@ pub trait Backend {
     }
 
     fn set_seed(&mut self, _seed: Option<u64>) {}
+
+    /// Called when evaluation enters a callable that is not intrinsic, with the name of the
+    /// callable, the functors applied to it and its argument.
+    fn enter_callable(&mut self, _name: &str, _functor: FunctorApp, _arg: &Value) {}
+
+    /// Called when evaluation returns from the callable most recently entered.
+    fn leave_callable(&mut self) {}
 }
 
 /// Default backend used when targeting sparse simulation.
@@ -479,4 +487,14 @@ where
         self.chained.set_seed(seed);
         self.main.set_seed(seed);
     }
+
+    fn enter_callable(&mut self, name: &str, functor: FunctorApp, arg: &Value) {
+        self.chained.enter_callable(name, functor, arg);
+        self.main.enter_callable(name, functor, arg);
+    }
+
+    fn leave_callable(&mut self) {
+        self.chained.leave_callable();
+        self.main.leave_callable();
+    }
 }
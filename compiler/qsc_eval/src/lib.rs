@@ -623,11 +623,17 @@ impl State {
                     continue;
                 }
                 Some(ExecGraphNode::Ret) => {
+                    if !self.call_stack.is_empty() {
+                        sim.leave_callable();
+                    }
                     self.leave_frame();
                     env.leave_scope();
                     continue;
                 }
                 Some(ExecGraphNode::RetFrame) => {
+                    if !self.call_stack.is_empty() {
+                        sim.leave_callable();
+                    }
                     self.leave_frame();
                     env.leave_current_frame();
                     continue;
@@ -1006,6 +1012,7 @@ impl State {
                 .expect("missing specialization should be a compilation error");
                 self.push_frame(spec_decl.exec_graph.clone(), callee_id, functor);
                 self.push_scope(env);
+                sim.enter_callable(&callee.name.name, functor, &arg);
 
                 self.bind_args_for_spec(
                     env,
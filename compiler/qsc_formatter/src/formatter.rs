@@ -10,6 +10,7 @@ use qsc_frontend::{
         Delim, InterpolatedEnding, InterpolatedStart,
     },
 };
+use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 mod tests;
@@ -19,7 +20,32 @@ mod tests;
 /// Applies formatting rules to the give code str and returns
 /// the formatted string.
 pub fn format_str(code: &str) -> String {
-    let mut edits = calculate_format_edits(code);
+    format_str_with_options(code, &FormatOptions::default())
+}
+
+/// Applies formatting rules to the given code str, using the style
+/// described by `options`, and returns the formatted string.
+pub fn format_str_with_options(code: &str, options: &FormatOptions) -> String {
+    apply_edits(code, &calculate_format_edits_with_options(code, options))
+}
+
+/// Applies formatting rules to the given code str, generating edits where
+/// the source code needs to be changed to comply with the format rules.
+pub fn calculate_format_edits(code: &str) -> Vec<TextEdit> {
+    calculate_format_edits_with_options(code, &FormatOptions::default())
+}
+
+/// Applies formatting rules to the given code str, using the style described
+/// by `options`, generating edits where the source code needs to be changed
+/// to comply with the format rules.
+pub fn calculate_format_edits_with_options(code: &str, options: &FormatOptions) -> Vec<TextEdit> {
+    calculate_edits(code, options, &Region::whole(code))
+}
+
+/// Applies the given edits to the code str and returns the edited string.
+/// The edits may be in any order, but must not overlap.
+pub fn apply_edits(code: &str, edits: &[TextEdit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
     edits.sort_by_key(|edit| edit.span.hi); // sort edits by their span's hi value from lowest to highest
     edits.reverse(); // sort from highest to lowest so that that as edits are applied they don't invalidate later applications of edits
     let mut new_code = String::from(code);
@@ -32,20 +58,83 @@ pub fn format_str(code: &str) -> String {
     new_code
 }
 
-/// Applies formatting rules to the given code str, generating edits where
-/// the source code needs to be changed to comply with the format rules.
-pub fn calculate_format_edits(code: &str) -> Vec<TextEdit> {
-    let tokens = concrete::ConcreteTokenIterator::new(code);
+/// Generates the edits needed to format the part of the given code str
+/// covered by `range`. Only the statements and items around the range are
+/// formatted, and only edits that touch the range are returned.
+pub fn calculate_range_format_edits(
+    code: &str,
+    range: Span,
+    options: &FormatOptions,
+) -> Vec<TextEdit> {
+    calculate_edits(code, options, &Region::around(code, range))
+        .into_iter()
+        .filter(|edit| edit.span.lo <= range.hi && range.lo <= edit.span.hi)
+        .collect()
+}
+
+/// Generates the edits needed to format the given region of the code str.
+/// When lines have a maximum width, the region is first formatted without
+/// wrapping any lines, and the layout of that formatting is used to decide
+/// which argument lists, tuples and arrays to split as the region is formatted.
+fn calculate_edits(code: &str, options: &FormatOptions, region: &Region) -> Vec<TextEdit> {
+    let edits = format_region(code, options, region, None);
+    let Some(max_line_width) = options.max_line_width else {
+        return edits;
+    };
+
+    let text = region.text(code);
+    let region_edits = edits
+        .iter()
+        .map(|edit| {
+            TextEdit::new(
+                &edit.new_text,
+                edit.span.lo - region.lo,
+                edit.span.hi - region.lo,
+            )
+        })
+        .collect::<Vec<_>>();
+    let formatted = apply_edits(text, &region_edits);
+    if formatted
+        .lines()
+        .all(|line| line.trim_end().chars().count() <= max_line_width)
+    {
+        return edits;
+    }
+
+    match FlatLayout::new(text, &formatted) {
+        Some(layout) => format_region(code, options, region, Some(&layout)),
+        None => edits,
+    }
+}
+
+fn format_region(
+    code: &str,
+    options: &FormatOptions,
+    region: &Region,
+    layout: Option<&FlatLayout>,
+) -> Vec<TextEdit> {
+    let tokens =
+        concrete::ConcreteTokenIterator::new(region.text(code)).map(|token| ConcreteToken {
+            kind: token.kind,
+            span: Span {
+                lo: token.span.lo + region.lo,
+                hi: token.span.hi + region.lo,
+            },
+        });
     let mut edits = vec![];
 
     let mut formatter = Formatter {
         code,
-        indent_level: 0,
-        delim_newlines_stack: vec![],
+        options,
+        layout,
+        token_index: 0,
+        wrapped: vec![],
+        column: 0,
+        indent_level: region.depth,
+        delim_newlines_stack: vec![NewlineContext::Newlines; region.depth],
         type_param_state: TypeParameterListState::NoState,
         spec_decl_state: SpecDeclState::NoState,
     };
-
     // The sliding window used is over three adjacent tokens
     #[allow(unused_assignments)]
     let mut one = None;
@@ -93,6 +182,41 @@ pub fn calculate_format_edits(code: &str) -> Vec<TextEdit> {
 
 // Public types
 
+/// Options that control the style applied by the formatter.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatOptions {
+    /// The number of spaces used for each level of indentation.
+    pub indent_width: usize,
+    /// The maximum length of a line. Argument lists, tuples and arrays on
+    /// longer lines are split with one item per line. When this is `None`,
+    /// lines are never wrapped.
+    pub max_line_width: Option<usize>,
+    /// Where the opening brace of a block is placed.
+    pub brace_style: BraceStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            max_line_width: None,
+            brace_style: BraceStyle::SameLine,
+        }
+    }
+}
+
+/// The placement of the opening brace of a block.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BraceStyle {
+    /// The brace ends the line that starts the block, i.e. `if x {`.
+    #[default]
+    SameLine,
+    /// The brace starts a new line after the one that starts the block.
+    NextLine,
+}
+
 #[derive(Debug)]
 pub struct TextEdit {
     pub new_text: String,
@@ -196,8 +320,216 @@ impl Delimiter {
     }
 }
 
+/// A part of the code that can be formatted on its own, because it
+/// starts and ends on a line break at a known indentation.
+struct Region {
+    lo: u32,
+    hi: u32,
+    /// The number of blocks that the region is nested in.
+    depth: usize,
+}
+
+impl Region {
+    fn whole(code: &str) -> Self {
+        Self {
+            lo: 0,
+            hi: u32::try_from(code.len()).expect("code length should fit into u32"),
+            depth: 0,
+        }
+    }
+
+    /// Finds the smallest region around `range`. A region can start or end
+    /// at a `;`, `{` or `}` that ends its line when every delimiter around it
+    /// is a block with its contents on separate lines, since the indentation
+    /// there only depends on the number of those blocks.
+    fn around(code: &str, range: Span) -> Self {
+        use ConcreteTokenKind::*;
+        use TokenKind::*;
+
+        let mut region = Self::whole(code);
+        // For each open delimiter, whether it is a block with its contents on separate lines.
+        let mut blocks: Vec<bool> = vec![];
+        let tokens = syntax_tokens(code);
+        for (i, token) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1);
+            let ends_line = next.is_none_or(|next| {
+                code[token.span.hi as usize..next.span.lo as usize].contains('\n')
+            });
+            match &token.kind {
+                Syntax(Open(delim)) => blocks.push(
+                    *delim == Delim::Brace
+                        && (ends_line
+                            || next.is_some_and(|next| match &next.kind {
+                                Comment => true,
+                                Syntax(cooked) => is_newline_keyword_or_ampersat(cooked),
+                                _ => false,
+                            })),
+                ),
+                Syntax(Close(_)) => {
+                    blocks.pop();
+                }
+                _ => {}
+            }
+
+            let is_boundary = ends_line
+                && matches!(
+                    token.kind,
+                    Syntax(Semi | Open(Delim::Brace) | Close(Delim::Brace))
+                )
+                && blocks.iter().all(|is_block| *is_block);
+            if is_boundary {
+                // The formatter counts a block from the whitespace after its `{`.
+                let depth =
+                    blocks.len() - usize::from(matches!(token.kind, Syntax(Open(Delim::Brace))));
+                if token.span.lo < range.lo {
+                    region.lo = token.span.lo;
+                    region.depth = depth;
+                } else if token.span.lo >= range.hi {
+                    region.hi = token.span.hi;
+                    break;
+                }
+            }
+        }
+        region
+    }
+
+    fn text<'a>(&self, code: &'a str) -> &'a str {
+        &code[self.lo as usize..self.hi as usize]
+    }
+}
+
+/// Where the tokens of a region end up when it is formatted without
+/// wrapping any lines. Positions are counted in characters.
+struct FlatLayout {
+    tokens: Vec<FlatToken>,
+    /// The width of each line, not counting trailing whitespace.
+    line_widths: Vec<usize>,
+}
+
+struct FlatToken {
+    line: usize,
+    column: usize,
+    /// The innermost delimiter around the token.
+    parent: Option<usize>,
+    /// For an open delimiter, the end of the item it is part of in the
+    /// sequence of its parent: after the next `,` or before the closing
+    /// delimiter of the parent, as a line and column.
+    item_end: Option<(usize, usize)>,
+}
+
+impl FlatLayout {
+    /// Lays out the syntax tokens of `formatted`, the result of formatting
+    /// `code` without wrapping. Returns `None` if the tokens of the two don't
+    /// line up, which formatting should never cause.
+    fn new(code: &str, formatted: &str) -> Option<Self> {
+        use ConcreteTokenKind::*;
+        use TokenKind::*;
+
+        let formatted_tokens = syntax_tokens(formatted);
+        if syntax_tokens(code).len() != formatted_tokens.len() {
+            return None;
+        }
+
+        // Each token's start and end as a line and column.
+        let mut positions = Vec::with_capacity(formatted_tokens.len());
+        let (mut line, mut column, mut offset) = (0, 0, 0);
+        let mut advance = |to: u32| {
+            for c in formatted[offset..to as usize].chars() {
+                if c == '\n' {
+                    line += 1;
+                    column = 0;
+                } else {
+                    column += 1;
+                }
+            }
+            offset = to as usize;
+            (line, column)
+        };
+        for token in &formatted_tokens {
+            positions.push((advance(token.span.lo), advance(token.span.hi)));
+        }
+
+        let mut tokens: Vec<FlatToken> = Vec::with_capacity(formatted_tokens.len());
+        // The open delimiters around the current token, each with the open
+        // delimiters in its sequence that are waiting for their item to end.
+        let mut open: Vec<(usize, Vec<usize>)> = vec![];
+        for (i, token) in formatted_tokens.iter().enumerate() {
+            let ((line, column), end) = positions[i];
+            tokens.push(FlatToken {
+                line,
+                column,
+                parent: open.last().map(|(parent, _)| *parent),
+                item_end: None,
+            });
+            match &token.kind {
+                Syntax(Comma) => {
+                    if let Some((_, waiting)) = open.last_mut() {
+                        for j in waiting.drain(..) {
+                            tokens[j].item_end = Some(end);
+                        }
+                    }
+                }
+                Syntax(Open(_)) => {
+                    if let Some((_, waiting)) = open.last_mut() {
+                        waiting.push(i);
+                    }
+                    open.push((i, vec![]));
+                }
+                Syntax(Close(_)) => {
+                    if let Some((_, waiting)) = open.pop() {
+                        for j in waiting {
+                            tokens[j].item_end = Some((line, column));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            tokens,
+            line_widths: formatted
+                .split('\n')
+                .map(|line| line.trim_end().chars().count())
+                .collect(),
+        })
+    }
+
+    /// The width from the start of the given open delimiter to the end of its
+    /// line, given the delimiters whose sequences have been wrapped so far.
+    /// A wrapped sequence ends the line at the end of the item it contains.
+    fn rest_of_line_width(&self, open: usize, wrapped: &[usize]) -> usize {
+        let token = &self.tokens[open];
+        let mut line_end = self.line_widths[token.line];
+        let mut child = open;
+        while let Some(parent) = self.tokens[child].parent {
+            if wrapped.contains(&parent) {
+                if let Some((line, column)) = self.tokens[child].item_end {
+                    if line == token.line {
+                        line_end = line_end.min(column);
+                    }
+                }
+                break;
+            }
+            child = parent;
+        }
+        line_end.saturating_sub(token.column)
+    }
+}
+
 struct Formatter<'a> {
     code: &'a str,
+    options: &'a FormatOptions,
+    /// The layout of the code formatted without wrapping, when lines
+    /// have a maximum width.
+    layout: Option<&'a FlatLayout>,
+    /// The index of the left token among the tokens that aren't whitespace.
+    token_index: usize,
+    /// The indices of the open delimiters whose sequences are split
+    /// onto separate lines to keep lines within the maximum width.
+    wrapped: Vec<usize>,
+    /// The column of the formatted code where the left token starts.
+    column: usize,
     indent_level: usize,
     delim_newlines_stack: Vec<NewlineContext>,
     type_param_state: TypeParameterListState,
@@ -227,10 +559,16 @@ impl<'a> Formatter<'a> {
         let (left_delim_state, right_delim_state) =
             self.update_type_param_state(&left.kind, &right.kind);
 
+        let is_line_break = !are_newlines_in_spaces && self.exceeds_line_width(left, right);
+        if is_line_break {
+            self.wrapped.push(self.token_index);
+        }
+        self.token_index += 1;
+
         let newline_context = self.update_indent_level(
             left_delim_state,
             right_delim_state,
-            are_newlines_in_spaces,
+            are_newlines_in_spaces || is_line_break,
             does_right_required_newline,
             matches!(right.kind, Comment),
         );
@@ -239,10 +577,22 @@ impl<'a> Formatter<'a> {
             (Comment | Syntax(DocComment), _) => {
                 // remove whitespace at the ends of comments
                 effect_trim_comment(left, &mut edits, self.code);
-                effect_correct_indentation(left, whitespace, right, &mut edits, self.indent_level);
+                effect_correct_indentation(
+                    left,
+                    whitespace,
+                    right,
+                    &mut edits,
+                    self.indent(self.indent_level),
+                );
             }
             (_, Comment) if matches!(left_delim_state, Delimiter::Open) => {
-                effect_correct_indentation(left, whitespace, right, &mut edits, self.indent_level);
+                effect_correct_indentation(
+                    left,
+                    whitespace,
+                    right,
+                    &mut edits,
+                    self.indent(self.indent_level),
+                );
             }
             (_, Comment) => {
                 if are_newlines_in_spaces {
@@ -251,7 +601,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 // else do nothing, preserving the user's spaces before the comment
@@ -272,7 +622,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (_, Semi) => {
@@ -300,7 +650,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Comma, _) if matches!(newline_context, NewlineContext::Newlines) => {
@@ -309,7 +659,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Comma, _) => {
@@ -324,7 +674,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (Open(Delim::Bracket | Delim::Paren), _)
@@ -358,7 +708,19 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
+                    );
+                }
+                (
+                    Close(Delim::Brace),
+                    Keyword(Keyword::Until | Keyword::Elif | Keyword::Else | Keyword::Apply),
+                ) if self.options.brace_style == BraceStyle::NextLine => {
+                    effect_correct_indentation(
+                        left,
+                        whitespace,
+                        right,
+                        &mut edits,
+                        self.indent(self.indent_level),
                     );
                 }
                 (_, Keyword(Keyword::Until))
@@ -384,7 +746,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level,
+                        self.indent(self.indent_level),
                     );
                 }
                 (String(StringToken::Interpolated(_, InterpolatedEnding::LBrace)), _)
@@ -395,6 +757,19 @@ impl<'a> Formatter<'a> {
                     // Special-case specialization declaration ellipses to have a space after
                     effect_single_space(left, whitespace, right, &mut edits);
                 }
+                (_, Open(Delim::Brace))
+                    if self.options.brace_style == BraceStyle::NextLine
+                        && !is_prefix(cooked_left)
+                        && self.is_block_brace(right) =>
+                {
+                    effect_correct_indentation(
+                        left,
+                        whitespace,
+                        right,
+                        &mut edits,
+                        self.indent(self.indent_level),
+                    );
+                }
                 (_, Open(Delim::Brace)) => {
                     // Special-case braces to have a leading single space with values
                     if is_prefix(cooked_left) {
@@ -456,7 +831,7 @@ impl<'a> Formatter<'a> {
                         whitespace,
                         right,
                         &mut edits,
-                        self.indent_level + 1,
+                        self.indent(self.indent_level + 1),
                     );
                 }
                 (_, _) if is_bin_op(cooked_right) => {
@@ -466,9 +841,73 @@ impl<'a> Formatter<'a> {
             },
             _ => {}
         }
+        self.advance_column(left, whitespace, &edits);
         edits
     }
 
+    /// Whether the sequence opened by `left` has to be split onto separate
+    /// lines to keep its line within the maximum width.
+    fn exceeds_line_width(&self, left: &ConcreteToken, right: &ConcreteToken) -> bool {
+        let (Some(max_line_width), Some(layout)) = (self.options.max_line_width, self.layout)
+        else {
+            return false;
+        };
+        matches!(
+            left.kind,
+            ConcreteTokenKind::Syntax(TokenKind::Open(Delim::Paren | Delim::Bracket))
+        ) && !matches!(right.kind, ConcreteTokenKind::Syntax(TokenKind::Close(_)))
+            && self.column + layout.rest_of_line_width(self.token_index, &self.wrapped)
+                > max_line_width
+    }
+
+    /// Moves the column past `left` and the whitespace after it, as they
+    /// are after applying `edits`.
+    fn advance_column(&mut self, left: &ConcreteToken, whitespace: &str, edits: &[TextEdit]) {
+        let whitespace_hi = left.span.hi + u32::try_from(whitespace.len()).unwrap_or_default();
+        for text in [
+            edited_text(edits, left.span, get_token_contents(self.code, left)),
+            edited_text(
+                edits,
+                Span {
+                    lo: left.span.hi,
+                    hi: whitespace_hi,
+                },
+                whitespace,
+            ),
+        ] {
+            match text.rfind('\n') {
+                Some(newline) => self.column = text[newline + 1..].chars().count(),
+                None => self.column += text.chars().count(),
+            }
+        }
+    }
+
+    /// Returns the number of spaces used to indent the given level.
+    fn indent(&self, level: usize) -> usize {
+        level * self.options.indent_width
+    }
+
+    /// Whether the given `{` starts a block whose contents are placed on
+    /// separate lines, as opposed to an empty block or a single-line value.
+    fn is_block_brace(&self, brace: &ConcreteToken) -> bool {
+        let rest = &self.code[brace.span.hi as usize..];
+        let mut tokens = concrete::ConcreteTokenIterator::new(rest);
+        let next = match tokens.next() {
+            Some(token) if matches!(token.kind, ConcreteTokenKind::WhiteSpace) => {
+                if get_token_contents(rest, &token).contains('\n') {
+                    return true;
+                }
+                tokens.next()
+            }
+            token => token,
+        };
+        next.is_some_and(|token| match &token.kind {
+            ConcreteTokenKind::Comment => true,
+            ConcreteTokenKind::Syntax(cooked) => is_newline_keyword_or_ampersat(cooked),
+            _ => false,
+        })
+    }
+
     fn update_spec_decl_state(&mut self, left_kind: &ConcreteTokenKind) {
         use qsc_frontend::keyword::Keyword;
         use ConcreteTokenKind::*;
@@ -613,8 +1052,18 @@ impl<'a> Formatter<'a> {
 
 // Helper Functions
 
-fn make_indent_string(level: usize) -> String {
-    "    ".repeat(level)
+fn syntax_tokens(code: &str) -> Vec<ConcreteToken> {
+    concrete::ConcreteTokenIterator::new(code)
+        .filter(|token| !matches!(token.kind, ConcreteTokenKind::WhiteSpace))
+        .collect()
+}
+
+/// The text of the given span after applying `edits`, given its original text.
+fn edited_text<'a>(edits: &'a [TextEdit], span: Span, text: &'a str) -> &'a str {
+    edits
+        .iter()
+        .find(|edit| edit.span == span)
+        .map_or(text, |edit| edit.new_text.as_str())
 }
 
 fn get_token_contents<'a>(code: &'a str, token: &ConcreteToken) -> &'a str {
@@ -811,7 +1260,7 @@ fn effect_correct_indentation(
     whitespace: &str,
     right: &ConcreteToken,
     edits: &mut Vec<TextEdit>,
-    indent_level: usize,
+    indent: usize,
 ) {
     let mut count_newlines = whitespace.chars().filter(|c| *c == '\n').count();
 
@@ -825,7 +1274,7 @@ fn effect_correct_indentation(
     } else {
         "\n".repeat(count_newlines)
     };
-    new_whitespace.push_str(&make_indent_string(indent_level));
+    new_whitespace.push_str(&" ".repeat(indent));
     if whitespace != new_whitespace {
         edits.push(TextEdit::new(
             new_whitespace.as_str(),
@@ -1,8 +1,10 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use super::{BraceStyle, FormatOptions};
 use expect_test::{expect, Expect};
 use indoc::indoc;
+use qsc_data_structures::span::Span;
 
 fn check(input: &str, expect: &Expect) {
     let actual = super::format_str(input);
@@ -14,6 +16,11 @@ fn check_edits(input: &str, expect: &Expect) {
     expect.assert_debug_eq(&actual);
 }
 
+fn check_with_options(input: &str, options: &FormatOptions, expect: &Expect) {
+    let actual = super::format_str_with_options(input, options);
+    expect.assert_eq(&actual);
+}
+
 // Removing trailing whitespace from lines
 
 #[test]
@@ -1105,6 +1112,242 @@ fn remove_extra_whitespace_from_start_of_code() {
     check(input, &expect!["namespace Foo {}"]);
 }
 
+// Formatting options
+
+#[test]
+fn indent_width_option() {
+    check_with_options(
+        indoc! {r#"
+        operation Foo() : Unit {
+            if true {
+                let x = 3;
+            }
+        }
+        "#},
+        &FormatOptions {
+            indent_width: 2,
+            ..FormatOptions::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+              if true {
+                let x = 3;
+              }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn next_line_brace_style() {
+    check_with_options(
+        indoc! {r#"
+        namespace Foo {
+            operation Bar() : Unit {
+                if true {
+                    let x = 3;
+                } else {
+                    let y = 4;
+                }
+                let z = new Pair { First = 1, Second = 2 };
+            }
+        }
+        "#},
+        &FormatOptions {
+            brace_style: BraceStyle::NextLine,
+            ..FormatOptions::default()
+        },
+        &expect![[r#"
+            namespace Foo
+            {
+                operation Bar() : Unit
+                {
+                    if true
+                    {
+                        let x = 3;
+                    }
+                    else
+                    {
+                        let y = 4;
+                    }
+                    let z = new Pair { First = 1, Second = 2 };
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn next_line_brace_style_keeps_empty_blocks() {
+    check_with_options(
+        "operation Foo() : Unit {}",
+        &FormatOptions {
+            brace_style: BraceStyle::NextLine,
+            ..FormatOptions::default()
+        },
+        &expect!["operation Foo() : Unit {}"],
+    );
+}
+
+#[test]
+fn long_argument_list_is_wrapped() {
+    check_with_options(
+        indoc! {r#"
+        operation Foo() : Unit {
+            ApplyToEachControlled(SomeOperationWithALongName, [firstQubit, secondQubit]);
+        }
+        "#},
+        &FormatOptions {
+            max_line_width: Some(60),
+            ..FormatOptions::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+                ApplyToEachControlled(
+                    SomeOperationWithALongName,
+                    [firstQubit, secondQubit]
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn nested_sequences_are_wrapped_until_lines_fit() {
+    check_with_options(
+        indoc! {r#"
+        operation Foo() : Unit {
+            let pairs = [(firstQubitName, secondQubitName), (thirdQubitName, fourthQubitName)];
+        }
+        "#},
+        &FormatOptions {
+            max_line_width: Some(50),
+            ..FormatOptions::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+                let pairs = [
+                    (firstQubitName, secondQubitName),
+                    (thirdQubitName, fourthQubitName)
+                ];
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn items_still_too_long_after_wrapping_are_wrapped() {
+    check_with_options(
+        indoc! {r#"
+        operation Foo() : Unit {
+            Outer(Inner(firstArgumentName, secondArgumentName), last);
+        }
+        "#},
+        &FormatOptions {
+            max_line_width: Some(40),
+            ..FormatOptions::default()
+        },
+        &expect![[r#"
+            operation Foo() : Unit {
+                Outer(
+                    Inner(
+                        firstArgumentName,
+                        secondArgumentName
+                    ),
+                    last
+                );
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn short_lines_are_not_wrapped() {
+    let input = indoc! {r#"
+        operation Foo() : Unit {
+            Bar(1, 2, 3);
+        }
+        "#};
+    let options = FormatOptions {
+        max_line_width: Some(40),
+        ..FormatOptions::default()
+    };
+    assert!(super::calculate_format_edits_with_options(input, &options).is_empty());
+}
+
+#[test]
+fn range_formatting_only_edits_range() {
+    let input = indoc! {r#"
+        operation Foo() : Unit {
+        let x  =  3;
+        let y  =  4;
+        }
+        "#};
+    let lo = u32::try_from(input.find("let y").expect("input should contain `let y`"))
+        .expect("offset should fit in u32");
+    let range = Span { lo, hi: lo + 12 };
+    let actual = super::calculate_range_format_edits(input, range, &FormatOptions::default());
+    expect![[r#"
+        [
+            TextEdit {
+                new_text: "\n    ",
+                span: Span {
+                    lo: 37,
+                    hi: 38,
+                },
+            },
+            TextEdit {
+                new_text: " ",
+                span: Span {
+                    lo: 43,
+                    hi: 45,
+                },
+            },
+            TextEdit {
+                new_text: " ",
+                span: Span {
+                    lo: 46,
+                    hi: 48,
+                },
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&actual);
+}
+
+#[test]
+fn range_formatting_matches_whole_document_formatting() {
+    let input = indoc! {r#"
+        namespace Test {
+            operation Foo() : Unit {
+                if true {
+                let x  =  [firstQubitName, secondQubitName, thirdQubitName];
+                }
+            }
+        }
+        "#};
+    let options = FormatOptions {
+        max_line_width: Some(50),
+        ..FormatOptions::default()
+    };
+    let lo = u32::try_from(input.find("let x").expect("input should contain `let x`"))
+        .expect("offset should fit in u32");
+    let range = Span { lo, hi: lo + 61 };
+    let region = super::Region::around(input, range);
+    assert_eq!(
+        region.text(input),
+        "{\n        let x  =  [firstQubitName, secondQubitName, thirdQubitName];\n        }"
+    );
+    assert_eq!(region.depth, 2);
+    let actual = super::calculate_range_format_edits(input, range, &options);
+    let expected = super::calculate_format_edits_with_options(input, &options)
+        .into_iter()
+        .filter(|edit| edit.span.lo <= range.hi && range.lo <= edit.span.hi)
+        .collect::<Vec<_>>();
+    assert_eq!(actual.len(), 7);
+    assert_eq!(format!("{actual:?}"), format!("{expected:?}"));
+}
+
 // Extra test cases for sanity
 
 #[test]
@@ -180,6 +180,18 @@ pub type SourceContents = Arc<str>;
 #[error(transparent)]
 pub struct Error(pub(super) ErrorKind);
 
+impl Error {
+    /// The name that could not be resolved, if this is an error about a name
+    /// that is not declared in any namespace.
+    #[must_use]
+    pub fn unresolved_name(&self) -> Option<&str> {
+        match &self.0 {
+            ErrorKind::Resolve(error) => error.unresolved_name(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Diagnostic, Error)]
 #[diagnostic(transparent)]
 pub(super) enum ErrorKind {
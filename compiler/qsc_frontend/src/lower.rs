@@ -232,6 +232,25 @@ impl With<'_> {
                     None
                 }
             },
+            Ok(hir::Attr::Deprecated(_)) => match &*attr.arg.kind {
+                // @Deprecated()
+                ast::ExprKind::Tuple(args) if args.is_empty() => Some(hir::Attr::Deprecated(None)),
+                _ => {
+                    // @Deprecated("Replacement")
+                    if let ast::ExprKind::Paren(inner) = &*attr.arg.kind {
+                        if let ast::ExprKind::Lit(lit) = &*inner.kind {
+                            if let ast::Lit::String(replacement) = &**lit {
+                                return Some(hir::Attr::Deprecated(Some(Rc::clone(replacement))));
+                            }
+                        }
+                    }
+                    self.lowerer.errors.push(Error::InvalidAttrArgs(
+                        "() or a replacement name".to_string(),
+                        attr.arg.span,
+                    ));
+                    None
+                }
+            },
             Ok(hir::Attr::Unimplemented) => match &*attr.arg.kind {
                 ast::ExprKind::Tuple(args) if args.is_empty() => Some(hir::Attr::Unimplemented),
                 _ => {
@@ -265,6 +284,17 @@ impl With<'_> {
                 }
                 None
             }
+            // Lint level attributes are read from the AST by the linter,
+            // so they only need to name a lint and are not kept in the HIR.
+            Err(()) if matches!(attr.name.name.as_ref(), "Allow" | "Warn" | "Error") => {
+                if lint_name_arg(&attr.arg).is_none() {
+                    self.lowerer.errors.push(Error::InvalidAttrArgs(
+                        "lint name".to_string(),
+                        attr.arg.span,
+                    ));
+                }
+                None
+            }
             Err(()) => {
                 self.lowerer.errors.push(Error::UnknownAttr(
                     attr.name.name.to_string(),
@@ -880,3 +910,17 @@ fn is_partial_app(arg: &ast::Expr) -> bool {
         _ => false,
     }
 }
+
+/// Returns the lint name in the argument of a lint level attribute, e.g. `@Allow("LintName")`.
+fn lint_name_arg(arg: &ast::Expr) -> Option<&Rc<str>> {
+    let ast::ExprKind::Paren(inner) = arg.kind.as_ref() else {
+        return None;
+    };
+    match inner.kind.as_ref() {
+        ast::ExprKind::Lit(lit) => match lit.as_ref() {
+            ast::Lit::String(name) => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
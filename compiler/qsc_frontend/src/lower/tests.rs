@@ -131,6 +131,98 @@ fn test_target_profile_attr_wrong_args() {
     );
 }
 
+#[test]
+fn test_deprecated_attr_with_replacement() {
+    check_hir(
+        indoc! {r#"
+            namespace input {
+                @Deprecated("Bar")
+                function Foo() : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            Package:
+                Item 0 [0-71] (Public):
+                    Namespace (Ident 5 [10-15] "input"): Item 1
+                Item 1 [22-69] (Public):
+                    Parent: 0
+                    Deprecated(Some("Bar"))
+                    Callable 0 [45-69] (function):
+                        name: Ident 1 [54-57] "Foo"
+                        input: Pat 2 [57-59] [Type Unit]: Unit
+                        output: Unit
+                        functors: empty set
+                        body: SpecDecl 3 [45-69]: Impl:
+                            Block 4 [67-69]: <empty>
+                        adj: <none>
+                        ctl: <none>
+                        ctl-adj: <none>"#]],
+    );
+}
+
+#[test]
+fn test_deprecated_attr_wrong_args() {
+    check_errors(
+        indoc! {"
+            namespace input {
+                @Deprecated(Bar)
+                function Foo() : Unit {}
+            }
+        "},
+        &expect![[r#"
+            [
+                InvalidAttrArgs(
+                    "() or a replacement name",
+                    Span {
+                        lo: 33,
+                        hi: 38,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_lint_level_attrs_allowed() {
+    check_errors(
+        indoc! {r#"
+            namespace input {
+                @Allow("NeedlessParens")
+                @Warn("DivisionByZero")
+                @Error("RedundantSemicolons")
+                function Foo() : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_lint_level_attr_wrong_args() {
+    check_errors(
+        indoc! {"
+            namespace input {
+                @Allow(NeedlessParens)
+                function Foo() : Unit {}
+            }
+        "},
+        &expect![[r#"
+            [
+                InvalidAttrArgs(
+                    "lint name",
+                    Span {
+                        lo: 28,
+                        hi: 44,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_unknown_attr() {
     check_errors(
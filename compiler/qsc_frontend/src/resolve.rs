@@ -142,6 +142,17 @@ pub(super) enum Error {
     GlobExportNotSupported(#[label] Span),
 }
 
+impl Error {
+    /// The name that could not be found, if this error is about a name that
+    /// is not declared anywhere.
+    pub(super) fn unresolved_name(&self) -> Option<&str> {
+        match self {
+            Error::NotFound(name, _) => Some(name),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Scope {
     /// The span that the scope applies to. For callables and namespaces, this includes
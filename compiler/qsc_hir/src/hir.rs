@@ -1313,6 +1313,8 @@ impl Display for Ident {
 pub enum Attr {
     /// Provide pre-processing information about when an item should be included in compilation.
     Config,
+    /// Indicates that an item is deprecated, with the name of an optional replacement.
+    Deprecated(Option<Rc<str>>),
     /// Indicates that a callable is an entry point to a program.
     EntryPoint,
     /// Indicates that an item does not have an implementation available for use.
@@ -1325,6 +1327,7 @@ impl FromStr for Attr {
     fn from_str(s: &str) -> result::Result<Self, Self::Err> {
         match s {
             "Config" => Ok(Self::Config),
+            "Deprecated" => Ok(Self::Deprecated(None)),
             "EntryPoint" => Ok(Self::EntryPoint),
             "Unimplemented" => Ok(Self::Unimplemented),
             _ => Err(()),
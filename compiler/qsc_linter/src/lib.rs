@@ -6,6 +6,8 @@
 //! It includes lints for the following stages of the compilation process:
 //!  - AST
 //!  - HIR
+//!  - Runtime capabilities analysis, through the opt-in `profileCompatibility`
+//!    lint reported by the `run_profile_lints` function.
 //!
 //! # Usage
 //!
@@ -66,5 +68,8 @@ mod lints;
 #[cfg(test)]
 mod tests;
 
-pub use linter::{run_lints, Lint, LintConfig, LintKind, LintLevel};
+pub use linter::{
+    run_lints, run_lints_with_dependencies, run_profile_lints, Lint, LintConfig, LintKind,
+    LintLevel, ProfileLint,
+};
 pub use lints::{ast::AstLint, hir::HirLint};
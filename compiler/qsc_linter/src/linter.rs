@@ -3,31 +3,115 @@
 
 pub(crate) mod ast;
 pub(crate) mod hir;
+mod overrides;
+mod profile;
 
-use self::{ast::run_ast_lints, hir::run_hir_lints};
+pub use self::profile::{run_profile_lints, ProfileLint};
+use self::{ast::run_ast_lints, hir::run_hir_lints, overrides::LintOverrides};
 use crate::lints::{ast::AstLint, hir::HirLint};
 use miette::{Diagnostic, LabeledSpan};
 use qsc_data_structures::span::Span;
-use qsc_frontend::compile::CompileUnit;
+use qsc_frontend::compile::{CompileUnit, PackageStore};
+use qsc_hir::hir::{Item, ItemId};
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display, str::FromStr};
 
 /// The entry point to the linter. It takes a [`qsc_frontend::compile::CompileUnit`]
 /// as input and outputs a [`Vec<Lint>`](Lint).
+///
+/// The levels in `config` apply to the whole unit. They can be overriden locally
+/// with an `@Allow`, `@Warn` or `@Error` attribute on an item, or silenced for a
+/// single line with a `// qsharp-lint-ignore` comment. The nearest enclosing
+/// override of a lint wins.
+///
+/// Lints that depend on items from other packages, such as uses of deprecated
+/// items or standard library callables, are only reported by [`run_lints_with_dependencies`].
 #[must_use]
 pub fn run_lints(compile_unit: &CompileUnit, config: Option<&[LintConfig]>) -> Vec<Lint> {
-    let mut ast_lints = run_ast_lints(&compile_unit.ast.package, config);
-    let mut hir_lints = run_hir_lints(&compile_unit.package, config);
+    filter_allowed(run_lints_without_filtering(None, compile_unit, config))
+}
 
-    let mut lints = Vec::new();
-    lints.append(&mut ast_lints);
-    lints.append(&mut hir_lints);
+/// Like [`run_lints`], but also looks up the items the unit uses from its
+/// dependencies in `package_store`.
+#[must_use]
+pub fn run_lints_with_dependencies(
+    package_store: &PackageStore,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    filter_allowed(run_lints_without_filtering(
+        Some(package_store),
+        compile_unit,
+        config,
+    ))
+}
+
+fn filter_allowed(lints: Vec<Lint>) -> Vec<Lint> {
     lints
         .into_iter()
         .filter(|lint| !matches!(lint.level, LintLevel::Allow))
         .collect()
 }
 
+/// Runs the lints with their local overrides applied, keeping the
+/// lints that end up at the [`LintLevel::Allow`] level.
+pub(crate) fn run_lints_without_filtering(
+    package_store: Option<&PackageStore>,
+    compile_unit: &CompileUnit,
+    config: Option<&[LintConfig]>,
+) -> Vec<Lint> {
+    let compilation = Compilation {
+        package_store,
+        compile_unit,
+    };
+
+    let mut ast_lints = run_ast_lints(&compile_unit.ast.package, config, compilation);
+    let mut hir_lints = run_hir_lints(&compile_unit.package, config, compilation);
+
+    let mut lints = Vec::new();
+    lints.append(&mut ast_lints);
+    lints.append(&mut hir_lints);
+
+    apply_overrides(compilation, &mut lints);
+    lints
+}
+
+/// Applies the local overrides in the compilation to the levels of `lints`.
+fn apply_overrides<'a>(compilation: Compilation, lints: impl IntoIterator<Item = &'a mut Lint>) {
+    let overrides = LintOverrides::new(compilation);
+    for lint in lints {
+        if let Some(level) = overrides.level(lint) {
+            if !matches!(lint.level, LintLevel::ForceWarn | LintLevel::ForceError) {
+                lint.level = level;
+            }
+        }
+    }
+}
+
+/// The compilation being linted. Lints that need more than the tree they walk,
+/// such as name resolutions, types, items, or the source text, get them from here.
+#[derive(Clone, Copy)]
+pub(crate) struct Compilation<'a> {
+    pub package_store: Option<&'a PackageStore>,
+    pub compile_unit: &'a CompileUnit,
+}
+
+impl<'a> Compilation<'a> {
+    /// Returns the HIR item referred to by `item_id`, which may be in a dependency.
+    /// An `item_id` without a package refers to the unit being linted.
+    pub fn resolve_item(&self, item_id: &ItemId) -> Option<&'a Item> {
+        match item_id.package {
+            Some(package_id) => self
+                .package_store?
+                .get(package_id)?
+                .package
+                .items
+                .get(item_id.item),
+            None => self.compile_unit.package.items.get(item_id.item),
+        }
+    }
+}
+
 /// A lint emited by the linter.
 #[derive(Debug, Clone, thiserror::Error)]
 pub struct Lint {
@@ -36,9 +120,9 @@ pub struct Lint {
     /// The lint level: allow, warning, error.
     pub level: LintLevel,
     /// The message the user will see in the code editor.
-    pub message: &'static str,
+    pub message: Cow<'static, str>,
     /// The help text the user will see in the code editor.
-    pub help: &'static str,
+    pub help: Cow<'static, str>,
     /// An enum identifying this lint.
     pub kind: LintKind,
 }
@@ -50,6 +134,13 @@ impl std::fmt::Display for Lint {
 }
 
 impl Diagnostic for Lint {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(match self.kind {
+            LintKind::Ast(lint) => format!("Qsc.Lint.{lint:?}"),
+            LintKind::Hir(lint) => format!("Qsc.Lint.{lint:?}"),
+        }))
+    }
+
     fn severity(&self) -> Option<miette::Severity> {
         match self.level {
             LintLevel::Allow => None,
@@ -68,7 +159,7 @@ impl Diagnostic for Lint {
         if self.help.is_empty() {
             None
         } else {
-            Some(Box::new(self.help))
+            Some(Box::new(&self.help))
         }
     }
 }
@@ -121,3 +212,13 @@ pub enum LintKind {
     /// HIR lint name.
     Hir(HirLint),
 }
+
+impl FromStr for LintKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AstLint::from_str(s)
+            .map(LintKind::Ast)
+            .or_else(|()| HirLint::from_str(s).map(LintKind::Hir))
+    }
+}
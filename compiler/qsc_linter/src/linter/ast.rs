@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::{
+    linter::Compilation,
     lints::ast::{AstLint, CombinedAstLints},
     Lint, LintConfig, LintLevel,
 };
@@ -16,7 +17,11 @@ use qsc_ast::{
 /// The entry point to the AST linter. It takes a [`qsc_ast::ast::Package`]
 /// as input and outputs a [`Vec<Lint>`](Lint).
 #[must_use]
-pub fn run_ast_lints(package: &qsc_ast::ast::Package, config: Option<&[LintConfig]>) -> Vec<Lint> {
+pub(crate) fn run_ast_lints(
+    package: &qsc_ast::ast::Package,
+    config: Option<&[LintConfig]>,
+    compilation: Compilation,
+) -> Vec<Lint> {
     let config: Vec<(AstLint, LintLevel)> = config
         .unwrap_or(&[])
         .iter()
@@ -29,7 +34,7 @@ pub fn run_ast_lints(package: &qsc_ast::ast::Package, config: Option<&[LintConfi
         })
         .collect();
 
-    let mut lints = CombinedAstLints::from_config(config);
+    let mut lints = CombinedAstLints::from_config(config, compilation);
 
     for node in package.nodes.iter() {
         match node {
@@ -76,13 +81,13 @@ pub(crate) trait AstLintPass {
 macro_rules! declare_ast_lints {
     ($( ($lint_name:ident, $default_level:expr, $msg:expr, $help:expr) ),* $(,)?) => {
         // Declare the structs representing each lint.
-        use crate::{Lint, LintKind, LintLevel, linter::ast::AstLintPass};
+        use crate::{Lint, LintKind, LintLevel, linter::{ast::AstLintPass, Compilation}};
         $(declare_ast_lints!{ @LINT_STRUCT $lint_name, $default_level, $msg, $help})*
 
         // This is a silly wrapper module to avoid contaminating the environment
         // calling the macro with unwanted imports.
         mod _ast_macro_expansion {
-            use crate::{linter::ast::{declare_ast_lints, AstLintPass}, Lint, LintLevel};
+            use crate::{linter::{ast::{declare_ast_lints, AstLintPass}, Compilation}, Lint, LintLevel};
             use qsc_ast::{
                 ast::{
                     Attr, Block, CallableDecl, Expr, FunctorExpr, Ident, Item, Namespace, Package, Pat, Path,
@@ -106,28 +111,23 @@ macro_rules! declare_ast_lints {
 
     // Declare & implement a struct representing a lint.
     (@LINT_STRUCT $lint_name:ident, $default_level:expr, $msg:expr, $help:expr) => {
-        pub(crate) struct $lint_name {
+        pub(crate) struct $lint_name<'compilation> {
             level: LintLevel,
             message: &'static str,
             help: &'static str,
             kind: LintKind,
+            // Not every lint needs to look up names, types, or items.
+            #[allow(dead_code)]
+            compilation: Compilation<'compilation>,
         }
 
-        impl Default for $lint_name {
-            fn default() -> Self {
-                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help, kind: LintKind::Ast(AstLint::$lint_name) }
-            }
-        }
+        impl<'compilation> $lint_name<'compilation> {
+            const DEFAULT_LEVEL: LintLevel = $default_level;
 
-        impl From<LintLevel> for $lint_name {
-            fn from(value: LintLevel) -> Self {
-                Self { level: value, message: $msg, help: $help, kind: LintKind::Ast(AstLint::$lint_name) }
+            fn new(compilation: Compilation<'compilation>) -> Self {
+                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help, kind: LintKind::Ast(AstLint::$lint_name), compilation }
             }
         }
-
-        impl $lint_name {
-            const DEFAULT_LEVEL: LintLevel = $default_level;
-        }
     };
 
     // Declare the `AstLint` enum.
@@ -143,6 +143,19 @@ macro_rules! declare_ast_lints {
                 $lint_name
             ),*
         }
+
+        impl std::str::FromStr for AstLint {
+            type Err = ();
+
+            // Lint names are matched ignoring case, so that both the `PascalCase`
+            // and the `camelCase` spellings of a lint name are accepted.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s.eq_ignore_ascii_case(stringify!($lint_name)) {
+                    return Ok(AstLint::$lint_name);
+                })*
+                Err(())
+            }
+        }
     };
 
     // Declare & implement the `CombinedAstLints` structure.
@@ -155,24 +168,24 @@ macro_rules! declare_ast_lints {
         /// Combined AST lints for speed. This combined lint allow us to
         /// evaluate all the lints in a single AST pass, instead of doing
         /// an individual pass for each lint in the linter.
-        pub(crate) struct CombinedAstLints {
+        pub(crate) struct CombinedAstLints<'compilation> {
             pub buffer: Vec<Lint>,
-            $($lint_name: $lint_name),*
+            $($lint_name: $lint_name<'compilation>),*
         }
 
-        impl Default for CombinedAstLints {
-            fn default() -> Self {
+        impl<'compilation> CombinedAstLints<'compilation> {
+            fn new(compilation: Compilation<'compilation>) -> Self {
                 Self {
                     buffer: Vec::default(),
-                    $($lint_name: <$lint_name>::default()),*
+                    $($lint_name: <$lint_name>::new(compilation)),*
                 }
             }
         }
 
         // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
-        impl CombinedAstLints {
-            pub fn from_config(config: Vec<(AstLint, LintLevel)>) -> Self {
-                let mut combined_ast_lints = Self::default();
+        impl<'compilation> CombinedAstLints<'compilation> {
+            pub fn from_config(config: Vec<(AstLint, LintLevel)>, compilation: Compilation<'compilation>) -> Self {
+                let mut combined_ast_lints = Self::new(compilation);
                 for (lint, level) in config {
                     match lint {
                         $(AstLint::$lint_name => combined_ast_lints.$lint_name.level = level),*
@@ -200,7 +213,7 @@ macro_rules! declare_ast_lints {
             fn check_ident(&mut self, ident: &Ident) { $(self.$lint_name.check_ident(ident, &mut self.buffer));*; }
         }
 
-        impl<'a> Visitor<'a> for CombinedAstLints {
+        impl<'a> Visitor<'a> for CombinedAstLints<'_> {
             fn visit_package(&mut self, package: &'a Package) {
                 self.check_package(package);
                 visit::walk_package(self, package);
@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::{
+    linter::Compilation,
     lints::hir::{CombinedHirLints, HirLint},
     Lint, LintConfig, LintLevel,
 };
@@ -13,7 +14,11 @@ use qsc_hir::{
 /// The entry point to the HIR linter. It takes a [`qsc_hir::hir::Package`]
 /// as input and outputs a [`Vec<Lint>`](Lint).
 #[must_use]
-pub fn run_hir_lints(package: &Package, config: Option<&[LintConfig]>) -> Vec<Lint> {
+pub(crate) fn run_hir_lints(
+    package: &Package,
+    config: Option<&[LintConfig]>,
+    compilation: Compilation,
+) -> Vec<Lint> {
     let config: Vec<(HirLint, LintLevel)> = config
         .unwrap_or(&[])
         .iter()
@@ -26,7 +31,7 @@ pub fn run_hir_lints(package: &Package, config: Option<&[LintConfig]>) -> Vec<Li
         })
         .collect();
 
-    let mut lints = CombinedHirLints::from_config(config);
+    let mut lints = CombinedHirLints::from_config(config, compilation);
 
     for (_, item) in &package.items {
         lints.visit_item(item);
@@ -67,13 +72,13 @@ pub(crate) trait HirLintPass {
 macro_rules! declare_hir_lints {
     ($( ($lint_name:ident, $default_level:expr, $msg:expr, $help:expr) ),* $(,)?) => {
         // Declare the structs representing each lint.
-        use crate::{Lint, LintKind, LintLevel, linter::hir::HirLintPass};
+        use crate::{Lint, LintKind, LintLevel, linter::{hir::HirLintPass, Compilation}};
         $(declare_hir_lints!{ @LINT_STRUCT $lint_name, $default_level, $msg, $help })*
 
         // This is a silly wrapper module to avoid contaminating the environment
         // calling the macro with unwanted imports.
         mod _hir_macro_expansion {
-            use crate::{linter::hir::{declare_hir_lints, HirLintPass}, Lint, LintLevel};
+            use crate::{linter::{hir::{declare_hir_lints, HirLintPass}, Compilation}, Lint, LintLevel};
             use qsc_hir::{
                 hir::{Block, CallableDecl, Expr, Ident, Item, Package, Pat, QubitInit, SpecDecl, Stmt},
                 visit::{self, Visitor},
@@ -94,28 +99,23 @@ macro_rules! declare_hir_lints {
 
     // Declare & implement a struct representing a lint.
     (@LINT_STRUCT $lint_name:ident, $default_level:expr, $msg:expr, $help:expr) => {
-        pub(crate) struct $lint_name {
+        pub(crate) struct $lint_name<'compilation> {
             level: LintLevel,
             message: &'static str,
             help: &'static str,
             kind: LintKind,
+            // Not every lint needs to look up items or the AST.
+            #[allow(dead_code)]
+            compilation: Compilation<'compilation>,
         }
 
-        impl Default for $lint_name {
-            fn default() -> Self {
-                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help, kind: LintKind::Hir(HirLint::$lint_name) }
-            }
-        }
+        impl<'compilation> $lint_name<'compilation> {
+            const DEFAULT_LEVEL: LintLevel = $default_level;
 
-        impl From<LintLevel> for $lint_name {
-            fn from(value: LintLevel) -> Self {
-                Self { level: value, message: $msg, help: $help, kind: LintKind::Hir(HirLint::$lint_name) }
+            fn new(compilation: Compilation<'compilation>) -> Self {
+                Self { level: Self::DEFAULT_LEVEL, message: $msg, help: $help, kind: LintKind::Hir(HirLint::$lint_name), compilation }
             }
         }
-
-        impl $lint_name {
-            const DEFAULT_LEVEL: LintLevel = $default_level;
-        }
     };
 
     // Declare the `HirLint` enum.
@@ -131,6 +131,19 @@ macro_rules! declare_hir_lints {
                 $lint_name
             ),*
         }
+
+        impl std::str::FromStr for HirLint {
+            type Err = ();
+
+            // Lint names are matched ignoring case, so that both the `PascalCase`
+            // and the `camelCase` spellings of a lint name are accepted.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s.eq_ignore_ascii_case(stringify!($lint_name)) {
+                    return Ok(HirLint::$lint_name);
+                })*
+                Err(())
+            }
+        }
     };
 
     // Declare & implement the `CombinedAstLints` structure.
@@ -143,24 +156,24 @@ macro_rules! declare_hir_lints {
         /// Combined HIR lints for speed. This combined lint allow us to
         /// evaluate all the lints in a single HIR pass, instead of doing
         /// an individual pass for each lint in the linter.
-        pub(crate) struct CombinedHirLints {
+        pub(crate) struct CombinedHirLints<'compilation> {
             pub buffer: Vec<Lint>,
-            $($lint_name: $lint_name),*
+            $($lint_name: $lint_name<'compilation>),*
         }
 
-        impl Default for CombinedHirLints {
-            fn default() -> Self {
+        impl<'compilation> CombinedHirLints<'compilation> {
+            fn new(compilation: Compilation<'compilation>) -> Self {
                 Self {
                     buffer: Vec::default(),
-                    $($lint_name: <$lint_name>::default()),*
+                    $($lint_name: <$lint_name>::new(compilation)),*
                 }
             }
         }
 
         // Most of the calls here are empty methods and they get optimized at compile time to a no-op.
-        impl CombinedHirLints {
-            pub fn from_config(config: Vec<(HirLint, LintLevel)>) -> Self {
-                let mut combined_hir_lints = Self::default();
+        impl<'compilation> CombinedHirLints<'compilation> {
+            pub fn from_config(config: Vec<(HirLint, LintLevel)>, compilation: Compilation<'compilation>) -> Self {
+                let mut combined_hir_lints = Self::new(compilation);
                 for (lint, level) in config {
                     match lint {
                         $(HirLint::$lint_name => combined_hir_lints.$lint_name.level = level),*
@@ -181,7 +194,7 @@ macro_rules! declare_hir_lints {
             fn check_stmt(&mut self, stmt: &Stmt) { $(self.$lint_name.check_stmt(stmt, &mut self.buffer));* }
         }
 
-        impl<'a> Visitor<'a> for CombinedHirLints {
+        impl<'a> Visitor<'a> for CombinedHirLints<'_> {
             fn visit_block(&mut self, block: &'a Block) {
                 self.check_block(block);
                 visit::walk_block(self, block);
@@ -0,0 +1,155 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Compilation, Lint, LintKind, LintLevel};
+use qsc_ast::{
+    ast::{Attr, ExprKind, Item, Lit, TopLevelNode},
+    visit::{self, Visitor},
+};
+use qsc_data_structures::span::Span;
+use qsc_frontend::lex::raw;
+use std::str::FromStr;
+
+/// The comment that silences lints on a line, followed by the lint names.
+const IGNORE_COMMENT: &str = "qsharp-lint-ignore";
+
+/// Local overrides of the configured lint levels in a compile unit.
+pub(crate) struct LintOverrides {
+    overrides: Vec<Override>,
+}
+
+/// Sets the level of a lint for everything within a span.
+struct Override {
+    span: Span,
+    kind: LintKind,
+    level: LintLevel,
+}
+
+impl LintOverrides {
+    pub(crate) fn new(compilation: Compilation) -> Self {
+        let mut collector = AttrOverrides {
+            overrides: Vec::new(),
+        };
+        for node in &*compilation.compile_unit.ast.package.nodes {
+            match node {
+                TopLevelNode::Namespace(namespace) => collector.visit_namespace(namespace),
+                TopLevelNode::Stmt(stmt) => collector.visit_stmt(stmt),
+            }
+        }
+
+        let mut overrides = collector.overrides;
+        overrides.extend(comment_overrides(compilation));
+        Self { overrides }
+    }
+
+    /// Returns the level set for `lint` by the innermost override
+    /// whose span contains the start of the lint.
+    pub(crate) fn level(&self, lint: &Lint) -> Option<LintLevel> {
+        self.overrides
+            .iter()
+            .filter(|o| {
+                o.kind == lint.kind && o.span.lo <= lint.span.lo && lint.span.lo <= o.span.hi
+            })
+            .min_by_key(|o| o.span.hi - o.span.lo)
+            .map(|o| o.level)
+    }
+}
+
+/// Collects the `@Allow("Name")`, `@Warn("Name")` and `@Error("Name")`
+/// attributes, which apply to the whole item they are attached to.
+struct AttrOverrides {
+    overrides: Vec<Override>,
+}
+
+impl<'a> Visitor<'a> for AttrOverrides {
+    fn visit_item(&mut self, item: &'a Item) {
+        for attr in &*item.attrs {
+            if let Some((kind, level)) = attr_override(attr) {
+                self.overrides.push(Override {
+                    span: item.span,
+                    kind,
+                    level,
+                });
+            }
+        }
+        visit::walk_item(self, item);
+    }
+}
+
+fn attr_override(attr: &Attr) -> Option<(LintKind, LintLevel)> {
+    let level = match attr.name.name.as_ref() {
+        "Allow" => LintLevel::Allow,
+        "Warn" => LintLevel::Warn,
+        "Error" => LintLevel::Error,
+        _ => return None,
+    };
+    let ExprKind::Paren(inner) = &*attr.arg.kind else {
+        return None;
+    };
+    let ExprKind::Lit(lit) = &*inner.kind else {
+        return None;
+    };
+    let Lit::String(name) = &**lit else {
+        return None;
+    };
+    Some((LintKind::from_str(name).ok()?, level))
+}
+
+/// Collects the `// qsharp-lint-ignore name1, name2` comments. A comment after
+/// some code applies to its own line, and a comment on a line by itself applies
+/// to the next line. Comments are taken from the lexer, so `//` inside string
+/// literals is not mistaken for a comment.
+fn comment_overrides(compilation: Compilation) -> Vec<Override> {
+    let mut overrides = Vec::new();
+    for source in compilation.compile_unit.sources.iter() {
+        let contents = source.contents.as_ref();
+        let mut tokens = raw::Lexer::new(contents).peekable();
+        while let Some(token) = tokens.next() {
+            if token.kind != raw::TokenKind::Comment(raw::CommentKind::Normal) {
+                continue;
+            }
+            let comment_start = token.offset as usize;
+            let comment_end = tokens
+                .peek()
+                .map_or(contents.len(), |next| next.offset as usize);
+            let Some(names) = contents[comment_start..comment_end]
+                .trim_start_matches('/')
+                .trim_start()
+                .strip_prefix(IGNORE_COMMENT)
+            else {
+                continue;
+            };
+
+            let line_start = contents[..comment_start].rfind('\n').map_or(0, |i| i + 1);
+            let target_start = if contents[line_start..comment_start].trim().is_empty() {
+                match contents[comment_start..].find('\n') {
+                    Some(i) => comment_start + i + 1,
+                    None => continue,
+                }
+            } else {
+                line_start
+            };
+            let target_end = contents[target_start..]
+                .find('\n')
+                .map_or(contents.len(), |i| target_start + i);
+            let target_line = contents[target_start..target_end].trim_end_matches('\r');
+
+            let lo = source.offset + u32::try_from(target_start).expect("offset should fit in u32");
+            let hi = lo + u32::try_from(target_line.len()).expect("length should fit in u32");
+
+            for name in names
+                .split([',', ' ', '\t'])
+                .filter(|name| !name.is_empty())
+            {
+                if let Ok(kind) = LintKind::from_str(name) {
+                    overrides.push(Override {
+                        span: Span { lo, hi },
+                        kind,
+                        level: LintLevel::Allow,
+                    });
+                }
+            }
+        }
+    }
+    overrides
+}
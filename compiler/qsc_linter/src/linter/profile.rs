@@ -0,0 +1,307 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{Compilation, Lint, LintConfig, LintKind, LintLevel};
+use crate::lints::hir::{HirLint, ProfileCompatibility};
+use miette::Diagnostic;
+use qsc_data_structures::{span::Span, target::TargetCapabilityFlags};
+use qsc_fir::{
+    fir::{
+        Block, BlockId, CallableImpl, Expr, ExprId, ExprKind, Global, ItemId, Package, PackageId,
+        PackageLookup, PackageStore, Pat, PatId, Res, Stmt, StmtId, UnOp,
+    },
+    visit::{self, Visitor},
+};
+use qsc_frontend::compile;
+use qsc_rca::{
+    errors::{generate_errors_from_runtime_features, get_missing_runtime_features, Error},
+    ComputeKind, PackageStoreComputeProperties, RuntimeFeatureFlags,
+};
+use rustc_hash::FxHashSet;
+
+/// How many calls deep the origin of a runtime feature is looked for.
+const MAX_CALL_DEPTH: usize = 16;
+
+/// Runs the `profileCompatibility` lint on the unit with `package_id` in `package_store`,
+/// reporting each expression that needs runtime features the target does not support.
+/// The unit must already be lowered to `fir_package_id` in `fir_store`, and analyzed
+/// into `compute_properties`.
+///
+/// The lint is opt-in: nothing is reported unless `config` enables it. Each lint repeats
+/// a capabilities check error, and adds the chain of calls that introduced the runtime
+/// feature that is missing.
+#[must_use]
+pub fn run_profile_lints(
+    package_store: &compile::PackageStore,
+    package_id: qsc_hir::hir::PackageId,
+    fir_store: &PackageStore,
+    fir_package_id: PackageId,
+    compute_properties: &PackageStoreComputeProperties,
+    capabilities: TargetCapabilityFlags,
+    config: Option<&[LintConfig]>,
+) -> Vec<ProfileLint> {
+    let level = profile_lint_level(config);
+    if level == LintLevel::Allow {
+        return Vec::new();
+    }
+    let Some(compile_unit) = package_store.get(package_id) else {
+        return Vec::new();
+    };
+
+    let compilation = Compilation {
+        package_store: Some(package_store),
+        compile_unit,
+    };
+    let tracer = FeatureTracer {
+        store: fir_store,
+        compute_properties,
+        capabilities,
+    };
+
+    let errors = qsc_passes::run_fir_passes(
+        fir_store.get(fir_package_id),
+        compute_properties.get(fir_package_id),
+        capabilities,
+    );
+    let mut spans = Vec::new();
+    for error in &errors {
+        if let Some(span) = error_span(error) {
+            if !spans.contains(&span) {
+                spans.push(span);
+            }
+        }
+    }
+
+    let mut lints = Vec::new();
+    for span in spans {
+        match tracer.find_expr(fir_package_id, span) {
+            Some((expr_id, missing)) => {
+                for feature in missing.iter() {
+                    let chain = tracer.call_chain(fir_package_id, expr_id, feature);
+                    lints.extend(
+                        generate_errors_from_runtime_features(feature, span)
+                            .iter()
+                            .map(|error| profile_lint(compilation, error, span, level, &chain)),
+                    );
+                }
+            }
+            // Features that affect a whole specialization or the program output
+            // are not tied to an expression, so there is no call chain to follow.
+            None => lints.extend(errors.iter().filter_map(|error| match error {
+                qsc_passes::Error::CapabilitiesCk(error) if error_span(error) == Some(span) => {
+                    Some(profile_lint(compilation, error, span, level, &[]))
+                }
+                _ => None,
+            })),
+        }
+    }
+
+    super::apply_overrides(compilation, lints.iter_mut().map(|lint| &mut lint.lint));
+    lints.retain(|lint| lint.lint.level != LintLevel::Allow);
+    lints
+}
+
+/// A `profileCompatibility` lint, along with the code of the capabilities
+/// check error that it repeats.
+#[derive(Debug, Clone)]
+pub struct ProfileLint {
+    /// The lint.
+    pub lint: Lint,
+    code: String,
+}
+
+impl ProfileLint {
+    /// Returns `true` if the lint reports `error` at the error level, which is
+    /// the case if it repeats an error with the same code at the same span.
+    pub fn reports_error(&self, error: &impl Diagnostic) -> bool {
+        matches!(self.lint.level, LintLevel::Error | LintLevel::ForceError)
+            && error
+                .code()
+                .is_some_and(|code| code.to_string() == self.code)
+            && error_span(error) == Some(self.lint.span)
+    }
+}
+
+fn profile_lint_level(config: Option<&[LintConfig]>) -> LintLevel {
+    config
+        .unwrap_or(&[])
+        .iter()
+        .rev()
+        .find(|lint_config| lint_config.kind == LintKind::Hir(HirLint::ProfileCompatibility))
+        .map_or(LintLevel::Allow, |lint_config| lint_config.level)
+}
+
+fn error_span(error: &impl Diagnostic) -> Option<Span> {
+    let label = error.labels()?.next()?;
+    let lo = u32::try_from(label.offset()).ok()?;
+    let hi = u32::try_from(label.offset() + label.len()).ok()?;
+    Some(Span { lo, hi })
+}
+
+fn profile_lint(
+    compilation: Compilation,
+    error: &Error,
+    span: Span,
+    level: LintLevel,
+    chain: &[String],
+) -> ProfileLint {
+    let message = if chain.is_empty() {
+        error.to_string()
+    } else {
+        format!("{error}, introduced through {}", chain.join(" -> "))
+    };
+    let help = error
+        .help()
+        .map(|help| help.to_string())
+        .unwrap_or_default();
+    ProfileLint {
+        lint: ProfileCompatibility::with_level(compilation, level).lint(span, message, help),
+        code: error
+            .code()
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Follows runtime features through the call graph back to where they come from.
+struct FeatureTracer<'a> {
+    store: &'a PackageStore,
+    compute_properties: &'a PackageStoreComputeProperties,
+    capabilities: TargetCapabilityFlags,
+}
+
+impl FeatureTracer<'_> {
+    /// Finds the expression at `span` and the runtime features it uses that the target lacks.
+    fn find_expr(
+        &self,
+        package_id: PackageId,
+        span: Span,
+    ) -> Option<(ExprId, RuntimeFeatureFlags)> {
+        self.store
+            .get(package_id)
+            .exprs
+            .iter()
+            .filter(|(_, expr)| expr.span == span)
+            .find_map(|(expr_id, _)| {
+                let missing = get_missing_runtime_features(
+                    self.runtime_features(package_id, expr_id),
+                    self.capabilities,
+                );
+                (!missing.is_empty()).then_some((expr_id, missing))
+            })
+    }
+
+    fn runtime_features(&self, package_id: PackageId, expr_id: ExprId) -> RuntimeFeatureFlags {
+        match self
+            .compute_properties
+            .get(package_id)
+            .exprs
+            .get(expr_id)
+            .map(|properties| properties.inherent)
+        {
+            Some(ComputeKind::Quantum(properties)) => properties.runtime_features,
+            _ => RuntimeFeatureFlags::empty(),
+        }
+    }
+
+    /// Returns the names of the callables that `feature` was introduced through,
+    /// starting with the callable called by the expression `expr_id` and ending
+    /// with the one whose body uses the feature.
+    fn call_chain(
+        &self,
+        mut package_id: PackageId,
+        mut expr_id: ExprId,
+        feature: RuntimeFeatureFlags,
+    ) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut visited = FxHashSet::default();
+        while chain.len() < MAX_CALL_DEPTH {
+            let package = self.store.get(package_id);
+            let ExprKind::Call(callee, _) = &package.get_expr(expr_id).kind else {
+                break;
+            };
+            let Some(item_id) = callee_item(package, *callee) else {
+                break;
+            };
+            let callee_package_id = item_id.package.unwrap_or(package_id);
+            if !visited.insert((callee_package_id, item_id.item)) {
+                break;
+            }
+            let callee_package = self.store.get(callee_package_id);
+            let Some(Global::Callable(decl)) = callee_package.get_global(item_id.item) else {
+                break;
+            };
+            let CallableImpl::Spec(spec_impl) = &decl.implementation else {
+                break;
+            };
+            let mut finder = OriginFinder {
+                tracer: self,
+                package_id: callee_package_id,
+                package: callee_package,
+                feature,
+                origin: None,
+            };
+            finder.visit_block(spec_impl.body.block);
+            // A callable that only uses the feature because of its arguments
+            // did not introduce it, so it is not part of the chain.
+            let Some(origin) = finder.origin else {
+                break;
+            };
+            chain.push(format!("`{}`", decl.name.name));
+            package_id = callee_package_id;
+            expr_id = origin;
+        }
+        chain
+    }
+}
+
+/// Returns the callable item a call expression refers to, looking through functor applications.
+fn callee_item(package: &Package, callee: ExprId) -> Option<ItemId> {
+    match &package.get_expr(callee).kind {
+        ExprKind::Var(Res::Item(item_id), _) => Some(*item_id),
+        ExprKind::UnOp(UnOp::Functor(_), inner) => callee_item(package, *inner),
+        _ => None,
+    }
+}
+
+/// Finds the innermost expression in a callable body that uses a runtime feature.
+struct OriginFinder<'a, 'b> {
+    tracer: &'b FeatureTracer<'a>,
+    package_id: PackageId,
+    package: &'a Package,
+    feature: RuntimeFeatureFlags,
+    origin: Option<ExprId>,
+}
+
+impl<'a> Visitor<'a> for OriginFinder<'a, '_> {
+    fn get_block(&self, id: BlockId) -> &'a Block {
+        self.package.get_block(id)
+    }
+
+    fn get_expr(&self, id: ExprId) -> &'a Expr {
+        self.package.get_expr(id)
+    }
+
+    fn get_pat(&self, id: PatId) -> &'a Pat {
+        self.package.get_pat(id)
+    }
+
+    fn get_stmt(&self, id: StmtId) -> &'a Stmt {
+        self.package.get_stmt(id)
+    }
+
+    fn visit_expr(&mut self, expr_id: ExprId) {
+        if self.origin.is_some() {
+            return;
+        }
+        visit::walk_expr(self, expr_id);
+        if self.origin.is_none()
+            && self
+                .tracer
+                .runtime_features(self.package_id, expr_id)
+                .contains(self.feature)
+        {
+            self.origin = Some(expr_id);
+        }
+    }
+}
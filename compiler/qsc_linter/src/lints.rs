@@ -9,8 +9,8 @@ macro_rules! lint {
         Lint {
             span: $span,
             level: $lint.level,
-            message: $lint.message,
-            help: $lint.help,
+            message: $lint.message.into(),
+            help: $lint.help.into(),
             kind: $lint.kind,
         }
     };
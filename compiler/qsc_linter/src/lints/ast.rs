@@ -3,16 +3,42 @@
 
 use super::lint;
 use crate::linter::ast::declare_ast_lints;
-use qsc_ast::ast::{BinOp, ExprKind, Lit, StmtKind};
+use qsc_ast::ast::{BinOp, ExprKind, Lit, Path, StmtKind};
 use qsc_data_structures::span::Span;
+use qsc_frontend::resolve::Res;
+use qsc_hir::hir;
 
 declare_ast_lints! {
+    (Deprecated, LintLevel::Warn, "use of deprecated item", "this item is deprecated and may be removed in a future version"),
     (DivisionByZero, LintLevel::Error, "attempt to divide by zero", "division by zero will fail at runtime"),
     (NeedlessParens, LintLevel::Allow, "unnecessary parentheses", "remove the extra parentheses for clarity"),
     (RedundantSemicolons, LintLevel::Warn, "redundant semicolons", "remove the redundant semicolons"),
 }
 
-impl AstLintPass for DivisionByZero {
+impl AstLintPass for Deprecated<'_> {
+    /// Checks every reference to an item, whether it is called, used as a type,
+    /// or imported, since the item may be declared in another package.
+    fn check_path(&self, path: &Path, buffer: &mut Vec<Lint>) {
+        if let Some(Res::Item(item_id, _)) = self.compilation.compile_unit.ast.names.get(path.id) {
+            let deprecated = self.compilation.resolve_item(item_id).and_then(|item| {
+                item.attrs.iter().find_map(|attr| match attr {
+                    hir::Attr::Deprecated(replacement) => Some(replacement),
+                    _ => None,
+                })
+            });
+            match deprecated {
+                Some(Some(replacement)) => buffer.push(Lint {
+                    message: format!("{}; use `{replacement}` instead", self.message).into(),
+                    ..lint!(self, path.span)
+                }),
+                Some(None) => buffer.push(lint!(self, path.span)),
+                None => {}
+            }
+        }
+    }
+}
+
+impl AstLintPass for DivisionByZero<'_> {
     fn check_expr(&self, expr: &qsc_ast::ast::Expr, buffer: &mut Vec<Lint>) {
         if let ExprKind::BinOp(BinOp::Div, _, ref rhs) = *expr.kind {
             if let ExprKind::Lit(ref lit) = *rhs.kind {
@@ -24,7 +50,7 @@ impl AstLintPass for DivisionByZero {
     }
 }
 
-impl NeedlessParens {
+impl NeedlessParens<'_> {
     /// The idea is that if we find a expr of the form:
     /// a + (expr)
     /// and `expr` has higher precedence than `+`, then the
@@ -44,7 +70,7 @@ impl NeedlessParens {
     }
 }
 
-impl AstLintPass for NeedlessParens {
+impl AstLintPass for NeedlessParens<'_> {
     fn check_expr(&self, expr: &qsc_ast::ast::Expr, buffer: &mut Vec<Lint>) {
         match &*expr.kind {
             ExprKind::BinOp(_, left, right) => {
@@ -68,7 +94,7 @@ impl AstLintPass for NeedlessParens {
     }
 }
 
-impl RedundantSemicolons {
+impl RedundantSemicolons<'_> {
     /// Helper function that pushes a lint to the buffer if we have
     /// found two or more semicolons.
     fn maybe_push(&self, seq: &mut Option<Span>, buffer: &mut Vec<Lint>) {
@@ -78,7 +104,7 @@ impl RedundantSemicolons {
     }
 }
 
-impl AstLintPass for RedundantSemicolons {
+impl AstLintPass for RedundantSemicolons<'_> {
     /// Checks if there are redundant semicolons. The idea is that a redundant
     /// semicolon is parsed as an Empty statement. If we have multiple empty
     /// statements in a row, we group them as single lint, that spans from
@@ -1,18 +1,30 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use std::mem::replace;
+
+use qsc_ast::ast::QubitSource;
+use qsc_data_structures::span::Span;
 use qsc_hir::{
-    hir::{CallableDecl, CallableKind, Expr, ExprKind, SpecBody, SpecDecl, Stmt, StmtKind},
-    ty::Ty,
+    hir::{
+        Block, CallableDecl, CallableKind, Expr, ExprKind, Functor, ItemId, ItemKind, Mutability,
+        NodeId, Pat, PatKind, Res, SpecBody, SpecDecl, Stmt, StmtKind, UnOp,
+    },
+    ty::{Prim, Ty},
     visit::{self, Visitor},
 };
+use rustc_hash::FxHashMap;
 
 use crate::linter::hir::declare_hir_lints;
 
 use super::lint;
 
 declare_hir_lints! {
-    (NeedlessOperation, LintLevel::Allow, "operation does not contain any quantum operations", "this callable can be declared as a function instead")
+    (BorrowedQubitModified, LintLevel::Warn, "borrowed qubit may be left in a modified state", "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block"),
+    (MeasuredQubitNotReset, LintLevel::Allow, "qubit is measured but not reset before it is released", "use `MResetZ` to measure and reset the qubit in one step"),
+    (NeedlessOperation, LintLevel::Allow, "operation does not contain any quantum operations", "this callable can be declared as a function instead"),
+    (ProfileCompatibility, LintLevel::Allow, "code is not supported by the target profile", ""),
+    (UnusedQubits, LintLevel::Warn, "qubits are allocated but never used", "these qubits are never passed to an operation, so the allocation can be removed"),
 }
 
 /// Helper to check if an operation has desired operation characteristics
@@ -91,7 +103,7 @@ impl Visitor<'_> for IsQuantumOperation {
 
 /// HIR Lint for [`NeedlessOperation`], suggesting to use function
 /// We use [`IsQuantumOperation`] helper to check if a operation has desired operation characteristics
-impl HirLintPass for NeedlessOperation {
+impl HirLintPass for NeedlessOperation<'_> {
     fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
         if decl.kind == CallableKind::Operation {
             let mut op_limits = IsQuantumOperation::default();
@@ -104,3 +116,549 @@ impl HirLintPass for NeedlessOperation {
         }
     }
 }
+
+/// Reported by [`crate::run_profile_lints`], which needs the runtime capabilities
+/// analysis rather than a single pass over the HIR.
+impl HirLintPass for ProfileCompatibility<'_> {}
+
+impl<'compilation> ProfileCompatibility<'compilation> {
+    pub(crate) fn with_level(compilation: Compilation<'compilation>, level: LintLevel) -> Self {
+        Self {
+            level,
+            ..Self::new(compilation)
+        }
+    }
+
+    /// Creates the lint at `span`, with a message and help text describing
+    /// the runtime feature that the target does not support.
+    pub(crate) fn lint(&self, span: Span, message: String, help: String) -> Lint {
+        Lint {
+            message: message.into(),
+            help: help.into(),
+            ..lint!(self, span)
+        }
+    }
+}
+
+// By the time the linter runs, the qubit allocations in the HIR have been replaced
+// with calls to the runtime, so an allocation is recognized by its call and a `use`
+// is told apart from a `borrow` by the statement in the AST. Qubits are compared by
+// the local variables they are bound to, following immutable aliases.
+
+impl HirLintPass for UnusedQubits<'_> {
+    fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
+        for scope in qubit_scopes(decl, self.compilation) {
+            let mut uses = QubitUses {
+                compilation: self.compilation,
+                qubits: scope.qubits,
+                in_function_args: false,
+                used: false,
+            };
+            scope.stmts.iter().for_each(|stmt| uses.visit_stmt(stmt));
+            if !uses.used {
+                buffer.push(lint!(self, scope.pat_span));
+            }
+        }
+    }
+}
+
+impl HirLintPass for MeasuredQubitNotReset<'_> {
+    fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
+        for scope in qubit_scopes(decl, self.compilation) {
+            if scope.source != QubitSource::Fresh {
+                continue;
+            }
+            let roots = scope.qubits.roots();
+            let mut last_uses = LastQubitUses {
+                compilation: self.compilation,
+                qubits: scope.qubits,
+                last_uses: FxHashMap::default(),
+            };
+            scope
+                .stmts
+                .iter()
+                .for_each(|stmt| last_uses.visit_stmt(stmt));
+            for qubit in roots {
+                if let Some(Some(measurement)) = last_uses.last_uses.get(&qubit) {
+                    buffer.push(lint!(self, *measurement));
+                }
+            }
+        }
+    }
+}
+
+impl HirLintPass for BorrowedQubitModified<'_> {
+    fn check_callable_decl(&self, decl: &CallableDecl, buffer: &mut Vec<Lint>) {
+        for scope in qubit_scopes(decl, self.compilation) {
+            if scope.source != QubitSource::Dirty {
+                continue;
+            }
+            let mut calls = BorrowedQubitCalls {
+                compilation: self.compilation,
+                qubits: scope.qubits,
+                calls: FxHashMap::default(),
+                irreversible: false,
+            };
+            scope.stmts.iter().for_each(|stmt| calls.visit_stmt(stmt));
+            if calls.modified() {
+                buffer.push(lint!(self, scope.pat_span));
+            }
+        }
+    }
+}
+
+/// A `use` or `borrow` statement, along with the statements during which its qubits are live.
+struct QubitScope<'a> {
+    source: QubitSource,
+    pat_span: Span,
+    qubits: Qubits,
+    stmts: &'a [Stmt],
+}
+
+/// Finds the qubit allocations in the specializations of a callable. Specializations
+/// generated from another one share its span, and are only searched once.
+fn qubit_scopes<'a>(decl: &'a CallableDecl, compilation: Compilation) -> Vec<QubitScope<'a>> {
+    let mut finder = QubitScopeFinder {
+        compilation,
+        scopes: Vec::new(),
+    };
+    let mut spans = Vec::new();
+    let specs = [Some(&decl.body), decl.adj.as_ref(), decl.ctl.as_ref()];
+    for spec in specs.into_iter().chain([decl.ctl_adj.as_ref()]).flatten() {
+        if let SpecBody::Impl(_, block) = &spec.body {
+            if !spans.contains(&block.span) {
+                spans.push(block.span);
+                finder.visit_block(block);
+            }
+        }
+    }
+    finder.scopes
+}
+
+struct QubitScopeFinder<'a, 'b> {
+    compilation: Compilation<'b>,
+    scopes: Vec<QubitScope<'a>>,
+}
+
+impl<'a> Visitor<'a> for QubitScopeFinder<'a, '_> {
+    /// An allocation without its own block lasts until the end of the enclosing block.
+    /// The allocations of a statement binding several qubits follow one another.
+    fn visit_block(&mut self, block: &'a Block) {
+        let mut current: Option<(Span, QubitScope)> = None;
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            let allocation = allocated_qubit(stmt, self.compilation)
+                .and_then(|(id, span)| qubit_stmt(self.compilation, span).map(|found| (id, found)));
+            match (allocation, &mut current) {
+                (Some((id, (stmt_span, _, _))), Some((span, scope))) if stmt_span == *span => {
+                    scope.qubits.insert(id);
+                    scope.stmts = &block.stmts[i + 1..];
+                }
+                (Some((id, (stmt_span, source, pat_span))), current) => {
+                    let mut qubits = Qubits::default();
+                    qubits.insert(id);
+                    let scope = QubitScope {
+                        source,
+                        pat_span,
+                        qubits,
+                        stmts: &block.stmts[i + 1..],
+                    };
+                    if let Some((_, scope)) = current.replace((stmt_span, scope)) {
+                        self.scopes.push(scope);
+                    }
+                }
+                (None, _) => {}
+            }
+        }
+        if let Some((_, scope)) = current {
+            self.scopes.push(scope);
+        }
+        visit::walk_block(self, block);
+    }
+}
+
+/// Returns the local variable and the span of a qubit allocation introduced by
+/// replacing a `use` or `borrow` statement.
+fn allocated_qubit(stmt: &Stmt, compilation: Compilation) -> Option<(NodeId, Span)> {
+    let StmtKind::Local(_, pat, init) = &stmt.kind else {
+        return None;
+    };
+    let (PatKind::Bind(ident), ExprKind::Call(callee, _)) = (&pat.kind, &init.kind) else {
+        return None;
+    };
+    ["__quantum__rt__qubit_allocate", "AllocateQubitArray"]
+        .iter()
+        .any(|name| is_std_callable(callee, compilation, "QIR.Runtime", name))
+        .then_some((ident.id, init.span))
+}
+
+/// Finds the innermost `use` or `borrow` statement in the AST around `span`, and
+/// returns its span, its kind of qubits, and the span of its pattern.
+fn qubit_stmt(compilation: Compilation, span: Span) -> Option<(Span, QubitSource, Span)> {
+    struct Finder {
+        span: Span,
+        found: Option<(Span, QubitSource, Span)>,
+    }
+
+    impl<'a> qsc_ast::visit::Visitor<'a> for Finder {
+        fn visit_item(&mut self, item: &'a qsc_ast::ast::Item) {
+            if item.span.lo <= self.span.lo && self.span.hi <= item.span.hi {
+                qsc_ast::visit::walk_item(self, item);
+            }
+        }
+
+        fn visit_stmt(&mut self, stmt: &'a qsc_ast::ast::Stmt) {
+            if stmt.span.lo <= self.span.lo && self.span.hi <= stmt.span.hi {
+                if let qsc_ast::ast::StmtKind::Qubit(source, pat, _, _) = &*stmt.kind {
+                    self.found = Some((stmt.span, *source, pat.span));
+                }
+                qsc_ast::visit::walk_stmt(self, stmt);
+            }
+        }
+    }
+
+    let mut finder = Finder { span, found: None };
+    qsc_ast::visit::Visitor::visit_package(&mut finder, &compilation.compile_unit.ast.package);
+    finder.found
+}
+
+/// The local variables bound to the qubits of a scope, each mapped to the
+/// allocated qubit it is an alias of.
+#[derive(Clone, Default)]
+struct Qubits(FxHashMap<NodeId, NodeId>);
+
+impl Qubits {
+    fn insert(&mut self, id: NodeId) {
+        self.0.insert(id, id);
+    }
+
+    fn roots(&self) -> Vec<NodeId> {
+        self.0
+            .iter()
+            .filter_map(|(id, root)| (id == root).then_some(*id))
+            .collect()
+    }
+
+    /// Returns the allocated qubit that `expr` refers to, if it is a qubit variable.
+    fn root(&self, expr: &Expr) -> Option<NodeId> {
+        match &expr.kind {
+            ExprKind::Var(Res::Local(id), _) => self.0.get(id).copied(),
+            _ => None,
+        }
+    }
+
+    /// Records the variables bound by `pat` to qubit variables in `init` as aliases.
+    /// Returns `true` if `init` consists only of qubit variables.
+    fn bind_aliases(&mut self, pat: &Pat, init: &Expr) -> bool {
+        match (&pat.kind, &init.kind) {
+            (PatKind::Bind(ident), _) => match self.root(init) {
+                Some(root) => {
+                    self.0.insert(ident.id, root);
+                    true
+                }
+                None => false,
+            },
+            (PatKind::Tuple(pats), ExprKind::Tuple(items)) if pats.len() == items.len() => {
+                pats.iter().zip(items).fold(true, |aliases, (pat, item)| {
+                    self.bind_aliases(pat, item) && aliases
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Follows an immutable binding, returning `true` if it only introduces aliases.
+    fn visit_local(&mut self, stmt: &Stmt) -> bool {
+        match &stmt.kind {
+            StmtKind::Local(Mutability::Immutable, pat, init) => self.bind_aliases(pat, init),
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if `callee` refers to the standard library callable `namespace.name`.
+fn is_std_callable(callee: &Expr, compilation: Compilation, namespace: &str, name: &str) -> bool {
+    let ExprKind::Var(Res::Item(item_id), _) = &callee.kind else {
+        return false;
+    };
+    let Some(item) = compilation.resolve_item(item_id) else {
+        return false;
+    };
+    let ItemKind::Callable(decl) = &item.kind else {
+        return false;
+    };
+    let parent = item.parent.and_then(|parent| {
+        compilation.resolve_item(&ItemId {
+            package: item_id.package,
+            item: parent,
+        })
+    });
+    item_id.package.is_some()
+        && decl.name.name.as_ref() == name
+        && matches!(parent, Some(qsc_hir::hir::Item { kind: ItemKind::Namespace(ns, _), .. })
+            if ns.iter().map(|ident| ident.name.as_ref()).eq(namespace.split('.')))
+}
+
+/// Returns `true` if `callee` releases qubits at the end of their scope.
+fn is_release(callee: &Expr, compilation: Compilation) -> bool {
+    ["__quantum__rt__qubit_release", "ReleaseQubitArray"]
+        .iter()
+        .any(|name| is_std_callable(callee, compilation, "QIR.Runtime", name))
+}
+
+fn callable_kind(callee: &Expr) -> Option<CallableKind> {
+    match &callee.ty {
+        Ty::Arrow(arrow) => Some(arrow.kind),
+        _ => None,
+    }
+}
+
+fn contains_result(ty: &Ty) -> bool {
+    match ty {
+        Ty::Prim(Prim::Result) => true,
+        Ty::Array(item) => contains_result(item),
+        Ty::Tuple(items) => items.iter().any(contains_result),
+        _ => false,
+    }
+}
+
+/// Checks whether any of the qubits are used for anything other than
+/// being passed to a function, which can't act on them.
+struct QubitUses<'a> {
+    compilation: Compilation<'a>,
+    qubits: Qubits,
+    in_function_args: bool,
+    used: bool,
+}
+
+impl<'a> Visitor<'a> for QubitUses<'_> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if !self.qubits.visit_local(stmt) {
+            visit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if self.used {
+            return;
+        }
+        match &expr.kind {
+            ExprKind::Var(..) => {
+                if !self.in_function_args && self.qubits.root(expr).is_some() {
+                    self.used = true;
+                }
+            }
+            ExprKind::Call(callee, _) if is_release(callee, self.compilation) => {}
+            ExprKind::Call(callee, args) => {
+                let is_function = callable_kind(callee) == Some(CallableKind::Function);
+                let in_function_args = replace(&mut self.in_function_args, false);
+                self.visit_expr(callee);
+                self.in_function_args = is_function;
+                self.visit_expr(args);
+                self.in_function_args = in_function_args;
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Tracks the last use of each qubit, which is the span of the `M` callee
+/// if the qubit was last used by a measurement.
+struct LastQubitUses<'a> {
+    compilation: Compilation<'a>,
+    qubits: Qubits,
+    last_uses: FxHashMap<NodeId, Option<Span>>,
+}
+
+impl<'a> Visitor<'a> for LastQubitUses<'_> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if !self.qubits.visit_local(stmt) {
+            visit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match &expr.kind {
+            ExprKind::Call(callee, _) if is_release(callee, self.compilation) => {}
+            ExprKind::Call(callee, args)
+                if is_std_callable(
+                    callee,
+                    self.compilation,
+                    "Microsoft.Quantum.Intrinsic",
+                    "M",
+                ) =>
+            {
+                match self.qubits.root(args) {
+                    Some(id) => {
+                        self.last_uses.insert(id, Some(callee.span));
+                    }
+                    None => visit::walk_expr(self, expr),
+                }
+            }
+            ExprKind::Var(..) => {
+                if let Some(id) = self.qubits.root(expr) {
+                    self.last_uses.insert(id, None);
+                }
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Self-inverse standard library operations, which are undone by applying them again.
+const SELF_INVERSE_OPERATIONS: &[(&str, &str)] = &[
+    ("Microsoft.Quantum.Intrinsic", "CCNOT"),
+    ("Microsoft.Quantum.Intrinsic", "CNOT"),
+    ("Microsoft.Quantum.Intrinsic", "H"),
+    ("Microsoft.Quantum.Intrinsic", "SWAP"),
+    ("Microsoft.Quantum.Intrinsic", "X"),
+    ("Microsoft.Quantum.Intrinsic", "Y"),
+    ("Microsoft.Quantum.Intrinsic", "Z"),
+    ("Microsoft.Quantum.Canon", "CX"),
+    ("Microsoft.Quantum.Canon", "CY"),
+    ("Microsoft.Quantum.Canon", "CZ"),
+];
+
+/// The callee or the arguments of a call, compared by what the names in them resolve to.
+/// Expressions that are not a name, a literal, or built from those are never equal.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Operand {
+    Res(Res),
+    Lit(String),
+    Array(Vec<Operand>),
+    Tuple(Vec<Operand>),
+    Index(Box<Operand>, Box<Operand>),
+    UnOp(UnOp, Box<Operand>),
+    Expr(NodeId),
+}
+
+/// Balances the operation calls applied to borrowed qubits outside of `within` blocks,
+/// which are undone automatically. A call is undone by an `Adjoint` call of the same
+/// operation on the same arguments, or, for a self-inverse operation, by repeating it.
+/// A borrowed qubit is never restored after a measurement or reset.
+struct BorrowedQubitCalls<'a> {
+    compilation: Compilation<'a>,
+    qubits: Qubits,
+    calls: FxHashMap<(Operand, Operand), i64>,
+    irreversible: bool,
+}
+
+impl BorrowedQubitCalls<'_> {
+    fn uses_qubits(&self, expr: &Expr) -> bool {
+        let mut uses = QubitUses {
+            compilation: self.compilation,
+            qubits: self.qubits.clone(),
+            in_function_args: false,
+            used: false,
+        };
+        uses.visit_expr(expr);
+        uses.used
+    }
+
+    /// Returns `true` if the qubits may be left modified, because a call
+    /// was not undone.
+    fn modified(&self) -> bool {
+        self.irreversible || self.calls.values().any(|balance| *balance != 0)
+    }
+
+    fn operand(&self, expr: &Expr) -> Operand {
+        match &expr.kind {
+            ExprKind::Var(Res::Local(id), _) => {
+                Operand::Res(Res::Local(self.qubits.root(expr).unwrap_or(*id)))
+            }
+            ExprKind::Var(res, _) => Operand::Res(*res),
+            ExprKind::Lit(lit) => Operand::Lit(format!("{lit:?}")),
+            ExprKind::Array(items) => {
+                Operand::Array(items.iter().map(|e| self.operand(e)).collect())
+            }
+            ExprKind::Tuple(items) => {
+                Operand::Tuple(items.iter().map(|e| self.operand(e)).collect())
+            }
+            ExprKind::Index(array, index) => {
+                Operand::Index(Box::new(self.operand(array)), Box::new(self.operand(index)))
+            }
+            ExprKind::UnOp(op, operand) => Operand::UnOp(*op, Box::new(self.operand(operand))),
+            _ => Operand::Expr(expr.id),
+        }
+    }
+
+    fn push_call(&mut self, callee: &Expr, args: &Expr) {
+        let (callee, adjoint) = match &callee.kind {
+            ExprKind::UnOp(UnOp::Functor(Functor::Adj), inner) => (&**inner, true),
+            _ => (callee, false),
+        };
+        let self_inverse = SELF_INVERSE_OPERATIONS
+            .iter()
+            .any(|(namespace, name)| is_std_callable(callee, self.compilation, namespace, name));
+        let key = (self.operand(callee), self.operand(args));
+        let balance = self.calls.entry(key).or_default();
+        *balance = if self_inverse {
+            // Applying a self-inverse operation or its adjoint toggles the state.
+            1 - *balance
+        } else if adjoint {
+            *balance - 1
+        } else {
+            *balance + 1
+        };
+    }
+}
+
+impl<'a> Visitor<'a> for BorrowedQubitCalls<'_> {
+    /// A `within` block is expanded into a copy of the block followed, after the
+    /// `apply` block, by its inverse. Both share the span of the `within` block and
+    /// are skipped, since they undo each other.
+    fn visit_block(&mut self, block: &'a Block) {
+        let span = |stmt: &Stmt| match &stmt.kind {
+            StmtKind::Expr(Expr {
+                kind: ExprKind::Block(block),
+                ..
+            }) if block.span.lo < block.span.hi => Some(block.span),
+            _ => None,
+        };
+        for stmt in &block.stmts {
+            let conjugated = span(stmt).is_some_and(|within| {
+                block
+                    .stmts
+                    .iter()
+                    .filter(|other| span(other) == Some(within))
+                    .count()
+                    > 1
+            });
+            if !conjugated {
+                self.visit_stmt(stmt);
+            }
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        if !self.qubits.visit_local(stmt) {
+            visit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match &expr.kind {
+            ExprKind::Call(callee, args)
+                if callable_kind(callee) == Some(CallableKind::Operation)
+                    && !is_release(callee, self.compilation)
+                    && self.uses_qubits(args) =>
+            {
+                let returns_result =
+                    matches!(&callee.ty, Ty::Arrow(arrow) if contains_result(&arrow.output));
+                let is_reset = ["Reset", "ResetAll"].iter().any(|name| {
+                    is_std_callable(
+                        callee,
+                        self.compilation,
+                        "Microsoft.Quantum.Intrinsic",
+                        name,
+                    )
+                });
+                if returns_result || is_reset {
+                    self.irreversible = true;
+                } else {
+                    self.push_call(callee, args);
+                }
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
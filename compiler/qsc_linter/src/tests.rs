@@ -1,15 +1,17 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+#![allow(clippy::needless_raw_string_hashes)]
+
 use crate::{
-    linter::{ast::run_ast_lints, hir::run_hir_lints},
-    Lint, LintConfig, LintLevel,
+    linter::run_lints_without_filtering, run_profile_lints, HirLint, Lint, LintConfig, LintKind,
+    LintLevel,
 };
 use expect_test::{expect, Expect};
 use indoc::indoc;
 use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
-use qsc_frontend::compile::{self, CompileUnit, PackageStore, SourceMap};
-use qsc_hir::hir::CallableKind;
+use qsc_frontend::compile::{self, PackageStore, SourceMap};
+use qsc_hir::hir::{CallableKind, PackageId};
 use qsc_passes::PackageType;
 
 #[test]
@@ -266,21 +268,732 @@ fn needless_operation_partial_application() {
     );
 }
 
+#[test]
+fn unused_qubits() {
+    check(
+        &wrap_in_callable("use q = Qubit();", CallableKind::Operation),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "q",
+                    level: Warn,
+                    message: "qubits are allocated but never used",
+                    help: "these qubits are never passed to an operation, so the allocation can be removed",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_qubits_no_lint_when_passed_to_operation() {
+    check(
+        &wrap_in_callable(
+            "use (q1, q2) = (Qubit(), Qubit()); H(q2);",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn unused_qubits_only_passed_to_function() {
+    check(
+        indoc! {"
+        function Identity(q : Qubit) : Qubit {
+            q
+        }
+
+        operation RunProgram() : Unit {
+            borrow q = Qubit() {
+                let q2 = Identity(q);
+            }
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "q",
+                    level: Warn,
+                    message: "qubits are allocated but never used",
+                    help: "these qubits are never passed to an operation, so the allocation can be removed",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn measured_qubit_not_reset() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); H(q); let r = M(q);",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "M",
+                    level: Allow,
+                    message: "qubit is measured but not reset before it is released",
+                    help: "use `MResetZ` to measure and reset the qubit in one step",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn measured_qubit_not_reset_no_lint_when_used_after_measurement() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); let r = M(q); if r == One { X(q); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); borrow b = Qubit() { CNOT(q, b); X(b); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Warn,
+                    message: "borrowed qubit may be left in a modified state",
+                    help: "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_measured() {
+    check(
+        &wrap_in_callable("borrow b = Qubit(); let r = M(b);", CallableKind::Operation),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Warn,
+                    message: "borrowed qubit may be left in a modified state",
+                    help: "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_no_lint_when_restored() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); borrow b = Qubit() { S(b); CNOT(q, b); Adjoint S(b); CNOT(q, b); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_repeated_rotation() {
+    check(
+        &wrap_in_callable(
+            "borrow b = Qubit() { Rx(0.1, b); Rx(0.1, b); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Warn,
+                    message: "borrowed qubit may be left in a modified state",
+                    help: "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_no_lint_when_rotation_adjoint() {
+    check(
+        &wrap_in_callable(
+            "borrow b = Qubit() { Rx(0.1, b); H(b); H(b); Adjoint Rx(0.1, b); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_no_lint_in_conjugation() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); borrow b = Qubit() { within { CNOT(q, b); } apply { Z(q); } }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_no_lint_when_restored_through_alias() {
+    check(
+        &wrap_in_callable(
+            "borrow b = Qubit() { let alias = b; S(b); Adjoint S(alias); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_tuple() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); borrow (a, b) = (Qubit(), Qubit()) { CNOT(q, a); CNOT(q, b); CNOT(q, a); }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "(a, b)",
+                    level: Warn,
+                    message: "borrowed qubit may be left in a modified state",
+                    help: "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_no_lint_in_conjugation_with_loop() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); borrow bs = Qubit[2] { within { for i in 0..1 { CNOT(q, bs[i]); } } apply { Z(q); } }",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn borrowed_qubit_modified_reported_once_with_generated_specializations() {
+    check(
+        indoc! {"
+        operation Op() : Unit is Adj + Ctl {
+            borrow b = Qubit() { X(b); }
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "b",
+                    level: Warn,
+                    message: "borrowed qubit may be left in a modified state",
+                    help: "operations applied to a borrowed qubit must be undone before it is released, for example with a `within` block",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unused_qubits_through_alias() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); let alias = q; use r = Qubit(); let other = r; H(other);",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "q",
+                    level: Warn,
+                    message: "qubits are allocated but never used",
+                    help: "these qubits are never passed to an operation, so the allocation can be removed",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn measured_qubit_not_reset_through_alias() {
+    check(
+        &wrap_in_callable(
+            "use q = Qubit(); let alias = q; let r = M(alias);",
+            CallableKind::Operation,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "M",
+                    level: Allow,
+                    message: "qubit is measured but not reset before it is released",
+                    help: "use `MResetZ` to measure and reset the qubit in one step",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_callable() {
+    check(
+        indoc! {"
+        @Deprecated(\"NewOp\")
+        operation OldOp(q : Qubit) : Unit {
+            H(q);
+        }
+
+        operation RunProgram() : Unit {
+            use q = Qubit();
+            OldOp(q);
+            let op = OldOp;
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "OldOp",
+                    level: Warn,
+                    message: "use of deprecated item; use `NewOp` instead",
+                    help: "this item is deprecated and may be removed in a future version",
+                },
+                SrcLint {
+                    source: "OldOp",
+                    level: Warn,
+                    message: "use of deprecated item; use `NewOp` instead",
+                    help: "this item is deprecated and may be removed in a future version",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_type() {
+    check(
+        indoc! {"
+        @Deprecated()
+        newtype Pair = (Int, Int);
+
+        function First(p : Pair) : Int {
+            p::Item1
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Pair",
+                    level: Warn,
+                    message: "use of deprecated item",
+                    help: "this item is deprecated and may be removed in a future version",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_callable_in_dependency() {
+    check_with_dependency(
+        indoc! {"
+        namespace Library {
+            @Deprecated(\"Library.NewOp\")
+            operation OldOp(q : Qubit) : Unit {
+                H(q);
+            }
+        }
+    "},
+        indoc! {"
+        operation RunProgram() : Unit {
+            use q = Qubit();
+            Library.OldOp(q);
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "Library.OldOp",
+                    level: Warn,
+                    message: "use of deprecated item; use `Library.NewOp` instead",
+                    help: "this item is deprecated and may be removed in a future version",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn allow_attr_overrides_lint_level() {
+    check(
+        indoc! {"
+        @Allow(\"DivisionByZero\")
+        function Quotient() : Int {
+            2 / 0
+        }
+
+        function Remainder() : Int {
+            2 % 0 + 2 / 0
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Allow,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn lint_attr_accepts_camel_case_names() {
+    check(
+        indoc! {"
+        @Error(\"needlessParens\")
+        function Sum() : Int {
+            let x = (2);
+            x
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "(2)",
+                    level: Error,
+                    message: "unnecessary parentheses",
+                    help: "remove the extra parentheses for clarity",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn nearest_lint_attr_wins() {
+    check(
+        indoc! {"
+        @Allow(\"NeedlessParens\")
+        function Outer() : Int {
+            @Warn(\"NeedlessParens\")
+            function Inner() : Int {
+                let y = (3);
+                y
+            }
+            let x = (2);
+            x + Inner()
+        }
+    "},
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "(3)",
+                    level: Warn,
+                    message: "unnecessary parentheses",
+                    help: "remove the extra parentheses for clarity",
+                },
+                SrcLint {
+                    source: "(2)",
+                    level: Allow,
+                    message: "unnecessary parentheses",
+                    help: "remove the extra parentheses for clarity",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_on_same_line() {
+    check(
+        &wrap_in_callable(
+            "let x = 2 / 0; // qsharp-lint-ignore divisionByZero
+            let y = 3 / 0;",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Allow,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+                SrcLint {
+                    source: "3 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_on_previous_line() {
+    check(
+        &wrap_in_callable(
+            "// qsharp-lint-ignore DivisionByZero, redundantSemicolons
+            let x = 2 / 0;;;
+            let y = 3 / 0;",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: ";;",
+                    level: Allow,
+                    message: "redundant semicolons",
+                    help: "remove the redundant semicolons",
+                },
+                SrcLint {
+                    source: "2 / 0",
+                    level: Allow,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+                SrcLint {
+                    source: "3 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_only_applies_to_named_lints() {
+    check(
+        &wrap_in_callable(
+            "let x = 2 / 0;;; // qsharp-lint-ignore redundantSemicolons",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: ";;",
+                    level: Allow,
+                    message: "redundant semicolons",
+                    help: "remove the redundant semicolons",
+                },
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn ignore_comment_inside_string_is_not_a_comment() {
+    check(
+        &wrap_in_callable(
+            "let s = \"// qsharp-lint-ignore divisionByZero\"; let x = 2 / 0;",
+            CallableKind::Function,
+        ),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "2 / 0",
+                    level: Error,
+                    message: "attempt to divide by zero",
+                    help: "division by zero will fail at runtime",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn profile_compatibility_is_opt_in() {
+    check_profile(
+        indoc! {"
+            operation RunProgram() : Double {
+                use q = Qubit();
+                if M(q) == One { 1.0 } else { 0.0 }
+            }
+        "},
+        None,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn profile_compatibility_names_missing_feature() {
+    check_profile(
+        indoc! {"
+            operation RunProgram() : Unit {
+                use q = Qubit();
+                let x = if M(q) == One { 1.0 } else { 0.0 };
+                Rx(x, q);
+            }
+        "},
+        Some(LintLevel::Warn),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "if M(q) == One { 1.0 } else { 0.0 }",
+                    level: Warn,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+                SrcLint {
+                    source: "Rx(x, q)",
+                    level: Warn,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn profile_compatibility_names_call_chain() {
+    check_profile(
+        indoc! {"
+            operation Angle(q : Qubit) : Double {
+                if M(q) == One { 1.0 } else { 0.0 }
+            }
+            operation Rotate(q : Qubit) : Unit {
+                let angle = Angle(q);
+                Rx(angle, q);
+            }
+            operation RunProgram() : Unit {
+                use q = Qubit();
+                Rotate(q);
+            }
+        "},
+        Some(LintLevel::Warn),
+        &expect![[r#"
+            [
+                SrcLint {
+                    source: "if M(q) == One { 1.0 } else { 0.0 }",
+                    level: Warn,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+                SrcLint {
+                    source: "Angle(q)",
+                    level: Warn,
+                    message: "cannot use a dynamic double value, introduced through `Angle`",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+                SrcLint {
+                    source: "Rx(angle, q)",
+                    level: Warn,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+                SrcLint {
+                    source: "Rotate(q)",
+                    level: Warn,
+                    message: "cannot use a dynamic double value, introduced through `Rotate` -> `Angle`",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn profile_compatibility_respects_local_overrides() {
+    check_profile(
+        indoc! {"
+            @Allow(\"profileCompatibility\")
+            operation RunProgram() : Unit {
+                use q = Qubit();
+                let x = if M(q) == One { 1.0 } else { 0.0 };
+                Rx(x, q);
+            }
+        "},
+        Some(LintLevel::Warn),
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
 fn check(source: &str, expected: &Expect) {
-    let source = wrap_in_namespace(source);
     let mut store = PackageStore::new(compile::core());
     let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
-    let sources = SourceMap::new([("source.qs".into(), source.clone().into())], None);
-    let (package, _) = qsc::compile::compile(
+    check_lints(&store, &[std], source, expected);
+}
+
+/// Checks the lints in `source`, which depends on the library package `dependency`.
+fn check_with_dependency(dependency: &str, source: &str, expected: &Expect) {
+    let mut store = PackageStore::new(compile::core());
+    let std = store.insert(compile::std(&store, TargetCapabilityFlags::all()));
+    let sources = SourceMap::new([("dependency.qs".into(), dependency.into())], None);
+    let (dependency, errors) = qsc::compile::compile(
         &store,
         &[std],
         sources,
+        PackageType::Lib,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+    let dependency = store.insert(dependency);
+    check_lints(&store, &[std, dependency], source, expected);
+}
+
+fn check_lints(store: &PackageStore, dependencies: &[PackageId], source: &str, expected: &Expect) {
+    let source = wrap_in_namespace(source);
+    let sources = SourceMap::new([("source.qs".into(), source.clone().into())], None);
+    let (package, _) = qsc::compile::compile(
+        store,
+        dependencies,
+        sources,
         PackageType::Exe,
         TargetCapabilityFlags::all(),
         LanguageFeatures::default(),
     );
 
-    let actual: Vec<SrcLint> = run_lints(&package, None)
+    let actual: Vec<SrcLint> = run_lints_without_filtering(Some(store), &package, None)
         .into_iter()
         .map(|lint| SrcLint::from(&lint, &source))
         .collect();
@@ -288,6 +1001,49 @@ fn check(source: &str, expected: &Expect) {
     expected.assert_debug_eq(&actual);
 }
 
+/// Checks the profile compatibility lints in `source` for the Adaptive profile,
+/// with the lint set to `level` in the configuration.
+fn check_profile(source: &str, level: Option<LintLevel>, expected: &Expect) {
+    let capabilities = TargetCapabilityFlags::Adaptive;
+    let mut store = PackageStore::new(qsc::compile::core());
+    let std = store.insert(qsc::compile::std(&store, capabilities));
+    let source = wrap_in_namespace(source);
+    let sources = SourceMap::new([("source.qs".into(), source.clone().into())], None);
+    let (package, errors) = qsc::compile::compile(
+        &store,
+        &[std],
+        sources,
+        PackageType::Lib,
+        capabilities,
+        LanguageFeatures::default(),
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+    let package_id = store.insert(package);
+    let config = level.map(|level| {
+        vec![LintConfig {
+            kind: LintKind::Hir(HirLint::ProfileCompatibility),
+            level,
+        }]
+    });
+
+    let (fir_store, fir_package_id) = qsc_passes::lower_hir_to_fir(&store, package_id);
+    let compute_properties = qsc_rca::Analyzer::init(&fir_store).analyze_all();
+    let actual: Vec<SrcLint> = run_profile_lints(
+        &store,
+        package_id,
+        &fir_store,
+        fir_package_id,
+        &compute_properties,
+        capabilities,
+        config.as_deref(),
+    )
+    .into_iter()
+    .map(|profile_lint| SrcLint::from(&profile_lint.lint, &source))
+    .collect();
+
+    expected.assert_debug_eq(&actual);
+}
+
 /// Wraps some source code into a namespace, to make testing easier.
 fn wrap_in_namespace(source: &str) -> String {
     format!(
@@ -311,8 +1067,8 @@ fn wrap_in_callable(source: &str, callable_type: CallableKind) -> String {
 struct SrcLint {
     source: String,
     level: LintLevel,
-    message: &'static str,
-    help: &'static str,
+    message: String,
+    help: String,
 }
 
 impl SrcLint {
@@ -320,8 +1076,8 @@ impl SrcLint {
         Self {
             source: source[lint.span].into(),
             level: lint.level,
-            message: lint.message,
-            help: lint.help,
+            message: lint.message.to_string(),
+            help: lint.help.to_string(),
         }
     }
 }
@@ -340,12 +1096,3 @@ impl std::fmt::Display for SrcLint {
         )
     }
 }
-
-fn run_lints(compile_unit: &CompileUnit, config: Option<&[LintConfig]>) -> Vec<Lint> {
-    let mut ast_lints = run_ast_lints(&compile_unit.ast.package, config);
-    let mut hir_lints = run_hir_lints(&compile_unit.package, config);
-    let mut lints = Vec::new();
-    lints.append(&mut ast_lints);
-    lints.append(&mut hir_lints);
-    lints
-}
@@ -12,6 +12,9 @@ use qsc_hir::hir::{self, SpecBody, SpecGen};
 use std::iter::once;
 use std::{clone::Clone, rc::Rc};
 
+#[cfg(test)]
+mod tests;
+
 #[must_use]
 pub fn map_hir_package_to_fir(package: hir::PackageId) -> fir::PackageId {
     fir::PackageId::from(Into::<usize>::into(package))
@@ -868,7 +871,13 @@ fn lower_generics(generics: &[qsc_hir::ty::GenericParam]) -> Vec<qsc_fir::ty::Ge
 }
 
 fn lower_attrs(attrs: &[hir::Attr]) -> Vec<fir::Attr> {
-    attrs.iter().map(|_| fir::Attr::EntryPoint).collect()
+    attrs
+        .iter()
+        .filter_map(|attr| match attr {
+            hir::Attr::EntryPoint => Some(fir::Attr::EntryPoint),
+            _ => None,
+        })
+        .collect()
 }
 
 fn lower_functors(functors: qsc_hir::ty::FunctorSetValue) -> qsc_fir::ty::FunctorSetValue {
@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use crate::Lowerer;
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc_data_structures::{language_features::LanguageFeatures, target::TargetCapabilityFlags};
+use qsc_fir::fir::ItemKind;
+use qsc_frontend::compile::{self, PackageStore, SourceMap};
+use qsc_hir::hir::PackageId;
+use std::fmt::Write;
+
+/// Lowers `source` to FIR and lists the attributes of each callable.
+fn check_attrs(source: &str, expect: &Expect) {
+    let store = PackageStore::new(compile::core());
+    let sources = SourceMap::new([("test.qs".into(), source.into())], None);
+    let unit = compile::compile(
+        &store,
+        &[PackageId::CORE],
+        sources,
+        TargetCapabilityFlags::all(),
+        LanguageFeatures::default(),
+    );
+    assert!(unit.errors.is_empty(), "{:?}", unit.errors);
+
+    let package = Lowerer::new().lower_package(&unit.package);
+    let mut actual = String::new();
+    for item in package.items.values() {
+        if let ItemKind::Callable(decl) = &item.kind {
+            writeln!(actual, "{}: {:?}", decl.name.name, item.attrs)
+                .expect("writing to string should succeed");
+        }
+    }
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn only_entry_point_attr_is_lowered() {
+    check_attrs(
+        indoc! {r#"
+            namespace Test {
+                @Config(Unrestricted)
+                function Configured() : Unit {}
+
+                @Deprecated("Main")
+                function Old() : Unit {}
+
+                @EntryPoint()
+                operation Main() : Unit {}
+
+                function Plain() : Unit {}
+            }
+        "#},
+        &expect![[r#"
+            Configured: []
+            Old: []
+            Main: [EntryPoint]
+            Plain: []
+        "#]],
+    );
+}
@@ -50,23 +50,32 @@ pub fn run_rca_pass(
     package_id: qsc_fir::fir::PackageId,
     capabilities: TargetCapabilityFlags,
 ) -> Result<PackageStoreComputeProperties, Vec<crate::Error>> {
+    let (compute_properties, errors) = analyze_rca_pass(fir_store, package_id, capabilities);
+    if errors.is_empty() {
+        Ok(compute_properties)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Like [`run_rca_pass`], but returns the compute properties even if the
+/// capabilities check fails.
+pub fn analyze_rca_pass(
+    fir_store: &qsc_fir::fir::PackageStore,
+    package_id: qsc_fir::fir::PackageId,
+    capabilities: TargetCapabilityFlags,
+) -> (PackageStoreComputeProperties, Vec<crate::Error>) {
     let analyzer = Analyzer::init(fir_store);
     let compute_properties = analyzer.analyze_all();
     let fir_package = fir_store.get(package_id);
 
     let package_compute_properties = compute_properties.get(package_id);
-    let mut errors =
-        check_supported_capabilities(fir_package, package_compute_properties, capabilities);
-
-    if errors.is_empty() {
-        Ok(compute_properties)
-    } else {
-        let errors = errors
-            .drain(..)
+    let errors =
+        check_supported_capabilities(fir_package, package_compute_properties, capabilities)
+            .into_iter()
             .map(crate::Error::CapabilitiesCk)
-            .collect::<Vec<_>>();
-        Err(errors)
-    }
+            .collect();
+    (compute_properties, errors)
 }
 
 #[must_use]
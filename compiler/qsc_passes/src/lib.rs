@@ -15,7 +15,7 @@ mod replace_qubit_allocation;
 mod spec_gen;
 
 use callable_limits::CallableLimits;
-use capabilitiesck::{check_supported_capabilities, lower_store, run_rca_pass};
+use capabilitiesck::{analyze_rca_pass, check_supported_capabilities, lower_store, run_rca_pass};
 use entry_point::generate_entry_expr;
 use loop_unification::LoopUni;
 use miette::Diagnostic;
@@ -136,6 +136,17 @@ impl PassContext {
     ) -> Result<PackageStoreComputeProperties, Vec<Error>> {
         run_rca_pass(fir_store, package_id, capabilities)
     }
+
+    /// Like [`Self::run_fir_passes_on_fir`], but returns the compute properties along
+    /// with the capabilities check errors, so that they can be reused when there are errors.
+    #[must_use]
+    pub fn analyze_fir(
+        fir_store: &qsc_fir::fir::PackageStore,
+        package_id: qsc_fir::fir::PackageId,
+        capabilities: TargetCapabilityFlags,
+    ) -> (PackageStoreComputeProperties, Vec<Error>) {
+        analyze_rca_pass(fir_store, package_id, capabilities)
+    }
 }
 
 /// Run the default set of passes required for evaluation.
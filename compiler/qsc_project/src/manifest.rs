@@ -9,6 +9,7 @@ use std::{
     fs::{self, DirEntry, FileType},
 };
 
+pub use qsc_formatter::formatter::FormatOptions;
 pub use qsc_linter::LintConfig;
 use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, sync::Arc};
@@ -25,6 +26,8 @@ pub struct Manifest {
     pub language_features: Vec<String>,
     #[serde(default)]
     pub lints: Vec<LintConfig>,
+    #[serde(default)]
+    pub format: FormatOptions,
 }
 
 /// Describes the contents and location of a Q# manifest file.
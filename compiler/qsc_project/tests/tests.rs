@@ -37,6 +37,11 @@ fn basic_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -69,6 +74,11 @@ fn circular_imports() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -101,6 +111,11 @@ fn different_files_same_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -123,6 +138,11 @@ fn empty_manifest() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -157,6 +177,11 @@ fn folder_structure() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -186,6 +211,11 @@ fn hidden_files() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -219,6 +249,11 @@ fn peer_file() {
                     license: None,
                     language_features: [],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
@@ -243,7 +278,39 @@ fn language_feature() {
                         "v2-preview-syntax",
                     ],
                     lints: [],
+                    format: FormatOptions {
+                        indent_width: 4,
+                        max_line_width: None,
+                        brace_style: SameLine,
+                    },
                 },
             }"#]],
     );
 }
+
+#[test]
+fn format_options() {
+    check(
+        &"format_options".into(),
+        &expect![[r#"
+        Project {
+            sources: [
+                (
+                    "format_options/src/Project.qs",
+                    "namespace Project\n{\n  @EntryPoint()\n  operation Entry() : Unit\n  {\n  }\n}\n",
+                ),
+            ],
+            manifest: Manifest {
+                author: None,
+                license: None,
+                language_features: [],
+                lints: [],
+                format: FormatOptions {
+                    indent_width: 2,
+                    max_line_width: None,
+                    brace_style: NextLine,
+                },
+            },
+        }"#]],
+    );
+}
@@ -1,17 +1,26 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+#[cfg(test)]
+mod tests;
+
+use std::rc::Rc;
+
 use miette::Diagnostic;
 use qsc::{
+    ast::{self, visit::Visitor},
     compile::ErrorKind,
+    display::Lookup,
     error::WithSource,
+    hir::{Attr, ItemKind, Visibility},
     line_column::{Encoding, Range},
-    Span,
+    resolve, Span,
 };
 use qsc_linter::{AstLint, HirLint};
 
 use crate::{
     compilation::Compilation,
+    completion::{get_namespaces_in_scope, get_open_insertion},
     protocol::{CodeAction, CodeActionKind, TextEdit, WorkspaceEdit},
 };
 
@@ -87,8 +96,59 @@ fn quick_fixes(
                     kind: Some(CodeActionKind::QuickFix),
                     is_preferred: None,
                 }),
+                LintKind::Hir(HirLint::MeasuredQubitNotReset) => code_actions.push(CodeAction {
+                    title: "Use `MResetZ` to measure and reset the qubit".to_string(),
+                    edit: Some(WorkspaceEdit {
+                        changes: vec![(
+                            source_name.to_string(),
+                            vec![TextEdit {
+                                // The lint span covers the `M` callee, so the
+                                // argument is left as it is.
+                                new_text: "Microsoft.Quantum.Measurement.MResetZ".to_string(),
+                                range: resolve_range(diagnostic, encoding)
+                                    .expect("range should exist"),
+                            }],
+                        )],
+                    }),
+                    kind: Some(CodeActionKind::QuickFix),
+                    is_preferred: None,
+                }),
+                LintKind::Ast(AstLint::Deprecated) => {
+                    if let Some(replacement) = deprecation_replacement(compilation, lint.span) {
+                        code_actions.push(CodeAction {
+                            title: format!("Replace with `{replacement}`"),
+                            edit: Some(WorkspaceEdit {
+                                changes: vec![(
+                                    source_name.to_string(),
+                                    vec![TextEdit {
+                                        new_text: replacement.to_string(),
+                                        range: resolve_range(diagnostic, encoding)
+                                            .expect("range should exist"),
+                                    }],
+                                )],
+                            }),
+                            kind: Some(CodeActionKind::QuickFix),
+                            is_preferred: Some(true),
+                        });
+                    }
+                }
                 LintKind::Ast(AstLint::DivisionByZero)
-                | LintKind::Hir(HirLint::NeedlessOperation) => (),
+                | LintKind::Hir(
+                    HirLint::BorrowedQubitModified
+                    | HirLint::NeedlessOperation
+                    | HirLint::ProfileCompatibility
+                    | HirLint::UnusedQubits,
+                ) => (),
+            }
+        } else if let ErrorKind::Frontend(error) = diagnostic.error() {
+            if let Some(name) = error.unresolved_name() {
+                code_actions.extend(import_fixes(
+                    compilation,
+                    source_name,
+                    name,
+                    diagnostic,
+                    encoding,
+                ));
             }
         }
     }
@@ -96,6 +156,154 @@ fn quick_fixes(
     code_actions
 }
 
+/// Returns the replacement name given in the `@Deprecated` attribute
+/// of the item referred to by the path at `span`, if there is one.
+fn deprecation_replacement(compilation: &Compilation, span: Span) -> Option<Rc<str>> {
+    struct PathFinder<'a> {
+        span: Span,
+        path: Option<&'a ast::Path>,
+    }
+
+    impl<'a> Visitor<'a> for PathFinder<'a> {
+        fn visit_path(&mut self, path: &'a ast::Path) {
+            if path.span == self.span {
+                self.path = Some(path);
+            }
+        }
+    }
+
+    let mut finder = PathFinder { span, path: None };
+    finder.visit_package(&compilation.user_unit().ast.package);
+    let Some(resolve::Res::Item(item_id, _)) = compilation.get_res(finder.path?.id) else {
+        return None;
+    };
+    let (item, _, _) = compilation.resolve_item_relative_to_user_package(item_id);
+    item.attrs.iter().find_map(|attr| match attr {
+        Attr::Deprecated(replacement) => replacement.clone(),
+        _ => None,
+    })
+}
+
+/// For an unresolved name, offers an `open` statement and a fully qualified
+/// rewrite for each namespace that declares or exports an accessible item with
+/// that name and is not already in scope.
+fn import_fixes(
+    compilation: &Compilation,
+    source_name: &str,
+    name: &str,
+    diagnostic: &WithSource<ErrorKind>,
+    encoding: Encoding,
+) -> Vec<CodeAction> {
+    let (Some(span), Some(range)) = (
+        resolve_span(diagnostic),
+        resolve_range(diagnostic, encoding),
+    ) else {
+        return Vec::new();
+    };
+
+    if name.contains('.') {
+        // The name is already qualified, so there's no namespace to import.
+        return Vec::new();
+    }
+
+    let (insert_open_range, indent) = get_open_insertion(compilation, span.lo, encoding);
+    let namespaces_in_scope = get_namespaces_in_scope(compilation, span.lo);
+
+    let mut code_actions = Vec::new();
+    for namespace in candidate_namespaces(compilation, name) {
+        if namespaces_in_scope.contains(&namespace) {
+            continue;
+        }
+
+        if let Some(insert_open_range) = insert_open_range {
+            code_actions.push(CodeAction {
+                title: format!("Add `open {namespace};`"),
+                edit: Some(WorkspaceEdit {
+                    changes: vec![(
+                        source_name.to_string(),
+                        vec![TextEdit {
+                            new_text: format!("open {namespace};{indent}"),
+                            range: insert_open_range,
+                        }],
+                    )],
+                }),
+                kind: Some(CodeActionKind::QuickFix),
+                is_preferred: None,
+            });
+        }
+        code_actions.push(CodeAction {
+            title: format!("Use `{namespace}.{name}`"),
+            edit: Some(WorkspaceEdit {
+                changes: vec![(
+                    source_name.to_string(),
+                    vec![TextEdit {
+                        new_text: format!("{namespace}.{name}"),
+                        range,
+                    }],
+                )],
+            }),
+            kind: Some(CodeActionKind::QuickFix),
+            is_preferred: None,
+        });
+    }
+
+    code_actions
+}
+
+/// Returns the sorted names of all namespaces, across the user package and its
+/// dependencies, that declare a callable or type named `name` which is
+/// accessible from user code, or that export an item under that name.
+fn candidate_namespaces(compilation: &Compilation, name: &str) -> Vec<Rc<str>> {
+    let exporting_namespaces = compilation.package_store.iter().flat_map(|(_, unit)| {
+        unit.ast.package.nodes.iter().filter_map(|node| match node {
+            ast::TopLevelNode::Namespace(namespace)
+                if namespace.exports().any(|export| {
+                    !export.is_glob
+                        && export
+                            .alias
+                            .as_ref()
+                            .unwrap_or(&export.path.name)
+                            .name
+                            .as_ref()
+                            == name
+                }) =>
+            {
+                Some(namespace.name.name())
+            }
+            _ => None,
+        })
+    });
+
+    let mut namespaces = compilation
+        .package_store
+        .iter()
+        .flat_map(|(package_id, unit)| {
+            let package = &unit.package;
+            let is_user_package = package_id == compilation.user_package_id;
+            package.items.values().filter_map(move |item| {
+                let item_name = match &item.kind {
+                    ItemKind::Callable(decl) => &decl.name.name,
+                    ItemKind::Ty(ident, _) => &ident.name,
+                    ItemKind::Namespace(..) => return None,
+                };
+                if item_name.as_ref() != name
+                    || (matches!(item.visibility, Visibility::Internal) && !is_user_package)
+                {
+                    return None;
+                }
+                match &package.items.get(item.parent?)?.kind {
+                    ItemKind::Namespace(namespace, _) => Some(namespace.name()),
+                    _ => None,
+                }
+            })
+        })
+        .chain(exporting_namespaces)
+        .collect::<Vec<_>>();
+    namespaces.sort_unstable();
+    namespaces.dedup();
+    namespaces
+}
+
 /// Returns true if the error has a `Range` and it overlaps
 /// with the code action's range.
 fn is_error_relevant(error: &WithSource<ErrorKind>, span: Span) -> bool {
@@ -0,0 +1,619 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+
+use super::get_code_actions;
+use crate::{
+    test_utils::{
+        add_lints, compile_notebook_with_fake_stdlib,
+        compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+    Encoding,
+};
+use qsc::line_column::{Position, Range};
+
+/// Checks the code actions offered for the range between the two `◉` markers.
+fn check(source_with_markers: &str, expect: &Expect) {
+    let (compilation, target_spans) =
+        compile_with_fake_stdlib_and_markers_no_cursor(source_with_markers);
+    let actual = get_code_actions(&compilation, "<source>", target_spans[0], Encoding::Utf8);
+    expect.assert_debug_eq(&actual);
+}
+
+/// Like [`check`], but also offers the fixes for lints.
+fn check_with_lints(source_with_markers: &str, expect: &Expect) {
+    let (mut compilation, target_spans) =
+        compile_with_fake_stdlib_and_markers_no_cursor(source_with_markers);
+    add_lints(&mut compilation);
+    let actual = get_code_actions(&compilation, "<source>", target_spans[0], Encoding::Utf8);
+    expect.assert_debug_eq(&actual);
+}
+
+#[test]
+fn unresolved_callable_offers_open_and_qualified_name() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            ◉Fake◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Add `open FakeStdLib;`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "open FakeStdLib;\n        ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                                end: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Use `FakeStdLib.Fake`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "FakeStdLib.Fake",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 3,
+                                                    column: 12,
+                                                },
+                                                end: Position {
+                                                    line: 3,
+                                                    column: 16,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unresolved_type_offers_open_and_qualified_name() {
+    check(
+        r#"
+    namespace Test {
+        function Foo(x : ◉Udt◉) : Unit {}
+    }
+    "#,
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Add `open FakeStdLib;`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "open FakeStdLib;\n        ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                                end: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Use `FakeStdLib.Udt`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "FakeStdLib.Udt",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 25,
+                                                },
+                                                end: Position {
+                                                    line: 2,
+                                                    column: 28,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn unresolved_name_with_no_candidates() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            ◉DoesNotExist◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn internal_item_in_dependency_not_offered() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            ◉Hidden◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn unstable_namespace_offered() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            ◉UnstableFake◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Add `open Microsoft.Quantum.Unstable;`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "open Microsoft.Quantum.Unstable;\n        ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                                end: Position {
+                                                    line: 2,
+                                                    column: 8,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Use `Microsoft.Quantum.Unstable.UnstableFake`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "Microsoft.Quantum.Unstable.UnstableFake",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 3,
+                                                    column: 12,
+                                                },
+                                                end: Position {
+                                                    line: 3,
+                                                    column: 24,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+#[allow(clippy::too_many_lines)]
+fn exporting_namespace_offered() {
+    check(
+        r#"
+    namespace Lib {
+        export Impl.Helper;
+    }
+    namespace Impl {
+        operation Helper() : Unit {}
+    }
+    namespace Test {
+        operation Main() : Unit {
+            ◉Helper◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Add `open Impl;`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "open Impl;\n        ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 8,
+                                                    column: 8,
+                                                },
+                                                end: Position {
+                                                    line: 8,
+                                                    column: 8,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Use `Impl.Helper`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "Impl.Helper",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 9,
+                                                    column: 12,
+                                                },
+                                                end: Position {
+                                                    line: 9,
+                                                    column: 18,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Add `open Lib;`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "open Lib;\n        ",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 8,
+                                                    column: 8,
+                                                },
+                                                end: Position {
+                                                    line: 8,
+                                                    column: 8,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+                CodeAction {
+                    title: "Use `Lib.Helper`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "Lib.Helper",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 9,
+                                                    column: 12,
+                                                },
+                                                end: Position {
+                                                    line: 9,
+                                                    column: 18,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: None,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn namespace_in_scope_not_offered() {
+    check(
+        r#"
+    namespace Test {
+        open FakeStdLib;
+        function Main() : Unit {
+            let x : ◉Fake◉ = 1;
+        }
+    }
+    "#,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn notebook_open_inserted_at_top_of_cell() {
+    let compilation = compile_notebook_with_fake_stdlib(
+        [("cell1", "operation Main() : Unit {\n    Fake();\n}")].into_iter(),
+    );
+    let actual = get_code_actions(
+        &compilation,
+        "cell1",
+        Range {
+            start: Position { line: 1, column: 4 },
+            end: Position { line: 1, column: 8 },
+        },
+        Encoding::Utf8,
+    );
+    expect![[r#"
+        [
+            CodeAction {
+                title: "Add `open FakeStdLib;`",
+                edit: Some(
+                    WorkspaceEdit {
+                        changes: [
+                            (
+                                "cell1",
+                                [
+                                    TextEdit {
+                                        new_text: "open FakeStdLib;\n",
+                                        range: Range {
+                                            start: Position {
+                                                line: 0,
+                                                column: 0,
+                                            },
+                                            end: Position {
+                                                line: 0,
+                                                column: 0,
+                                            },
+                                        },
+                                    },
+                                ],
+                            ),
+                        ],
+                    },
+                ),
+                kind: Some(
+                    QuickFix,
+                ),
+                is_preferred: None,
+            },
+            CodeAction {
+                title: "Use `FakeStdLib.Fake`",
+                edit: Some(
+                    WorkspaceEdit {
+                        changes: [
+                            (
+                                "cell1",
+                                [
+                                    TextEdit {
+                                        new_text: "FakeStdLib.Fake",
+                                        range: Range {
+                                            start: Position {
+                                                line: 1,
+                                                column: 4,
+                                            },
+                                            end: Position {
+                                                line: 1,
+                                                column: 8,
+                                            },
+                                        },
+                                    },
+                                ],
+                            ),
+                        ],
+                    },
+                ),
+                kind: Some(
+                    QuickFix,
+                ),
+                is_preferred: None,
+            },
+        ]
+    "#]]
+    .assert_debug_eq(&actual);
+}
+
+#[test]
+fn deprecated_callable_offers_replacement() {
+    check_with_lints(
+        r#"
+    namespace Test {
+        @Deprecated("Bar")
+        function Foo() : Unit {}
+        function Bar() : Unit {}
+        function Main() : Unit {
+            ◉Foo◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                CodeAction {
+                    title: "Replace with `Bar`",
+                    edit: Some(
+                        WorkspaceEdit {
+                            changes: [
+                                (
+                                    "<source>",
+                                    [
+                                        TextEdit {
+                                            new_text: "Bar",
+                                            range: Range {
+                                                start: Position {
+                                                    line: 6,
+                                                    column: 12,
+                                                },
+                                                end: Position {
+                                                    line: 6,
+                                                    column: 15,
+                                                },
+                                            },
+                                        },
+                                    ],
+                                ),
+                            ],
+                        },
+                    ),
+                    kind: Some(
+                        QuickFix,
+                    ),
+                    is_preferred: Some(
+                        true,
+                    ),
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn deprecated_callable_without_replacement_has_no_fix() {
+    check_with_lints(
+        r#"
+    namespace Test {
+        @Deprecated()
+        function Foo() : Unit {}
+        function Main() : Unit {
+            ◉Foo◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
@@ -14,7 +14,7 @@ use qsc::{
     target::Profile,
     CompileUnit, LanguageFeatures, PackageStore, PackageType, PassContext, SourceMap, Span,
 };
-use qsc_linter::LintConfig;
+use qsc_linter::{Lint, LintConfig};
 use std::sync::Arc;
 
 /// Represents an immutable compilation state that can be used
@@ -77,15 +77,22 @@ impl Compilation {
             .get(package_id)
             .expect("expected to find user package");
 
-        run_fir_passes(
+        let profile_lints = run_fir_passes(
             &mut errors,
             target_profile,
+            lints_config,
             &package_store,
             package_id,
             unit,
         );
 
-        run_linter_passes(lints_config, &mut errors, unit);
+        run_linter_passes(
+            lints_config,
+            &mut errors,
+            profile_lints,
+            &package_store,
+            unit,
+        );
 
         Self {
             package_store,
@@ -133,15 +140,22 @@ impl Compilation {
             .get(package_id)
             .expect("expected to find user package");
 
-        run_fir_passes(
+        let profile_lints = run_fir_passes(
             &mut errors,
             target_profile,
+            lints_config,
             &package_store,
             package_id,
             unit,
         );
 
-        run_linter_passes(lints_config, &mut errors, unit);
+        run_linter_passes(
+            lints_config,
+            &mut errors,
+            profile_lints,
+            &package_store,
+            unit,
+        );
 
         Self {
             package_store,
@@ -264,50 +278,72 @@ impl Compilation {
 /// This function only runs passes if there are no compile
 /// errors in the package and if the target profile is not `Base`
 /// or `Unrestricted`.
+///
+/// Returns the profile compatibility lints, which explain the capabilities
+/// check errors in more detail. An error is left out only if a lint at the
+/// error level already reports it, so that local lint level overrides can't
+/// hide errors for code the target doesn't support.
 fn run_fir_passes(
     errors: &mut Vec<WithSource<compile::ErrorKind>>,
     target_profile: Profile,
+    lints_config: &[LintConfig],
     package_store: &PackageStore,
     package_id: PackageId,
     unit: &CompileUnit,
-) {
+) -> Vec<Lint> {
     if !errors.is_empty() {
         // can't run passes on a package with errors
-        return;
+        return Vec::new();
     }
 
     if target_profile == Profile::Unrestricted {
         // no point in running passes on unrestricted profile
-        return;
+        return Vec::new();
     }
 
     let (fir_store, fir_package_id) = qsc::lower_hir_to_fir(package_store, package_id);
-    let caps_results =
-        PassContext::run_fir_passes_on_fir(&fir_store, fir_package_id, target_profile.into());
-    if let Err(caps_errors) = caps_results {
-        for err in caps_errors {
-            let err = WithSource::from_map(&unit.sources, compile::ErrorKind::Pass(err));
-            errors.push(err);
+    let (compute_properties, caps_errors) =
+        PassContext::analyze_fir(&fir_store, fir_package_id, target_profile.into());
+    let profile_lints = qsc::linter::run_profile_lints(
+        package_store,
+        package_id,
+        &fir_store,
+        fir_package_id,
+        &compute_properties,
+        target_profile.into(),
+        Some(lints_config),
+    );
+    for err in caps_errors {
+        if profile_lints.iter().any(|lint| lint.reports_error(&err)) {
+            continue;
         }
+        let err = WithSource::from_map(&unit.sources, compile::ErrorKind::Pass(err));
+        errors.push(err);
     }
+    profile_lints.into_iter().map(|lint| lint.lint).collect()
 }
 
-/// Compute new lints and append them to the errors Vec.
+/// Compute new lints and append them to the errors Vec, along with
+/// the profile compatibility lints from [`run_fir_passes`].
 /// Lints are only computed if the erros vector is empty. For performance
 /// reasons we don't want to waste time running lints every few keystrokes,
 /// if the user is in the middle of typing a statement, for example.
 fn run_linter_passes(
     config: &[LintConfig],
     errors: &mut Vec<WithSource<compile::ErrorKind>>,
+    profile_lints: Vec<Lint>,
+    package_store: &PackageStore,
     unit: &CompileUnit,
 ) {
+    let mut lints = Vec::new();
     if errors.is_empty() {
-        let lints = qsc::linter::run_lints(unit, Some(config));
-        let lints = lints
-            .into_iter()
-            .map(|lint| WithSource::from_map(&unit.sources, qsc::compile::ErrorKind::Lint(lint)));
-        errors.extend(lints);
+        lints = qsc::linter::run_lints_with_dependencies(package_store, unit, Some(config));
     }
+    lints.extend(profile_lints);
+    let lints = lints
+        .into_iter()
+        .map(|lint| WithSource::from_map(&unit.sources, qsc::compile::ErrorKind::Lint(lint)));
+    errors.extend(lints);
 }
 
 impl Lookup for Compilation {
@@ -34,41 +34,14 @@ pub(crate) fn get_completions(
     let user_ast_package = &compilation.user_unit().ast.package;
 
     // Determine context for the offset
-    let mut context_finder = ContextFinder {
-        offset,
-        context: if user_ast_package.nodes.is_empty() {
-            // The parser failed entirely, no context to go on
-            Context::NoCompilation
-        } else {
-            // Starting context is top-level (i.e. outside a namespace block)
-            Context::TopLevel
-        },
-        opens: vec![],
-        start_of_namespace: None,
-        current_namespace_name: None,
-    };
-    context_finder.visit_package(user_ast_package);
-
-    let insert_open_at = match compilation.kind {
-        CompilationKind::OpenProject => context_finder.start_of_namespace,
-        // Since notebooks don't typically contain namespace declarations,
-        // open statements should just get before the first non-whitespace
-        // character (i.e. at the top of the cell)
-        CompilationKind::Notebook => Some(get_first_non_whitespace_in_source(compilation, offset)),
-    };
-
-    let insert_open_range = insert_open_at.map(|o| {
-        into_range(
-            position_encoding,
-            qsc::Span { lo: o, hi: o },
-            &compilation.user_unit().sources,
-        )
-    });
+    let mut context_finder = ContextFinder::find(user_ast_package, offset);
 
-    let indent = match insert_open_at {
-        Some(start) => get_indent(compilation, start),
-        None => String::new(),
-    };
+    let (insert_open_range, indent) = open_insertion(
+        compilation,
+        context_finder.start_of_namespace,
+        offset,
+        position_encoding,
+    );
 
     let mut prelude_ns_ids: Vec<_> = PRELUDE
         .into_iter()
@@ -156,6 +129,70 @@ pub(crate) fn get_completions(
     }
 }
 
+/// Returns the range at which an `open` statement should be inserted
+/// to bring a namespace into scope at `offset`, along with the indentation
+/// that should follow the inserted statement.
+pub(crate) fn get_open_insertion(
+    compilation: &Compilation,
+    offset: u32,
+    position_encoding: Encoding,
+) -> (Option<Range>, String) {
+    let context_finder = ContextFinder::find(&compilation.user_unit().ast.package, offset);
+
+    open_insertion(
+        compilation,
+        context_finder.start_of_namespace,
+        offset,
+        position_encoding,
+    )
+}
+
+/// Returns the names of the namespaces whose items are in scope at `offset`
+/// without qualification, i.e., the enclosing namespace and the namespaces
+/// opened without an alias.
+pub(crate) fn get_namespaces_in_scope(compilation: &Compilation, offset: u32) -> Vec<Rc<str>> {
+    let context_finder = ContextFinder::find(&compilation.user_unit().ast.package, offset);
+
+    context_finder
+        .opens
+        .into_iter()
+        .filter(|(_, alias)| alias.is_none())
+        .map(|(name, _)| name)
+        .chain(context_finder.current_namespace_name)
+        .map(|name| name.join(".").into())
+        .collect()
+}
+
+fn open_insertion(
+    compilation: &Compilation,
+    start_of_namespace: Option<u32>,
+    offset: u32,
+    position_encoding: Encoding,
+) -> (Option<Range>, String) {
+    let insert_open_at = match compilation.kind {
+        CompilationKind::OpenProject => start_of_namespace,
+        // Since notebooks don't typically contain namespace declarations,
+        // open statements should just get before the first non-whitespace
+        // character (i.e. at the top of the cell)
+        CompilationKind::Notebook => Some(get_first_non_whitespace_in_source(compilation, offset)),
+    };
+
+    let insert_open_range = insert_open_at.map(|o| {
+        into_range(
+            position_encoding,
+            qsc::Span { lo: o, hi: o },
+            &compilation.user_unit().sources,
+        )
+    });
+
+    let indent = match insert_open_at {
+        Some(start) => get_indent(compilation, start),
+        None => String::new(),
+    };
+
+    (insert_open_range, indent)
+}
+
 fn get_first_non_whitespace_in_source(compilation: &Compilation, package_offset: u32) -> u32 {
     const QSHARP_MAGIC: &str = "//qsharp";
     let source = compilation
@@ -626,6 +663,28 @@ struct ContextFinder {
     current_namespace_name: Option<Vec<Rc<str>>>,
 }
 
+impl ContextFinder {
+    /// Finds the context, the enclosing namespace and the opens in scope at
+    /// `offset` in `package`.
+    fn find(package: &qsc::ast::Package, offset: u32) -> Self {
+        let mut context_finder = ContextFinder {
+            offset,
+            context: if package.nodes.is_empty() {
+                // The parser failed entirely, no context to go on
+                Context::NoCompilation
+            } else {
+                // Starting context is top-level (i.e. outside a namespace block)
+                Context::TopLevel
+            },
+            opens: vec![],
+            start_of_namespace: None,
+            current_namespace_name: None,
+        };
+        context_finder.visit_package(package);
+        context_finder
+    }
+}
+
 #[derive(Debug, PartialEq)]
 enum Context {
     NoCompilation,
@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use std::{fmt::Write, iter};
+
+use miette::{Diagnostic as _, LabeledSpan, Severity};
+use qsc::{
+    compile::{Error, ErrorKind},
+    error::docs_url,
+    line_column::Encoding,
+    linter::LintKind,
+    location::Location,
+    Span,
+};
+use qsc_linter::AstLint;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag},
+    qsc_utils::into_location,
+};
+
+/// Returns the diagnostics for the errors whose primary label falls in `source_name`.
+/// Errors without any labels aren't associated with a document,
+/// so they are only reported through [`crate::protocol::DiagnosticUpdate`].
+pub(crate) fn get_diagnostics(
+    compilation: &Compilation,
+    source_name: &str,
+    (): (),
+    position_encoding: Encoding,
+) -> Vec<Diagnostic> {
+    compilation
+        .errors
+        .iter()
+        .filter_map(|error| to_diagnostic(compilation, source_name, error, position_encoding))
+        .collect()
+}
+
+/// Converts an error into a [`Diagnostic`] in `source_name`.
+/// The first label is the primary location of the error, and any
+/// further labels with messages become related information entries,
+/// which may be in other documents.
+fn to_diagnostic(
+    compilation: &Compilation,
+    source_name: &str,
+    error: &Error,
+    position_encoding: Encoding,
+) -> Option<Diagnostic> {
+    let mut labels = error.labels().into_iter().flatten();
+    let primary = label_location(compilation, &labels.next()?, position_encoding);
+    if primary.source.as_ref() != source_name {
+        return None;
+    }
+
+    let related_information = labels
+        .filter_map(|label| {
+            Some(DiagnosticRelatedInformation {
+                message: label.label()?.to_string(),
+                location: label_location(compilation, &label, position_encoding),
+            })
+        })
+        .collect();
+
+    // e.g. "type error: insufficient type information to infer type"
+    let mut message = error.to_string();
+    for source in iter::successors(std::error::Error::source(error), |e| e.source()) {
+        write!(message, ": {source}").expect("message should be writable");
+    }
+    if let Some(help) = error.help() {
+        write!(message, "\n\nhelp: {help}").expect("message should be writable");
+    }
+
+    let code = error.code().map(|code| code.to_string());
+    let code_description = docs_url(error);
+
+    let tags = match error.error() {
+        ErrorKind::Lint(lint) if lint.kind == LintKind::Ast(AstLint::Deprecated) => {
+            vec![DiagnosticTag::Deprecated]
+        }
+        _ => Vec::new(),
+    };
+
+    Some(Diagnostic {
+        range: primary.range,
+        severity: match error.severity().unwrap_or(Severity::Error) {
+            Severity::Error => DiagnosticSeverity::Error,
+            Severity::Warning => DiagnosticSeverity::Warning,
+            Severity::Advice => DiagnosticSeverity::Information,
+        },
+        message,
+        code,
+        code_description,
+        related_information,
+        tags,
+    })
+}
+
+fn label_location(
+    compilation: &Compilation,
+    label: &LabeledSpan,
+    position_encoding: Encoding,
+) -> Location {
+    let lo = u32::try_from(label.offset()).expect("offset should fit in u32");
+    let len = u32::try_from(label.len()).expect("length should fit in u32");
+    into_location(
+        position_encoding,
+        compilation,
+        Span { lo, hi: lo + len },
+        compilation.user_package_id,
+    )
+}
@@ -0,0 +1,326 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+
+use super::get_diagnostics;
+use crate::{
+    test_utils::{
+        add_lints, compile_project_with_fake_stdlib_and_markers_no_cursor,
+        compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+    Encoding,
+};
+
+fn check(source: &str, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source);
+    let actual = get_diagnostics(&compilation, "<source>", (), Encoding::Utf8);
+    expect.assert_debug_eq(&actual);
+}
+
+fn check_with_lints(source: &str, expect: &Expect) {
+    let (mut compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source);
+    add_lints(&mut compilation);
+    let actual = get_diagnostics(&compilation, "<source>", (), Encoding::Utf8);
+    expect.assert_debug_eq(&actual);
+}
+
+fn check_project(sources: &[(&str, &str)], source_name: &str, expect: &Expect) {
+    let (compilation, _) = compile_project_with_fake_stdlib_and_markers_no_cursor(sources);
+    let actual = get_diagnostics(&compilation, source_name, (), Encoding::Utf8);
+    expect.assert_debug_eq(&actual);
+}
+
+#[test]
+fn no_errors() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {}
+    }
+    "#,
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn unresolved_name() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            Bar();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Diagnostic {
+                    range: Range {
+                        start: Position {
+                            line: 3,
+                            column: 12,
+                        },
+                        end: Position {
+                            line: 3,
+                            column: 15,
+                        },
+                    },
+                    severity: Error,
+                    message: "name error: `Bar` not found",
+                    code: Some(
+                        "Qsc.Resolve.NotFound",
+                    ),
+                    code_description: None,
+                    related_information: [],
+                    tags: [],
+                },
+                Diagnostic {
+                    range: Range {
+                        start: Position {
+                            line: 3,
+                            column: 12,
+                        },
+                        end: Position {
+                            line: 3,
+                            column: 17,
+                        },
+                    },
+                    severity: Error,
+                    message: "type error: insufficient type information to infer type\n\nhelp: provide a type annotation",
+                    code: Some(
+                        "Qsc.TypeCk.AmbiguousTy",
+                    ),
+                    code_description: None,
+                    related_information: [],
+                    tags: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn secondary_labels_are_related_information() {
+    check(
+        r#"
+    namespace A {
+        function Foo() : Unit {}
+    }
+    namespace B {
+        function Foo() : Unit {}
+    }
+    namespace Test {
+        open A;
+        open B;
+        function Bar() : Unit {
+            Foo();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Diagnostic {
+                    range: Range {
+                        start: Position {
+                            line: 11,
+                            column: 12,
+                        },
+                        end: Position {
+                            line: 11,
+                            column: 15,
+                        },
+                    },
+                    severity: Error,
+                    message: "name error: `Foo` could refer to the item in `A` or `B`",
+                    code: Some(
+                        "Qsc.Resolve.Ambiguous",
+                    ),
+                    code_description: None,
+                    related_information: [
+                        DiagnosticRelatedInformation {
+                            location: Location {
+                                source: "<source>",
+                                range: Range {
+                                    start: Position {
+                                        line: 8,
+                                        column: 13,
+                                    },
+                                    end: Position {
+                                        line: 8,
+                                        column: 14,
+                                    },
+                                },
+                            },
+                            message: "found in this namespace",
+                        },
+                        DiagnosticRelatedInformation {
+                            location: Location {
+                                source: "<source>",
+                                range: Range {
+                                    start: Position {
+                                        line: 9,
+                                        column: 13,
+                                    },
+                                    end: Position {
+                                        line: 9,
+                                        column: 14,
+                                    },
+                                },
+                            },
+                            message: "and also in this namespace",
+                        },
+                    ],
+                    tags: [],
+                },
+                Diagnostic {
+                    range: Range {
+                        start: Position {
+                            line: 11,
+                            column: 12,
+                        },
+                        end: Position {
+                            line: 11,
+                            column: 17,
+                        },
+                    },
+                    severity: Error,
+                    message: "type error: insufficient type information to infer type\n\nhelp: provide a type annotation",
+                    code: Some(
+                        "Qsc.TypeCk.AmbiguousTy",
+                    ),
+                    code_description: None,
+                    related_information: [],
+                    tags: [],
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn errors_in_other_documents_excluded() {
+    check_project(
+        &[
+            (
+                "A.qs",
+                r#"
+    namespace A {
+        function Foo() : Unit {
+            Bar();
+        }
+    }
+    "#,
+            ),
+            (
+                "B.qs",
+                r#"
+    namespace B {
+        function Foo() : Unit {}
+    }
+    "#,
+            ),
+        ],
+        "B.qs",
+        &expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn no_docs_url_for_undocumented_codes() {
+    let (mut compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(
+        r#"
+    namespace Test {
+        function Foo() : Unit {
+            let x : Int = 1.0;
+            Bar();
+            let y = 1 / 0;
+        }
+    }
+    "#,
+    );
+    add_lints(&mut compilation);
+    let actual = get_diagnostics(&compilation, "<source>", (), Encoding::Utf8);
+    expect![[r#"
+        [
+            (
+                Some(
+                    "Qsc.Resolve.NotFound",
+                ),
+                None,
+            ),
+            (
+                Some(
+                    "Qsc.TypeCk.TyMismatch",
+                ),
+                None,
+            ),
+            (
+                Some(
+                    "Qsc.TypeCk.AmbiguousTy",
+                ),
+                None,
+            ),
+            (
+                Some(
+                    "Qsc.Lint.DivisionByZero",
+                ),
+                None,
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(
+        &actual
+            .iter()
+            .map(|d| (d.code.as_deref(), d.code_description.as_deref()))
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn deprecated_item_is_tagged() {
+    check_with_lints(
+        r#"
+    namespace Test {
+        @Deprecated("Bar")
+        function Foo() : Unit {}
+        function Bar() : Unit {}
+        function Main() : Unit {
+            Foo();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Diagnostic {
+                    range: Range {
+                        start: Position {
+                            line: 6,
+                            column: 12,
+                        },
+                        end: Position {
+                            line: 6,
+                            column: 15,
+                        },
+                    },
+                    severity: Warning,
+                    message: "use of deprecated item; use `Bar` instead\n\nhelp: this item is deprecated and may be removed in a future version",
+                    code: Some(
+                        "Qsc.Lint.Deprecated",
+                    ),
+                    code_description: None,
+                    related_information: [],
+                    tags: [
+                        Deprecated,
+                    ],
+                },
+            ]
+        "#]],
+    );
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::compilation::Compilation;
+use crate::protocol::{DocumentHighlight, DocumentHighlightKind};
+use crate::qsc_utils::into_range;
+use crate::references::{field_update_name, find_references, ReferenceFinder};
+use qsc::ast::visit::{walk_expr, walk_stmt, Visitor};
+use qsc::line_column::{Encoding, Position};
+use qsc::{ast, Span};
+
+pub(crate) fn get_document_highlights(
+    compilation: &Compilation,
+    source_name: &str,
+    position: Position,
+    position_encoding: Encoding,
+) -> Vec<DocumentHighlight> {
+    let offset =
+        compilation.source_position_to_package_offset(source_name, position, position_encoding);
+    let source_span = compilation.package_span_of_source(source_name);
+
+    let references = find_references(
+        compilation,
+        offset,
+        ReferenceFinder::new(position_encoding, compilation, true).within(source_span),
+    );
+
+    let mut write_finder = WriteFinder {
+        compilation,
+        source_span,
+        writes: vec![],
+    };
+    write_finder.visit_package(&compilation.user_unit().ast.package);
+    let writes = write_finder
+        .writes
+        .into_iter()
+        .map(|span| into_range(position_encoding, span, &compilation.user_unit().sources))
+        .collect::<Vec<_>>();
+
+    references
+        .into_iter()
+        .filter(|location| location.source.as_ref() == source_name)
+        .map(|location| DocumentHighlight {
+            kind: if writes.contains(&location.range) {
+                DocumentHighlightKind::Write
+            } else {
+                DocumentHighlightKind::Read
+            },
+            range: location.range,
+        })
+        .collect()
+}
+
+/// Collects the spans of names that are written to: names bound by
+/// `mutable` declarations, the targets of `set` statements, and the fields
+/// updated in place by `set ... w/= Field <- ...`.
+struct WriteFinder<'a> {
+    compilation: &'a Compilation,
+    source_span: Span,
+    writes: Vec<Span>,
+}
+
+impl WriteFinder<'_> {
+    fn push_bindings(&mut self, pat: &ast::Pat) {
+        match &*pat.kind {
+            ast::PatKind::Bind(ident, _) => self.writes.push(ident.span),
+            ast::PatKind::Paren(pat) => self.push_bindings(pat),
+            ast::PatKind::Tuple(pats) => pats.iter().for_each(|pat| self.push_bindings(pat)),
+            _ => {}
+        }
+    }
+
+    fn push_targets(&mut self, expr: &ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Path(path) => self.writes.push(path.name.span),
+            ast::ExprKind::Field(record, field) => {
+                self.writes.push(field.span);
+                self.push_targets(record);
+            }
+            ast::ExprKind::Paren(expr) => self.push_targets(expr),
+            ast::ExprKind::Tuple(exprs) => exprs.iter().for_each(|expr| self.push_targets(expr)),
+            _ => {}
+        }
+    }
+
+    fn push_field_update(&mut self, container: &ast::Expr, index: &ast::Expr) {
+        if let Some(field) = field_update_name(self.compilation, container, index) {
+            self.writes.push(field.span);
+        }
+    }
+}
+
+impl Visitor<'_> for WriteFinder<'_> {
+    fn visit_package(&mut self, package: &'_ ast::Package) {
+        for node in &*package.nodes {
+            match node {
+                ast::TopLevelNode::Namespace(namespace)
+                    if self.source_span.contains(namespace.span.lo) =>
+                {
+                    self.visit_namespace(namespace);
+                }
+                ast::TopLevelNode::Stmt(stmt) if self.source_span.contains(stmt.span.lo) => {
+                    self.visit_stmt(stmt);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'_ ast::Stmt) {
+        if let ast::StmtKind::Local(ast::Mutability::Mutable, pat, _) = &*stmt.kind {
+            self.push_bindings(pat);
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'_ ast::Expr) {
+        match &*expr.kind {
+            ast::ExprKind::Assign(lhs, _) | ast::ExprKind::AssignOp(_, lhs, _) => {
+                self.push_targets(lhs);
+            }
+            ast::ExprKind::AssignUpdate(container, index, _) => {
+                self.push_targets(container);
+                self.push_field_update(container, index);
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use super::get_document_highlights;
+use crate::{
+    protocol::DocumentHighlightKind,
+    test_utils::{
+        compile_project_with_fake_stdlib_and_markers, compile_with_fake_stdlib_and_markers,
+    },
+    Encoding,
+};
+use expect_test::{expect, Expect};
+
+/// Asserts that the highlights at the cursor position match the ranges between `◉` markers,
+/// and checks the highlight kinds in order.
+fn check(source_with_markers: &str, expect: &Expect) {
+    let (compilation, cursor_position, target_spans) =
+        compile_with_fake_stdlib_and_markers(source_with_markers);
+    let actual = get_document_highlights(&compilation, "<source>", cursor_position, Encoding::Utf8);
+    assert_eq!(
+        actual.iter().map(|h| h.range).collect::<Vec<_>>(),
+        target_spans
+    );
+    expect.assert_debug_eq(&actual.iter().map(|h| &h.kind).collect::<Vec<_>>());
+}
+
+#[test]
+fn local_reads() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            let ◉x↘◉ = 1;
+            let y = ◉x◉ + ◉x◉;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn mutable_writes() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            mutable ◉x◉ = 1;
+            set ◉x◉ += 1;
+            set ◉x↘◉ = ◉x◉ * 2;
+            let y = ◉x◉;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Write,
+                Write,
+                Write,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn tuple_set_writes() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            mutable (◉a↘◉, b) = (1, 2);
+            set (◉a◉, b) = (b, ◉a◉);
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Write,
+                Write,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn array_update_writes() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            mutable ◉arr↘◉ = [0, 0];
+            set ◉arr◉ w/= 0 <- 1;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Write,
+                Write,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn parameter() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo(◉q◉ : Qubit) : Unit {
+            H(◉q↘◉);
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn callable_item() {
+    check(
+        r#"
+    namespace Test {
+        operation ◉Bar◉() : Unit {}
+        operation Foo() : Unit {
+            ◉Ba↘r◉();
+            ◉Bar◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn struct_field() {
+    check(
+        r#"
+    namespace Test {
+        struct Pair { ◉First◉ : Int, Second : Int }
+        function Foo() : Int {
+            let p = new Pair { ◉First◉ = 1, Second = 2 };
+            p::◉Fir↘st◉
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn std_item_declaration_excluded() {
+    check(
+        r#"
+    namespace Test {
+        open FakeStdLib;
+        operation Foo() : Unit {
+            ◉Fa↘ke◉();
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn references_in_other_sources_excluded() {
+    let (compilation, cursor_uri, cursor_position, target_spans) =
+        compile_project_with_fake_stdlib_and_markers(&[
+            (
+                "<source1>",
+                "namespace A { operation ◉Foo◉() : Unit {} operation Bar() : Unit { ◉F↘oo◉(); } }",
+            ),
+            (
+                "<source2>",
+                "namespace B { open A; operation Baz() : Unit { Foo(); } }",
+            ),
+        ]);
+    let actual =
+        get_document_highlights(&compilation, &cursor_uri, cursor_position, Encoding::Utf8);
+    assert_eq!(
+        actual.iter().map(|h| h.range).collect::<Vec<_>>(),
+        target_spans.iter().map(|l| l.range).collect::<Vec<_>>()
+    );
+    assert!(actual.iter().all(|h| h.kind == DocumentHighlightKind::Read));
+}
+
+#[test]
+fn struct_field_assign_writes() {
+    check(
+        r#"
+    namespace Test {
+        struct Pair { ◉First◉ : Int, Second : Int }
+        function Foo() : Int {
+            mutable p = new Pair { ◉First◉ = 1, Second = 2 };
+            set p::◉First◉ = 3;
+            set p w/= ◉First◉ <- 4;
+            set p w/= Second <- 5;
+            let q = p w/ ◉First◉ <- 6;
+            p::◉Fir↘st◉
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Write,
+                Write,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_update_writes_container() {
+    check(
+        r#"
+    namespace Test {
+        struct Pair { First : Int, Second : Int }
+        function Foo() : Int {
+            mutable ◉p↘◉ = new Pair { First = 1, Second = 2 };
+            set ◉p◉::First = 3;
+            set ◉p◉ w/= First <- 4;
+            let q = ◉p◉ w/ First <- 5;
+            q::First
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Write,
+                Write,
+                Write,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn array_update_index_read() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {
+            let ◉i↘◉ = 0;
+            mutable arr = [0, 0];
+            set arr w/= ◉i◉ <- ◉i◉;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Read,
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn field_update_cursor_on_field() {
+    check(
+        r#"
+    namespace Test {
+        struct Pair { ◉First◉ : Int, Second : Int }
+        function Foo() : Unit {
+            mutable p = new Pair { ◉First◉ = 1, Second = 2 };
+            set p w/= ◉Fi↘rst◉ <- 4;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                Read,
+                Read,
+                Write,
+            ]
+        "#]],
+    );
+}
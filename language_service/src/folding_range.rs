@@ -0,0 +1,245 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    compilation::Compilation,
+    protocol::{FoldingRange, FoldingRangeKind},
+    qsc_utils::into_range,
+};
+use qsc::{
+    ast::{
+        self,
+        visit::{self, Visitor},
+    },
+    line_column::Encoding,
+    Span,
+};
+use qsc_frontend::lex::raw;
+
+pub(crate) fn get_folding_ranges(
+    compilation: &Compilation,
+    source_name: &str,
+    position_encoding: Encoding,
+) -> Vec<FoldingRange> {
+    let unit = compilation.user_unit();
+    let source = unit
+        .sources
+        .find_by_name(source_name)
+        .expect("source should exist in the user source map");
+    let source_span = compilation.package_span_of_source(source_name);
+
+    let mut finder = FoldingRangeFinder {
+        source_span,
+        spans: Vec::new(),
+    };
+    finder.visit_package(&unit.ast.package);
+
+    let mut spans = finder.spans;
+    spans.extend(
+        doc_comment_runs(&source.contents)
+            .into_iter()
+            .map(|span| (span + source.offset, Some(FoldingRangeKind::Comment))),
+    );
+    spans.extend(
+        region_comments(&source.contents)
+            .into_iter()
+            .map(|span| (span + source.offset, Some(FoldingRangeKind::Region))),
+    );
+
+    let mut ranges = spans
+        .into_iter()
+        .map(|(span, kind)| FoldingRange {
+            range: into_range(position_encoding, span, &unit.sources),
+            kind,
+        })
+        // Ranges that start and end on the same line can't be folded.
+        .filter(|folding_range| folding_range.range.end.line > folding_range.range.start.line)
+        .collect::<Vec<_>>();
+
+    // Editors can only fold one range per line, so keep the
+    // outermost range that starts on any given line.
+    ranges.sort_by_key(|folding_range| {
+        (
+            folding_range.range.start.line,
+            std::cmp::Reverse(folding_range.range.end.line),
+        )
+    });
+    ranges.dedup_by_key(|folding_range| folding_range.range.start.line);
+    ranges
+}
+
+/// Finds runs of two or more `///` comments on consecutive lines and returns
+/// their spans, relative to the start of `contents`.
+fn doc_comment_runs(contents: &str) -> Vec<Span> {
+    let mut runs = Vec::new();
+    // The start of the current run, the end of its last comment, and its comment count.
+    let mut current: Option<(usize, usize, u32)> = None;
+    for (kind, lo, hi) in comments(contents) {
+        if kind != raw::CommentKind::Doc {
+            continue;
+        }
+        current = match current {
+            Some((run_lo, run_hi, count)) if is_line_break(&contents[run_hi..lo]) => {
+                Some((run_lo, hi, count + 1))
+            }
+            run => {
+                if let Some(run) = run {
+                    push_run(&mut runs, run);
+                }
+                Some((lo, hi, 1))
+            }
+        };
+    }
+    if let Some(run) = current {
+        push_run(&mut runs, run);
+    }
+    runs
+}
+
+/// Finds `// #region` comments and their matching `// #endregion` comments
+/// and returns the spans between them, relative to the start of `contents`.
+/// Regions can be nested, and unmatched comments are ignored.
+fn region_comments(contents: &str) -> Vec<Span> {
+    let mut regions = Vec::new();
+    let mut open_regions = Vec::new();
+    for (kind, lo, hi) in comments(contents) {
+        let line_start = contents[..lo].rfind('\n').map_or(0, |i| i + 1);
+        if kind != raw::CommentKind::Normal || !contents[line_start..lo].trim().is_empty() {
+            continue;
+        }
+        let comment = contents[lo..hi].trim_start_matches('/').trim_start();
+        if comment.starts_with("#region") {
+            open_regions.push(lo);
+        } else if comment.starts_with("#endregion") {
+            if let Some(lo) = open_regions.pop() {
+                regions.push(Span {
+                    lo: u32::try_from(lo).expect("offset should fit into u32"),
+                    hi: u32::try_from(hi).expect("offset should fit into u32"),
+                });
+            }
+        }
+    }
+    regions
+}
+
+/// Returns the kind, start and end of the comments in `contents`. Comments
+/// are taken from the lexer, so `//` inside string literals is not mistaken
+/// for a comment.
+fn comments(contents: &str) -> impl Iterator<Item = (raw::CommentKind, usize, usize)> + '_ {
+    raw::Lexer::new(contents).filter_map(|token| match token.kind {
+        raw::TokenKind::Comment(kind) => {
+            let lo = token.offset as usize;
+            let hi = contents[lo..]
+                .find(['\n', '\r'])
+                .map_or(contents.len(), |i| lo + i);
+            Some((kind, lo, lo + contents[lo..hi].trim_end().len()))
+        }
+        _ => None,
+    })
+}
+
+/// Returns true if `gap` only consists of whitespace with a single line break.
+fn is_line_break(gap: &str) -> bool {
+    gap.trim().is_empty() && gap.matches('\n').count() == 1
+}
+
+fn push_run(runs: &mut Vec<Span>, (lo, hi, count): (usize, usize, u32)) {
+    if count > 1 {
+        runs.push(Span {
+            lo: u32::try_from(lo).expect("offset should fit into u32"),
+            hi: u32::try_from(hi).expect("offset should fit into u32"),
+        });
+    }
+}
+
+struct FoldingRangeFinder {
+    source_span: Span,
+    spans: Vec<(Span, Option<FoldingRangeKind>)>,
+}
+
+impl FoldingRangeFinder {
+    fn push(&mut self, span: Span) {
+        self.push_with_kind(span, None);
+    }
+
+    fn push_with_kind(&mut self, span: Span, kind: Option<FoldingRangeKind>) {
+        if self.source_span.touches(span.lo) {
+            self.spans.push((span, kind));
+        }
+    }
+
+    /// Adds a range for each run of two or more consecutive `open` or
+    /// `import` items.
+    fn push_import_runs(&mut self, items: &[Box<ast::Item>]) {
+        let mut run: Option<(Span, usize)> = None;
+        for item in items {
+            let is_import = match &*item.kind {
+                ast::ItemKind::Open(..) => true,
+                ast::ItemKind::ImportOrExport(decl) => !decl.is_export(),
+                _ => false,
+            };
+            run = match run {
+                Some((span, count)) if is_import => Some((
+                    Span {
+                        lo: span.lo,
+                        hi: item.span.hi,
+                    },
+                    count + 1,
+                )),
+                None if is_import => Some((item.span, 1)),
+                _ => {
+                    self.push_import_run(run);
+                    None
+                }
+            };
+        }
+        self.push_import_run(run);
+    }
+
+    fn push_import_run(&mut self, run: Option<(Span, usize)>) {
+        if let Some((span, count)) = run {
+            if count > 1 {
+                self.push_with_kind(span, Some(FoldingRangeKind::Imports));
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for FoldingRangeFinder {
+    fn visit_namespace(&mut self, namespace: &'a ast::Namespace) {
+        // Namespaces from other sources can be skipped entirely.
+        if self.source_span.touches(namespace.span.lo) {
+            // The namespace span includes any leading doc comment, which gets its
+            // own folding range, so start folding from the namespace name instead.
+            self.push(Span {
+                lo: namespace.name.span().lo,
+                hi: namespace.span.hi,
+            });
+            self.push_import_runs(&namespace.items);
+            visit::walk_namespace(self, namespace);
+        }
+    }
+
+    fn visit_callable_decl(&mut self, decl: &'a ast::CallableDecl) {
+        self.push(decl.span);
+        visit::walk_callable_decl(self, decl);
+    }
+
+    fn visit_struct_decl(&mut self, decl: &'a ast::StructDecl) {
+        self.push(decl.span);
+        visit::walk_struct_decl(self, decl);
+    }
+
+    fn visit_spec_decl(&mut self, decl: &'a ast::SpecDecl) {
+        self.push(decl.span);
+        visit::walk_spec_decl(self, decl);
+    }
+
+    fn visit_block(&mut self, block: &'a ast::Block) {
+        self.push(block.span);
+        visit::walk_block(self, block);
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+
+use super::get_folding_ranges;
+use crate::{
+    test_utils::{
+        compile_notebook_with_fake_stdlib, compile_with_fake_stdlib_and_markers_no_cursor,
+    },
+    Encoding,
+};
+
+/// Checks the folding ranges, rendered as `start_line..end_line` with their kind.
+fn check(source: &str, expect: &Expect) {
+    let (compilation, _) = compile_with_fake_stdlib_and_markers_no_cursor(source);
+    let actual = get_folding_ranges(&compilation, "<source>", Encoding::Utf8)
+        .into_iter()
+        .map(|r| format!("{}..{} {:?}", r.range.start.line, r.range.end.line, r.kind))
+        .collect::<Vec<_>>();
+    expect.assert_debug_eq(&actual);
+}
+
+#[test]
+fn namespace_and_callable() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            let x = 1;
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..5 None",
+                "2..4 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn single_line_items_not_folded() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo() : Unit {}
+        function Bar() : Int { 1 }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..4 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn nested_blocks() {
+    check(
+        r#"
+    namespace Test {
+        operation Main() : Unit {
+            use q = Qubit();
+            within {
+                H(q);
+            } apply {
+                X(q);
+            }
+            repeat {
+                H(q);
+            } until M(q) == Zero
+            fixup {
+                X(q);
+            }
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..16 None",
+                "2..15 None",
+                "4..6 None",
+                "6..8 None",
+                "9..11 None",
+                "12..14 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn specializations() {
+    check(
+        r#"
+    namespace Test {
+        operation Foo(q : Qubit) : Unit is Adj {
+            body ... {
+                X(q);
+            }
+            adjoint ... {
+                X(q);
+            }
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..10 None",
+                "2..9 None",
+                "3..5 None",
+                "6..8 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn struct_decl() {
+    check(
+        r#"
+    namespace Test {
+        struct Pair {
+            First : Int,
+            Second : Int,
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..6 None",
+                "2..5 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn doc_comment_runs() {
+    check(
+        r#"
+    namespace Test {
+        /// # Summary
+        /// Does nothing.
+        operation Foo() : Unit {}
+
+        /// Single line doc comment.
+        operation Bar() : Unit {}
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..8 None",
+                "2..3 Some(Comment)",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn notebook_top_level_block() {
+    let compilation = compile_notebook_with_fake_stdlib(
+        [(
+            "cell1",
+            "operation Main() : Unit {\n    if true {\n        Fake();\n    }\n}",
+        )]
+        .into_iter(),
+    );
+    let actual = get_folding_ranges(&compilation, "cell1", Encoding::Utf8)
+        .into_iter()
+        .map(|r| format!("{}..{} {:?}", r.range.start.line, r.range.end.line, r.kind))
+        .collect::<Vec<_>>();
+    expect![[r#"
+        [
+            "0..4 None",
+            "1..3 None",
+        ]
+    "#]]
+    .assert_debug_eq(&actual);
+}
+
+#[test]
+fn namespace_doc_comment() {
+    check(
+        r#"
+    /// Namespace docs
+    /// over two lines.
+    namespace Test {
+        operation Foo() : Unit {
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..2 Some(Comment)",
+                "3..6 None",
+                "4..5 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn consecutive_opens_and_imports() {
+    check(
+        r#"
+    namespace Test {
+        open FakeStdLib;
+        open Microsoft.Quantum.Unstable;
+        import FakeStdLib.Fake;
+        operation Main() : Unit {
+        }
+        open FakeStdLib as Std;
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..8 None",
+                "2..4 Some(Imports)",
+                "5..6 None",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn region_comments() {
+    check(
+        r#"
+    namespace Test {
+        // #region Helpers
+        function Helper() : Unit {}
+        //#region Nested
+        function Nested() : Unit {}
+        //#endregion
+        // #endregion
+        // #endregion without region
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..9 None",
+                "2..7 Some(Region)",
+                "4..6 Some(Region)",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn comment_markers_in_string_not_folded() {
+    check(
+        r#"
+    namespace Test {
+        function Foo() : String {
+            "first line
+            /// not a doc comment
+            /// still not a doc comment
+            // #region not a region
+            // #endregion"
+        }
+    }
+    "#,
+        &expect![[r#"
+            [
+                "1..9 None",
+                "2..8 None",
+            ]
+        "#]],
+    );
+}
@@ -3,12 +3,16 @@
 
 use crate::{compilation::Compilation, protocol::TextEdit};
 
-use qsc::formatter::calculate_format_edits;
+use qsc::formatter::{
+    calculate_format_edits_with_options, calculate_range_format_edits, FormatOptions,
+};
 use qsc::line_column::{Encoding, Range};
+use qsc::Span;
 
 pub(crate) fn get_format_changes(
     compilation: &Compilation,
     source_name: &str,
+    options: &FormatOptions,
     encoding: Encoding,
 ) -> Vec<TextEdit> {
     let contents = &compilation
@@ -18,7 +22,47 @@ pub(crate) fn get_format_changes(
         .expect("can't find source by name")
         .contents;
 
-    calculate_format_edits(contents)
+    to_text_edits(
+        contents,
+        calculate_format_edits_with_options(contents, options),
+        encoding,
+    )
+}
+
+pub(crate) fn get_range_format_changes(
+    compilation: &Compilation,
+    source_name: &str,
+    range: Range,
+    options: &FormatOptions,
+    encoding: Encoding,
+) -> Vec<TextEdit> {
+    let source = compilation
+        .user_unit()
+        .sources
+        .find_by_name(source_name)
+        .expect("can't find source by name");
+    let contents = &source.contents;
+
+    // The formatter works on offsets into this source, not into the package.
+    let package_span = compilation.source_range_to_package_span(source_name, range, encoding);
+    let span = Span {
+        lo: package_span.lo - source.offset,
+        hi: package_span.hi - source.offset,
+    };
+
+    to_text_edits(
+        contents,
+        calculate_range_format_edits(contents, span, options),
+        encoding,
+    )
+}
+
+fn to_text_edits(
+    contents: &str,
+    edits: Vec<qsc::formatter::TextEdit>,
+    encoding: Encoding,
+) -> Vec<TextEdit> {
+    edits
         .into_iter()
         .map(|edit| TextEdit {
             new_text: edit.new_text,
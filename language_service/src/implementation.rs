@@ -0,0 +1,90 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::compilation::Compilation;
+use crate::definition::get_definition;
+use crate::qsc_utils::into_location;
+use qsc::ast::visit::{walk_item, walk_namespace, Visitor};
+use qsc::display::Lookup;
+use qsc::line_column::{Encoding, Position};
+use qsc::location::Location;
+use qsc::{ast, hir, resolve};
+
+/// Finds the original declaration of the item at the given position.
+/// Items named in `import` and `export` declarations, including aliased
+/// ones, are followed through to the item they were resolved to.
+/// Everywhere else this is the same as going to the definition, since references
+/// to re-exported items already resolve to the original item.
+pub(crate) fn get_implementation(
+    compilation: &Compilation,
+    source_name: &str,
+    position: Position,
+    position_encoding: Encoding,
+) -> Option<Location> {
+    let offset =
+        compilation.source_position_to_package_offset(source_name, position, position_encoding);
+    let user_ast_package = &compilation.user_unit().ast.package;
+
+    let mut finder = ImportOrExportFinder {
+        compilation,
+        offset,
+        item_id: None,
+    };
+    finder.visit_package(user_ast_package);
+
+    match finder.item_id {
+        Some(item_id) => {
+            let (item, _, resolved_item_id) =
+                compilation.resolve_item_relative_to_user_package(&item_id);
+            let span = match &item.kind {
+                hir::ItemKind::Callable(decl) => decl.name.span,
+                hir::ItemKind::Ty(type_name, _) => type_name.span,
+                hir::ItemKind::Namespace(..) => return None,
+            };
+            Some(into_location(
+                position_encoding,
+                compilation,
+                span,
+                resolved_item_id
+                    .package
+                    .expect("package id should be resolved"),
+            ))
+        }
+        None => get_definition(compilation, source_name, position, position_encoding),
+    }
+}
+
+/// Finds the item that an `import` or `export` item at the offset resolved to.
+struct ImportOrExportFinder<'a> {
+    compilation: &'a Compilation,
+    offset: u32,
+    item_id: Option<hir::ItemId>,
+}
+
+impl<'a> Visitor<'a> for ImportOrExportFinder<'a> {
+    fn visit_namespace(&mut self, namespace: &'a ast::Namespace) {
+        if namespace.span.contains(self.offset) {
+            walk_namespace(self, namespace);
+        }
+    }
+
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        if let ast::ItemKind::ImportOrExport(decl) = &*item.kind {
+            if let Some(item) = decl
+                .items()
+                .find(|item| !item.is_glob && item.span().touches(self.offset))
+            {
+                if let Some(resolve::Res::Item(item_id, _)) =
+                    self.compilation.get_res(item.name().id)
+                {
+                    self.item_id = Some(*item_id);
+                }
+            }
+        } else if item.span.contains(self.offset) {
+            walk_item(self, item);
+        }
+    }
+}
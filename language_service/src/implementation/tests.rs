@@ -0,0 +1,189 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+
+use super::get_implementation;
+use crate::{
+    test_utils::{
+        compile_project_with_fake_stdlib_and_markers, compile_with_fake_stdlib_and_markers,
+    },
+    Encoding,
+};
+
+/// Asserts that the implementation given at the cursor position matches the expected location.
+/// The cursor position is indicated by a `↘` marker in the source text.
+/// The expected implementation range is indicated by `◉` markers in the source text.
+fn assert_implementation(sources_with_markers: &[(&str, &str)]) {
+    let (compilation, cursor_uri, cursor_position, target_spans) =
+        compile_project_with_fake_stdlib_and_markers(sources_with_markers);
+    let actual_implementation =
+        get_implementation(&compilation, &cursor_uri, cursor_position, Encoding::Utf8);
+    assert_eq!(target_spans.first(), actual_implementation.as_ref());
+}
+
+fn check(source_with_markers: &str, expect: &Expect) {
+    let (compilation, cursor_position, _) =
+        compile_with_fake_stdlib_and_markers(source_with_markers);
+    let actual_implementation =
+        get_implementation(&compilation, "<source>", cursor_position, Encoding::Utf8);
+    expect.assert_debug_eq(&actual_implementation);
+}
+
+#[test]
+fn export_item() {
+    assert_implementation(&[(
+        "<source>",
+        r#"
+    namespace Test {
+        internal function ◉Bar◉() : Unit {}
+        export B↘ar;
+    }
+    "#,
+    )]);
+}
+
+#[test]
+fn export_alias() {
+    assert_implementation(&[(
+        "<source>",
+        r#"
+    namespace Test {
+        internal function ◉Bar◉() : Unit {}
+        export Bar as B↘az;
+    }
+    "#,
+    )]);
+}
+
+#[test]
+fn export_from_other_namespace() {
+    assert_implementation(&[
+        (
+            "A.qs",
+            r#"
+    namespace A {
+        internal newtype ◉Pair◉ = (Int, Int);
+    }
+    "#,
+        ),
+        (
+            "B.qs",
+            r#"
+    namespace B {
+        export A.P↘air as Tuple;
+    }
+    "#,
+        ),
+    ]);
+}
+
+#[test]
+fn reexported_callable_ref() {
+    assert_implementation(&[
+        (
+            "A.qs",
+            r#"
+    namespace A {
+        internal function ◉Bar◉() : Unit {}
+        export Bar as Baz;
+    }
+    "#,
+        ),
+        (
+            "B.qs",
+            r#"
+    namespace B {
+        function Foo() : Unit {
+            A.B↘az();
+        }
+    }
+    "#,
+        ),
+    ]);
+}
+
+#[test]
+fn import_item() {
+    assert_implementation(&[
+        (
+            "A.qs",
+            r#"
+    namespace A {
+        function ◉Bar◉() : Unit {}
+    }
+    "#,
+        ),
+        (
+            "B.qs",
+            r#"
+    namespace B {
+        import A.Bar as B↘az;
+        function Foo() : Unit {
+            Baz();
+        }
+    }
+    "#,
+        ),
+    ]);
+}
+
+#[test]
+fn local_import_item() {
+    assert_implementation(&[(
+        "<source>",
+        r#"
+    namespace Test {
+        function ◉Bar◉() : Unit {}
+    }
+    namespace Other {
+        function Foo() : Unit {
+            import Test.B↘ar;
+            Bar();
+        }
+    }
+    "#,
+    )]);
+}
+
+#[test]
+fn std_export() {
+    check(
+        r#"
+    namespace Test {
+        export FakeStdLib.F↘ake;
+    }
+    "#,
+        &expect![[r#"
+            Some(
+                Location {
+                    source: "qsharp-library-source:<std>",
+                    range: Range {
+                        start: Position {
+                            line: 1,
+                            column: 26,
+                        },
+                        end: Position {
+                            line: 1,
+                            column: 30,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn glob_import() {
+    assert_implementation(&[(
+        "<source>",
+        r#"
+    namespace Test {
+        import FakeStdLib.↘*;
+    }
+    "#,
+    )]);
+}
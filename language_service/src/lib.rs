@@ -6,28 +6,35 @@ pub mod code_lens;
 mod compilation;
 pub mod completion;
 pub mod definition;
+pub mod diagnostic;
+pub mod document_highlight;
+pub mod folding_range;
 pub mod format;
 pub mod hover;
+pub mod implementation;
 mod name_locator;
 mod project_system;
 pub mod protocol;
 mod qsc_utils;
 pub mod references;
 pub mod rename;
+pub mod selection_range;
 pub mod signature_help;
 mod state;
 #[cfg(test)]
 mod test_utils;
 #[cfg(test)]
 mod tests;
+pub mod type_definition;
 
 use compilation::Compilation;
 use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use futures_util::StreamExt;
 use log::{trace, warn};
 use protocol::{
-    CodeAction, CodeLens, CompletionList, DiagnosticUpdate, Hover, NotebookMetadata, SignatureHelp,
-    TextEdit, WorkspaceConfigurationUpdate,
+    CodeAction, CodeLens, CompletionList, Diagnostic, DiagnosticUpdate, DocumentHighlight,
+    FoldingRange, Hover, NotebookMetadata, SelectionRange, SignatureHelp, TextEdit,
+    WorkspaceConfigurationUpdate,
 };
 use qsc::{
     line_column::{Encoding, Position, Range},
@@ -206,6 +213,28 @@ impl LanguageService {
         self.document_op(definition::get_definition, "get_definition", uri, position)
     }
 
+    /// LSP: textDocument/typeDefinition
+    #[must_use]
+    pub fn get_type_definition(&self, uri: &str, position: Position) -> Option<Location> {
+        self.document_op(
+            type_definition::get_type_definition,
+            "get_type_definition",
+            uri,
+            position,
+        )
+    }
+
+    /// LSP: textDocument/implementation
+    #[must_use]
+    pub fn get_implementation(&self, uri: &str, position: Position) -> Option<Location> {
+        self.document_op(
+            implementation::get_implementation,
+            "get_implementation",
+            uri,
+            position,
+        )
+    }
+
     /// LSP: textDocument/references
     #[must_use]
     pub fn get_references(
@@ -230,12 +259,30 @@ impl LanguageService {
         )
     }
 
+    /// LSP: textDocument/documentHighlight
+    #[must_use]
+    pub fn get_document_highlights(&self, uri: &str, position: Position) -> Vec<DocumentHighlight> {
+        self.document_op(
+            document_highlight::get_document_highlights,
+            "get_document_highlights",
+            uri,
+            position,
+        )
+    }
+
+    /// LSP: textDocument/diagnostic
+    #[must_use]
+    pub fn get_diagnostics(&self, uri: &str) -> Vec<Diagnostic> {
+        self.document_op(diagnostic::get_diagnostics, "get_diagnostics", uri, ())
+    }
+
     /// LSP: textDocument/format
     #[must_use]
     pub fn get_format_changes(&self, uri: &str) -> Vec<TextEdit> {
+        let options = self.state.borrow().get_format_options(uri);
         self.document_op(
             |compilation, uri, (), position_encoding| {
-                format::get_format_changes(compilation, uri, position_encoding)
+                format::get_format_changes(compilation, uri, &options, position_encoding)
             },
             "get_format_changes",
             uri,
@@ -243,6 +290,26 @@ impl LanguageService {
         )
     }
 
+    /// LSP: textDocument/rangeFormatting
+    #[must_use]
+    pub fn get_range_format_changes(&self, uri: &str, range: Range) -> Vec<TextEdit> {
+        let options = self.state.borrow().get_format_options(uri);
+        self.document_op(
+            |compilation, uri, range, position_encoding| {
+                format::get_range_format_changes(
+                    compilation,
+                    uri,
+                    range,
+                    &options,
+                    position_encoding,
+                )
+            },
+            "get_range_format_changes",
+            uri,
+            range,
+        )
+    }
+
     /// LSP: textDocument/hover
     #[must_use]
     pub fn get_hover(&self, uri: &str, position: Position) -> Option<Hover> {
@@ -285,6 +352,30 @@ impl LanguageService {
         )
     }
 
+    /// LSP: textDocument/foldingRange
+    #[must_use]
+    pub fn get_folding_ranges(&self, uri: &str) -> Vec<FoldingRange> {
+        self.document_op(
+            |compilation, uri, (), position_encoding| {
+                folding_range::get_folding_ranges(compilation, uri, position_encoding)
+            },
+            "get_folding_ranges",
+            uri,
+            (),
+        )
+    }
+
+    /// LSP: textDocument/selectionRange
+    #[must_use]
+    pub fn get_selection_ranges(&self, uri: &str, positions: Vec<Position>) -> Vec<SelectionRange> {
+        self.document_op(
+            selection_range::get_selection_ranges,
+            "get_selection_ranges",
+            uri,
+            positions,
+        )
+    }
+
     /// Executes an operation that takes a document uri, using the current compilation for that document.
     /// All "read" operations should go through this method. This method will borrow the current
     /// compilation state to perform the request.
@@ -6,6 +6,7 @@ use std::rc::Rc;
 
 use crate::compilation::Compilation;
 use crate::qsc_utils::find_ident;
+use crate::references::field_update_name;
 use qsc::ast::visit::{walk_expr, walk_namespace, walk_pat, walk_ty, walk_ty_def, Visitor};
 use qsc::display::Lookup;
 use qsc::{ast, hir, resolve};
@@ -334,6 +335,22 @@ impl<'inner, 'package, T: Handler<'package>> Visitor<'package> for Locator<'inne
                         }
                     }
                 }
+                ast::ExprKind::AssignUpdate(container, index, _)
+                | ast::ExprKind::TernOp(ast::TernOp::Update, container, index, _)
+                    if index.span.touches(self.offset) =>
+                {
+                    match (
+                        field_update_name(self.compilation, container, index),
+                        self.compilation.get_ty(container.id),
+                    ) {
+                        (Some(field_ref), Some(hir::ty::Ty::Udt(_, res))) => {
+                            if let Some((item_id, field_def)) = self.get_field_def(res, field_ref) {
+                                self.inner.at_field_ref(field_ref, &item_id, field_def);
+                            }
+                        }
+                        _ => walk_expr(self, expr),
+                    }
+                }
                 ast::ExprKind::Struct(ty_name, copy, fields) => {
                     if ty_name.span.touches(self.offset) {
                         self.visit_path(ty_name);
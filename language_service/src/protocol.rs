@@ -2,6 +2,7 @@
 // Licensed under the MIT License.
 
 use qsc::line_column::Range;
+use qsc::location::Location;
 use qsc::{
     compile::Error, linter::LintConfig, project::Manifest, target::Profile, LanguageFeatures,
     PackageType,
@@ -23,6 +24,41 @@ pub struct DiagnosticUpdate {
     pub errors: Vec<Error>,
 }
 
+/// A compilation error or lint, converted for display in a single document.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub range: Range,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// The error code, e.g. `Qsc.Resolve.NotFound`.
+    pub code: Option<String>,
+    /// A link to documentation describing the error code.
+    pub code_description: Option<String>,
+    /// Secondary labels on the error, which may point to other documents.
+    pub related_information: Vec<DiagnosticRelatedInformation>,
+    /// Hints about how the editor should render the diagnostic.
+    pub tags: Vec<DiagnosticTag>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Information,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiagnosticTag {
+    /// The diagnostic marks a use of deprecated code, usually shown with a strike-through.
+    Deprecated,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DiagnosticRelatedInformation {
+    pub location: Location,
+    pub message: String,
+}
+
 #[derive(Debug)]
 pub enum CodeActionKind {
     Empty,
@@ -178,3 +214,36 @@ pub struct OperationInfo {
     pub operation: String,
     pub total_num_qubits: u32,
 }
+
+#[derive(Debug, PartialEq)]
+pub struct FoldingRange {
+    pub range: Range,
+    pub kind: Option<FoldingRangeKind>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FoldingRangeKind {
+    Comment,
+    Imports,
+    Region,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SelectionRange {
+    pub range: Range,
+    /// The enclosing selection range, if any. The parent's range
+    /// always contains this range.
+    pub parent: Option<Box<SelectionRange>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DocumentHighlight {
+    pub range: Range,
+    pub kind: DocumentHighlightKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DocumentHighlightKind {
+    Read,
+    Write,
+}
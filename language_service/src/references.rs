@@ -26,10 +26,24 @@ pub(crate) fn get_references(
 ) -> Vec<Location> {
     let offset =
         compilation.source_position_to_package_offset(source_name, position, position_encoding);
+
+    find_references(
+        compilation,
+        offset,
+        ReferenceFinder::new(position_encoding, compilation, include_declaration),
+    )
+}
+
+/// Finds the references to the symbol at `offset` using `reference_finder`.
+pub(crate) fn find_references<'a>(
+    compilation: &'a Compilation,
+    offset: u32,
+    reference_finder: ReferenceFinder<'a>,
+) -> Vec<Location> {
     let user_ast_package = &compilation.user_unit().ast.package;
 
     let mut name_handler = NameHandler {
-        reference_finder: ReferenceFinder::new(position_encoding, compilation, include_declaration),
+        reference_finder,
         references: vec![],
     };
 
@@ -43,6 +57,8 @@ pub(crate) struct ReferenceFinder<'a> {
     position_encoding: Encoding,
     compilation: &'a Compilation,
     include_declaration: bool,
+    /// If set, only references within this span of the user package are searched for.
+    search_span: Option<Span>,
 }
 
 struct NameHandler<'a> {
@@ -190,9 +206,19 @@ impl<'a> ReferenceFinder<'a> {
             position_encoding,
             compilation,
             include_declaration,
+            search_span: None,
         }
     }
 
+    /// Restricts the search for references to the top-level nodes of the
+    /// user package that start within `span`, typically a single source.
+    /// The declaration is still included regardless of where it is.
+    #[must_use]
+    pub fn within(mut self, span: Span) -> Self {
+        self.search_span = Some(span);
+        self
+    }
+
     pub fn for_item(&self, item_id: &hir::ItemId) -> Vec<Location> {
         let mut locations = vec![];
 
@@ -221,7 +247,7 @@ impl<'a> ReferenceFinder<'a> {
             locations: vec![],
         };
 
-        find_refs.visit_package(&self.compilation.user_unit().ast.package);
+        self.visit_user_package(&mut find_refs);
         locations.extend(
             find_refs
                 .locations
@@ -266,7 +292,7 @@ impl<'a> ReferenceFinder<'a> {
             locations: vec![],
         };
 
-        find_refs.visit_package(&self.compilation.user_unit().ast.package);
+        self.visit_user_package(&mut find_refs);
         locations.extend(
             find_refs
                 .locations
@@ -311,6 +337,28 @@ impl<'a> ReferenceFinder<'a> {
             .collect()
     }
 
+    fn visit_user_package(&self, visitor: &mut impl Visitor<'a>) {
+        let package = &self.compilation.user_unit().ast.package;
+        match self.search_span {
+            Some(span) => {
+                for node in &*package.nodes {
+                    match node {
+                        ast::TopLevelNode::Namespace(namespace)
+                            if span.contains(namespace.span.lo) =>
+                        {
+                            visitor.visit_namespace(namespace);
+                        }
+                        ast::TopLevelNode::Stmt(stmt) if span.contains(stmt.span.lo) => {
+                            visitor.visit_stmt(stmt);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            None => visitor.visit_package(package),
+        }
+    }
+
     fn location(&self, location: Span, package_id: PackageId) -> Location {
         into_location(
             self.position_encoding,
@@ -379,6 +427,21 @@ impl<'a> Visitor<'_> for FindFieldRefs<'a> {
                     }
                 }
             }
+            ast::ExprKind::AssignUpdate(container, index, _)
+            | ast::ExprKind::TernOp(ast::TernOp::Update, container, index, _) => {
+                if let Some(field_name) = field_update_name(self.compilation, container, index) {
+                    if field_name.name == self.field_name {
+                        if let Some(Ty::Udt(_, Res::Item(id))) =
+                            self.compilation.get_ty(container.id)
+                        {
+                            if self.eq(id) {
+                                self.locations.push(field_name.span);
+                            }
+                        }
+                    }
+                }
+                walk_expr(self, expr);
+            }
             ast::ExprKind::Struct(struct_name, copy, fields) => {
                 self.visit_path(struct_name);
                 if let Some(copy) = copy {
@@ -411,6 +474,21 @@ impl<'a> FindFieldRefs<'a> {
     }
 }
 
+/// Returns the field name in the index of a `w/` or `w/=` update expression
+/// whose container is a struct, or `None` if the update is an array update.
+pub(crate) fn field_update_name<'a>(
+    compilation: &Compilation,
+    container: &ast::Expr,
+    index: &'a ast::Expr,
+) -> Option<&'a ast::Ident> {
+    match (&*index.kind, compilation.get_ty(container.id)) {
+        (ast::ExprKind::Path(path), Some(Ty::Udt(..))) if path.namespace.is_none() => {
+            Some(&path.name)
+        }
+        _ => None,
+    }
+}
+
 struct FindLocalLocations<'a> {
     node_id: ast::NodeId,
     compilation: &'a Compilation,
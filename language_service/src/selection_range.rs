@@ -0,0 +1,189 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{compilation::Compilation, protocol::SelectionRange, qsc_utils::into_range};
+use qsc::{
+    ast::{
+        self,
+        visit::{self, Visitor},
+    },
+    line_column::{Encoding, Position},
+    Span,
+};
+
+pub(crate) fn get_selection_ranges(
+    compilation: &Compilation,
+    source_name: &str,
+    positions: Vec<Position>,
+    position_encoding: Encoding,
+) -> Vec<SelectionRange> {
+    let unit = compilation.user_unit();
+    let source_span = compilation.package_span_of_source(source_name);
+
+    positions
+        .into_iter()
+        .map(|position| {
+            let offset = compilation.source_position_to_package_offset(
+                source_name,
+                position,
+                position_encoding,
+            );
+
+            let mut finder = SelectionRangeFinder {
+                offset,
+                spans: vec![source_span],
+            };
+            finder.visit_package(&unit.ast.package);
+
+            // Build the chain from the outermost span inwards, so that
+            // the innermost span is the one returned.
+            finder
+                .spans
+                .into_iter()
+                .fold(None, |parent, span| {
+                    Some(SelectionRange {
+                        range: into_range(position_encoding, span, &unit.sources),
+                        parent: parent.map(Box::new),
+                    })
+                })
+                .expect("spans should at least contain the source span")
+        })
+        .collect()
+}
+
+/// Collects the spans of all the AST nodes enclosing an offset,
+/// from the outermost to the innermost.
+struct SelectionRangeFinder {
+    offset: u32,
+    spans: Vec<Span>,
+}
+
+impl SelectionRangeFinder {
+    /// Pushes the span if it encloses the offset and is strictly
+    /// inside the innermost span found so far. Returns whether
+    /// the node's children should be searched.
+    fn push(&mut self, span: Span) -> bool {
+        if !span.touches(self.offset) {
+            return false;
+        }
+        match self.spans.last() {
+            Some(last) if *last == span => true,
+            Some(last) if last.lo > span.lo || last.hi < span.hi => false,
+            _ => {
+                self.spans.push(span);
+                true
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for SelectionRangeFinder {
+    fn visit_namespace(&mut self, namespace: &'a ast::Namespace) {
+        if self.push(namespace.span) {
+            visit::walk_namespace(self, namespace);
+        }
+    }
+
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        if self.push(item.span) {
+            visit::walk_item(self, item);
+        }
+    }
+
+    fn visit_attr(&mut self, attr: &'a ast::Attr) {
+        if self.push(attr.span) {
+            visit::walk_attr(self, attr);
+        }
+    }
+
+    fn visit_ty_def(&mut self, def: &'a ast::TyDef) {
+        if self.push(def.span) {
+            visit::walk_ty_def(self, def);
+        }
+    }
+
+    fn visit_callable_decl(&mut self, decl: &'a ast::CallableDecl) {
+        if self.push(decl.span) {
+            visit::walk_callable_decl(self, decl);
+        }
+    }
+
+    fn visit_struct_decl(&mut self, decl: &'a ast::StructDecl) {
+        if self.push(decl.span) {
+            visit::walk_struct_decl(self, decl);
+        }
+    }
+
+    fn visit_field_def(&mut self, def: &'a ast::FieldDef) {
+        if self.push(def.span) {
+            visit::walk_field_def(self, def);
+        }
+    }
+
+    fn visit_spec_decl(&mut self, decl: &'a ast::SpecDecl) {
+        if self.push(decl.span) {
+            visit::walk_spec_decl(self, decl);
+        }
+    }
+
+    fn visit_functor_expr(&mut self, expr: &'a ast::FunctorExpr) {
+        if self.push(expr.span) {
+            visit::walk_functor_expr(self, expr);
+        }
+    }
+
+    fn visit_ty(&mut self, ty: &'a ast::Ty) {
+        if self.push(ty.span) {
+            visit::walk_ty(self, ty);
+        }
+    }
+
+    fn visit_block(&mut self, block: &'a ast::Block) {
+        if self.push(block.span) {
+            visit::walk_block(self, block);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt) {
+        if self.push(stmt.span) {
+            visit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a ast::Expr) {
+        if self.push(expr.span) {
+            visit::walk_expr(self, expr);
+        }
+    }
+
+    fn visit_field_assign(&mut self, assign: &'a ast::FieldAssign) {
+        if self.push(assign.span) {
+            visit::walk_field_assign(self, assign);
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'a ast::Pat) {
+        if self.push(pat.span) {
+            visit::walk_pat(self, pat);
+        }
+    }
+
+    fn visit_qubit_init(&mut self, init: &'a ast::QubitInit) {
+        if self.push(init.span) {
+            visit::walk_qubit_init(self, init);
+        }
+    }
+
+    fn visit_path(&mut self, path: &'a ast::Path) {
+        if self.push(path.span) {
+            visit::walk_path(self, path);
+        }
+    }
+
+    fn visit_ident(&mut self, ident: &'a ast::Ident) {
+        self.push(ident.span);
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+
+use super::get_selection_ranges;
+use crate::{test_utils::compile_with_fake_stdlib_and_markers, Encoding};
+use qsc::line_column::Range;
+
+/// Checks the selection ranges at the cursor, rendered from
+/// the innermost to the outermost as the selected source text.
+fn check(source_with_markers: &str, expect: &Expect) {
+    let (compilation, cursor_position, _) =
+        compile_with_fake_stdlib_and_markers(source_with_markers);
+    let source = &compilation
+        .user_unit()
+        .sources
+        .find_by_name("<source>")
+        .expect("source should exist")
+        .contents;
+    let selection_ranges = get_selection_ranges(
+        &compilation,
+        "<source>",
+        vec![cursor_position],
+        Encoding::Utf8,
+    );
+    assert_eq!(selection_ranges.len(), 1);
+
+    let mut actual = Vec::new();
+    let mut current = selection_ranges.into_iter().next();
+    while let Some(selection_range) = current {
+        actual.push(text_in_range(source, selection_range.range));
+        current = selection_range.parent.map(|parent| *parent);
+    }
+    expect.assert_debug_eq(&actual);
+}
+
+fn text_in_range(source: &str, range: Range) -> String {
+    let lo = range.start.to_utf8_byte_offset(Encoding::Utf8, source) as usize;
+    let hi = range.end.to_utf8_byte_offset(Encoding::Utf8, source) as usize;
+    source[lo..hi].to_string()
+}
+
+#[test]
+fn identifier_in_call_args() {
+    check(
+        r#"namespace Test {
+    operation Foo(a : Int, b : Int) : Unit {}
+    operation Main() : Unit {
+        Foo(1, a↘bc);
+    }
+}"#,
+        &expect![[r#"
+            [
+                "abc",
+                "(1, abc)",
+                "Foo(1, abc)",
+                "Foo(1, abc);",
+                "{\n        Foo(1, abc);\n    }",
+                "operation Main() : Unit {\n        Foo(1, abc);\n    }",
+                "namespace Test {\n    operation Foo(a : Int, b : Int) : Unit {}\n    operation Main() : Unit {\n        Foo(1, abc);\n    }\n}",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn callable_name() {
+    check(
+        r#"namespace Test {
+    operation F↘oo() : Unit {}
+}"#,
+        &expect![[r#"
+            [
+                "Foo",
+                "operation Foo() : Unit {}",
+                "namespace Test {\n    operation Foo() : Unit {}\n}",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn within_apply() {
+    check(
+        r#"namespace Test {
+    operation Main() : Unit {
+        use q = Qubit();
+        within {
+            H(q);
+        } apply {
+            X(↘q);
+        }
+    }
+}"#,
+        &expect![[r#"
+            [
+                "q",
+                "(q)",
+                "X(q)",
+                "X(q);",
+                "{\n            X(q);\n        }",
+                "within {\n            H(q);\n        } apply {\n            X(q);\n        }",
+                "{\n        use q = Qubit();\n        within {\n            H(q);\n        } apply {\n            X(q);\n        }\n    }",
+                "operation Main() : Unit {\n        use q = Qubit();\n        within {\n            H(q);\n        } apply {\n            X(q);\n        }\n    }",
+                "namespace Test {\n    operation Main() : Unit {\n        use q = Qubit();\n        within {\n            H(q);\n        } apply {\n            X(q);\n        }\n    }\n}",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn whitespace_between_items() {
+    check(
+        r#"namespace Test {
+    operation Foo() : Unit {}
+↘
+    operation Bar() : Unit {}
+}"#,
+        &expect![[r#"
+            [
+                "namespace Test {\n    operation Foo() : Unit {}\n\n    operation Bar() : Unit {}\n}",
+            ]
+        "#]],
+    );
+}
@@ -9,7 +9,9 @@ use super::protocol::{DiagnosticUpdate, NotebookMetadata};
 use crate::protocol::WorkspaceConfigurationUpdate;
 use log::{error, trace};
 use miette::Diagnostic;
-use qsc::{compile::Error, target::Profile, LanguageFeatures, PackageType};
+use qsc::{
+    compile::Error, formatter::FormatOptions, target::Profile, LanguageFeatures, PackageType,
+};
 use qsc_linter::LintConfig;
 use qsc_project::{FileSystemAsync, JSFileEntry};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -88,6 +90,7 @@ pub struct PartialConfiguration {
     pub package_type: Option<PackageType>,
     pub language_features: Option<LanguageFeatures>,
     pub lints_config: Vec<LintConfig>,
+    pub format_options: FormatOptions,
 }
 
 pub(super) struct CompilationStateUpdater<'a> {
@@ -122,6 +125,7 @@ struct LoadManifestResult {
     sources: Vec<(Arc<str>, Arc<str>)>,
     language_features: Option<LanguageFeatures>,
     lints: Vec<LintConfig>,
+    format: FormatOptions,
 }
 
 impl<'a> CompilationStateUpdater<'a> {
@@ -167,6 +171,7 @@ impl<'a> CompilationStateUpdater<'a> {
             sources,
             language_features,
             lints: lints_config,
+            format: format_options,
         } = project.unwrap_or_else(|| {
             // If we are in single file mode, use the file's path as the compilation identifier.
             LoadManifestResult {
@@ -174,6 +179,7 @@ impl<'a> CompilationStateUpdater<'a> {
                 sources: vec![(doc_uri.clone(), text.clone())],
                 language_features: None,
                 lints: Vec::default(),
+                format: FormatOptions::default(),
             }
         });
 
@@ -204,6 +210,7 @@ impl<'a> CompilationStateUpdater<'a> {
             &compilation_uri,
             language_features,
             lints_config,
+            format_options,
         );
 
         self.publish_diagnostics();
@@ -228,6 +235,7 @@ impl<'a> CompilationStateUpdater<'a> {
                             .collect::<LanguageFeatures>(),
                     ),
                     lints: manifest.manifest.lints.clone(),
+                    format: manifest.manifest.format.clone(),
                 }),
                 Err(e) => {
                     error!("failed to load manifest: {e:?}, defaulting to single-file mode");
@@ -250,6 +258,7 @@ impl<'a> CompilationStateUpdater<'a> {
         compilation_uri: &Arc<str>,
         language_features: Option<LanguageFeatures>,
         lints_config: Vec<LintConfig>,
+        format_options: FormatOptions,
     ) {
         self.with_state_mut(|state| {
             // replace source with one from memory if it exists
@@ -265,6 +274,7 @@ impl<'a> CompilationStateUpdater<'a> {
             let compilation_overrides = PartialConfiguration {
                 language_features,
                 lints_config,
+                format_options,
                 ..PartialConfiguration::default()
             };
 
@@ -299,6 +309,7 @@ impl<'a> CompilationStateUpdater<'a> {
                 compilation_uri,
                 language_features,
                 lints: lints_config,
+                format: format_options,
             }) = project
             {
                 self.insert_buffer_aware_compilation(
@@ -306,6 +317,7 @@ impl<'a> CompilationStateUpdater<'a> {
                     &compilation_uri,
                     language_features,
                     lints_config,
+                    format_options,
                 );
             }
         }
@@ -368,7 +380,12 @@ impl<'a> CompilationStateUpdater<'a> {
                 language_features: Some(notebook_metadata.language_features),
                 lints_config: notebook_metadata
                     .manifest
-                    .map(|manifest| manifest.lints)
+                    .as_ref()
+                    .map(|manifest| manifest.lints.clone())
+                    .unwrap_or_default(),
+                format_options: notebook_metadata
+                    .manifest
+                    .map(|manifest| manifest.format)
                     .unwrap_or_default(),
             };
             let configuration = merge_configurations(&notebook_configuration, &configuration);
@@ -545,6 +562,16 @@ impl<'a> CompilationStateUpdater<'a> {
 }
 
 impl CompilationState {
+    /// Returns the formatting options from the manifest of the project
+    /// the document belongs to, or the defaults if there are none.
+    pub(crate) fn get_format_options(&self, uri: &str) -> FormatOptions {
+        self.open_documents
+            .get(uri)
+            .and_then(|doc| self.compilations.get(&doc.compilation))
+            .map(|(_, configuration)| configuration.format_options.clone())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn get_compilation(&self, uri: &str) -> Option<&Compilation> {
         let compilation_uri = &self
             .open_documents
@@ -8,7 +8,7 @@ use super::{CompilationState, CompilationStateUpdater};
 use crate::protocol::{DiagnosticUpdate, NotebookMetadata, WorkspaceConfigurationUpdate};
 use expect_test::{expect, Expect};
 use qsc::{compile::ErrorKind, target::Profile, PackageType};
-use qsc_linter::{AstLint, LintConfig, LintKind, LintLevel};
+use qsc_linter::{AstLint, HirLint, LintConfig, LintKind, LintLevel};
 use qsc_project::{EntryType, JSFileEntry, Manifest, ManifestDescriptor};
 use rustc_hash::FxHashMap;
 use std::{cell::RefCell, fmt::Write, future::ready, rc::Rc, sync::Arc};
@@ -423,6 +423,172 @@ async fn base_profile_rca_errors_are_reported_when_compilation_succeeds() {
     );
 }
 
+#[tokio::test]
+async fn profile_compatibility_lints_replace_rca_errors() {
+    let errors = RefCell::new(Vec::new());
+    let mut updater = new_updater(&errors);
+
+    updater.update_configuration(WorkspaceConfigurationUpdate {
+        target_profile: Some(Profile::Base),
+        package_type: Some(PackageType::Lib),
+        lints_config: Some(vec![LintConfig {
+            kind: LintKind::Hir(HirLint::ProfileCompatibility),
+            level: LintLevel::Error,
+        }]),
+        ..WorkspaceConfigurationUpdate::default()
+    });
+
+    updater
+        .update_document("single/foo.qs", 1, "namespace Test { operation RcaCheck() : Double { use q = Qubit(); mutable x = 1.0; if MResetZ(q) == One { set x = 2.0; } x } }")
+        .await;
+
+    let lints: &[ErrorKind] = &errors.take()[0].2;
+    check_lints(
+        lints,
+        &expect![[r#"
+        [
+            Lint(
+                Lint {
+                    span: Span {
+                        lo: 86,
+                        hi: 103,
+                    },
+                    level: Error,
+                    message: "cannot use a dynamic bool value",
+                    help: "using a bool value that depends on a measurement result is not supported by the configured target profile",
+                    kind: Hir(
+                        ProfileCompatibility,
+                    ),
+                },
+            ),
+            Lint(
+                Lint {
+                    span: Span {
+                        lo: 106,
+                        hi: 117,
+                    },
+                    level: Error,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                    kind: Hir(
+                        ProfileCompatibility,
+                    ),
+                },
+            ),
+            Lint(
+                Lint {
+                    span: Span {
+                        lo: 121,
+                        hi: 122,
+                    },
+                    level: Error,
+                    message: "cannot use a dynamic double value",
+                    help: "using a double value that depends on a measurement result is not supported by the configured target profile",
+                    kind: Hir(
+                        ProfileCompatibility,
+                    ),
+                },
+            ),
+        ]"#]],
+    );
+}
+
+#[tokio::test]
+async fn profile_compatibility_lints_with_call_chain_replace_rca_errors() {
+    let errors = RefCell::new(Vec::new());
+    let mut updater = new_updater(&errors);
+
+    updater.update_configuration(WorkspaceConfigurationUpdate {
+        target_profile: Some(Profile::AdaptiveRI),
+        package_type: Some(PackageType::Lib),
+        lints_config: Some(vec![LintConfig {
+            kind: LintKind::Hir(HirLint::ProfileCompatibility),
+            level: LintLevel::Error,
+        }]),
+        ..WorkspaceConfigurationUpdate::default()
+    });
+
+    updater
+        .update_document("single/foo.qs", 1, "namespace Test { operation Angle(q : Qubit) : Double { if M(q) == One { 1.0 } else { 0.0 } } operation Rotate(q : Qubit) : Unit { let angle = Angle(q); Rx(angle, q); } }")
+        .await;
+
+    check_error_kinds(
+        &errors.take()[0].2,
+        &expect![[r#"
+            [
+                "lint (Error): cannot use a dynamic double value",
+                "lint (Error): cannot use a dynamic double value, introduced through `Angle`",
+                "lint (Error): cannot use a dynamic double value",
+            ]
+        "#]],
+    );
+}
+
+#[tokio::test]
+async fn profile_compatibility_lints_at_warn_level_keep_rca_errors() {
+    let errors = RefCell::new(Vec::new());
+    let mut updater = new_updater(&errors);
+
+    updater.update_configuration(WorkspaceConfigurationUpdate {
+        target_profile: Some(Profile::Base),
+        package_type: Some(PackageType::Lib),
+        lints_config: Some(vec![LintConfig {
+            kind: LintKind::Hir(HirLint::ProfileCompatibility),
+            level: LintLevel::Warn,
+        }]),
+        ..WorkspaceConfigurationUpdate::default()
+    });
+
+    updater
+        .update_document("single/foo.qs", 1, "namespace Test { operation RcaCheck() : Double { use q = Qubit(); mutable x = 1.0; if MResetZ(q) == One { set x = 2.0; } x } }")
+        .await;
+
+    check_error_kinds(
+        &errors.take()[0].2,
+        &expect![[r#"
+        [
+            "pass error: cannot use a dynamic bool value",
+            "pass error: cannot use a dynamic double value",
+            "pass error: cannot use a dynamic double value",
+            "lint (Warn): cannot use a dynamic bool value",
+            "lint (Warn): cannot use a dynamic double value",
+            "lint (Warn): cannot use a dynamic double value",
+        ]
+    "#]],
+    );
+}
+
+#[tokio::test]
+async fn profile_compatibility_lint_allowed_locally_keeps_rca_errors() {
+    let errors = RefCell::new(Vec::new());
+    let mut updater = new_updater(&errors);
+
+    updater.update_configuration(WorkspaceConfigurationUpdate {
+        target_profile: Some(Profile::Base),
+        package_type: Some(PackageType::Lib),
+        lints_config: Some(vec![LintConfig {
+            kind: LintKind::Hir(HirLint::ProfileCompatibility),
+            level: LintLevel::Error,
+        }]),
+        ..WorkspaceConfigurationUpdate::default()
+    });
+
+    updater
+        .update_document("single/foo.qs", 1, "namespace Test { @Allow(\"ProfileCompatibility\") operation RcaCheck() : Double { use q = Qubit(); mutable x = 1.0; if MResetZ(q) == One { set x = 2.0; } x } }")
+        .await;
+
+    check_error_kinds(
+        &errors.take()[0].2,
+        &expect![[r#"
+        [
+            "pass error: cannot use a dynamic bool value",
+            "pass error: cannot use a dynamic double value",
+            "pass error: cannot use a dynamic double value",
+        ]
+    "#]],
+    );
+}
+
 #[tokio::test]
 async fn package_type_update_causes_error() {
     let errors = RefCell::new(Vec::new());
@@ -1804,6 +1970,19 @@ fn check_lints(lints: &[ErrorKind], expected_lints: &Expect) {
     expected_lints.assert_eq(&format!("{lints:#?}"));
 }
 
+/// Checks the kind and message of each error, and the level of each lint.
+fn check_error_kinds(errors: &[ErrorKind], expected: &Expect) {
+    let kinds = errors
+        .iter()
+        .map(|error| match error {
+            ErrorKind::Lint(lint) => format!("lint ({:?}): {}", lint.level, lint.message),
+            ErrorKind::Pass(error) => format!("pass error: {error}"),
+            ErrorKind::Frontend(error) => format!("frontend error: {error}"),
+        })
+        .collect::<Vec<_>>();
+    expected.assert_debug_eq(&kinds);
+}
+
 thread_local! { static TEST_FS: RefCell<FsNode> = RefCell::new(test_fs()) }
 
 fn test_fs() -> FsNode {
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use crate::compilation::{Compilation, CompilationKind};
 use qsc::{
     compile,
+    error::WithSource,
     hir::PackageId,
     incremental::Compiler,
     line_column::{Encoding, Position, Range},
@@ -36,6 +37,18 @@ pub(crate) fn compile_with_fake_stdlib_and_markers_no_cursor(
     (compilation, target_spans.iter().map(|l| l.range).collect())
 }
 
+/// Appends the lints for the user package to the compilation's errors,
+/// as the language service does once a document compiles without errors.
+pub(crate) fn add_lints(compilation: &mut Compilation) {
+    let unit = compilation.user_unit();
+    let lints: Vec<_> =
+        qsc::linter::run_lints_with_dependencies(&compilation.package_store, unit, None)
+            .into_iter()
+            .map(|lint| WithSource::from_map(&unit.sources, compile::ErrorKind::Lint(lint)))
+            .collect();
+    compilation.errors.extend(lints);
+}
+
 pub(crate) fn compile_project_with_fake_stdlib_and_markers(
     sources_with_markers: &[(&str, &str)],
 ) -> (Compilation, String, Position, Vec<Location>) {
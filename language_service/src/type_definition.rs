@@ -0,0 +1,181 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#[cfg(test)]
+mod tests;
+
+use crate::compilation::Compilation;
+use crate::name_locator::{Handler, Locator, LocatorContext};
+use crate::qsc_utils::into_location;
+use qsc::ast::visit::Visitor;
+use qsc::display::Lookup;
+use qsc::hir::{ty::Ty, PackageId};
+use qsc::line_column::{Encoding, Position};
+use qsc::location::Location;
+use qsc::{ast, hir, Span};
+
+/// Finds the declaration of the user-defined type of the symbol at the given position.
+/// Array types are unwrapped to their item type.
+pub(crate) fn get_type_definition(
+    compilation: &Compilation,
+    source_name: &str,
+    position: Position,
+    position_encoding: Encoding,
+) -> Option<Location> {
+    let offset =
+        compilation.source_position_to_package_offset(source_name, position, position_encoding);
+    let user_ast_package = &compilation.user_unit().ast.package;
+
+    let mut type_definition_finder = TypeDefinitionFinder {
+        position_encoding,
+        compilation,
+        definition: None,
+    };
+
+    let mut locator = Locator::new(&mut type_definition_finder, offset, compilation);
+    locator.visit_package(user_ast_package);
+
+    type_definition_finder.definition
+}
+
+struct TypeDefinitionFinder<'a> {
+    position_encoding: Encoding,
+    compilation: &'a Compilation,
+    definition: Option<Location>,
+}
+
+impl<'a> Handler<'a> for TypeDefinitionFinder<'a> {
+    fn at_callable_def(
+        &mut self,
+        _: &LocatorContext<'a>,
+        _: &'a ast::Ident,
+        _: &'a ast::CallableDecl,
+    ) {
+    }
+
+    fn at_callable_ref(&mut self, _: &'a ast::Path, _: &'_ hir::ItemId, _: &'a hir::CallableDecl) {}
+
+    fn at_type_param_def(
+        &mut self,
+        _: &LocatorContext<'a>,
+        _: &'a ast::Ident,
+        _: hir::ty::ParamId,
+    ) {
+    }
+
+    fn at_type_param_ref(
+        &mut self,
+        _: &LocatorContext<'a>,
+        _: &'a ast::Ident,
+        _: hir::ty::ParamId,
+        _: &'a ast::Ident,
+    ) {
+    }
+
+    fn at_new_type_def(&mut self, _: &LocatorContext<'a>, _: &'a ast::Ident, _: &'a ast::TyDef) {}
+
+    fn at_struct_def(&mut self, _: &LocatorContext<'a>, _: &'a ast::Ident, _: &'a ast::StructDecl) {
+    }
+
+    fn at_new_type_ref(
+        &mut self,
+        _: &'a ast::Path,
+        item_id: &'_ hir::ItemId,
+        type_name: &'a hir::Ident,
+        _: &'a hir::ty::Udt,
+    ) {
+        self.definition = Some(self.location(
+            type_name.span,
+            item_id.package.expect("package id should be resolved"),
+        ));
+    }
+
+    fn at_field_def(
+        &mut self,
+        context: &LocatorContext<'a>,
+        field_name: &'a ast::Ident,
+        _: &'a ast::Ty,
+    ) {
+        let item_id = context
+            .current_udt_id
+            .expect("field definition should be inside a udt");
+        let (item, _, resolved_item_id) = self
+            .compilation
+            .resolve_item_relative_to_user_package(item_id);
+        if let hir::ItemKind::Ty(_, udt) = &item.kind {
+            if let Some(field) = udt.find_field_by_name(&field_name.name) {
+                self.definition = self.udt_location(
+                    &field.ty,
+                    resolved_item_id
+                        .package
+                        .expect("package id should be resolved"),
+                );
+            }
+        }
+    }
+
+    fn at_field_ref(
+        &mut self,
+        _: &'a ast::Ident,
+        item_id: &'_ hir::ItemId,
+        field_def: &'a hir::ty::UdtField,
+    ) {
+        self.definition = self.udt_location(
+            &field_def.ty,
+            item_id.package.expect("package id should be resolved"),
+        );
+    }
+
+    fn at_local_def(&mut self, _: &LocatorContext<'a>, _: &'a ast::Ident, pat: &'a ast::Pat) {
+        if let Some(ty) = self.compilation.get_ty(pat.id) {
+            self.definition = self.udt_location(ty, self.compilation.user_package_id);
+        }
+    }
+
+    fn at_local_ref(
+        &mut self,
+        _: &LocatorContext<'a>,
+        _: &'a ast::Path,
+        node_id: &'a ast::NodeId,
+        _: &'a ast::Ident,
+    ) {
+        if let Some(ty) = self.compilation.get_ty(*node_id) {
+            self.definition = self.udt_location(ty, self.compilation.user_package_id);
+        }
+    }
+}
+
+impl TypeDefinitionFinder<'_> {
+    /// Returns the location of the declaration of `ty` if it is a user-defined type.
+    /// `package_id` is the package that `ty` was found in, since type resolutions
+    /// are relative to their own package.
+    fn udt_location(&self, ty: &Ty, package_id: PackageId) -> Option<Location> {
+        match ty {
+            Ty::Array(item_ty) => self.udt_location(item_ty, package_id),
+            Ty::Udt(_, res @ hir::Res::Item(_)) => {
+                let (item, resolved_item_id) = self.compilation.resolve_item_res(package_id, res);
+                match &item.kind {
+                    hir::ItemKind::Ty(type_name, _) => Some(
+                        self.location(
+                            type_name.span,
+                            resolved_item_id
+                                .package
+                                .expect("package id should be resolved"),
+                        ),
+                    ),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn location(&self, location: Span, package_id: PackageId) -> Location {
+        into_location(
+            self.position_encoding,
+            self.compilation,
+            location,
+            package_id,
+        )
+    }
+}
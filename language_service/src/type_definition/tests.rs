@@ -0,0 +1,218 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+use qsc::location::Location;
+
+use super::get_type_definition;
+use crate::{test_utils::compile_with_fake_stdlib_and_markers, Encoding};
+
+/// Asserts that the type definition given at the cursor position matches the expected range.
+/// The cursor position is indicated by a `↘` marker in the source text.
+/// The expected type definition range is indicated by `◉` markers in the source text.
+fn assert_type_definition(source_with_markers: &str) {
+    let (compilation, cursor_position, target_spans) =
+        compile_with_fake_stdlib_and_markers(source_with_markers);
+    let actual_definition =
+        get_type_definition(&compilation, "<source>", cursor_position, Encoding::Utf8);
+    let expected_definition = if target_spans.is_empty() {
+        None
+    } else {
+        Some(Location {
+            source: "<source>".into(),
+            range: target_spans[0],
+        })
+    };
+    assert_eq!(&expected_definition, &actual_definition);
+}
+
+fn check(source_with_markers: &str, expect: &Expect) {
+    let (compilation, cursor_position, _) =
+        compile_with_fake_stdlib_and_markers(source_with_markers);
+    let actual_definition =
+        get_type_definition(&compilation, "<source>", cursor_position, Encoding::Utf8);
+    expect.assert_debug_eq(&actual_definition);
+}
+
+#[test]
+fn local_def_newtype() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        newtype ◉Pair◉ = (First : Int, Second : Int);
+        operation Foo() : Unit {
+            let p↘air = Pair(1, 2);
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn local_ref_struct() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        struct ◉Pair◉ { First : Int, Second : Int }
+        operation Foo() : Unit {
+            let pair = new Pair { First = 1, Second = 2 };
+            let x = p↘air;
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn param() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        struct ◉Pair◉ { First : Int, Second : Int }
+        function Foo(p↘air : Pair) : Unit {}
+    }
+    "#,
+    );
+}
+
+#[test]
+fn array_of_udt() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        newtype ◉Pair◉ = (First : Int, Second : Int);
+        function Foo(pa↘irs : Pair[]) : Unit {}
+    }
+    "#,
+    );
+}
+
+#[test]
+fn field_def() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        struct ◉Inner◉ { Value : Int }
+        struct Outer { In↘ner : Inner }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn field_ref() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        struct ◉Inner◉ { Value : Int }
+        struct Outer { Inner : Inner }
+        function Foo(outer : Outer) : Unit {
+            let x = outer::In↘ner;
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn struct_ref() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        struct ◉Pair◉ { First : Int, Second : Int }
+        function Foo() : Unit {
+            let pair = new Pa↘ir { First = 1, Second = 2 };
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn primitive_type() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        function Foo() : Unit {
+            let ↘x = 3;
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn callable_ref() {
+    assert_type_definition(
+        r#"
+    namespace Test {
+        function Bar() : Unit {}
+        function Foo() : Unit {
+            B↘ar();
+        }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn std_udt() {
+    check(
+        r#"
+    namespace Test {
+        open FakeStdLib;
+        function Foo(u↘dt : Udt) : Unit {}
+    }
+    "#,
+        &expect![[r#"
+            Some(
+                Location {
+                    source: "qsharp-library-source:<std>",
+                    range: Range {
+                        start: Position {
+                            line: 4,
+                            column: 24,
+                        },
+                        end: Position {
+                            line: 4,
+                            column: 27,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn std_struct_field_ref() {
+    check(
+        r#"
+    namespace Test {
+        open FakeStdLib;
+        function Foo(wrapper : StructWrapper) : Unit {
+            let x = wrapper::in↘ner;
+        }
+    }
+    "#,
+        &expect![[r#"
+            Some(
+                Location {
+                    source: "qsharp-library-source:<std>",
+                    range: Range {
+                        start: Position {
+                            line: 16,
+                            column: 23,
+                        },
+                        end: Position {
+                            line: 16,
+                            column: 33,
+                        },
+                    },
+                },
+            )
+        "#]],
+    );
+}
@@ -9,6 +9,7 @@ mod canon;
 mod convert;
 mod core;
 mod diagnostics;
+mod doctests;
 mod intrinsic;
 mod logical;
 mod math;
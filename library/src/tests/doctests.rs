@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use miette::Report;
+use qsc::{doctest::run_std_doctests, TargetCapabilityFlags};
+
+#[test]
+fn std_doc_examples_run() {
+    let failures = run_std_doctests(TargetCapabilityFlags::all());
+    let reports = failures
+        .into_iter()
+        .map(|failure| format!("{:?}", Report::new(failure)))
+        .collect::<Vec<_>>();
+    assert!(reports.is_empty(), "{}", reports.join("\n"));
+}
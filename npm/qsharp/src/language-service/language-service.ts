@@ -5,11 +5,14 @@ import type {
   ICodeAction,
   ICodeLens,
   ICompletionList,
+  IDocumentHighlight,
+  IFoldingRange,
   IHover,
   ILocation,
   INotebookMetadata,
   IPosition,
   IRange,
+  ISelectionRange,
   ISignatureHelp,
   ITextEdit,
   IWorkspaceConfiguration,
@@ -57,7 +60,16 @@ export interface ILanguageService {
     documentUri: string,
     position: IPosition,
   ): Promise<ICompletionList>;
+  getDocumentHighlights(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<IDocumentHighlight[]>;
+  getDiagnostics(documentUri: string): Promise<VSDiagnostic[]>;
   getFormatChanges(documentUri: string): Promise<ITextEdit[]>;
+  getRangeFormatChanges(
+    documentUri: string,
+    range: IRange,
+  ): Promise<ITextEdit[]>;
   getHover(
     documentUri: string,
     position: IPosition,
@@ -66,6 +78,14 @@ export interface ILanguageService {
     documentUri: string,
     position: IPosition,
   ): Promise<ILocation | undefined>;
+  getTypeDefinition(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ILocation | undefined>;
+  getImplementation(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ILocation | undefined>;
   getReferences(
     documentUri: string,
     position: IPosition,
@@ -85,6 +105,11 @@ export interface ILanguageService {
     position: IPosition,
   ): Promise<ITextEdit | undefined>;
   getCodeLenses(documentUri: string): Promise<ICodeLens[]>;
+  getFoldingRanges(documentUri: string): Promise<IFoldingRange[]>;
+  getSelectionRanges(
+    documentUri: string,
+    positions: IPosition[],
+  ): Promise<ISelectionRange[]>;
 
   dispose(): Promise<void>;
 
@@ -178,10 +203,28 @@ export class QSharpLanguageService implements ILanguageService {
     return this.languageService.get_completions(documentUri, position);
   }
 
+  async getDocumentHighlights(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<IDocumentHighlight[]> {
+    return this.languageService.get_document_highlights(documentUri, position);
+  }
+
+  async getDiagnostics(documentUri: string): Promise<VSDiagnostic[]> {
+    return this.languageService.get_diagnostics(documentUri);
+  }
+
   async getFormatChanges(documentUri: string): Promise<ITextEdit[]> {
     return this.languageService.get_format_changes(documentUri);
   }
 
+  async getRangeFormatChanges(
+    documentUri: string,
+    range: IRange,
+  ): Promise<ITextEdit[]> {
+    return this.languageService.get_range_format_changes(documentUri, range);
+  }
+
   async getHover(
     documentUri: string,
     position: IPosition,
@@ -196,6 +239,20 @@ export class QSharpLanguageService implements ILanguageService {
     return this.languageService.get_definition(documentUri, position);
   }
 
+  async getTypeDefinition(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ILocation | undefined> {
+    return this.languageService.get_type_definition(documentUri, position);
+  }
+
+  async getImplementation(
+    documentUri: string,
+    position: IPosition,
+  ): Promise<ILocation | undefined> {
+    return this.languageService.get_implementation(documentUri, position);
+  }
+
   async getReferences(
     documentUri: string,
     position: IPosition,
@@ -234,6 +291,17 @@ export class QSharpLanguageService implements ILanguageService {
     return this.languageService.get_code_lenses(documentUri);
   }
 
+  async getFoldingRanges(documentUri: string): Promise<IFoldingRange[]> {
+    return this.languageService.get_folding_ranges(documentUri);
+  }
+
+  async getSelectionRanges(
+    documentUri: string,
+    positions: IPosition[],
+  ): Promise<ISelectionRange[]> {
+    return this.languageService.get_selection_ranges(documentUri, positions);
+  }
+
   async dispose() {
     this.languageService.stop_background_work();
     await this.backgroundWork;
@@ -291,14 +359,21 @@ export const languageServiceProtocol: ServiceProtocol<
     closeNotebookDocument: "request",
     getCodeActions: "request",
     getCompletions: "request",
+    getDocumentHighlights: "request",
+    getDiagnostics: "request",
     getFormatChanges: "request",
+    getRangeFormatChanges: "request",
     getHover: "request",
     getDefinition: "request",
+    getTypeDefinition: "request",
+    getImplementation: "request",
     getReferences: "request",
     getSignatureHelp: "request",
     getRename: "request",
     prepareRename: "request",
     getCodeLenses: "request",
+    getFoldingRanges: "request",
+    getSelectionRanges: "request",
     dispose: "request",
     addEventListener: "addEventListener",
     removeEventListener: "removeEventListener",
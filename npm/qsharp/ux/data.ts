@@ -8,6 +8,7 @@ export type ReData = {
   physicalCountsFormatted: any;
   logicalQubit: any;
   tfactory: any;
+  magicStateFactories?: any;
   errorBudget: any;
   logicalCounts: any;
   frontierEntries: FrontierEntry[];
@@ -20,6 +21,7 @@ export type SingleEstimateResult = {
   physicalCountsFormatted: any;
   logicalQubit: any;
   tfactory: any;
+  magicStateFactories?: any;
   errorBudget: any;
   logicalCounts: any;
 };
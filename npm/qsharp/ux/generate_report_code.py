@@ -2,10 +2,16 @@
 # Licensed under the MIT License.
 
 # This script generates the code for the report page from the output_data.md file.
-# To run, simply execute `python generate_report_code.py` in the npm/ux folder.
-# copy the output and paste it into the report.tsx file.
+# To run, simply execute `python generate_report_code.py`, the output_data.md file
+# is read from the folder of this script.
+# copy the output and paste it into the report.ts file, and format it with prettier.
 # It provides a code for the CreateReport function.
+#
+# An entry is only added to the report if the condition named after `if` in its
+# path comment holds, e.g., `[//]: # "path/to/value if lattice_surgery"`.  The
+# conditions are defined in `conditions` below.
 
+import os
 import re
 
 parse = False
@@ -17,6 +23,7 @@ entries = []
 
 label = ""
 path = ""
+condition = ""
 value = ""
 description = ""
 explanation = ""
@@ -26,6 +33,8 @@ assumptions = []
 ignore_paths = [
     "floquet_code",
     "surface_code",
+    "color_code",
+    "qldpc_code",
     "qubit_gate_ns_e3",
     "qubit_gate_ns_e4",
     "qubit_gate_us_e3",
@@ -34,10 +43,30 @@ ignore_paths = [
     "qubit_maj_ns_e6",
 ]
 
+conditions = {
+    "psspc": 'result.jobParams.layout?.name != "latticeSurgery"',
+    "lattice_surgery": 'result.jobParams.layout?.name == "latticeSurgery"',
+    "threshold_formula": "result.jobParams.qecScheme.logicalErrorRate == null",
+    "logical_error_rate_formula": "result.jobParams.qecScheme.logicalErrorRate != null",
+    "logical_qubits_formula": "result.jobParams.qecScheme.logicalQubitsPerPatch != null",
+    "t_factory": "result.magicStateFactories?.t != null",
+    "ccz_factory": "result.magicStateFactories?.ccz != null",
+    "rotation_factory": "result.magicStateFactories?.rotation != null",
+}
+
+group_conditions = {
+    "T factory parameters": "result.tfactory != null",
+    "Logical layout": conditions["lattice_surgery"],
+    "Magic state factories": "result.magicStateFactories != null",
+}
+
 path_map = {
     "errorBudget/rotations": "result.errorBudget.rotations",
+    "jobParams/layout/magicStatePorts": "result.jobParams.layout.magicStatePorts",
+    "jobParams/layout/routingFraction": "result.jobParams.layout.routingFraction",
     "jobParams/qecScheme/crossingPrefactor": "result.jobParams.qecScheme.crossingPrefactor",
     "jobParams/qecScheme/errorCorrectionThreshold": "result.jobParams.qecScheme.errorCorrectionThreshold",
+    "jobParams/qecScheme/logicalErrorRate": "result.jobParams.qecScheme.logicalErrorRate",
     "jobParams/qecScheme/logicalCycleTime": "result.jobParams.qecScheme.logicalCycleTime",
     "jobParams/qecScheme/physicalQubitsPerLogicalQubit": "result.jobParams.qecScheme.physicalQubitsPerLogicalQubit",
     "jobParams/qubitParams/tGateErrorRate": "result.jobParams.qubitParams.tGateErrorRate",
@@ -50,8 +79,30 @@ path_map = {
     "logicalCounts/tCount": "numberFormat.format(result.logicalCounts.tCount)",
     "logicalQubit/codeDistance": "result.logicalQubit.codeDistance",
     "logicalQubit/logicalCyclesPerSecond": "numberFormatF64.format(result.physicalCounts.breakdown.clockFrequency)",
+    "logicalQubit/logicalErrorRate": "result.logicalQubit.logicalErrorRate",
     "logicalQubit/logicalCycleTime": "numberFormat.format(result.logicalQubit.logicalCycleTime)",
     "logicalQubit/physicalQubits": "numberFormat.format(result.logicalQubit.physicalQubits)",
+    "magicStateFactories/t/factory/logicalErrorRate": "result.magicStateFactories.t.factory.logicalErrorRate.toExponential(2)",
+    "magicStateFactories/t/factory/numRounds": "result.magicStateFactories.t.factory.numRounds",
+    "magicStateFactories/t/factory/physicalQubits": "numberFormat.format(result.magicStateFactories.t.factory.physicalQubits)",
+    "magicStateFactories/t/numFactories": "numberFormat.format(result.magicStateFactories.t.numFactories)",
+    "magicStateFactories/t/numFactoryRuns": "numberFormat.format(result.magicStateFactories.t.numFactoryRuns)",
+    "magicStateFactories/t/numStates": "numberFormat.format(result.magicStateFactories.t.numStates)",
+    "magicStateFactories/t/requiredLogicalErrorRate": "result.magicStateFactories.t.requiredLogicalErrorRate.toExponential(2)",
+    "magicStateFactories/ccz/factory/logicalErrorRate": "result.magicStateFactories.ccz.factory.logicalErrorRate.toExponential(2)",
+    "magicStateFactories/ccz/factory/numRounds": "result.magicStateFactories.ccz.factory.numRounds",
+    "magicStateFactories/ccz/factory/physicalQubits": "numberFormat.format(result.magicStateFactories.ccz.factory.physicalQubits)",
+    "magicStateFactories/ccz/numFactories": "numberFormat.format(result.magicStateFactories.ccz.numFactories)",
+    "magicStateFactories/ccz/numFactoryRuns": "numberFormat.format(result.magicStateFactories.ccz.numFactoryRuns)",
+    "magicStateFactories/ccz/numStates": "numberFormat.format(result.magicStateFactories.ccz.numStates)",
+    "magicStateFactories/ccz/requiredLogicalErrorRate": "result.magicStateFactories.ccz.requiredLogicalErrorRate.toExponential(2)",
+    "magicStateFactories/rotation/factory/logicalErrorRate": "result.magicStateFactories.rotation.factory.logicalErrorRate.toExponential(2)",
+    "magicStateFactories/rotation/factory/numRounds": "result.magicStateFactories.rotation.factory.numRounds",
+    "magicStateFactories/rotation/factory/physicalQubits": "numberFormat.format(result.magicStateFactories.rotation.factory.physicalQubits)",
+    "magicStateFactories/rotation/numFactories": "numberFormat.format(result.magicStateFactories.rotation.numFactories)",
+    "magicStateFactories/rotation/numFactoryRuns": "numberFormat.format(result.magicStateFactories.rotation.numFactoryRuns)",
+    "magicStateFactories/rotation/numStates": "numberFormat.format(result.magicStateFactories.rotation.numStates)",
+    "magicStateFactories/rotation/requiredLogicalErrorRate": "result.magicStateFactories.rotation.requiredLogicalErrorRate.toExponential(2)",
     "physicalCounts/breakdown/algorithmicLogicalDepth": "numberFormat.format(result.physicalCounts.breakdown.algorithmicLogicalDepth)",
     "physicalCounts/breakdown/algorithmicLogicalQubits": "numberFormat.format(result.physicalCounts.breakdown.algorithmicLogicalQubits)",
     "physicalCounts/breakdown/cliffordErrorRate": "result.physicalCounts.breakdown.cliffordErrorRate",
@@ -101,13 +152,19 @@ def add_group():
     global always_visible, entries, title
 
     if len(entries) != 0:
-        if title == "T factory parameters":
-            print("    if (result.tfactory != null) {")
+        if title in group_conditions:
+            print(f"    if ({group_conditions[title]}) {{")
 
         always_visible_str = "true" if always_visible else "false"
         print("    entries = [];")
-        for path, label, description, explanation in entries:
-            if path in [
+        for path, condition, label, description, explanation in entries:
+            if condition != "":
+                print(f"    if ({conditions[condition]}) {{")
+                print(
+                    f'        entries.push({{path: "{path}", label: "{label}", description: {description}, explanation: {explanation}}});'
+                )
+                print("    }")
+            elif path in [
                 "jobParams/qubitParams/oneQubitGateTime",
                 "jobParams/qubitParams/twoQubitGateTime",
                 "jobParams/qubitParams/oneQubitGateErrorRate",
@@ -140,7 +197,7 @@ def add_group():
         )
         print()
 
-        if title == "T factory parameters":
+        if title in group_conditions:
             print("    }")
 
         always_visible = False
@@ -190,7 +247,8 @@ def create_fmt_string(string):
     return f"`{string}`"
 
 
-with open("output_data.md", "r") as f:
+output_data_path = os.path.join(os.path.dirname(__file__), "output_data.md")
+with open(output_data_path, "r") as f:
     for line in f.readlines():
         line = line.strip()
 
@@ -212,7 +270,7 @@ with open("output_data.md", "r") as f:
         elif line.startswith("### "):
             label = line[4:].strip()
         elif line.startswith("[//]: #"):
-            path = line[9:-1]
+            path, _, condition = line[9:-1].partition(" if ")
         elif line.startswith("_"):
             description = line[1:-1]
         elif line.startswith("-"):
@@ -223,6 +281,7 @@ with open("output_data.md", "r") as f:
             entries.append(
                 (
                     path,
+                    condition,
                     label,
                     create_fmt_string(description),
                     create_fmt_string(explanation),
@@ -73,36 +73,84 @@ export function CreateReport(result: SingleEstimateResult): ReportData {
   });
 
   entries = [];
-  entries.push({
-    path: "physicalCountsFormatted/algorithmicLogicalQubits",
-    label: "Logical algorithmic qubits",
-    description: `Number of logical qubits for the algorithm after layout`,
-    explanation: `Laying out the logical qubits in the presence of nearest-neighbor constraints requires additional logical qubits. In particular, to layout the $Q_{\\rm alg} = ${numberFormat.format(
-      result.logicalCounts.numQubits,
-    )}$ logical qubits in the input algorithm, we require in total $2 \\cdot Q_{\\rm alg} + \\lceil \\sqrt{8 \\cdot Q_{\\rm alg}}\\rceil + 1 = ${numberFormat.format(
-      result.physicalCounts.breakdown.algorithmicLogicalQubits,
-    )}$ logical qubits.`,
-  });
-  entries.push({
-    path: "physicalCountsFormatted/algorithmicLogicalDepth",
-    label: "Algorithmic depth",
-    description: `Number of logical cycles for the algorithm`,
-    explanation: `To execute the algorithm using _Parallel Synthesis Sequential Pauli Computation_ (PSSPC), operations are scheduled in terms of multi-qubit Pauli measurements, for which assume an execution time of one logical cycle. Based on the input algorithm, we require one multi-qubit measurement for the ${numberFormat.format(
-      result.logicalCounts.measurementCount,
-    )} single-qubit measurements, the ${numberFormat.format(
-      result.logicalCounts.rotationCount,
-    )} arbitrary single-qubit rotations, and the ${numberFormat.format(
-      result.logicalCounts.tCount,
-    )} T gates, three multi-qubit measurements for each of the ${numberFormat.format(
-      result.logicalCounts.cczCount,
-    )} CCZ and ${numberFormat.format(
-      result.logicalCounts.ccixCount,
-    )} CCiX gates in the input program, as well as ${
-      result.physicalCountsFormatted.numTsPerRotation
-    } multi-qubit measurements for each of the ${numberFormat.format(
-      result.logicalCounts.rotationDepth,
-    )} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation.`,
-  });
+  if (result.jobParams.layout?.name != "latticeSurgery") {
+    entries.push({
+      path: "physicalCountsFormatted/algorithmicLogicalQubits",
+      label: "Logical algorithmic qubits",
+      description: `Number of logical qubits for the algorithm after layout`,
+      explanation: `Laying out the logical qubits in the presence of nearest-neighbor constraints requires additional logical qubits. In particular, to layout the $Q_{\\rm alg} = ${numberFormat.format(
+        result.logicalCounts.numQubits,
+      )}$ logical qubits in the input algorithm, we require in total $2 \\cdot Q_{\\rm alg} + \\lceil \\sqrt{8 \\cdot Q_{\\rm alg}}\\rceil + 1 = ${numberFormat.format(
+        result.physicalCounts.breakdown.algorithmicLogicalQubits,
+      )}$ logical qubits.`,
+    });
+  }
+  if (result.jobParams.layout?.name != "latticeSurgery") {
+    entries.push({
+      path: "physicalCountsFormatted/algorithmicLogicalDepth",
+      label: "Algorithmic depth",
+      description: `Number of logical cycles for the algorithm`,
+      explanation: `To execute the algorithm using _Parallel Synthesis Sequential Pauli Computation_ (PSSPC), operations are scheduled in terms of multi-qubit Pauli measurements, for which assume an execution time of one logical cycle. Based on the input algorithm, we require one multi-qubit measurement for the ${numberFormat.format(
+        result.logicalCounts.measurementCount,
+      )} single-qubit measurements, the ${numberFormat.format(
+        result.logicalCounts.rotationCount,
+      )} arbitrary single-qubit rotations, and the ${numberFormat.format(
+        result.logicalCounts.tCount,
+      )} T gates, three multi-qubit measurements for each of the ${numberFormat.format(
+        result.logicalCounts.cczCount,
+      )} CCZ and ${numberFormat.format(
+        result.logicalCounts.ccixCount,
+      )} CCiX gates in the input program, as well as ${
+        result.physicalCountsFormatted.numTsPerRotation
+      } multi-qubit measurements for each of the ${numberFormat.format(
+        result.logicalCounts.rotationDepth,
+      )} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation.`,
+    });
+  }
+  if (result.jobParams.layout?.name == "latticeSurgery") {
+    entries.push({
+      path: "physicalCountsFormatted/algorithmicLogicalQubits",
+      label: "Logical algorithmic qubits",
+      description: `Number of logical qubits for the algorithm after layout`,
+      explanation: `In the lattice surgery layout, the $Q_{\\rm alg} = ${numberFormat.format(
+        result.logicalCounts.numQubits,
+      )}$ logical qubits in the input algorithm are placed on data tiles, and a fraction $f = ${
+        result.jobParams.layout.routingFraction
+      }$ of all tiles is reserved as routing space for lattice surgery operations. We therefore require in total $\\lceil Q_{\\rm alg} / (1 - f) \\rceil = ${numberFormat.format(
+        result.physicalCounts.breakdown.algorithmicLogicalQubits,
+      )}$ logical qubits.`,
+    });
+  }
+  if (result.jobParams.layout?.name == "latticeSurgery") {
+    entries.push({
+      path: "physicalCountsFormatted/algorithmicLogicalDepth",
+      label: "Algorithmic depth",
+      description: `Number of logical cycles for the algorithm`,
+      explanation: `In the lattice surgery layout, operations are scheduled in terms of lattice surgery operations through the routing space, for which we assume an execution time of one logical cycle, and up to ${
+        result.jobParams.layout.magicStatePorts
+      } magic states are consumed in parallel in the same logical cycle. Based on the input algorithm, we require one logical cycle for each of the ${numberFormat.format(
+        result.logicalCounts.measurementCount,
+      )} single-qubit measurements, one logical cycle for each group of up to ${
+        result.jobParams.layout.magicStatePorts
+      } of the ${numberFormat.format(
+        result.logicalCounts.tCount,
+      )} T gates, three logical cycles for each group of up to ${
+        result.jobParams.layout.magicStatePorts
+      } of the ${numberFormat.format(
+        result.logicalCounts.cczCount,
+      )} CCZ and ${numberFormat.format(
+        result.logicalCounts.ccixCount,
+      )} CCiX gates, as well as ${
+        result.physicalCountsFormatted.numTsPerRotation
+      } logical cycles for each group of up to ${
+        result.jobParams.layout.magicStatePorts
+      } of the ${numberFormat.format(
+        result.logicalCounts.rotationCount,
+      )} arbitrary single-qubit rotations, but at least for each of the ${numberFormat.format(
+        result.logicalCounts.rotationDepth,
+      )} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation.`,
+    });
+  }
   entries.push({
     path: "physicalCountsFormatted/logicalDepth",
     label: "Logical depth",
@@ -234,14 +282,24 @@ export function CreateReport(result: SingleEstimateResult): ReportData {
     path: "jobParams/qecScheme/name",
     label: "QEC scheme",
     description: `Name of QEC scheme`,
-    explanation: `You can load pre-defined QEC schemes by using the name \`surface_code\` or \`floquet_code\`. The latter only works with Majorana qubits.`,
-  });
-  entries.push({
-    path: "logicalQubit/codeDistance",
-    label: "Code distance",
-    description: `Required code distance for error correction`,
-    explanation: `The code distance is the smallest odd integer greater or equal to $\\dfrac{2\\log(${result.jobParams.qecScheme.crossingPrefactor} / ${result.physicalCounts.breakdown.requiredLogicalQubitErrorRate})}{\\log(${result.jobParams.qecScheme.errorCorrectionThreshold}/${result.physicalCounts.breakdown.cliffordErrorRate})} - 1$`,
+    explanation: `You can load pre-defined QEC schemes by using the name \`surface_code\`, \`floquet_code\`, \`color_code\`, or \`qldpc_code\`. The \`floquet_code\` scheme only works with Majorana qubits, whereas the \`color_code\` and \`qldpc_code\` schemes only work with gate-based qubits.`,
   });
+  if (result.jobParams.qecScheme.logicalErrorRate == null) {
+    entries.push({
+      path: "logicalQubit/codeDistance",
+      label: "Code distance",
+      description: `Required code distance for error correction`,
+      explanation: `The code distance is the smallest odd integer greater or equal to $\\dfrac{2\\log(${result.jobParams.qecScheme.crossingPrefactor} / ${result.physicalCounts.breakdown.requiredLogicalQubitErrorRate})}{\\log(${result.jobParams.qecScheme.errorCorrectionThreshold}/${result.physicalCounts.breakdown.cliffordErrorRate})} - 1$`,
+    });
+  }
+  if (result.jobParams.qecScheme.logicalErrorRate != null) {
+    entries.push({
+      path: "logicalQubit/codeDistance",
+      label: "Code distance",
+      description: `Required code distance for error correction`,
+      explanation: `The code distance is the smallest odd integer for which the logical error rate computed using the formula ${result.jobParams.qecScheme.logicalErrorRate}, divided by the number of logical qubits in a patch, is at most ${result.physicalCounts.breakdown.requiredLogicalQubitErrorRate}.`,
+    });
+  }
   entries.push({
     path: "physicalCountsFormatted/physicalQubitsPerLogicalQubit",
     label: "Physical qubits",
@@ -254,12 +312,22 @@ export function CreateReport(result: SingleEstimateResult): ReportData {
     description: `Duration of a logical cycle in nanoseconds`,
     explanation: `The runtime of one logical cycle in nanoseconds is evaluated using the formula ${result.jobParams.qecScheme.logicalCycleTime} that can be user-specified.`,
   });
-  entries.push({
-    path: "physicalCountsFormatted/logicalErrorRate",
-    label: "Logical qubit error rate",
-    description: `Logical qubit error rate`,
-    explanation: `The logical qubit error rate is computed as $${result.jobParams.qecScheme.crossingPrefactor} \\cdot \\left(\\dfrac{${result.physicalCounts.breakdown.cliffordErrorRate}}{${result.jobParams.qecScheme.errorCorrectionThreshold}}\\right)^\\frac{${result.logicalQubit.codeDistance} + 1}{2}$`,
-  });
+  if (result.jobParams.qecScheme.logicalErrorRate == null) {
+    entries.push({
+      path: "physicalCountsFormatted/logicalErrorRate",
+      label: "Logical qubit error rate",
+      description: `Logical qubit error rate`,
+      explanation: `The logical qubit error rate is computed as $${result.jobParams.qecScheme.crossingPrefactor} \\cdot \\left(\\dfrac{${result.physicalCounts.breakdown.cliffordErrorRate}}{${result.jobParams.qecScheme.errorCorrectionThreshold}}\\right)^\\frac{${result.logicalQubit.codeDistance} + 1}{2}$`,
+    });
+  }
+  if (result.jobParams.qecScheme.logicalErrorRate != null) {
+    entries.push({
+      path: "physicalCountsFormatted/logicalErrorRate",
+      label: "Logical qubit error rate",
+      description: `Logical qubit error rate`,
+      explanation: `The logical qubit error rate is computed using the formula ${result.jobParams.qecScheme.logicalErrorRate} that can be user-specified, for a physical error rate of ${result.physicalCounts.breakdown.cliffordErrorRate} and a code distance of ${result.logicalQubit.codeDistance}.`,
+    });
+  }
   entries.push({
     path: "jobParams/qecScheme/crossingPrefactor",
     label: "Crossing prefactor",
@@ -288,6 +356,22 @@ export function CreateReport(result: SingleEstimateResult): ReportData {
       result.logicalQubit.physicalQubits,
     )}.`,
   });
+  if (result.jobParams.qecScheme.logicalQubitsPerPatch != null) {
+    entries.push({
+      path: "jobParams/qecScheme/logicalQubitsPerPatch",
+      label: "Logical qubits formula",
+      description: `QEC scheme formula used to compute number of logical qubits per patch`,
+      explanation: `This is the formula that is used to compute the number of logical qubits that are encoded in one patch. The physical qubits of the patch are shared among all its logical qubits.`,
+    });
+  }
+  if (result.jobParams.qecScheme.logicalErrorRate != null) {
+    entries.push({
+      path: "jobParams/qecScheme/logicalErrorRate",
+      label: "Logical error rate formula",
+      description: `QEC scheme formula used to compute the logical error rate of a patch`,
+      explanation: `This is the formula that is used to compute the logical error rate ${result.logicalQubit.logicalErrorRate} of a patch, which replaces the default formula based on the crossing prefactor and the error correction threshold.`,
+    });
+  }
   groups.push({
     title: "Logical qubit parameters",
     alwaysVisible: false,
@@ -576,6 +660,155 @@ export function CreateReport(result: SingleEstimateResult): ReportData {
   });
   groups.push({ title: "Constraints", alwaysVisible: false, entries: entries });
 
+  if (result.jobParams.layout?.name == "latticeSurgery") {
+    entries = [];
+    entries.push({
+      path: "jobParams/layout/routingFraction",
+      label: "Routing fraction",
+      description: `Fraction of all tiles reserved for routing in the lattice surgery layout`,
+      explanation: `This is the fraction of logical tiles that do not hold data qubits but are used as routing space to execute lattice surgery operations between data qubits and to bring magic states to them.`,
+    });
+    entries.push({
+      path: "jobParams/layout/magicStatePorts",
+      label: "Magic state ports",
+      description: `Number of magic states consumed in parallel in the lattice surgery layout`,
+      explanation: `This is the number of ports through which magic states enter the routing space, i.e., the number of magic states that can be consumed by the algorithm in the same logical cycle.`,
+    });
+    groups.push({
+      title: "Logical layout",
+      alwaysVisible: false,
+      entries: entries,
+    });
+  }
+  if (result.magicStateFactories != null) {
+    entries = [];
+    if (result.magicStateFactories?.t != null) {
+      entries.push({
+        path: "magicStateFactories/t/numFactories",
+        label: "Number of T factories",
+        description: `Number of factories producing T states`,
+        explanation: `The ${numberFormat.format(
+          result.magicStateFactories.t.numFactories,
+        )} copies of the T factory produce the ${numberFormat.format(
+          result.magicStateFactories.t.numStates,
+        )} T states during the algorithm's runtime in ${numberFormat.format(
+          result.magicStateFactories.t.numFactoryRuns,
+        )} runs.`,
+      });
+    }
+    if (result.magicStateFactories?.t != null) {
+      entries.push({
+        path: "magicStateFactories/t/physicalQubits",
+        label: "Physical T factory qubits",
+        description: `Number of physical qubits for the T factories`,
+        explanation: `Each T factory requires ${numberFormat.format(
+          result.magicStateFactories.t.factory.physicalQubits,
+        )} physical qubits and we run ${numberFormat.format(
+          result.magicStateFactories.t.numFactories,
+        )} in parallel.`,
+      });
+    }
+    if (result.magicStateFactories?.t != null) {
+      entries.push({
+        path: "magicStateFactories/t/factory/unitNamePerRound",
+        label: "Distillation units for T states",
+        description: `Distillation units per round of the T factory`,
+        explanation: `The T factory uses ${
+          result.magicStateFactories.t.factory.numRounds
+        } rounds of distillation, the last round produces T states with an error rate of ${result.magicStateFactories.t.factory.logicalErrorRate.toExponential(
+          2,
+        )}, which is equal or smaller than the required error rate ${result.magicStateFactories.t.requiredLogicalErrorRate.toExponential(
+          2,
+        )}.`,
+      });
+    }
+    if (result.magicStateFactories?.ccz != null) {
+      entries.push({
+        path: "magicStateFactories/ccz/numFactories",
+        label: "Number of CCZ factories",
+        description: `Number of factories producing CCZ states`,
+        explanation: `The ${numberFormat.format(
+          result.magicStateFactories.ccz.numFactories,
+        )} copies of the CCZ factory produce the ${numberFormat.format(
+          result.magicStateFactories.ccz.numStates,
+        )} CCZ states during the algorithm's runtime in ${numberFormat.format(
+          result.magicStateFactories.ccz.numFactoryRuns,
+        )} runs.`,
+      });
+    }
+    if (result.magicStateFactories?.ccz != null) {
+      entries.push({
+        path: "magicStateFactories/ccz/physicalQubits",
+        label: "Physical CCZ factory qubits",
+        description: `Number of physical qubits for the CCZ factories`,
+        explanation: `Each CCZ factory requires ${numberFormat.format(
+          result.magicStateFactories.ccz.factory.physicalQubits,
+        )} physical qubits and we run ${numberFormat.format(
+          result.magicStateFactories.ccz.numFactories,
+        )} in parallel.`,
+      });
+    }
+    if (result.magicStateFactories?.ccz != null) {
+      entries.push({
+        path: "magicStateFactories/ccz/factory/unitNamePerRound",
+        label: "Distillation units for CCZ states",
+        description: `Distillation units per round of the CCZ factory`,
+        explanation: `The CCZ factory uses ${
+          result.magicStateFactories.ccz.factory.numRounds
+        } rounds of distillation, the last round produces CCZ states with an error rate of ${result.magicStateFactories.ccz.factory.logicalErrorRate.toExponential(
+          2,
+        )}, which is equal or smaller than the required error rate ${result.magicStateFactories.ccz.requiredLogicalErrorRate.toExponential(
+          2,
+        )}.`,
+      });
+    }
+    if (result.magicStateFactories?.rotation != null) {
+      entries.push({
+        path: "magicStateFactories/rotation/numFactories",
+        label: "Number of rotation factories",
+        description: `Number of factories producing rotation states`,
+        explanation: `The ${numberFormat.format(
+          result.magicStateFactories.rotation.numFactories,
+        )} copies of the rotation factory produce the ${numberFormat.format(
+          result.magicStateFactories.rotation.numStates,
+        )} rotation states during the algorithm's runtime in ${numberFormat.format(
+          result.magicStateFactories.rotation.numFactoryRuns,
+        )} runs.`,
+      });
+    }
+    if (result.magicStateFactories?.rotation != null) {
+      entries.push({
+        path: "magicStateFactories/rotation/physicalQubits",
+        label: "Physical rotation factory qubits",
+        description: `Number of physical qubits for the rotation factories`,
+        explanation: `Each rotation factory requires ${numberFormat.format(
+          result.magicStateFactories.rotation.factory.physicalQubits,
+        )} physical qubits and we run ${numberFormat.format(
+          result.magicStateFactories.rotation.numFactories,
+        )} in parallel.`,
+      });
+    }
+    if (result.magicStateFactories?.rotation != null) {
+      entries.push({
+        path: "magicStateFactories/rotation/factory/unitNamePerRound",
+        label: "Distillation units for rotation states",
+        description: `Distillation units per round of the rotation factory`,
+        explanation: `The rotation factory uses ${
+          result.magicStateFactories.rotation.factory.numRounds
+        } rounds of distillation, the last round produces rotation states with an error rate of ${result.magicStateFactories.rotation.factory.logicalErrorRate.toExponential(
+          2,
+        )}, which is equal or smaller than the required error rate ${result.magicStateFactories.rotation.requiredLogicalErrorRate.toExponential(
+          2,
+        )}.`,
+      });
+    }
+    groups.push({
+      title: "Magic state factories",
+      alwaysVisible: false,
+      entries: entries,
+    });
+  }
+
   const assumptions = [
     "_More details on the following lists of assumptions can be found in the paper [Accessing requirements for scaling quantum computers and their applications](https://aka.ms/AQ/RE/Paper)._",
     "**Uniform independent physical noise.** We assume that the noise on physical qubits and physical qubit operations is the standard circuit noise model. In particular we assume error events at different space-time locations are independent and that error rates are uniform across the system in time and space.",
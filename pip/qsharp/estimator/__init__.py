@@ -7,9 +7,13 @@ from ._estimator import (
     EstimatorResult,
     QubitParams,
     QECScheme,
+    Layout,
+    MagicStateKind,
     MeasurementErrorRate,
     EstimatorQubitParams,
     EstimatorQecScheme,
+    EstimatorLayout,
+    EstimatorMagicStateFactories,
     ProtocolSpecificDistillationUnitSpecification,
     DistillationUnitSpecification,
     ErrorBudgetPartition,
@@ -24,9 +28,13 @@ __all__ = [
     "EstimatorResult",
     "QubitParams",
     "QECScheme",
+    "Layout",
+    "MagicStateKind",
     "MeasurementErrorRate",
     "EstimatorQubitParams",
     "EstimatorQecScheme",
+    "EstimatorLayout",
+    "EstimatorMagicStateFactories",
     "ProtocolSpecificDistillationUnitSpecification",
     "DistillationUnitSpecification",
     "ErrorBudgetPartition",
@@ -96,6 +96,19 @@ class QubitParams:
 class QECScheme:
     SURFACE_CODE = "surface_code"
     FLOQUET_CODE = "floquet_code"
+    COLOR_CODE = "color_code"
+    QLDPC_CODE = "qldpc_code"
+
+
+class Layout:
+    PSSPC = "psspc"
+    LATTICE_SURGERY = "latticeSurgery"
+
+
+class MagicStateKind:
+    T = "t"
+    CCZ = "ccz"
+    ROTATION = "rotation"
 
 
 def _check_error_rate(name, value):
@@ -229,6 +242,39 @@ class EstimatorQecScheme(AutoValidatingParams):
     crossing_prefactor: Optional[float] = None
     logical_cycle_time: Optional[str] = None
     physical_qubits_per_logical_qubit: Optional[str] = None
+    logical_qubits_per_patch: Optional[str] = None
+    logical_error_rate: Optional[str] = None
+
+
+@dataclass
+class EstimatorLayout(AutoValidatingParams):
+    @staticmethod
+    def check_routing_fraction(name, value):
+        if value < 0.0 or value >= 1.0:
+            raise ValueError(f"{name} must be at least 0 and less than 1")
+
+    @staticmethod
+    def at_least_one(name, value):
+        if value < 1:
+            raise ValueError(f"{name} must be at least 1")
+
+    name: Optional[str] = None
+    routing_fraction: Optional[float] = validating_field(check_routing_fraction)
+    magic_state_ports: Optional[int] = validating_field(at_least_one)
+
+    def post_validation(self, result):
+        if self.name != Layout.LATTICE_SURGERY and (
+            self.routing_fraction is not None or self.magic_state_ports is not None
+        ):
+            raise LookupError(
+                "routing_fraction and magic_state_ports can only be set for the lattice surgery layout"
+            )
+
+
+@dataclass
+class EstimatorMagicStateFactories(AutoValidatingParams):
+    ccz: Optional[bool] = None
+    rotation: Optional[bool] = None
 
 
 @dataclass
@@ -250,6 +296,7 @@ class DistillationUnitSpecification(AutoValidatingParams):
     display_name: Optional[str] = None
     num_input_ts: Optional[int] = None
     num_output_ts: Optional[int] = None
+    output_state: Optional[str] = None
     failure_probability_formula: Optional[str] = None
     output_error_rate_formula: Optional[str] = None
     physical_qubit_specification: Optional[
@@ -267,6 +314,7 @@ class DistillationUnitSpecification(AutoValidatingParams):
             self.display_name is not None
             or self.num_input_ts is not None
             or self.num_output_ts is not None
+            or self.output_state is not None
             or self.failure_probability_formula is not None
             or self.output_error_rate_formula is not None
             or self.physical_qubit_specification is not None
@@ -396,6 +444,10 @@ class EstimatorInputParamsItem:
         self.constraints: EstimatorConstraints = EstimatorConstraints()
         self.error_budget: Optional[Union[float, ErrorBudgetPartition]] = None
         self.estimate_type: Optional[str] = None
+        self.layout: EstimatorLayout = EstimatorLayout()
+        self.magic_state_factories: EstimatorMagicStateFactories = (
+            EstimatorMagicStateFactories()
+        )
 
     def as_dict(self, validate=True, additional_params=None) -> Dict[str, Any]:
         result = {}
@@ -473,6 +525,24 @@ class EstimatorInputParamsItem:
                 )
             result["estimateType"] = self.estimate_type
 
+        layout = self.layout.as_dict(validate)
+        if len(layout) != 0:
+            result["layout"] = layout
+        elif hasattr(additional_params, "layout"):
+            layout = additional_params.layout.as_dict(validate)
+            if len(layout) != 0:
+                result["layout"] = layout
+
+        magic_state_factories = self.magic_state_factories.as_dict(validate)
+        if len(magic_state_factories) != 0:
+            result["magicStateFactories"] = magic_state_factories
+        elif hasattr(additional_params, "magic_state_factories"):
+            magic_state_factories = additional_params.magic_state_factories.as_dict(
+                validate
+            )
+            if len(magic_state_factories) != 0:
+                result["magicStateFactories"] = magic_state_factories
+
         return result
 
 
@@ -271,7 +271,7 @@ impl Interpreter {
                         .into_iter()
                         .map(|e| match e {
                             re::Error::Interpreter(e) => e,
-                            re::Error::Estimation(_) => unreachable!(),
+                            re::Error::Program(_) | re::Error::Estimation(_) => unreachable!(),
                         })
                         .collect::<Vec<_>>(),
                 )))
@@ -280,6 +280,7 @@ impl Interpreter {
                 errors
                     .into_iter()
                     .map(|e| match e {
+                        re::Error::Program(e) => e.to_string(),
                         re::Error::Estimation(e) => e.to_string(),
                         re::Error::Interpreter(_) => unreachable!(),
                     })
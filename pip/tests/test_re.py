@@ -2,7 +2,14 @@
 # Licensed under the MIT License.
 
 import qsharp
-from qsharp.estimator import EstimatorParams, QubitParams, QECScheme, LogicalCounts
+from qsharp.estimator import (
+    EstimatorParams,
+    QubitParams,
+    QECScheme,
+    Layout,
+    LogicalCounts,
+    MagicStateKind,
+)
 
 
 def test_qsharp_estimation() -> None:
@@ -255,3 +262,57 @@ def test_building_frontier_from_logical_counts_with_single_params() -> None:
     first_entry = res["frontierEntries"][0]
     assert first_entry["physicalCounts"] is not None
     assert first_entry["physicalCountsFormatted"] is not None
+
+
+def test_estimation_with_lattice_surgery_layout() -> None:
+    logical_counts = LogicalCounts(
+        {
+            "numQubits": 100,
+            "tCount": 400,
+            "cczCount": 20,
+            "measurementCount": 10,
+        }
+    )
+    params = EstimatorParams(num_items=2)
+    params.items[1].layout.name = Layout.LATTICE_SURGERY
+    params.items[1].layout.routing_fraction = 0.25
+    params.items[1].layout.magic_state_ports = 4
+    res = logical_counts.estimate(params=params)
+
+    assert "layout" not in res[0]["jobParams"]
+    assert res[1]["jobParams"]["layout"] == {
+        "name": "latticeSurgery",
+        "routingFraction": 0.25,
+        "magicStatePorts": 4,
+    }
+    assert res[0]["physicalCounts"]["breakdown"]["algorithmicLogicalQubits"] == 230
+    assert res[1]["physicalCounts"]["breakdown"]["algorithmicLogicalQubits"] == 134
+
+
+def test_estimation_with_ccz_and_rotation_factories() -> None:
+    logical_counts = LogicalCounts(
+        {
+            "numQubits": 100,
+            "tCount": 400,
+            "rotationCount": 50,
+            "rotationDepth": 10,
+            "cczCount": 20,
+            "measurementCount": 10,
+        }
+    )
+    params = EstimatorParams(num_items=2)
+    params.qubit_params.name = QubitParams.GATE_NS_E4
+    params.error_budget = 0.3
+    params.items[1].magic_state_factories.ccz = True
+    params.items[1].magic_state_factories.rotation = True
+    res = logical_counts.estimate(params=params)
+
+    assert "magicStateFactories" not in res[0]
+    assert res[1]["jobParams"]["magicStateFactories"] == {
+        "ccz": True,
+        "rotation": True,
+    }
+    factories = res[1]["magicStateFactories"]
+    assert factories[MagicStateKind.CCZ]["numStates"] == 20
+    assert factories[MagicStateKind.ROTATION]["numStates"] == 100
+    assert res[1]["physicalCounts"]["breakdown"]["numTstates"] == 400
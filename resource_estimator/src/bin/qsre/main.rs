@@ -0,0 +1,205 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+allocator::assign_global!();
+
+#[cfg(test)]
+mod tests;
+
+use clap::{crate_version, ArgGroup, Parser, ValueEnum};
+use miette::{Context, IntoDiagnostic, Report};
+use qsc::{
+    interpret::Interpreter, LanguageFeatures, PackageType, SourceContents, SourceMap, SourceName,
+    TargetCapabilityFlags,
+};
+use qsc_project::{FileSystem, Manifest, StdFs};
+use resource_estimator::{
+    estimate_entry, estimate_entry_space_time_tradeoff, estimate_entry_sweep,
+    estimate_physical_resources_from_json, estimate_physical_resources_sweep_from_json, system,
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+enum OutputFormat {
+    /// A table with the entries of the report for each set of job parameters.
+    #[default]
+    Summary,
+    /// The full estimation results as JSON.
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "qsre", version = crate_version!())]
+#[command(author, about = "Q# resource estimator", next_line_help = true)]
+#[clap(group(ArgGroup::new("program").args(["sources", "qsharp_json", "counts"])))]
+struct Cli {
+    /// Q# source files to compile and estimate.
+    sources: Vec<PathBuf>,
+
+    /// Path to a Q# manifest for a project to compile and estimate.
+    #[arg(short, long)]
+    qsharp_json: Option<PathBuf>,
+
+    /// Path to a JSON file with logical resource counts to estimate instead of a Q# program.
+    #[arg(short, long, conflicts_with = "entry")]
+    counts: Option<PathBuf>,
+
+    /// Entry expression to estimate instead of the entry point of the program.
+    #[arg(short, long)]
+    entry: Option<String>,
+
+    /// Language features to compile with.
+    #[arg(short, long)]
+    features: Vec<String>,
+
+    /// Path to a JSON file with one set or an array of sets of job parameters.
+    #[arg(short, long)]
+    params: Option<PathBuf>,
+
+    /// Format of the estimation results.
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+
+    /// Estimate the program under up to this many logical qubit budgets and print the
+    /// resulting space-time trade-off curve as JSON.
+    #[arg(long, value_name = "NUM_BUDGETS", conflicts_with_all = ["counts", "output"])]
+    space_time_tradeoff: Option<usize>,
+
+    /// Path to a JSON file with a sweep specification of job parameter variations to estimate
+    /// instead of the job parameters, and print their results and combined Pareto frontier as
+    /// JSON.
+    #[arg(
+        short,
+        long,
+        conflicts_with_all = ["params", "output", "space_time_tradeoff"]
+    )]
+    sweep: Option<PathBuf>,
+}
+
+fn main() -> miette::Result<ExitCode> {
+    run(Cli::parse(), &mut std::io::stdout())
+}
+
+fn run(cli: Cli, out: &mut impl Write) -> miette::Result<ExitCode> {
+    let params = match &cli.params {
+        Some(path) => read_params(path)?,
+        None => String::new(),
+    };
+    let sweep = cli.sweep.as_deref().map(read_file).transpose()?;
+
+    let results = if let Some(counts) = &cli.counts {
+        let counts = read_file(counts)?;
+        let results = match &sweep {
+            Some(sweep) => estimate_physical_resources_sweep_from_json(&counts, sweep),
+            None => estimate_physical_resources_from_json(&counts, &params),
+        };
+        match results {
+            Ok(results) => results,
+            Err(error) => {
+                eprintln!("error: {:?}", Report::new(error));
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    } else {
+        let mut features = LanguageFeatures::from_iter(cli.features);
+        let mut sources = cli
+            .sources
+            .iter()
+            .map(read_source)
+            .collect::<miette::Result<Vec<_>>>()?;
+
+        if sources.is_empty() {
+            let fs = StdFs;
+            let manifest = Manifest::load(cli.qsharp_json)?;
+            if let Some(manifest) = manifest {
+                let project = fs.load_project(&manifest)?;
+                let mut project_sources = project.sources;
+
+                sources.append(&mut project_sources);
+
+                features.merge(LanguageFeatures::from_iter(
+                    manifest.manifest.language_features,
+                ));
+            }
+        }
+
+        let mut interpreter = match Interpreter::new(
+            true,
+            SourceMap::new(sources, cli.entry.map(std::convert::Into::into)),
+            PackageType::Exe,
+            TargetCapabilityFlags::all(),
+            features,
+        ) {
+            Ok(interpreter) => interpreter,
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("error: {:?}", Report::new(error));
+                }
+                return Ok(ExitCode::FAILURE);
+            }
+        };
+
+        let results = if let Some(sweep) = &sweep {
+            estimate_entry_sweep(&mut interpreter, sweep)
+        } else if let Some(num_budgets) = cli.space_time_tradeoff {
+            estimate_entry_space_time_tradeoff(&mut interpreter, &params, num_budgets)
+        } else {
+            estimate_entry(&mut interpreter, &params)
+        };
+        match results {
+            Ok(results) => results,
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("error: {:?}", Report::new(error));
+                }
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    };
+
+    // sweeps and space-time trade-offs have no summary format
+    if cli.output == OutputFormat::Json || sweep.is_some() || cli.space_time_tradeoff.is_some() {
+        writeln!(out, "{results}").into_diagnostic()?;
+    } else {
+        match system::format_summary(&results) {
+            Ok(summary) => write!(out, "{summary}").into_diagnostic()?,
+            Err(error) => {
+                eprintln!("error: {:?}", Report::new(error));
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn read_file(path: &Path) -> miette::Result<String> {
+    fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("could not read file `{}`", path.display()))
+}
+
+fn read_source(path: impl AsRef<Path>) -> miette::Result<(SourceName, SourceContents)> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .into_diagnostic()
+        .with_context(|| format!("could not read source file `{}`", path.display()))?;
+
+    Ok((path.to_string_lossy().into(), contents.into()))
+}
+
+/// Reads the job parameters from `path`, which may either contain a single
+/// set of job parameters or an array of them.
+fn read_params(path: &Path) -> miette::Result<String> {
+    let params = read_file(path)?;
+    if params.trim_start().starts_with('{') {
+        Ok(format!("[{params}]"))
+    } else {
+        Ok(params)
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use super::{run, Cli, OutputFormat};
+use clap::{error::ErrorKind, Parser};
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// A directory under the system temp directory that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("qsre-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("temp dir should be created");
+        Self(path)
+    }
+
+    fn write(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.0.join(name);
+        fs::write(&path, contents).expect("file should be written");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+const COUNTS: &str = r#"{
+    "numQubits": 100,
+    "tCount": 400,
+    "rotationCount": 50,
+    "rotationDepth": 10,
+    "cczCount": 20,
+    "ccixCount": 5,
+    "measurementCount": 10
+}"#;
+
+const PROGRAM: &str = "namespace Test {
+    @EntryPoint()
+    operation Main() : Unit {
+        use qs = Qubit[3];
+        CCNOT(qs[0], qs[1], qs[2]);
+        T(qs[0]);
+    }
+}";
+
+const SWEEP: &str =
+    r#"{"product": {"qubitParams": [{"name": "qubit_gate_ns_e3"}, {"name": "qubit_gate_ns_e4"}]}}"#;
+
+fn parse_error(args: &[&str]) -> ErrorKind {
+    Cli::try_parse_from(args)
+        .expect_err("arguments should be rejected")
+        .kind()
+}
+
+/// Runs the estimator and returns its exit code and output.
+fn run_cli(args: &[&str]) -> (ExitCode, String) {
+    let cli = Cli::try_parse_from(args).expect("arguments should be valid");
+    let mut out = Vec::new();
+    let exit_code = run(cli, &mut out).expect("estimation should not fail with an error");
+    (
+        exit_code,
+        String::from_utf8(out).expect("output should be valid utf-8"),
+    )
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().expect("path should be valid utf-8")
+}
+
+#[test]
+fn parse_sources_with_params() {
+    let cli = Cli::try_parse_from([
+        "qsre",
+        "a.qs",
+        "b.qs",
+        "-p",
+        "params.json",
+        "--output",
+        "json",
+    ])
+    .expect("arguments should be valid");
+
+    assert_eq!(cli.sources, [PathBuf::from("a.qs"), PathBuf::from("b.qs")]);
+    assert_eq!(cli.params, Some(PathBuf::from("params.json")));
+    assert_eq!(cli.output, OutputFormat::Json);
+    assert!(cli.counts.is_none());
+    assert!(cli.sweep.is_none());
+}
+
+#[test]
+fn parse_defaults_to_summary_output() {
+    let cli =
+        Cli::try_parse_from(["qsre", "-c", "counts.json"]).expect("arguments should be valid");
+
+    assert_eq!(cli.counts, Some(PathBuf::from("counts.json")));
+    assert_eq!(cli.output, OutputFormat::Summary);
+}
+
+#[test]
+fn parse_sources_and_counts_conflict() {
+    assert_eq!(
+        parse_error(&["qsre", "a.qs", "--counts", "counts.json"]),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn parse_counts_and_entry_conflict() {
+    assert_eq!(
+        parse_error(&["qsre", "--counts", "counts.json", "--entry", "Test.Main()"]),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn parse_space_time_tradeoff_and_counts_conflict() {
+    assert_eq!(
+        parse_error(&["qsre", "-c", "counts.json", "--space-time-tradeoff", "4"]),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn parse_sweep_and_params_conflict() {
+    assert_eq!(
+        parse_error(&[
+            "qsre",
+            "-c",
+            "counts.json",
+            "-s",
+            "sweep.json",
+            "-p",
+            "params.json"
+        ]),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn parse_sweep_and_output_conflict() {
+    assert_eq!(
+        parse_error(&["qsre", "a.qs", "--sweep", "sweep.json", "--output", "json"]),
+        ErrorKind::ArgumentConflict
+    );
+}
+
+#[test]
+fn parse_invalid_output_format() {
+    assert_eq!(
+        parse_error(&["qsre", "a.qs", "--output", "table"]),
+        ErrorKind::InvalidValue
+    );
+}
+
+#[test]
+fn counts_summary() {
+    let dir = TempDir::new("counts_summary");
+    let counts = dir.write("counts.json", COUNTS);
+
+    let (exit_code, output) = run_cli(&["qsre", "-c", path_str(&counts)]);
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    assert!(output.contains("Physical qubits"), "{output}");
+}
+
+#[test]
+fn counts_with_single_params_object() {
+    let dir = TempDir::new("counts_single_params");
+    let counts = dir.write("counts.json", COUNTS);
+    let params = dir.write("params.json", r#"{"errorBudget": 0.01}"#);
+
+    let (exit_code, output) = run_cli(&[
+        "qsre",
+        "-c",
+        path_str(&counts),
+        "-p",
+        path_str(&params),
+        "--output",
+        "json",
+    ]);
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let results: Value = serde_json::from_str(&output).expect("output should be valid json");
+    assert_eq!(results.as_array().map(Vec::len), Some(1));
+    assert_eq!(results[0]["status"], "success");
+}
+
+#[test]
+fn counts_sweep() {
+    let dir = TempDir::new("counts_sweep");
+    let counts = dir.write("counts.json", COUNTS);
+    let sweep = dir.write("sweep.json", SWEEP);
+
+    let (exit_code, output) = run_cli(&["qsre", "-c", path_str(&counts), "-s", path_str(&sweep)]);
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let result: Value = serde_json::from_str(&output).expect("output should be valid json");
+    assert_eq!(result["results"].as_array().map(Vec::len), Some(2));
+    assert!(!result["paretoFrontier"]
+        .as_array()
+        .expect("pareto frontier should be an array")
+        .is_empty());
+}
+
+#[test]
+fn program_sweep() {
+    let dir = TempDir::new("program_sweep");
+    let source = dir.write("Main.qs", PROGRAM);
+    let sweep = dir.write("sweep.json", SWEEP);
+
+    let (exit_code, output) = run_cli(&["qsre", path_str(&source), "--sweep", path_str(&sweep)]);
+
+    assert_eq!(exit_code, ExitCode::SUCCESS);
+    let result: Value = serde_json::from_str(&output).expect("output should be valid json");
+    assert_eq!(result["results"].as_array().map(Vec::len), Some(2));
+    assert_eq!(result["results"][0]["logicalCounts"]["cczCount"], 1);
+}
+
+#[test]
+fn invalid_counts_fails() {
+    let dir = TempDir::new("invalid_counts");
+    let counts = dir.write("counts.json", r#"{"numQubits": "many"}"#);
+
+    let (exit_code, output) = run_cli(&["qsre", "-c", path_str(&counts)]);
+
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert_eq!(output, "");
+}
+
+#[test]
+fn invalid_sweep_fails() {
+    let dir = TempDir::new("invalid_sweep");
+    let counts = dir.write("counts.json", COUNTS);
+    let sweep = dir.write("sweep.json", r#"{"product": {"maxDuration": ["1 s"]}}"#);
+
+    let (exit_code, output) = run_cli(&["qsre", "-c", path_str(&counts), "-s", path_str(&sweep)]);
+
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert_eq!(output, "");
+}
+
+#[test]
+fn compile_error_fails() {
+    let dir = TempDir::new("compile_error");
+    let source = dir.write(
+        "Main.qs",
+        "namespace Test { operation Main() : Unit { x } }",
+    );
+
+    let (exit_code, output) = run_cli(&["qsre", path_str(&source)]);
+
+    assert_eq!(exit_code, ExitCode::FAILURE);
+    assert_eq!(output, "");
+}
+
+#[test]
+fn missing_counts_file_is_error() {
+    let dir = TempDir::new("missing_counts");
+    let counts = dir.0.join("counts.json");
+    let cli =
+        Cli::try_parse_from(["qsre", "-c", path_str(&counts)]).expect("arguments should be valid");
+
+    let error = run(cli, &mut Vec::new()).expect_err("missing file should be an error");
+
+    assert_eq!(
+        error.to_string(),
+        format!("could not read file `{}`", counts.display())
+    );
+}
@@ -4,9 +4,13 @@
 #[cfg(test)]
 mod tests;
 
+mod call_tree;
+pub mod program;
+
+use call_tree::{CallTree, Counts};
 use num_bigint::BigUint;
 use num_complex::Complex;
-use qsc::{interpret::Value, Backend};
+use qsc::{interpret::Value, Backend, FunctorApp};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rustc_hash::FxHashMap;
 use std::{
@@ -17,7 +21,7 @@ use std::{
     iter::Sum,
 };
 
-use crate::system::LogicalResourceCounts;
+use crate::system::{LogicalResourceBreakdown, LogicalResourceCounts};
 
 /// Resource counter implementation
 ///
@@ -28,6 +32,9 @@ pub struct LogicalCounter {
     free_list: Vec<usize>,
     /// Next free qubit id, in case `free_list` is empty
     next_free: usize,
+    /// Number of qubits up to which fresh qubits are allocated instead of
+    /// reusing released ones
+    max_logical_qubits: Option<usize>,
     /// Depth counter
     max_layer: Vec<usize>,
     /// Layers
@@ -49,6 +56,8 @@ pub struct LogicalCounter {
     caching_layers: FxHashMap<String, LayerCache>,
     /// Repeating
     repeats: Vec<RepeatEntry>,
+    /// Counts per call path
+    call_tree: CallTree,
     /// Random number generator
     rnd: RefCell<StdRng>,
 }
@@ -58,6 +67,7 @@ impl Default for LogicalCounter {
         Self {
             free_list: vec![],
             next_free: 0,
+            max_logical_qubits: None,
             max_layer: vec![],
             layers: vec![],
             t_count: 0,
@@ -68,12 +78,31 @@ impl Default for LogicalCounter {
             caching_stack: vec![],
             caching_layers: FxHashMap::default(),
             repeats: vec![],
+            call_tree: CallTree::default(),
             rnd: RefCell::new(StdRng::seed_from_u64(0)),
         }
     }
 }
 
 impl LogicalCounter {
+    /// Creates a counter that trades depth for width under a budget of
+    /// `max_logical_qubits` logical qubits.
+    ///
+    /// By default, released qubits are reused right away, which serializes
+    /// operations on the reused qubit after those before its release.  This
+    /// counter instead allocates fresh qubits, whose operations can run in
+    /// parallel to earlier layers, until the budget is used up.  Only then are
+    /// released qubits reused, starting with the one that becomes available
+    /// first.  The budget is exceeded if the program has more qubits allocated
+    /// at the same time.
+    #[must_use]
+    pub fn with_max_logical_qubits(max_logical_qubits: usize) -> Self {
+        Self {
+            max_logical_qubits: Some(max_logical_qubits),
+            ..Self::default()
+        }
+    }
+
     #[must_use]
     pub fn logical_resources(&self) -> LogicalResourceCounts {
         LogicalResourceCounts {
@@ -87,6 +116,22 @@ impl LogicalCounter {
         }
     }
 
+    /// Returns the logical resources per callable in the call tree of the
+    /// program, together with the largest number of qubits allocated at the
+    /// same time in each of them.
+    ///
+    /// Resources of repeated blocks are attributed to the callables in the
+    /// repeated block, and resources of blocks reused from the estimate cache
+    /// to the callables in which they have been counted first.
+    #[must_use]
+    pub fn logical_breakdown(&self) -> LogicalResourceBreakdown {
+        self.call_tree.breakdown()
+    }
+
+    fn live_qubits(&self) -> usize {
+        self.next_free - self.free_list.len()
+    }
+
     fn schedule_r(&mut self, q: usize) {
         let level = self.level_at(q);
 
@@ -99,6 +144,20 @@ impl LogicalCounter {
         self.max_layer[q] += 1;
     }
 
+    /// Counts a Z rotation on `q`, by an angle that is not known before execution if `theta` is `None`.
+    fn rotation(&mut self, theta: Option<f64>, q: usize) {
+        if let Some(theta) = theta {
+            self.rz(theta, q);
+        } else {
+            self.r_count += 1;
+            self.call_tree.add(Counts {
+                r: 1,
+                ..Counts::default()
+            });
+            self.schedule_r(q);
+        }
+    }
+
     fn schedule_t(&mut self, q: usize) {
         let level = self.level_at(q);
 
@@ -138,12 +197,33 @@ impl LogicalCounter {
 
     fn level_at(&mut self, q: usize) -> usize {
         while self.max_layer.len() <= q {
-            self.qubit_allocate();
+            self.fresh_qubit();
+            self.call_tree.allocated(self.live_qubits());
         }
 
         self.max_layer[q]
     }
 
+    fn fresh_qubit(&mut self) -> usize {
+        let index = self.next_free;
+        self.next_free += 1;
+        self.max_layer.push(self.allocation_barrier);
+        index
+    }
+
+    /// Returns a released qubit to reuse for the next allocation, if any
+    fn reusable_qubit(&mut self) -> Option<usize> {
+        match self.max_logical_qubits {
+            None => self.free_list.pop(),
+            Some(max_logical_qubits) if self.next_free < max_logical_qubits => None,
+            Some(_) => {
+                let position = (0..self.free_list.len())
+                    .min_by_key(|&position| self.max_layer[self.free_list[position]])?;
+                Some(self.free_list.swap_remove(position))
+            }
+        }
+    }
+
     fn global_barrier(&mut self) -> usize {
         let depth = self.layers.len();
 
@@ -163,9 +243,11 @@ impl LogicalCounter {
             end_depth,
             combined_layer,
             m_count,
+            call_counts,
         }) = self.caching_layers.get(&label)
         {
             self.layers.extend_from_within(*start_depth..*end_depth);
+            self.call_tree.add_deltas(call_counts, 1);
 
             self.t_count += combined_layer.t;
             self.r_count += combined_layer.r;
@@ -181,6 +263,7 @@ impl LogicalCounter {
                 LayerCache::Begin {
                     start_depth: depth,
                     m_count: self.m_count,
+                    call_counts: self.call_tree.snapshot(),
                 },
             );
             self.caching_stack.push(label);
@@ -202,6 +285,7 @@ impl LogicalCounter {
         let LayerCache::Begin {
             start_depth,
             m_count,
+            call_counts,
         } = entry
         else {
             panic!("layer caching should always have matching begin and end");
@@ -219,6 +303,7 @@ impl LogicalCounter {
                 end_depth,
                 combined_layer: sum,
                 m_count: self.m_count - m_count,
+                call_counts: self.call_tree.since(&call_counts),
             },
         );
 
@@ -235,6 +320,7 @@ impl LogicalCounter {
                 .map_err(|_| format!("Estimate count {count} is too large to fit in a usize.",))?,
             start_depth,
             m_count: self.m_count,
+            call_counts: self.call_tree.snapshot(),
         });
 
         Ok(())
@@ -246,6 +332,7 @@ impl LogicalCounter {
             count,
             start_depth,
             m_count,
+            call_counts,
         }) = self.repeats.pop()
         {
             if count == 0 {
@@ -290,6 +377,9 @@ impl LogicalCounter {
             self.ccz_count += combined_ccz_count;
             self.m_count += combined_m_count;
 
+            let call_counts = self.call_tree.since(&call_counts);
+            self.call_tree.add_deltas(&call_counts, count - 1);
+
             self.global_barrier();
         }
     }
@@ -390,6 +480,12 @@ impl LogicalCounter {
         self.r_count += r_count;
         self.ccz_count += ccz_count;
         self.m_count += m_count;
+        self.call_tree.add(Counts {
+            t: t_count,
+            r: r_count,
+            ccz: ccz_count,
+            m: m_count,
+        });
 
         for qubit in qubits {
             self.max_layer[*qubit] += num_layers;
@@ -409,6 +505,10 @@ impl Backend for LogicalCounter {
 
     fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
         self.ccz_count += 1;
+        self.call_tree.add(Counts {
+            ccz: 1,
+            ..Counts::default()
+        });
         self.schedule_ccz(ctl0, ctl1, q);
     }
 
@@ -428,6 +528,10 @@ impl Backend for LogicalCounter {
 
     fn m(&mut self, _q: usize) -> Self::ResultType {
         self.m_count += 1;
+        self.call_tree.add(Counts {
+            m: 1,
+            ..Counts::default()
+        });
 
         self.rnd.borrow_mut().gen_bool(0.5)
     }
@@ -463,6 +567,10 @@ impl Backend for LogicalCounter {
             }
         } else {
             self.r_count += 1;
+            self.call_tree.add(Counts {
+                r: 1,
+                ..Counts::default()
+            });
             self.schedule_r(q);
         }
     }
@@ -482,12 +590,15 @@ impl Backend for LogicalCounter {
     }
 
     fn tadj(&mut self, q: usize) {
-        self.t_count += 1;
-        self.schedule_t(q);
+        self.t(q);
     }
 
     fn t(&mut self, q: usize) {
         self.t_count += 1;
+        self.call_tree.add(Counts {
+            t: 1,
+            ..Counts::default()
+        });
         self.schedule_t(q);
     }
 
@@ -498,14 +609,12 @@ impl Backend for LogicalCounter {
     fn z(&mut self, _q: usize) {}
 
     fn qubit_allocate(&mut self) -> usize {
-        if let Some(index) = self.free_list.pop() {
-            index
-        } else {
-            let index = self.next_free;
-            self.next_free += 1;
-            self.max_layer.push(self.allocation_barrier);
-            index
-        }
+        let index = match self.reusable_qubit() {
+            Some(index) => index,
+            None => self.fresh_qubit(),
+        };
+        self.call_tree.allocated(self.live_qubits());
+        index
     }
 
     fn qubit_release(&mut self, q: usize) {
@@ -568,6 +677,19 @@ impl Backend for LogicalCounter {
             _ => None,
         }
     }
+
+    fn enter_callable(&mut self, name: &str, functor: FunctorApp, _arg: &Value) {
+        let name = if functor == FunctorApp::default() {
+            name.to_string()
+        } else {
+            format!("{functor} {name}")
+        };
+        self.call_tree.enter(name, self.live_qubits());
+    }
+
+    fn leave_callable(&mut self) {
+        self.call_tree.leave();
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -612,12 +734,14 @@ enum LayerCache {
     Begin {
         start_depth: usize,
         m_count: usize,
+        call_counts: Vec<Counts>,
     },
     End {
         start_depth: usize,
         end_depth: usize,
         combined_layer: LayerInfo,
         m_count: usize,
+        call_counts: Vec<(usize, Counts)>,
     },
 }
 
@@ -625,4 +749,5 @@ struct RepeatEntry {
     count: usize,
     start_depth: usize,
     m_count: usize,
+    call_counts: Vec<Counts>,
 }
@@ -0,0 +1,192 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::ops::{AddAssign, Mul, Sub};
+
+use crate::system::LogicalResourceBreakdown;
+
+/// Attributes counted resources to the call path in which they occur
+///
+/// Each node corresponds to a call path; calls to the same callable from the
+/// same node share a node.  Node counts only include resources that are
+/// counted directly in the callable, not those of its callees.
+pub(super) struct CallTree {
+    nodes: Vec<Node>,
+    /// Path of nodes that are currently executing, the root is implicit
+    stack: Vec<usize>,
+}
+
+struct Node {
+    name: String,
+    children: Vec<usize>,
+    counts: Counts,
+    max_qubits: usize,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(super) struct Counts {
+    pub t: usize,
+    pub r: usize,
+    pub ccz: usize,
+    pub m: usize,
+}
+
+impl Default for CallTree {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::new("program".into())],
+            stack: vec![],
+        }
+    }
+}
+
+impl CallTree {
+    pub fn enter(&mut self, name: String, live_qubits: usize) {
+        let parent = self.current();
+        let node = if let Some(&node) = self.nodes[parent]
+            .children
+            .iter()
+            .find(|&&child| self.nodes[child].name == name)
+        {
+            node
+        } else {
+            let node = self.nodes.len();
+            self.nodes.push(Node::new(name));
+            self.nodes[parent].children.push(node);
+            node
+        };
+
+        self.stack.push(node);
+        self.allocated(live_qubits);
+    }
+
+    pub fn leave(&mut self) {
+        self.stack.pop();
+    }
+
+    pub fn add(&mut self, counts: Counts) {
+        let current = self.current();
+        self.nodes[current].counts += counts;
+    }
+
+    pub fn allocated(&mut self, live_qubits: usize) {
+        let current = self.current();
+        let max_qubits = &mut self.nodes[current].max_qubits;
+        *max_qubits = (*max_qubits).max(live_qubits);
+    }
+
+    /// The counts of all nodes, to compute the resources counted since then
+    /// with [`CallTree::since`].
+    pub fn snapshot(&self) -> Vec<Counts> {
+        self.nodes.iter().map(|node| node.counts).collect()
+    }
+
+    /// The counts that were added to each node since `snapshot` was taken.
+    pub fn since(&self, snapshot: &[Counts]) -> Vec<(usize, Counts)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let before = snapshot.get(index).copied().unwrap_or_default();
+                (index, node.counts - before)
+            })
+            .filter(|(_, counts)| !counts.is_empty())
+            .collect()
+    }
+
+    /// Adds `factor` times the counts of `deltas` to their nodes.
+    pub fn add_deltas(&mut self, deltas: &[(usize, Counts)], factor: usize) {
+        for &(index, counts) in deltas {
+            self.nodes[index].counts += counts * factor;
+        }
+    }
+
+    pub fn breakdown(&self) -> LogicalResourceBreakdown {
+        self.node_breakdown(0)
+    }
+
+    fn node_breakdown(&self, index: usize) -> LogicalResourceBreakdown {
+        let node = &self.nodes[index];
+        let children: Vec<_> = node
+            .children
+            .iter()
+            .map(|&child| self.node_breakdown(child))
+            .collect();
+
+        let mut breakdown = LogicalResourceBreakdown {
+            name: node.name.clone(),
+            t_count: node.counts.t as _,
+            rotation_count: node.counts.r as _,
+            ccz_count: node.counts.ccz as _,
+            measurement_count: node.counts.m as _,
+            max_qubits: node.max_qubits as _,
+            children: vec![],
+        };
+        for child in &children {
+            breakdown.t_count += child.t_count;
+            breakdown.rotation_count += child.rotation_count;
+            breakdown.ccz_count += child.ccz_count;
+            breakdown.measurement_count += child.measurement_count;
+            breakdown.max_qubits = breakdown.max_qubits.max(child.max_qubits);
+        }
+        breakdown.children = children;
+
+        breakdown
+    }
+
+    fn current(&self) -> usize {
+        self.stack.last().copied().unwrap_or(0)
+    }
+}
+
+impl Node {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            children: vec![],
+            counts: Counts::default(),
+            max_qubits: 0,
+        }
+    }
+}
+
+impl Counts {
+    fn is_empty(&self) -> bool {
+        self.t == 0 && self.r == 0 && self.ccz == 0 && self.m == 0
+    }
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.t += rhs.t;
+        self.r += rhs.r;
+        self.ccz += rhs.ccz;
+        self.m += rhs.m;
+    }
+}
+
+impl Sub for Counts {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            t: self.t - rhs.t,
+            r: self.r - rhs.r,
+            ccz: self.ccz - rhs.ccz,
+            m: self.m - rhs.m,
+        }
+    }
+}
+
+impl Mul<usize> for Counts {
+    type Output = Self;
+
+    fn mul(self, rhs: usize) -> Self {
+        Self {
+            t: self.t * rhs,
+            r: self.r * rhs,
+            ccz: self.ccz * rhs,
+            m: self.m * rhs,
+        }
+    }
+}
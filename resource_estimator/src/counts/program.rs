@@ -0,0 +1,225 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Logical resource counts for programs that have already been compiled, given either as
+//! RIR or as QIR text, without running any Q#.
+
+#[cfg(test)]
+mod tests;
+
+mod qir;
+
+use miette::Diagnostic;
+use qsc::{
+    rir::{BlockId, CallableId, CallableType, Instruction, Literal, Operand, Program},
+    Backend,
+};
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use super::LogicalCounter;
+use crate::system::LogicalResourceCounts;
+
+#[derive(Clone, Debug, Diagnostic, Error, PartialEq)]
+pub enum Error {
+    #[error("the program contains a loop starting at block {0}")]
+    #[diagnostic(code("Qsc.Estimates.Program.Loop"))]
+    #[diagnostic(help(
+        "only programs in which all loops have been unrolled, such as base profile programs, can be estimated"
+    ))]
+    Loop(u32),
+    #[error("callable `{0}` calls itself recursively")]
+    #[diagnostic(code("Qsc.Estimates.Program.Recursion"))]
+    Recursion(String),
+    #[error("intrinsic `{0}` is not supported by the resource estimator")]
+    #[diagnostic(code("Qsc.Estimates.Program.UnsupportedIntrinsic"))]
+    UnsupportedIntrinsic(String),
+    #[error("call to `{0}` has operands that are not known before execution")]
+    #[diagnostic(code("Qsc.Estimates.Program.DynamicOperand"))]
+    DynamicOperand(String),
+    #[error("QIR could not be parsed: {0}")]
+    #[diagnostic(code("Qsc.Estimates.Program.InvalidQir"))]
+    InvalidQir(String),
+}
+
+/// Computes the logical resource counts of a partially evaluated program.
+///
+/// The control-flow graph of the entry callable is walked in topological order and every
+/// reachable block is counted once, so for programs that branch on measurement results the
+/// counts are an upper bound that includes all branches. Loops must already be unrolled,
+/// as they are in base profile programs.
+/// # Errors
+/// Returns an error if the program contains a loop or recursion, calls an intrinsic that
+/// cannot be estimated, or passes a qubit that is not known before execution.
+pub fn logical_counts_from_rir(program: &Program) -> Result<LogicalResourceCounts, Error> {
+    let mut counter = LogicalCounter::default();
+    for _ in 0..program.num_qubits {
+        counter.qubit_allocate();
+    }
+    let mut walker = Walker {
+        program,
+        counter: &mut counter,
+        call_stack: Vec::new(),
+    };
+    walker.callable(program.entry)?;
+    Ok(counter.logical_resources())
+}
+
+/// Computes the logical resource counts of a QIR program given as LLVM IR text.
+/// The program is counted in the same way as by [`logical_counts_from_rir`].
+///
+/// The text is read line by line, as LLVM prints it: one instruction per line, and each
+/// function body starting on the line after its `define` and ending with a line holding
+/// only `}`. Only calls, branches and returns are read. Qubits and results must be constant
+/// ids, written as `null` or `inttoptr`, and results are recognized by their position in
+/// measurement, readout and output recording calls. Other pointers, such as output labels,
+/// can only be passed to `__quantum__rt__` runtime functions.
+/// # Errors
+/// Returns an error if the QIR cannot be parsed or if the program cannot be counted.
+pub fn logical_counts_from_qir(qir: &str) -> Result<LogicalResourceCounts, Error> {
+    logical_counts_from_rir(&qir::parse(qir)?)
+}
+
+struct Walker<'a> {
+    program: &'a Program,
+    counter: &'a mut LogicalCounter,
+    call_stack: Vec<CallableId>,
+}
+
+impl Walker<'_> {
+    fn callable(&mut self, id: CallableId) -> Result<(), Error> {
+        let callable = self.program.get_callable(id);
+        let Some(body) = callable.body else {
+            return Ok(());
+        };
+        if self.call_stack.contains(&id) {
+            return Err(Error::Recursion(callable.name.clone()));
+        }
+        self.call_stack.push(id);
+        for block in self.topological_order(body)? {
+            for instr in &self.program.get_block(block).0 {
+                if let Instruction::Call(callee, args, _) = instr {
+                    self.call(*callee, args)?;
+                }
+            }
+        }
+        self.call_stack.pop();
+        Ok(())
+    }
+
+    /// The blocks reachable from `entry`, each before all of its successors.
+    fn topological_order(&self, entry: BlockId) -> Result<Vec<BlockId>, Error> {
+        let mut order = Vec::new();
+        let mut done = FxHashSet::default();
+        let mut in_progress = FxHashSet::default();
+        // Each entry is a block and whether its successors have been visited.
+        let mut stack = vec![(entry, false)];
+        while let Some((block, visited)) = stack.pop() {
+            if visited {
+                in_progress.remove(&block);
+                done.insert(block);
+                order.push(block);
+                continue;
+            }
+            if done.contains(&block) {
+                continue;
+            }
+            in_progress.insert(block);
+            stack.push((block, true));
+            for successor in successors(self.program, block) {
+                if in_progress.contains(&successor) {
+                    return Err(Error::Loop(successor.0));
+                }
+                if !done.contains(&successor) {
+                    stack.push((successor, false));
+                }
+            }
+        }
+        order.reverse();
+        Ok(order)
+    }
+
+    fn call(&mut self, id: CallableId, args: &[Operand]) -> Result<(), Error> {
+        let callable = self.program.get_callable(id);
+        let name = callable.name.as_str();
+        match callable.call_type {
+            CallableType::Measurement => {
+                let [q, ..] = qubits(name, args)?[..] else {
+                    return Err(Error::DynamicOperand(name.to_string()));
+                };
+                self.counter.m(q);
+            }
+            CallableType::Reset => {
+                for q in qubits(name, args)? {
+                    self.counter.reset(q);
+                }
+            }
+            CallableType::Readout | CallableType::OutputRecording => {}
+            CallableType::Regular if callable.body.is_some() => self.callable(id)?,
+            CallableType::Regular => self.intrinsic(name, args)?,
+        }
+        Ok(())
+    }
+
+    fn intrinsic(&mut self, name: &str, args: &[Operand]) -> Result<(), Error> {
+        let Some(gate) = name.strip_prefix("__quantum__qis__") else {
+            // Runtime functions such as `__quantum__rt__initialize` apply no gates.
+            return Ok(());
+        };
+        let qs = qubits(name, args)?;
+        let angle = args.iter().find_map(|arg| match arg {
+            Operand::Literal(Literal::Double(angle)) => Some(Some(*angle)),
+            Operand::Variable(var) if var.ty == qsc::rir::Ty::Double => Some(None),
+            _ => None,
+        });
+        let counter = &mut *self.counter;
+        match (gate, &qs[..], angle) {
+            ("ccx__body", &[c0, c1, q], _) => counter.ccx(c0, c1, q),
+            ("cx__body" | "cnot__body", &[c, q], _) => counter.cx(c, q),
+            ("cy__body", &[c, q], _) => counter.cy(c, q),
+            ("cz__body", &[c, q], _) => counter.cz(c, q),
+            ("h__body", &[q], _) => counter.h(q),
+            ("s__body", &[q], _) => counter.s(q),
+            ("s__adj", &[q], _) => counter.sadj(q),
+            ("t__body", &[q], _) => counter.t(q),
+            ("t__adj", &[q], _) => counter.tadj(q),
+            ("x__body", &[q], _) => counter.x(q),
+            ("y__body", &[q], _) => counter.y(q),
+            ("z__body", &[q], _) => counter.z(q),
+            ("swap__body", &[q0, q1], _) => counter.swap(q0, q1),
+            ("rx__body" | "ry__body" | "rz__body", &[q], Some(angle)) => {
+                counter.rotation(angle, q);
+            }
+            ("rxx__body" | "ryy__body" | "rzz__body", &[q0, q1], Some(angle)) => {
+                counter.cx(q1, q0);
+                counter.rotation(angle, q0);
+                counter.cx(q1, q0);
+            }
+            _ => return Err(Error::UnsupportedIntrinsic(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+fn successors(program: &Program, block: BlockId) -> Vec<BlockId> {
+    match program.get_block(block).0.last() {
+        Some(Instruction::Jump(target)) => vec![*target],
+        Some(Instruction::Branch(_, if_true, if_false)) => vec![*if_false, *if_true],
+        _ => Vec::new(),
+    }
+}
+
+/// The qubits passed to `name`, which must all be known before execution.
+fn qubits(name: &str, args: &[Operand]) -> Result<Vec<usize>, Error> {
+    let mut qubits = Vec::new();
+    for arg in args {
+        match arg {
+            Operand::Literal(Literal::Qubit(q)) => qubits.push(*q as usize),
+            Operand::Variable(var) if var.ty == qsc::rir::Ty::Qubit => {
+                return Err(Error::DynamicOperand(name.to_string()));
+            }
+            _ => {}
+        }
+    }
+    Ok(qubits)
+}
@@ -0,0 +1,388 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! A reader for the subset of QIR that matters to resource counting: the functions defined in
+//! the module, their control flow, and the calls they make. All other instructions compute
+//! classical values and are skipped.
+
+use qsc::rir::{
+    Block, BlockId, Callable, CallableId, CallableType, Instruction, Literal, Operand, Program, Ty,
+    Variable, VariableId,
+};
+use rustc_hash::FxHashMap;
+
+use super::Error;
+
+/// Reads the QIR module `qir` into a program whose entry is the function with the
+/// `entry_point` attribute, or the only function defined in the module.
+pub(super) fn parse(qir: &str) -> Result<Program, Error> {
+    let mut parser = Parser::default();
+    let mut attributes = FxHashMap::default();
+    let mut functions = Vec::new();
+    let mut lines = qir.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("attributes ") {
+            if let Some((group, attrs)) = rest.split_once('=') {
+                attributes.insert(group.trim().to_string(), attrs.trim().to_string());
+            }
+        } else if line.starts_with("define ") {
+            let (name, groups) = signature(line)?;
+            // Bodies are read line by line, so they have to start on the next line.
+            if !line
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim_end()
+                .ends_with('{')
+            {
+                return Err(Error::InvalidQir(format!(
+                    "the body of function `{name}` does not start on the line after its definition"
+                )));
+            }
+            let id = parser.callable(&name);
+            let body = parser.function(&mut lines)?;
+            let callable = parser
+                .program
+                .callables
+                .get_mut(id)
+                .expect("callable should be present");
+            callable.body = Some(body);
+            callable.call_type = CallableType::Regular;
+            functions.push((id, groups));
+        }
+    }
+
+    // Attribute groups can be declared after the functions that use them.
+    let functions = functions
+        .into_iter()
+        .map(|(id, groups)| {
+            let attrs = groups
+                .iter()
+                .filter_map(|group| attributes.get(group))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ");
+            (id, attrs)
+        })
+        .collect::<Vec<_>>();
+    let (entry, entry_attrs) = match &functions[..] {
+        [] => {
+            return Err(Error::InvalidQir(
+                "the module does not define any function".into(),
+            ))
+        }
+        [function] => function.clone(),
+        _ => functions
+            .iter()
+            .find(|(_, attrs)| attrs.contains("\"entry_point\""))
+            .cloned()
+            .ok_or_else(|| {
+                Error::InvalidQir("no function has the `entry_point` attribute".into())
+            })?,
+    };
+
+    let mut program = parser.program;
+    program.entry = entry;
+    program.num_qubits = attribute_value(&entry_attrs, "required_num_qubits").unwrap_or_default();
+    program.num_results = attribute_value(&entry_attrs, "required_num_results").unwrap_or_default();
+    Ok(program)
+}
+
+/// The name of the function defined on `line` and the attribute groups it refers to.
+fn signature(line: &str) -> Result<(String, Vec<String>), Error> {
+    let invalid = || Error::InvalidQir(format!("invalid function definition `{line}`"));
+    let (_, rest) = line.split_once('@').ok_or_else(invalid)?;
+    let (name, rest) = rest.split_once('(').ok_or_else(invalid)?;
+    let (_, attrs) = rest.rsplit_once(')').ok_or_else(invalid)?;
+    let groups = attrs
+        .split_whitespace()
+        .filter(|attr| attr.starts_with('#'))
+        .map(ToString::to_string)
+        .collect();
+    Ok((name.trim_matches('"').to_string(), groups))
+}
+
+/// The value of an integer attribute such as `"required_num_qubits"="2"`.
+fn attribute_value(attrs: &str, name: &str) -> Option<u32> {
+    let (_, rest) = attrs.split_once(&format!("\"{name}\"=\""))?;
+    let (value, _) = rest.split_once('"')?;
+    value.parse().ok()
+}
+
+const PARAMETER_ATTRIBUTES: [&str; 6] = [
+    "immarg",
+    "nocapture",
+    "nonnull",
+    "noundef",
+    "readonly",
+    "writeonly",
+];
+
+#[derive(Default)]
+struct Parser {
+    program: Program,
+    callables: FxHashMap<String, CallableId>,
+    blocks: FxHashMap<String, BlockId>,
+    variables: FxHashMap<String, VariableId>,
+}
+
+impl Parser {
+    /// Reads the body of a function up to its closing brace and returns its first block.
+    fn function<'a>(
+        &mut self,
+        lines: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<BlockId, Error> {
+        // Labels are local to a function, and the first block may not have one.
+        self.blocks.clear();
+        let entry = self.new_block();
+        let mut current = Some(entry);
+        for line in lines.by_ref() {
+            // Drop comments, such as the predecessors listed after block labels.
+            let line = line.split(';').next().unwrap_or_default().trim();
+            if line == "}" {
+                return Ok(entry);
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(label) = line.strip_suffix(':') {
+                let block = self.block(label);
+                // The implicit first block is replaced if the body starts with a label.
+                if current == Some(entry) && self.program.get_block(entry).0.is_empty() {
+                    self.program
+                        .get_block_mut(entry)
+                        .0
+                        .push(Instruction::Jump(block));
+                }
+                current = Some(block);
+                continue;
+            }
+            let Some(block) = current else {
+                return Err(Error::InvalidQir(format!(
+                    "instruction `{line}` follows a terminator"
+                )));
+            };
+            let instr = self.instruction(line)?;
+            let is_terminator = matches!(
+                instr,
+                Some(Instruction::Jump(_) | Instruction::Branch(..) | Instruction::Return)
+            );
+            if let Some(instr) = instr {
+                self.program.get_block_mut(block).0.push(instr);
+            }
+            if is_terminator {
+                current = None;
+            }
+        }
+        Err(Error::InvalidQir("function body is not closed".into()))
+    }
+
+    fn instruction(&mut self, line: &str) -> Result<Option<Instruction>, Error> {
+        let (result, instr) = match line.split_once(" = ") {
+            Some((result, instr)) if result.starts_with('%') => (Some(result), instr.trim()),
+            _ => (None, line),
+        };
+        let mut words = instr.split_whitespace();
+        match words.next() {
+            Some("ret") => Ok(Some(Instruction::Return)),
+            Some("br") => self.branch(instr).map(Some),
+            Some("call" | "tail" | "musttail" | "notail") => self.call(instr, result).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn branch(&mut self, instr: &str) -> Result<Instruction, Error> {
+        let operands = instr.trim_start_matches("br").trim();
+        let labels = operands
+            .split(',')
+            .filter_map(|operand| operand.trim().strip_prefix("label %"))
+            .map(|label| self.block(label.trim()))
+            .collect::<Vec<_>>();
+        match labels[..] {
+            [target] => Ok(Instruction::Jump(target)),
+            [if_true, if_false] => {
+                let cond = operands
+                    .split(',')
+                    .next()
+                    .and_then(|cond| cond.split_whitespace().last())
+                    .unwrap_or_default();
+                Ok(Instruction::Branch(
+                    self.variable(cond, Ty::Boolean),
+                    if_true,
+                    if_false,
+                ))
+            }
+            _ => Err(Error::InvalidQir(format!("invalid branch `{instr}`"))),
+        }
+    }
+
+    fn call(&mut self, instr: &str, result: Option<&str>) -> Result<Instruction, Error> {
+        let invalid = || Error::InvalidQir(format!("invalid call `{instr}`"));
+        let (_, rest) = instr.split_once('@').ok_or_else(invalid)?;
+        let (name, rest) = rest.split_once('(').ok_or_else(invalid)?;
+        let (args, _) = rest.rsplit_once(')').ok_or_else(invalid)?;
+        let id = self.callable(name);
+        let callable = self.program.get_callable(id);
+        let call_type = callable.call_type;
+        let is_runtime = callable.name.starts_with("__quantum__rt__");
+
+        let mut operands = Vec::new();
+        for arg in split_args(args) {
+            let operand = self.operand(arg)?;
+            // Other pointers, such as output labels, can only be passed to runtime functions,
+            // which apply no gates.
+            if matches!(operand, Operand::Literal(Literal::Pointer)) && !is_runtime {
+                return Err(Error::InvalidQir(format!(
+                    "argument `{arg}` of call `{instr}` is a pointer that is not a qubit or result"
+                )));
+            }
+            operands.push(operand);
+        }
+        // Opaque pointers do not say whether they are qubits or results,
+        // so results are recognized by their position.
+        let result_arg = match call_type {
+            CallableType::Measurement => Some(1),
+            CallableType::Readout | CallableType::OutputRecording => Some(0),
+            _ => None,
+        };
+        if let Some(operand) = result_arg.and_then(|index| operands.get_mut(index)) {
+            if let Operand::Literal(Literal::Qubit(id)) = *operand {
+                *operand = Operand::Literal(Literal::Result(id));
+            }
+        }
+
+        let var = result.map(|result| self.variable(result, Ty::Boolean));
+        Ok(Instruction::Call(id, operands, var))
+    }
+
+    fn operand(&mut self, arg: &str) -> Result<Operand, Error> {
+        let invalid = || Error::InvalidQir(format!("invalid argument `{arg}`"));
+        let (ty, mut value) = arg.split_once(' ').ok_or_else(invalid)?;
+        // Parameter attributes come between the type and the value.
+        while let Some((attr, rest)) = value.trim().split_once(' ') {
+            if !PARAMETER_ATTRIBUTES.contains(&attr) {
+                break;
+            }
+            value = rest;
+        }
+        let value = value.trim();
+        if value.starts_with('%') {
+            let ty = match ty {
+                "double" => Ty::Double,
+                "i1" => Ty::Boolean,
+                ty if ty.starts_with('i') && !ty.ends_with('*') => Ty::Integer,
+                _ => Ty::Qubit,
+            };
+            return Ok(Operand::Variable(self.variable(value, ty)));
+        }
+        let literal = match ty {
+            "double" => Literal::Double(parse_double(value).ok_or_else(invalid)?),
+            "i1" => Literal::Bool(value == "true"),
+            "%Qubit*" => Literal::Qubit(pointer_id(value).ok_or_else(invalid)?),
+            "%Result*" => Literal::Result(pointer_id(value).ok_or_else(invalid)?),
+            // Opaque pointers are qubits when they are constant ids.
+            "ptr" => pointer_id(value).map_or(Literal::Pointer, Literal::Qubit),
+            ty if ty.ends_with('*') => Literal::Pointer,
+            _ => Literal::Integer(value.parse().map_err(|_| invalid())?),
+        };
+        Ok(Operand::Literal(literal))
+    }
+
+    fn callable(&mut self, name: &str) -> CallableId {
+        let name = name.trim().trim_matches('"');
+        if let Some(id) = self.callables.get(name) {
+            return *id;
+        }
+        let id = CallableId(self.callables.len() as u32);
+        self.program.callables.insert(
+            id,
+            Callable {
+                name: name.to_string(),
+                input_type: Vec::new(),
+                output_type: None,
+                body: None,
+                call_type: call_type(name),
+            },
+        );
+        self.callables.insert(name.to_string(), id);
+        id
+    }
+
+    fn block(&mut self, label: &str) -> BlockId {
+        if let Some(id) = self.blocks.get(label) {
+            return *id;
+        }
+        let id = self.new_block();
+        self.blocks.insert(label.to_string(), id);
+        id
+    }
+
+    fn new_block(&mut self) -> BlockId {
+        let id = BlockId(self.program.blocks.iter().count() as u32);
+        self.program.blocks.insert(id, Block::default());
+        id
+    }
+
+    fn variable(&mut self, name: &str, ty: Ty) -> Variable {
+        let next = VariableId(self.variables.len() as u32);
+        let variable_id = *self.variables.entry(name.to_string()).or_insert(next);
+        Variable { variable_id, ty }
+    }
+}
+
+fn call_type(name: &str) -> CallableType {
+    match name {
+        "__quantum__qis__m__body"
+        | "__quantum__qis__mz__body"
+        | "__quantum__qis__mresetz__body" => CallableType::Measurement,
+        "__quantum__qis__reset__body" => CallableType::Reset,
+        "__quantum__qis__read_result__body" | "__quantum__rt__read_result" => CallableType::Readout,
+        name if name.starts_with("__quantum__rt__") && name.ends_with("_record_output") => {
+            CallableType::OutputRecording
+        }
+        _ => CallableType::Regular,
+    }
+}
+
+/// Splits call arguments at the commas that are not nested in parentheses.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(args[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = args[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// The id of a qubit or result pointer, written as `null` or `inttoptr (i64 <id> to <ty>)`.
+fn pointer_id(value: &str) -> Option<u32> {
+    if value == "null" {
+        return Some(0);
+    }
+    let rest = value.strip_prefix("inttoptr")?.trim().strip_prefix('(')?;
+    let mut words = rest.split_whitespace();
+    words.next()?;
+    words.next()?.parse().ok()
+}
+
+/// Parses a floating-point constant, which LLVM may write in hexadecimal.
+fn parse_double(value: &str) -> Option<f64> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(f64::from_bits),
+        None => value.parse().ok(),
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+#![allow(clippy::needless_raw_string_hashes)]
+
+use expect_test::{expect, Expect};
+use indoc::indoc;
+use qsc::{
+    codegen::get_qir,
+    interpret::{GenericReceiver, Interpreter},
+    rir::builder::teleport_program,
+    target::Profile,
+    LanguageFeatures, PackageType, SourceMap,
+};
+
+use super::{logical_counts_from_qir, logical_counts_from_rir, Error};
+use crate::counts::LogicalCounter;
+
+fn qir(source: &str, profile: Profile) -> String {
+    let sources = SourceMap::new([("test".into(), source.into())], None);
+    get_qir(sources, LanguageFeatures::default(), profile.into()).expect("QIR should be generated")
+}
+
+fn check_qir_counts(qir: &str, expect: &Expect) {
+    let counts = logical_counts_from_qir(qir).expect("QIR should be counted");
+    expect.assert_debug_eq(&counts);
+}
+
+#[test]
+fn base_profile_qir_matches_simulated_counts() {
+    let source = indoc! {"
+        namespace Test {
+            @EntryPoint()
+            operation Main() : Result[] {
+                use qs = Qubit[4];
+                for q in qs {
+                    H(q);
+                    Rx(1.0, q);
+                }
+                T(qs[0]);
+                Adjoint T(qs[1]);
+                CCNOT(qs[0], qs[1], qs[2]);
+                Rzz(0.5, qs[2], qs[3]);
+                S(qs[3]);
+                Rz(Microsoft.Quantum.Math.PI() / 4.0, qs[3]);
+                MResetEachZ(qs)
+            }
+        }
+    "};
+
+    let counts =
+        logical_counts_from_qir(&qir(source, Profile::Base)).expect("QIR should be counted");
+
+    let sources = SourceMap::new([("test".into(), source.into())], None);
+    let mut interpreter = Interpreter::new(
+        true,
+        sources,
+        PackageType::Exe,
+        Profile::Unrestricted.into(),
+        LanguageFeatures::default(),
+    )
+    .expect("compilation should succeed");
+    let mut counter = LogicalCounter::default();
+    let mut stdout = std::io::sink();
+    let mut out = GenericReceiver::new(&mut stdout);
+    interpreter
+        .eval_entry_with_sim(&mut counter, &mut out)
+        .expect("evaluation should succeed");
+
+    assert_eq!(
+        format!("{counts:?}"),
+        format!("{:?}", counter.logical_resources())
+    );
+    expect![[r"
+        LogicalResourceCounts {
+            num_qubits: 4,
+            t_count: 3,
+            rotation_count: 5,
+            rotation_depth: 2,
+            ccz_count: 1,
+            ccix_count: 0,
+            measurement_count: 4,
+        }
+    "]]
+    .assert_debug_eq(&counts);
+}
+
+#[test]
+fn branches_are_counted_as_upper_bound() {
+    check_qir_counts(
+        &qir(
+            indoc! {"
+                namespace Test {
+                    @EntryPoint()
+                    operation Main() : Result {
+                        use (q, r) = (Qubit(), Qubit());
+                        H(q);
+                        if M(q) == One {
+                            T(r);
+                        } else {
+                            Rx(1.0, r);
+                        }
+                        MResetZ(r)
+                    }
+                }
+            "},
+            Profile::AdaptiveRI,
+        ),
+        &expect![[r"
+            LogicalResourceCounts {
+                num_qubits: 2,
+                t_count: 1,
+                rotation_count: 1,
+                rotation_depth: 1,
+                ccz_count: 0,
+                ccix_count: 0,
+                measurement_count: 2,
+            }
+        "]],
+    );
+}
+
+#[test]
+fn rir_program_is_counted() {
+    let counts = logical_counts_from_rir(&teleport_program()).expect("program should be counted");
+    expect![[r"
+        LogicalResourceCounts {
+            num_qubits: 3,
+            t_count: 0,
+            rotation_count: 0,
+            rotation_depth: 0,
+            ccz_count: 0,
+            ccix_count: 0,
+            measurement_count: 3,
+        }
+    "]]
+    .assert_debug_eq(&counts);
+}
+
+#[test]
+fn qir_with_opaque_pointers_and_hex_doubles() {
+    check_qir_counts(
+        indoc! {"
+            define void @main() #0 {
+            entry:
+              call void @__quantum__qis__rz__body(double 0x3FE921FB54442D18, ptr null)
+              call void @__quantum__qis__rz__body(double 0x3FF0000000000000, ptr inttoptr (i64 1 to ptr))
+              call void @__quantum__qis__cnot__body(ptr null, ptr inttoptr (i64 1 to ptr))
+              br label %exit
+
+            exit:                                             ; preds = %entry
+              call void @__quantum__qis__mz__body(ptr inttoptr (i64 1 to ptr), ptr writeonly null)
+              ret void
+            }
+
+            declare void @__quantum__qis__rz__body(double, ptr)
+
+            attributes #0 = { \"entry_point\" \"required_num_qubits\"=\"2\" }
+        "},
+        &expect![[r"
+            LogicalResourceCounts {
+                num_qubits: 2,
+                t_count: 1,
+                rotation_count: 1,
+                rotation_depth: 1,
+                ccz_count: 0,
+                ccix_count: 0,
+                measurement_count: 1,
+            }
+        "]],
+    );
+}
+
+#[test]
+fn qir_loop_is_error() {
+    let qir = indoc! {"
+        define void @main() #0 {
+        block_0:
+          br label %block_1
+        block_1:
+          call void @__quantum__qis__t__body(%Qubit* null)
+          %0 = call i1 @__quantum__qis__read_result__body(%Result* null)
+          br i1 %0, label %block_1, label %block_2
+        block_2:
+          ret void
+        }
+
+        attributes #0 = { \"entry_point\" }
+    "};
+    let error = logical_counts_from_qir(qir).expect_err("loop should not be counted");
+    assert_eq!(error, Error::Loop(2));
+}
+
+#[test]
+fn unknown_intrinsic_is_error() {
+    let qir = indoc! {"
+        define void @main() {
+          call void @__quantum__qis__u3__body(double 1.0, double 2.0, double 3.0, %Qubit* null)
+          ret void
+        }
+    "};
+    let error = logical_counts_from_qir(qir).expect_err("intrinsic should not be counted");
+    assert_eq!(
+        error,
+        Error::UnsupportedIntrinsic("__quantum__qis__u3__body".into())
+    );
+}
+
+#[test]
+fn qir_single_line_function_body_is_error() {
+    let qir = indoc! {"
+        define void @main() { call void @__quantum__qis__h__body(ptr null) ret void }
+    "};
+    let error = logical_counts_from_qir(qir).expect_err("body should not be read");
+    assert_eq!(
+        error,
+        Error::InvalidQir(
+            "the body of function `main` does not start on the line after its definition".into()
+        )
+    );
+}
+
+#[test]
+fn qir_pointer_that_is_not_qubit_is_error() {
+    let qir = indoc! {"
+        @0 = internal constant [4 x i8] c\"0_r\\00\"
+
+        define void @main() {
+          call void @__quantum__qis__h__body(ptr @0)
+          ret void
+        }
+    "};
+    let error = logical_counts_from_qir(qir).expect_err("label should not be a qubit");
+    assert_eq!(
+        error,
+        Error::InvalidQir(
+            "argument `ptr @0` of call `call void @__quantum__qis__h__body(ptr @0)` is a pointer that is not a qubit or result".into()
+        )
+    );
+}
+
+#[test]
+fn qir_output_labels_are_accepted() {
+    check_qir_counts(
+        indoc! {"
+            @0 = internal constant [4 x i8] c\"0_r\\00\"
+
+            define void @main() #0 {
+            entry:
+              call void @__quantum__rt__initialize(ptr null)
+              call void @__quantum__qis__h__body(ptr inttoptr (i64 1 to ptr))
+              call void @__quantum__qis__mresetz__body(ptr inttoptr (i64 1 to ptr), ptr null)
+              call void @__quantum__rt__result_record_output(ptr null, ptr @0)
+              ret void
+            }
+
+            attributes #0 = { \"entry_point\" \"required_num_qubits\"=\"2\" \"required_num_results\"=\"1\" }
+        "},
+        &expect![[r#"
+            LogicalResourceCounts {
+                num_qubits: 2,
+                t_count: 0,
+                rotation_count: 0,
+                rotation_depth: 0,
+                ccz_count: 0,
+                ccix_count: 0,
+                measurement_count: 1,
+            }
+        "#]],
+    );
+}
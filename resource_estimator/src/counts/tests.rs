@@ -13,27 +13,50 @@ use qsc::{
     LanguageFeatures, PackageType, SourceMap,
 };
 
+use crate::system::BreakdownMetric;
+
 use super::LogicalCounter;
 
-fn verify_logical_counts(source: &str, entry: Option<&str>, expect: &Expect) {
+fn interpreter(source: &str, entry: Option<&str>) -> Interpreter {
     let source_map = SourceMap::new([("test".into(), source.into())], entry.map(Into::into));
-    let mut interpreter = Interpreter::new(
+    Interpreter::new(
         true,
         source_map,
         PackageType::Exe,
         Profile::Unrestricted.into(),
         LanguageFeatures::default(),
     )
-    .expect("compilation should succeed");
-    let mut counter = LogicalCounter::default();
+    .expect("compilation should succeed")
+}
+
+fn count_resources(source: &str, entry: Option<&str>) -> LogicalCounter {
+    count_resources_with(LogicalCounter::default(), source, entry)
+}
+
+fn count_resources_with(
+    mut counter: LogicalCounter,
+    source: &str,
+    entry: Option<&str>,
+) -> LogicalCounter {
+    let mut interpreter = interpreter(source, entry);
     let mut stdout = std::io::sink();
     let mut out = GenericReceiver::new(&mut stdout);
     interpreter
         .eval_entry_with_sim(&mut counter, &mut out)
         .expect("evaluation should succeed");
+    counter
+}
+
+fn verify_logical_counts(source: &str, entry: Option<&str>, expect: &Expect) {
+    let counter = count_resources(source, entry);
     expect.assert_debug_eq(&counter.logical_resources());
 }
 
+fn verify_flamegraph(source: &str, metric: BreakdownMetric, expect: &Expect) {
+    let counter = count_resources(source, None);
+    expect.assert_eq(&counter.logical_breakdown().flamegraph(metric));
+}
+
 #[test]
 fn gates_are_counted() {
     verify_logical_counts(
@@ -236,3 +259,202 @@ fn pauli_i_rotation_for_global_phase_is_noop() {
         "#]],
     );
 }
+
+#[test]
+fn breakdown_attributes_counts_to_call_stack() {
+    verify_flamegraph(
+        indoc! {"
+            namespace Test {
+                operation Inner(q : Qubit) : Unit is Adj {
+                    T(q);
+                }
+
+                operation Outer(qs : Qubit[]) : Unit {
+                    Inner(qs[0]);
+                    Inner(qs[1]);
+                    T(qs[0]);
+                }
+
+                @EntryPoint()
+                operation Main() : Unit {
+                    use qs = Qubit[2];
+                    Outer(qs);
+                    Adjoint Inner(qs[0]);
+                    Inner(qs[1]);
+                }
+            }
+        "},
+        BreakdownMetric::TCount,
+        &expect![[r#"
+            program;Main;Outer;Inner;T 2
+            program;Main;Outer;T 1
+            program;Main;Adjoint Inner;Adjoint T 1
+            program;Main;Inner;T 1
+        "#]],
+    );
+}
+
+#[test]
+fn breakdown_tracks_max_qubits_per_callable() {
+    let counter = count_resources(
+        indoc! {"
+            namespace Test {
+                operation Helper() : Unit {
+                    use qs = Qubit[3];
+                }
+
+                @EntryPoint()
+                operation Main() : Unit {
+                    use q = Qubit();
+                    Helper();
+                    use more = Qubit[5];
+                }
+            }
+        "},
+        None,
+    );
+    let breakdown = counter.logical_breakdown();
+    assert_eq!(breakdown.max_qubits, 6);
+    let main = &breakdown.children[0];
+    assert_eq!(main.name, "Main");
+    assert_eq!(main.max_qubits, 6);
+    let helper = main
+        .children
+        .iter()
+        .find(|child| child.name == "Helper")
+        .expect("Helper should be in the breakdown");
+    assert_eq!(helper.max_qubits, 4);
+}
+
+#[test]
+fn breakdown_accounts_for_repeat_and_caching() {
+    verify_flamegraph(
+        indoc! {r#"
+            namespace Test {
+                open Microsoft.Quantum.ResourceEstimation;
+
+                operation Rotate(qs : Qubit[]) : Unit {
+                    for q in qs {
+                        Rz(1.0, q);
+                    }
+                }
+
+                @EntryPoint()
+                operation Main() : Unit {
+                    use qs = Qubit[2];
+                    within {
+                        RepeatEstimates(10);
+                    }
+                    apply {
+                        Rotate(qs);
+                    }
+                    for _ in 1..3 {
+                        if BeginEstimateCaching("Rotate", SingleVariant()) {
+                            Rotate(qs);
+                            EndEstimateCaching();
+                        }
+                    }
+                }
+            }
+        "#},
+        BreakdownMetric::RotationCount,
+        &expect![[r#"
+            program;Main;Rotate;Rz 26
+        "#]],
+    );
+}
+
+const PARALLEL_ROTATIONS: &str = indoc! {"
+    namespace Test {
+        @EntryPoint()
+        operation Main() : Unit {
+            for _ in 1..4 {
+                use q = Qubit();
+                Rx(0.1, q);
+                Reset(q);
+            }
+        }
+    }
+"};
+
+#[test]
+fn qubit_budget_trades_depth_for_width() {
+    let width_and_depth = |counter| {
+        let counts = count_resources_with(counter, PARALLEL_ROTATIONS, None).logical_resources();
+        (counts.num_qubits, counts.rotation_depth)
+    };
+
+    assert_eq!(width_and_depth(LogicalCounter::default()), (1, 4));
+    assert_eq!(
+        width_and_depth(LogicalCounter::with_max_logical_qubits(0)),
+        (1, 4)
+    );
+    assert_eq!(
+        width_and_depth(LogicalCounter::with_max_logical_qubits(2)),
+        (2, 2)
+    );
+    assert_eq!(
+        width_and_depth(LogicalCounter::with_max_logical_qubits(3)),
+        (3, 2)
+    );
+    assert_eq!(
+        width_and_depth(LogicalCounter::with_max_logical_qubits(4)),
+        (4, 1)
+    );
+    assert_eq!(
+        width_and_depth(LogicalCounter::with_max_logical_qubits(usize::MAX)),
+        (4, 1)
+    );
+}
+
+#[test]
+fn qubit_budget_is_exceeded_by_live_qubits() {
+    let counts = count_resources_with(
+        LogicalCounter::with_max_logical_qubits(2),
+        indoc! {"
+            namespace Test {
+                @EntryPoint()
+                operation Main() : Unit {
+                    use qs = Qubit[5];
+                    for q in qs {
+                        Rx(0.1, q);
+                    }
+                    ResetAll(qs);
+                }
+            }
+        "},
+        None,
+    )
+    .logical_resources();
+
+    assert_eq!((counts.num_qubits, counts.rotation_depth), (5, 1));
+}
+
+#[test]
+fn space_time_tradeoff_reports_budgets_that_reduce_depth() {
+    let mut interpreter = interpreter(PARALLEL_ROTATIONS, None);
+    let result = crate::estimate_entry_space_time_tradeoff(&mut interpreter, "", 4)
+        .expect("estimation should succeed");
+    let points: Vec<serde_json::Value> =
+        serde_json::from_str(&result).expect("result should be valid json");
+
+    let budgets: Vec<_> = points
+        .iter()
+        .map(|point| {
+            (
+                point["maxLogicalQubits"].as_u64(),
+                point["logicalCounts"]["numQubits"].as_u64(),
+                point["logicalCounts"]["rotationDepth"].as_u64(),
+                point["results"][0]["status"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        budgets,
+        vec![
+            (Some(1), Some(1), Some(4), Some("success")),
+            (Some(2), Some(2), Some(2), Some("success")),
+            (Some(4), Some(4), Some(1), Some("success")),
+        ]
+    );
+}
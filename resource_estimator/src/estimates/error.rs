@@ -37,16 +37,17 @@ pub enum Error {
     #[error("No solution found for the provided maximum number of physical qubits.")]
     #[diagnostic(code("Qsc.Estimates.MaxPhysicalQubitsTooSmall"))]
     MaxPhysicalQubitsTooSmall,
-    /// Resource estimation configuration can never produce T states
+    /// Resource estimation configuration can never produce magic states
     ///
-    /// ✅ This error cannot be triggered by the system.
-    #[error("Resource estimation configuration can never produce T states, required magic state output error rate was {0:.3e}")]
+    /// ✅ This does not contain user data and can be logged
+    /// 🧑‍💻 This indicates a user error
+    #[error("Resource estimation configuration can never produce magic states, required magic state output error rate was {0:.3e}")]
     #[diagnostic(code("Qsc.Estimates.CannotComputeMagicStates"))]
     CannotComputeMagicStates(f64),
     /// Constraint-based search only supports one magic state type.
     ///
-    /// ✅ This error cannot be triggered by the system, since only one magic
-    /// state type is supported.
+    /// ✅ This does not contain user data and can be logged
+    /// 🧑‍💻 This indicates a user error
     #[error("Constraint-based search only supports one magic state type.")]
     #[diagnostic(code("Qsc.Estimates.MultipleMagicStatesNotSupported"))]
     MultipleMagicStatesNotSupported,
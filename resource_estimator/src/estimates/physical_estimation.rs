@@ -619,6 +619,16 @@ impl<
 
             let mut factory_parts = vec![];
 
+            // The error budget for magic states is uniformly distributed among
+            // the types of magic states that are required by the algorithm.
+            let num_required_magic_state_types = (0..self.factory_builder.num_magic_state_types())
+                .filter(|&index| {
+                    self.layout_overhead
+                        .num_magic_states(&self.error_budget, index)
+                        > 0
+                })
+                .count();
+
             for index in 0..self.factory_builder.num_magic_state_types() {
                 let num_magic_states = self
                     .layout_overhead
@@ -630,7 +640,7 @@ impl<
                 }
 
                 let required_logical_magic_state_error_rate = (self.error_budget.magic_states()
-                    / self.factory_builder.num_magic_state_types() as f64)
+                    / num_required_magic_state_types as f64)
                     / (num_magic_states as f64);
 
                 let factories = self
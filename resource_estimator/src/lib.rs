@@ -21,12 +21,20 @@ pub mod estimates;
 /// customizaable gate-based and Majorana qubits, planar codes, and T-factories.
 pub mod system;
 
-pub use system::estimate_physical_resources_from_json;
+pub use system::{
+    estimate_physical_resources_from_json, estimate_physical_resources_sweep_from_json,
+};
 
-use counts::LogicalCounter;
+use counts::{
+    program::{logical_counts_from_qir, logical_counts_from_rir},
+    LogicalCounter,
+};
 use miette::Diagnostic;
 use qsc::interpret::{self, GenericReceiver, Interpreter};
-use system::estimate_physical_resources;
+use system::{
+    estimate_physical_resources, estimate_physical_resources_sweep,
+    estimate_physical_resources_with_breakdown,
+};
 use thiserror::Error;
 
 #[derive(Debug, Diagnostic, Error)]
@@ -34,18 +42,99 @@ use thiserror::Error;
 #[diagnostic(transparent)]
 pub enum Error {
     Interpreter(interpret::Error),
+    Program(counts::program::Error),
     Estimation(system::Error),
 }
 
 pub fn estimate_entry(interpreter: &mut Interpreter, params: &str) -> Result<String, Vec<Error>> {
     let mut counter = LogicalCounter::default();
+    count_entry(interpreter, &mut counter)?;
+    estimate_physical_resources_with_breakdown(
+        counter.logical_resources(),
+        Some(&counter.logical_breakdown()),
+        params,
+    )
+    .map_err(|e| vec![Error::Estimation(e)])
+}
+
+/// Estimates the resources of the entry point for all job parameter
+/// variations in the JSON sweep specification `sweep`, see
+/// [`system::estimate_physical_resources_sweep`].
+pub fn estimate_entry_sweep(
+    interpreter: &mut Interpreter,
+    sweep: &str,
+) -> Result<String, Vec<Error>> {
+    let mut counter = LogicalCounter::default();
+    count_entry(interpreter, &mut counter)?;
+    estimate_physical_resources_sweep(&counter.logical_resources(), sweep)
+        .map_err(|e| vec![Error::Estimation(e)])
+}
+
+/// Estimates the resources of the entry point under different budgets for the
+/// number of logical qubits, see [`LogicalCounter::with_max_logical_qubits`].
+///
+/// Up to `num_budgets` budgets are evenly spaced between the largest number
+/// of qubits the program allocates at the same time, and the number of
+/// qubits it allocates if no qubit is reused.  Budgets that do not reduce the
+/// rotation depth compared to a smaller budget are skipped.  Each remaining
+/// budget is reported with its logical counts and the physical estimation
+/// results for `params`, such that constraints such as `maxPhysicalQubits`
+/// apply to each point of the curve.
+pub fn estimate_entry_space_time_tradeoff(
+    interpreter: &mut Interpreter,
+    params: &str,
+    num_budgets: usize,
+) -> Result<String, Vec<Error>> {
+    let mut counter = LogicalCounter::default();
+    count_entry(interpreter, &mut counter)?;
+    let min_qubits = counter.logical_resources().num_qubits as usize;
+
+    let mut counter = LogicalCounter::with_max_logical_qubits(usize::MAX);
+    count_entry(interpreter, &mut counter)?;
+    let max_qubits = counter.logical_resources().num_qubits as usize;
+
+    let num_budgets = num_budgets.max(2);
+    let mut budgets: Vec<usize> = (0..num_budgets)
+        .map(|index| min_qubits + (max_qubits - min_qubits) * index / (num_budgets - 1))
+        .collect();
+    budgets.dedup();
+
+    let mut points = vec![];
+    let mut min_rotation_depth = u64::MAX;
+    for budget in budgets {
+        let mut counter = LogicalCounter::with_max_logical_qubits(budget);
+        count_entry(interpreter, &mut counter)?;
+        let logical_resources = counter.logical_resources();
+        if logical_resources.rotation_depth >= min_rotation_depth {
+            continue;
+        }
+        min_rotation_depth = logical_resources.rotation_depth;
+
+        let logical_counts = serde_json::to_value(&logical_resources)
+            .expect("serializing to json value should succeed");
+        let results = estimate_physical_resources(logical_resources, params)
+            .map_err(|e| vec![Error::Estimation(e)])?;
+        points.push(serde_json::json!({
+            "maxLogicalQubits": budget,
+            "logicalCounts": logical_counts,
+            "results": serde_json::from_str::<serde_json::Value>(&results)
+                .expect("estimation results should be valid json"),
+        }));
+    }
+
+    Ok(serde_json::to_string(&points).expect("serializing to json string should succeed"))
+}
+
+fn count_entry(
+    interpreter: &mut Interpreter,
+    counter: &mut LogicalCounter,
+) -> Result<(), Vec<Error>> {
     let mut stdout = std::io::sink();
     let mut out = GenericReceiver::new(&mut stdout);
     interpreter
-        .eval_entry_with_sim(&mut counter, &mut out)
+        .eval_entry_with_sim(counter, &mut out)
         .map_err(|e| e.into_iter().map(Error::Interpreter).collect::<Vec<_>>())?;
-    estimate_physical_resources(counter.logical_resources(), params)
-        .map_err(|e| vec![Error::Estimation(e)])
+    Ok(())
 }
 
 pub fn estimate_expr(
@@ -60,6 +149,22 @@ pub fn estimate_expr(
         .run_with_sim(&mut counter, &mut out, expr)
         .map_err(|e| e.into_iter().map(Error::Interpreter).collect::<Vec<_>>())?
         .map_err(|e| vec![Error::Interpreter(e[0].clone())])?;
-    estimate_physical_resources(counter.logical_resources(), params)
-        .map_err(|e| vec![Error::Estimation(e)])
+    estimate_physical_resources_with_breakdown(
+        counter.logical_resources(),
+        Some(&counter.logical_breakdown()),
+        params,
+    )
+    .map_err(|e| vec![Error::Estimation(e)])
+}
+
+/// Estimates the resources of a program that has already been partially evaluated into RIR.
+pub fn estimate_rir(program: &qsc::rir::Program, params: &str) -> Result<String, Vec<Error>> {
+    let counts = logical_counts_from_rir(program).map_err(|e| vec![Error::Program(e)])?;
+    estimate_physical_resources(counts, params).map_err(|e| vec![Error::Estimation(e)])
+}
+
+/// Estimates the resources of a QIR program, such as one produced by another compiler.
+pub fn estimate_qir(qir: &str, params: &str) -> Result<String, Vec<Error>> {
+    let counts = logical_counts_from_qir(qir).map_err(|e| vec![Error::Program(e)])?;
+    estimate_physical_resources(counts, params).map_err(|e| vec![Error::Estimation(e)])
 }
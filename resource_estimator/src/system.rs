@@ -20,16 +20,23 @@ mod optimization;
 mod serialization;
 
 use crate::estimates::{Overhead, PhysicalResourceEstimation};
-use std::rc::Rc;
+use std::{fmt::Write, rc::Rc};
 
 pub use self::modeling::{
     GateBasedPhysicalQubit, MajoranaQubit, PhysicalQubit, Protocol, TFactory,
 };
 pub use self::optimization::TFactoryBuilder;
-pub use self::{data::LogicalResourceCounts, error::Error};
-use data::{EstimateType, JobParams};
+pub use self::{
+    data::{BreakdownMetric, LogicalResourceBreakdown, LogicalResourceCounts},
+    error::Error,
+};
+use data::{
+    pareto_frontier, EstimateType, JobParams, Layout, ParetoPoint, Report, SweepResult,
+    SweepSpecification,
+};
 pub use data::{LayoutReportData, PartitioningOverhead};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 pub(crate) type Result<T> = std::result::Result<T, error::Error>;
 
@@ -47,6 +54,19 @@ pub fn estimate_physical_resources<
 >(
     logical_resources: L,
     params: &str,
+) -> Result<String> {
+    estimate_physical_resources_with_breakdown(logical_resources, None, params)
+}
+
+/// Estimates physical resources like [`estimate_physical_resources`], and adds
+/// the logical resources per callable in `breakdown` to the results that
+/// request them in their `profiling` job parameters.
+pub fn estimate_physical_resources_with_breakdown<
+    L: Overhead + LayoutReportData + PartitioningOverhead + Serialize,
+>(
+    logical_resources: L,
+    breakdown: Option<&LogicalResourceBreakdown>,
+    params: &str,
 ) -> Result<String> {
     let job_params_array = if params.is_empty() {
         vec![JobParams::default()]
@@ -57,7 +77,8 @@ pub fn estimate_physical_resources<
     let mut results: Vec<String> = Vec::with_capacity(job_params_array.len());
     let logical_resources = Rc::new(logical_resources);
     for job_params in job_params_array {
-        let result = estimate_single(logical_resources.clone(), job_params);
+        let result = estimate_single(logical_resources.clone(), job_params)
+            .map(|result| result.with_breakdown(breakdown));
         match result {
             Ok(result) => results.push(
                 serde_json::to_string(&result).expect("serializing to json string should succeed"),
@@ -71,26 +92,160 @@ pub fn estimate_physical_resources<
     Ok(format!("[{}]", results.join(",")))
 }
 
+/// Estimates physical resources like [`estimate_physical_resources_sweep`],
+/// for the logical resource counts in the JSON string `logical_resources`.
+pub fn estimate_physical_resources_sweep_from_json(
+    logical_resources: &str,
+    sweep: &str,
+) -> std::result::Result<String, Error> {
+    let logical_resources: LogicalResourceCounts = serde_json::from_str(logical_resources)
+        .map_err(|e| error::Error::IO(error::IO::CannotParseJSON(e)))?;
+    estimate_physical_resources_sweep(&logical_resources, sweep)
+}
+
+/// Estimates physical resources for all job parameter variations in the JSON
+/// sweep specification `sweep`, and returns the results of each variation
+/// together with their combined Pareto frontier of physical qubits and
+/// runtime.
+///
+/// The variations are estimated in parallel on all available cores, except
+/// when compiling to WebAssembly.
+pub fn estimate_physical_resources_sweep<
+    L: Overhead + LayoutReportData + PartitioningOverhead + Serialize + Clone + Sync,
+>(
+    logical_resources: &L,
+    sweep: &str,
+) -> Result<String> {
+    let sweep: SweepSpecification =
+        serde_json::from_str(sweep).map_err(|e| error::Error::IO(error::IO::CannotParseJSON(e)))?;
+    let job_params_array = sweep.job_params();
+
+    let outcomes = map_in_parallel(&job_params_array, |job_params| {
+        estimate_sweep_item(logical_resources, job_params)
+    });
+
+    let mut results = Vec::with_capacity(outcomes.len());
+    let mut points = vec![];
+    for (result_index, (result, qubits_and_runtime)) in outcomes.into_iter().enumerate() {
+        results.push(result);
+        points.extend(qubits_and_runtime.into_iter().map(
+            |(frontier_index, physical_qubits, runtime)| ParetoPoint {
+                result_index,
+                frontier_index,
+                physical_qubits,
+                runtime,
+            },
+        ));
+    }
+
+    Ok(serde_json::to_string(&SweepResult {
+        results,
+        pareto_frontier: pareto_frontier(points),
+    })
+    .expect("serializing to json string should succeed"))
+}
+
+type SweepItemOutcome = (Value, Vec<(Option<usize>, u64, u64)>);
+
+fn estimate_sweep_item<
+    L: Overhead + LayoutReportData + PartitioningOverhead + Serialize + Clone,
+>(
+    logical_resources: &L,
+    job_params: &Value,
+) -> SweepItemOutcome {
+    let result = JobParams::deserialize(job_params)
+        .map_err(|e| error::Error::IO(error::IO::CannotParseJSON(e)))
+        .and_then(|job_params| estimate_single(Rc::new(logical_resources.clone()), job_params));
+
+    match result {
+        Ok(result) => {
+            let qubits_and_runtime = result.qubits_and_runtime();
+            (
+                serde_json::to_value(&result).expect("serializing to json value should succeed"),
+                qubits_and_runtime,
+            )
+        }
+        Err(err) => (
+            serde_json::to_value(data::Failure::new(err))
+                .expect("serializing to json value should succeed"),
+            vec![],
+        ),
+    }
+}
+
+/// Applies `f` to all items, distributing them over the available cores.
+#[cfg(not(target_arch = "wasm32"))]
+fn map_in_parallel<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let num_threads = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
+        .min(items.len());
+    let next_index = AtomicUsize::new(0);
+
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = vec![];
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        outcomes.push((index, f(item)));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (index, outcome) in worker.join().expect("estimation thread should not panic") {
+                results[index] = Some(outcome);
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("all items should have been processed"))
+        .collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn map_in_parallel<T, R>(items: &[T], f: impl Fn(&T) -> R) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
 fn estimate_single<L: Overhead + LayoutReportData + PartitioningOverhead + Serialize>(
     logical_resources: Rc<L>,
     mut job_params: JobParams,
-) -> Result<data::Success<L>> {
+) -> Result<data::Success<Layout<L>>> {
     let qubit = job_params.qubit_params().clone();
 
     let ftp = Protocol::load_from_specification(job_params.qec_scheme_mut(), &qubit)?;
+    let magic_state_kinds = job_params.magic_state_factories().kinds();
     let distillation_unit_templates = job_params
         .distillation_unit_specifications()
-        .as_templates()?;
+        .as_templates(&magic_state_kinds)?;
     // create error budget partitioning
     let partitioning = job_params
         .error_budget()
         .partitioning(logical_resources.as_ref())?;
+    job_params.layout().validate()?;
+    let layout = Layout::new(
+        job_params.layout().clone(),
+        magic_state_kinds.clone(),
+        logical_resources,
+    );
 
     let mut estimation = PhysicalResourceEstimation::new(
         ftp,
         qubit,
         TFactoryBuilder::default(),
-        logical_resources,
+        Rc::new(layout),
         partitioning,
     );
     if let Some(logical_depth_factor) = job_params.constraints().logical_depth_factor {
@@ -108,6 +263,9 @@ fn estimate_single<L: Overhead + LayoutReportData + PartitioningOverhead + Seria
     estimation
         .factory_builder_mut()
         .set_distillation_unit_templates(distillation_unit_templates);
+    estimation
+        .factory_builder_mut()
+        .set_magic_state_kinds(magic_state_kinds);
 
     match job_params.estimate_type() {
         EstimateType::Frontier => {
@@ -133,6 +291,41 @@ fn estimate_single<L: Overhead + LayoutReportData + PartitioningOverhead + Seria
     }
 }
 
+/// Formats the JSON `results` of a physical resource estimation as
+/// human-readable tables, one for each set of job parameters, based on the
+/// report data of each result.
+pub fn format_summary(results: &str) -> Result<String> {
+    let results: Vec<Value> = serde_json::from_str(results)
+        .map_err(|e| error::Error::IO(error::IO::CannotParseJSON(e)))?;
+
+    let mut summaries = Vec::with_capacity(results.len());
+    for (index, result) in results.iter().enumerate() {
+        let mut summary = if results.len() > 1 {
+            format!("Result {index}\n\n")
+        } else {
+            String::new()
+        };
+
+        if result["status"] == "success" {
+            let report = Report::deserialize(&result["reportData"])
+                .map_err(|e| error::Error::IO(error::IO::CannotParseJSON(e)))?;
+            summary.push_str(&report.summary(result));
+        } else {
+            writeln!(
+                summary,
+                "Error [{}]: {}",
+                result["code"].as_str().unwrap_or_default(),
+                result["message"].as_str().unwrap_or_default()
+            )
+            .expect("writing to string should succeed");
+        }
+
+        summaries.push(summary);
+    }
+
+    Ok(summaries.join("\n"))
+}
+
 fn serialize_error(err: error::Error) -> String {
     serde_json::to_string(&data::Failure::new(err))
         .expect("serializing to json string should succeed")
@@ -18,6 +18,11 @@ pub const NUM_MEASUREMENTS_PER_R: u64 = 1;
 #[allow(clippy::doc_markdown)]
 pub const NUM_MEASUREMENTS_PER_TOF: u64 = 3;
 
+/// Expected number of rotation states to implement one rotation, since each
+/// injection applies the inverse rotation with probability 1/2, which is then
+/// corrected by injecting a rotation state with twice the angle
+pub const NUM_ROTATION_STATES_PER_ROTATION: u64 = 2;
+
 /// A coefficient in Ts per rotation
 pub const NUM_TS_PER_ROTATION_A_COEFFICIENT: f64 = 0.53;
 
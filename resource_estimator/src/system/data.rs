@@ -1,20 +1,26 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+mod breakdown;
 mod constraints;
 mod job_params;
+mod layout;
 mod logical_counts;
 mod physical_counts;
 mod report;
 mod result;
+mod sweep;
 mod tfactory;
 
+pub use breakdown::{BreakdownMetric, LogicalResourceBreakdown};
 pub use constraints::Constraints;
 pub use job_params::{EstimateType, JobParams, PartitioningOverhead};
+pub use layout::{Layout, LayoutSpecification};
 pub use logical_counts::{LayoutReportData, LogicalResourceCounts};
 pub use physical_counts::{PhysicalResourceCounts, PhysicalResourceCountsBreakdown};
 pub use report::{FormattedPhysicalResourceCounts, Report};
 pub use result::{Failure, Success};
+pub use sweep::{pareto_frontier, ParetoPoint, SweepResult, SweepSpecification};
 
 #[cfg(test)]
 pub use tfactory::{
@@ -0,0 +1,100 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+/// Logical resources of a callable in the call tree of a program
+///
+/// All counts include the resources of the callables it calls.  Calls to the
+/// same callable from the same caller are merged into a single entry.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
+pub struct LogicalResourceBreakdown {
+    pub name: String,
+    pub t_count: u64,
+    pub rotation_count: u64,
+    pub ccz_count: u64,
+    pub measurement_count: u64,
+    /// The largest number of qubits that were allocated at the same time
+    /// while the callable was executing
+    pub max_qubits: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<LogicalResourceBreakdown>,
+}
+
+/// The count that is used as sample value in a flamegraph
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
+pub enum BreakdownMetric {
+    TCount,
+    RotationCount,
+    CczCount,
+    MeasurementCount,
+}
+
+impl LogicalResourceBreakdown {
+    /// Returns a copy of the breakdown in which callables that are deeper than
+    /// `depth` below this one are merged into their caller at that depth.
+    #[must_use]
+    pub fn truncated(&self, depth: usize) -> Self {
+        Self {
+            name: self.name.clone(),
+            children: if depth == 0 {
+                Vec::new()
+            } else {
+                self.children
+                    .iter()
+                    .map(|child| child.truncated(depth - 1))
+                    .collect()
+            },
+            ..*self
+        }
+    }
+
+    /// Exports the breakdown in the collapsed stack format that is read by
+    /// flamegraph tools, i.e., one line `caller;callee value` per call path
+    /// with the value of `metric` that is not accounted for by callees.
+    #[must_use]
+    pub fn flamegraph(&self, metric: BreakdownMetric) -> String {
+        let mut output = String::new();
+        self.write_collapsed_stacks(metric, &mut Vec::new(), &mut output);
+        output
+    }
+
+    fn write_collapsed_stacks<'a>(
+        &'a self,
+        metric: BreakdownMetric,
+        path: &mut Vec<&'a str>,
+        output: &mut String,
+    ) {
+        path.push(&self.name);
+
+        let value = self.count(metric)
+            - self
+                .children
+                .iter()
+                .map(|child| child.count(metric))
+                .sum::<u64>();
+        if value > 0 {
+            writeln!(output, "{} {value}", path.join(";"))
+                .expect("writing to string should succeed");
+        }
+
+        for child in &self.children {
+            child.write_collapsed_stacks(metric, path, output);
+        }
+
+        path.pop();
+    }
+
+    fn count(&self, metric: BreakdownMetric) -> u64 {
+        match metric {
+            BreakdownMetric::TCount => self.t_count,
+            BreakdownMetric::RotationCount => self.rotation_count,
+            BreakdownMetric::CczCount => self.ccz_count,
+            BreakdownMetric::MeasurementCount => self.measurement_count,
+        }
+    }
+}
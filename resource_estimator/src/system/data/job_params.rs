@@ -11,7 +11,10 @@ use super::super::{
 };
 use crate::estimates::ErrorBudget;
 
-use super::{tfactory::TFactoryDistillationUnitSpecifications, Constraints};
+use super::{
+    tfactory::{MagicStateFactories, TFactoryDistillationUnitSpecifications},
+    BreakdownMetric, Constraints, LayoutSpecification,
+};
 
 #[derive(Default, Deserialize, Serialize)]
 #[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
@@ -36,6 +39,12 @@ pub struct JobParams {
 
     #[serde(default)]
     estimate_type: EstimateType,
+
+    #[serde(default, skip_serializing_if = "LayoutSpecification::is_default")]
+    layout: LayoutSpecification,
+
+    #[serde(default, skip_serializing_if = "MagicStateFactories::is_default")]
+    magic_state_factories: MagicStateFactories,
 }
 
 impl JobParams {
@@ -69,6 +78,12 @@ impl JobParams {
         &self.constraints
     }
 
+    #[must_use]
+    #[inline]
+    pub fn profiling(&self) -> &Profiling {
+        &self.profiling
+    }
+
     #[must_use]
     #[inline]
     pub fn distillation_unit_specifications(&self) -> &TFactoryDistillationUnitSpecifications {
@@ -80,15 +95,32 @@ impl JobParams {
     pub fn estimate_type(&self) -> &EstimateType {
         &self.estimate_type
     }
+
+    #[must_use]
+    #[inline]
+    pub fn layout(&self) -> &LayoutSpecification {
+        &self.layout
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn magic_state_factories(&self) -> &MagicStateFactories {
+        &self.magic_state_factories
+    }
 }
 
 #[derive(Serialize, Deserialize, Default, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Profiling {
+    /// Adds the logical resources per callable up to this depth in the call
+    /// tree to the result
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub call_stack_depth: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub inline_functions: Option<bool>,
+    /// Adds a flamegraph of this count per callable to the result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flamegraph: Option<BreakdownMetric>,
 }
 
 impl Profiling {
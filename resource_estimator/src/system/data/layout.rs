@@ -0,0 +1,258 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    estimates::{ErrorBudget, Overhead},
+    system::{
+        constants::{
+            NUM_MEASUREMENTS_PER_R, NUM_MEASUREMENTS_PER_TOF, NUM_ROTATION_STATES_PER_ROTATION,
+        },
+        error::Error,
+        modeling::MagicStateKind,
+    },
+};
+
+use super::{LayoutReportData, PartitioningOverhead};
+
+/// The logical layout model that maps pre-layout logical resources to
+/// logical qubits, logical cycles, and magic states
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(
+    tag = "name",
+    rename_all(serialize = "camelCase", deserialize = "camelCase"),
+    deny_unknown_fields
+)]
+pub enum LayoutSpecification {
+    /// Parallel Synthesis Sequential Pauli Computation (PSSPC), which is the
+    /// layout implemented by the logical resource counts themselves
+    #[default]
+    Psspc,
+    /// A 2D lattice surgery layout in which data tiles are surrounded by
+    /// routing tiles, and magic states are consumed through a fixed number of
+    /// ports
+    #[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
+    LatticeSurgery {
+        /// Fraction of all tiles reserved for routing (in `[0, 1)`)
+        #[serde(default = "LayoutSpecification::default_routing_fraction")]
+        routing_fraction: f64,
+        /// Number of magic states that can be consumed in the same logical
+        /// cycle
+        #[serde(default = "LayoutSpecification::default_magic_state_ports")]
+        magic_state_ports: u64,
+    },
+}
+
+impl LayoutSpecification {
+    fn default_routing_fraction() -> f64 {
+        0.5
+    }
+
+    fn default_magic_state_ports() -> u64 {
+        1
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> crate::system::Result<()> {
+        if let Self::LatticeSurgery {
+            routing_fraction,
+            magic_state_ports,
+        } = *self
+        {
+            if !(0.0..1.0).contains(&routing_fraction) {
+                return Err(Error::InvalidValue(
+                    String::from("layout.routingFraction"),
+                    0.0,
+                    1.0,
+                ));
+            }
+
+            if magic_state_ports == 0 {
+                return Err(Error::InvalidValue(
+                    String::from("layout.magicStatePorts"),
+                    1.0,
+                    f64::INFINITY,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the layout selected in the job parameters to some logical
+/// resources
+///
+/// The magic state kinds determine which magic state index is used for which
+/// kind of magic states.  CCZ gates and rotations are implemented using T
+/// states, unless there is a dedicated kind of magic states for them.
+///
+/// Serializes as the wrapped logical resources, such that the logical counts
+/// in the output do not depend on the layout.
+pub struct Layout<L> {
+    specification: LayoutSpecification,
+    magic_state_kinds: Vec<MagicStateKind>,
+    counts: Rc<L>,
+}
+
+impl<L> Layout<L> {
+    pub fn new(
+        specification: LayoutSpecification,
+        magic_state_kinds: Vec<MagicStateKind>,
+        counts: Rc<L>,
+    ) -> Self {
+        Self {
+            specification,
+            magic_state_kinds,
+            counts,
+        }
+    }
+
+    fn has_magic_state_kind(&self, kind: MagicStateKind) -> bool {
+        self.magic_state_kinds.contains(&kind)
+    }
+}
+
+impl<L: LayoutReportData> Layout<L> {
+    /// The number of logical cycles to apply a layer of rotations
+    fn cycles_per_rotation(&self, budget: &ErrorBudget) -> u64 {
+        if self.has_magic_state_kind(MagicStateKind::Rotation) {
+            NUM_ROTATION_STATES_PER_ROTATION * NUM_MEASUREMENTS_PER_R
+        } else {
+            self.counts
+                .num_ts_per_rotation(budget.rotations())
+                .unwrap_or_default()
+                * NUM_MEASUREMENTS_PER_R
+        }
+    }
+}
+
+impl<L: Overhead + LayoutReportData> Overhead for Layout<L> {
+    fn logical_qubits(&self) -> u64 {
+        match self.specification {
+            LayoutSpecification::Psspc => self.counts.logical_qubits(),
+            LayoutSpecification::LatticeSurgery {
+                routing_fraction, ..
+            } => (self.counts.num_qubits() as f64 / (1.0 - routing_fraction)).ceil() as u64,
+        }
+    }
+
+    fn logical_depth(&self, budget: &ErrorBudget) -> u64 {
+        match self.specification {
+            LayoutSpecification::Psspc if !self.has_magic_state_kind(MagicStateKind::Rotation) => {
+                self.counts.logical_depth(budget)
+            }
+            LayoutSpecification::Psspc => {
+                let counts = self.counts.as_ref();
+                (counts.measurement_count() + counts.rotation_count() + counts.t_count())
+                    * NUM_MEASUREMENTS_PER_R
+                    + (counts.ccz_count() + counts.ccix_count()) * NUM_MEASUREMENTS_PER_TOF
+                    + self.cycles_per_rotation(budget) * counts.rotation_depth()
+            }
+            LayoutSpecification::LatticeSurgery {
+                magic_state_ports, ..
+            } => {
+                // Non-Clifford operations are executed in parallel as long as
+                // there are enough ports to consume their magic states, but
+                // rotations in different layers remain sequential.
+                let counts = self.counts.as_ref();
+                let rotation_steps = counts
+                    .rotation_depth()
+                    .max(counts.rotation_count().div_ceil(magic_state_ports));
+
+                counts.measurement_count() * NUM_MEASUREMENTS_PER_R
+                    + counts.t_count().div_ceil(magic_state_ports) * NUM_MEASUREMENTS_PER_R
+                    + (counts.ccz_count() + counts.ccix_count()).div_ceil(magic_state_ports)
+                        * NUM_MEASUREMENTS_PER_TOF
+                    + self.cycles_per_rotation(budget) * rotation_steps
+            }
+        }
+    }
+
+    fn num_magic_states(&self, budget: &ErrorBudget, index: usize) -> u64 {
+        // The number of magic states does not depend on the layout, but on
+        // the kinds of magic states that are available.
+        if self.magic_state_kinds.len() == 1 {
+            return self.counts.num_magic_states(budget, index);
+        }
+
+        let counts = self.counts.as_ref();
+        let num_cczs = counts.ccz_count() + counts.ccix_count();
+        match self.magic_state_kinds[index] {
+            MagicStateKind::T => {
+                let mut num_ts = counts.t_count();
+                if !self.has_magic_state_kind(MagicStateKind::Ccz) {
+                    num_ts += 4 * num_cczs;
+                }
+                if !self.has_magic_state_kind(MagicStateKind::Rotation) {
+                    num_ts += counts
+                        .num_ts_per_rotation(budget.rotations())
+                        .unwrap_or_default()
+                        * counts.rotation_count();
+                }
+                num_ts
+            }
+            MagicStateKind::Ccz => num_cczs,
+            MagicStateKind::Rotation => NUM_ROTATION_STATES_PER_ROTATION * counts.rotation_count(),
+        }
+    }
+}
+
+impl<L: PartitioningOverhead> PartitioningOverhead for Layout<L> {
+    fn has_tgates(&self) -> bool {
+        self.counts.has_tgates()
+    }
+
+    fn has_rotations(&self) -> bool {
+        self.counts.has_rotations()
+    }
+}
+
+impl<L: LayoutReportData> LayoutReportData for Layout<L> {
+    fn num_qubits(&self) -> u64 {
+        self.counts.num_qubits()
+    }
+
+    fn t_count(&self) -> u64 {
+        self.counts.t_count()
+    }
+
+    fn rotation_count(&self) -> u64 {
+        self.counts.rotation_count()
+    }
+
+    fn rotation_depth(&self) -> u64 {
+        self.counts.rotation_depth()
+    }
+
+    fn ccz_count(&self) -> u64 {
+        self.counts.ccz_count()
+    }
+
+    fn ccix_count(&self) -> u64 {
+        self.counts.ccix_count()
+    }
+
+    fn measurement_count(&self) -> u64 {
+        self.counts.measurement_count()
+    }
+
+    fn num_ts_per_rotation(&self, eps_synthesis: f64) -> Option<u64> {
+        self.counts.num_ts_per_rotation(eps_synthesis)
+    }
+}
+
+impl<L: Serialize> Serialize for Layout<L> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.counts.serialize(serializer)
+    }
+}
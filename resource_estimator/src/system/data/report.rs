@@ -6,19 +6,22 @@
 #[cfg(test)]
 mod tests;
 
-use serde::Serialize;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::estimates::{Factory, FactoryPart, Overhead, PhysicalResourceEstimationResult};
 use crate::system::modeling::Protocol;
 
-use super::LayoutReportData;
 use super::{
-    super::modeling::{PhysicalInstructionSet, TFactory},
+    super::modeling::{MagicStateKind, PhysicalInstructionSet, TFactory},
     job_params::JobParams,
 };
+use super::{LayoutReportData, LayoutSpecification};
 
-#[derive(Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
 pub struct Report {
     groups: Vec<ReportEntryGroup>,
     assumptions: Vec<String>,
@@ -36,8 +39,8 @@ impl Report {
         formatted_counts: &FormattedPhysicalResourceCounts,
     ) -> Self {
         let logical_counts = result.layout_overhead();
-        // In this system, we consider T as the only magic state type, therefore
-        // there is only one factory part in the result.
+        // T states are always the first magic state type, factories for other
+        // kinds of magic states are reported in a separate group.
         let part = result.factory_parts()[0].as_ref();
         let factory = part.map(FactoryPart::factory);
 
@@ -55,8 +58,17 @@ impl Report {
         });
 
         let mut entries = vec![];
-        entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalQubits", "Logical algorithmic qubits", r#"Number of logical qubits for the algorithm after layout"#, &format!(r#"Laying out the logical qubits in the presence of nearest-neighbor constraints requires additional logical qubits.  In particular, to layout the $Q_{{\rm alg}} = {}$ logical qubits in the input algorithm, we require in total $2 \cdot Q_{{\rm alg}} + \lceil \sqrt{{8 \cdot Q_{{\rm alg}}}}\rceil + 1 = {}$ logical qubits."#, format_thousand_sep(&logical_counts.num_qubits()), format_thousand_sep(&result.layout_overhead().logical_qubits()))));
-        entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalDepth", "Algorithmic depth", r#"Number of logical cycles for the algorithm"#, &format!(r#"To execute the algorithm using _Parallel Synthesis Sequential Pauli Computation_ (PSSPC), operations are scheduled in terms of multi-qubit Pauli measurements, for which assume an execution time of one logical cycle.  Based on the input algorithm, we require one multi-qubit measurement for the {} single-qubit measurements, the {} arbitrary single-qubit rotations, and the {} T gates, three multi-qubit measurements for each of the {} CCZ and {} CCiX gates in the input program, as well as {} multi-qubit measurements for each of the {} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation."#, format_thousand_sep(&logical_counts.measurement_count()), format_thousand_sep(&logical_counts.rotation_count()), format_thousand_sep(&logical_counts.t_count()), format_thousand_sep(&logical_counts.ccz_count()), format_thousand_sep(&logical_counts.ccix_count()), formatted_counts.num_ts_per_rotation, format_thousand_sep(&logical_counts.rotation_depth()))));
+        if let LayoutSpecification::LatticeSurgery {
+            routing_fraction,
+            magic_state_ports,
+        } = *job_params.layout()
+        {
+            entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalQubits", "Logical algorithmic qubits", r#"Number of logical qubits for the algorithm after layout"#, &format!(r#"In the lattice surgery layout, the $Q_{{\rm alg}} = {}$ logical qubits in the input algorithm are placed on data tiles, and a fraction $f = {}$ of all tiles is reserved as routing space for lattice surgery operations.  We therefore require in total $\lceil Q_{{\rm alg}} / (1 - f) \rceil = {}$ logical qubits."#, format_thousand_sep(&logical_counts.num_qubits()), routing_fraction, format_thousand_sep(&result.layout_overhead().logical_qubits()))));
+            entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalDepth", "Algorithmic depth", r#"Number of logical cycles for the algorithm"#, &format!(r#"In the lattice surgery layout, operations are scheduled in terms of lattice surgery operations through the routing space, for which we assume an execution time of one logical cycle, and up to {} magic states are consumed in parallel in the same logical cycle.  Based on the input algorithm, we require one logical cycle for each of the {} single-qubit measurements, one logical cycle for each group of up to {} of the {} T gates, three logical cycles for each group of up to {} of the {} CCZ and {} CCiX gates, as well as {} logical cycles for each group of up to {} of the {} arbitrary single-qubit rotations, but at least for each of the {} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation."#, magic_state_ports, format_thousand_sep(&logical_counts.measurement_count()), magic_state_ports, format_thousand_sep(&logical_counts.t_count()), magic_state_ports, format_thousand_sep(&logical_counts.ccz_count()), format_thousand_sep(&logical_counts.ccix_count()), formatted_counts.num_ts_per_rotation, magic_state_ports, format_thousand_sep(&logical_counts.rotation_count()), format_thousand_sep(&logical_counts.rotation_depth()))));
+        } else {
+            entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalQubits", "Logical algorithmic qubits", r#"Number of logical qubits for the algorithm after layout"#, &format!(r#"Laying out the logical qubits in the presence of nearest-neighbor constraints requires additional logical qubits.  In particular, to layout the $Q_{{\rm alg}} = {}$ logical qubits in the input algorithm, we require in total $2 \cdot Q_{{\rm alg}} + \lceil \sqrt{{8 \cdot Q_{{\rm alg}}}}\rceil + 1 = {}$ logical qubits."#, format_thousand_sep(&logical_counts.num_qubits()), format_thousand_sep(&result.layout_overhead().logical_qubits()))));
+            entries.push(ReportEntry::new("physicalCountsFormatted/algorithmicLogicalDepth", "Algorithmic depth", r#"Number of logical cycles for the algorithm"#, &format!(r#"To execute the algorithm using _Parallel Synthesis Sequential Pauli Computation_ (PSSPC), operations are scheduled in terms of multi-qubit Pauli measurements, for which assume an execution time of one logical cycle.  Based on the input algorithm, we require one multi-qubit measurement for the {} single-qubit measurements, the {} arbitrary single-qubit rotations, and the {} T gates, three multi-qubit measurements for each of the {} CCZ and {} CCiX gates in the input program, as well as {} multi-qubit measurements for each of the {} non-Clifford layers in which there is at least one single-qubit rotation with an arbitrary angle rotation."#, format_thousand_sep(&logical_counts.measurement_count()), format_thousand_sep(&logical_counts.rotation_count()), format_thousand_sep(&logical_counts.t_count()), format_thousand_sep(&logical_counts.ccz_count()), format_thousand_sep(&logical_counts.ccix_count()), formatted_counts.num_ts_per_rotation, format_thousand_sep(&logical_counts.rotation_depth()))));
+        }
         entries.push(ReportEntry::new("physicalCountsFormatted/logicalDepth", "Logical depth", r#"Number of logical cycles performed"#, &format!(r#"This number is usually equal to the logical depth of the algorithm, which is {}.  However, in the case in which a single T factory is slower than the execution time of the algorithm, we adjust the logical cycle depth to exceed the T factory's execution time."#, format_thousand_sep(&result.algorithmic_logical_depth()))));
         entries.push(ReportEntry::new("physicalCountsFormatted/clockFrequency", "Clock frequency", r#"Number of logical cycles per second"#, &format!(r#"This is the number of logical cycles that can be performed within one second.  The logical cycle time is {}."#, formatted_counts.logical_cycle_time)));
         entries.push(ReportEntry::new("physicalCountsFormatted/numTstates", "Number of T states", r#"Number of T states consumed by the algorithm"#, &format!(r#"To execute the algorithm, we require one T state for each of the {} T gates, four T states for each of the {} CCZ and {} CCiX gates, as well as {} for each of the {} single-qubit rotation gates with arbitrary angle rotation."#, format_thousand_sep(&logical_counts.t_count()), format_thousand_sep(&logical_counts.ccz_count()), format_thousand_sep(&logical_counts.ccix_count()), formatted_counts.num_ts_per_rotation, format_thousand_sep(&logical_counts.rotation_count()))));
@@ -74,11 +86,19 @@ impl Report {
         });
 
         let mut entries = vec![];
-        entries.push(ReportEntry::new("jobParams/qecScheme/name", "QEC scheme", r#"Name of QEC scheme"#, r#"You can load pre-defined QEC schemes by using the name `surface_code` or `floquet_code`. The latter only works with Majorana qubits."#));
-        entries.push(ReportEntry::new("logicalQubit/codeDistance", "Code distance", r#"Required code distance for error correction"#, &format!(r#"The code distance is the smallest odd integer greater or equal to $\dfrac{{2\log({} / {})}}{{\log({}/{})}} - 1$"#, job_params.qec_scheme().crossing_prefactor.expect("crossing prefactor should be set"), result.required_logical_error_rate(), job_params.qec_scheme().error_correction_threshold.expect("error correction threshold should be set"), result.logical_patch().physical_qubit().clifford_error_rate())));
+        entries.push(ReportEntry::new("jobParams/qecScheme/name", "QEC scheme", r#"Name of QEC scheme"#, r#"You can load pre-defined QEC schemes by using the name `surface_code`, `floquet_code`, `color_code`, or `qldpc_code`. The `floquet_code` scheme only works with Majorana qubits, whereas the `color_code` and `qldpc_code` schemes only work with gate-based qubits."#));
+        if let Some(logical_error_rate) = job_params.qec_scheme().logical_error_rate.as_ref() {
+            entries.push(ReportEntry::new("logicalQubit/codeDistance", "Code distance", r#"Required code distance for error correction"#, &format!(r#"The code distance is the smallest odd integer for which the logical error rate computed using the formula {}, divided by the {} logical qubits in a patch, is at most {}."#, logical_error_rate, result.logical_patch().logical_qubits(), result.required_logical_error_rate())));
+        } else {
+            entries.push(ReportEntry::new("logicalQubit/codeDistance", "Code distance", r#"Required code distance for error correction"#, &format!(r#"The code distance is the smallest odd integer greater or equal to $\dfrac{{2\log({} / {})}}{{\log({}/{})}} - 1$"#, job_params.qec_scheme().crossing_prefactor.expect("crossing prefactor should be set"), result.required_logical_error_rate(), job_params.qec_scheme().error_correction_threshold.expect("error correction threshold should be set"), result.logical_patch().physical_qubit().clifford_error_rate())));
+        }
         entries.push(ReportEntry::new("physicalCountsFormatted/physicalQubitsPerLogicalQubit", "Physical qubits", r#"Number of physical qubits per logical qubit"#, &format!(r#"The number of physical qubits per logical qubit are evaluated using the formula {} that can be user-specified."#, job_params.qec_scheme().physical_qubits_per_logical_qubit.as_ref().expect("physical qubits per logical qubit should be set"))));
         entries.push(ReportEntry::new("physicalCountsFormatted/logicalCycleTime", "Logical cycle time", r#"Duration of a logical cycle in nanoseconds"#, &format!(r#"The runtime of one logical cycle in nanoseconds is evaluated using the formula {} that can be user-specified."#, job_params.qec_scheme().logical_cycle_time.as_ref().expect("logical cycle time should be set"))));
-        entries.push(ReportEntry::new("physicalCountsFormatted/logicalErrorRate", "Logical qubit error rate", r#"Logical qubit error rate"#, &format!(r#"The logical qubit error rate is computed as ${} \cdot \left(\dfrac{{{}}}{{{}}}\right)^\frac{{{} + 1}}{{2}}$"#, job_params.qec_scheme().crossing_prefactor.expect("crossing prefactor should be set"), result.logical_patch().physical_qubit().clifford_error_rate(), job_params.qec_scheme().error_correction_threshold.expect("error correction threshold should be set"), result.logical_patch().code_parameter())));
+        if let Some(logical_error_rate) = job_params.qec_scheme().logical_error_rate.as_ref() {
+            entries.push(ReportEntry::new("physicalCountsFormatted/logicalErrorRate", "Logical qubit error rate", r#"Logical qubit error rate"#, &format!(r#"The logical qubit error rate is computed using the formula {} that can be user-specified, for a physical error rate of {} and a code distance of {}."#, logical_error_rate, result.logical_patch().physical_qubit().clifford_error_rate(), result.logical_patch().code_parameter())));
+        } else {
+            entries.push(ReportEntry::new("physicalCountsFormatted/logicalErrorRate", "Logical qubit error rate", r#"Logical qubit error rate"#, &format!(r#"The logical qubit error rate is computed as ${} \cdot \left(\dfrac{{{}}}{{{}}}\right)^\frac{{{} + 1}}{{2}}$"#, job_params.qec_scheme().crossing_prefactor.expect("crossing prefactor should be set"), result.logical_patch().physical_qubit().clifford_error_rate(), job_params.qec_scheme().error_correction_threshold.expect("error correction threshold should be set"), result.logical_patch().code_parameter())));
+        }
         entries.push(ReportEntry::new("jobParams/qecScheme/crossingPrefactor", "Crossing prefactor", r#"Crossing prefactor used in QEC scheme"#, r#"The crossing prefactor is usually extracted numerically from simulations when fitting an exponential curve to model the relationship between logical and physical error rate."#));
         entries.push(ReportEntry::new("jobParams/qecScheme/errorCorrectionThreshold", "Error correction threshold", r#"Error correction threshold used in QEC scheme"#, r#"The error correction threshold is the physical error rate below which the error rate of the logical qubit is less than the error rate of the physical qubit that constitute it.  This value is usually extracted numerically from simulations of the logical error rate."#));
         entries.push(ReportEntry::new(
@@ -91,6 +111,12 @@ impl Report {
             ),
         ));
         entries.push(ReportEntry::new("jobParams/qecScheme/physicalQubitsPerLogicalQubit", "Physical qubits formula", r#"QEC scheme formula used to compute number of physical qubits per logical qubit"#, &format!(r#"This is the formula that is used to compute the number of physical qubits per logical qubits {}."#, format_thousand_sep(&result.logical_patch().physical_qubits()))));
+        if job_params.qec_scheme().logical_qubits_per_patch.is_some() {
+            entries.push(ReportEntry::new("jobParams/qecScheme/logicalQubitsPerPatch", "Logical qubits formula", r#"QEC scheme formula used to compute number of logical qubits per patch"#, &format!(r#"This is the formula that is used to compute the number of logical qubits {} that are encoded in one patch.  The physical qubits of the patch are shared among all its logical qubits."#, format_thousand_sep(&result.logical_patch().logical_qubits()))));
+        }
+        if job_params.qec_scheme().logical_error_rate.is_some() {
+            entries.push(ReportEntry::new("jobParams/qecScheme/logicalErrorRate", "Logical error rate formula", r#"QEC scheme formula used to compute the logical error rate of a patch"#, &format!(r#"This is the formula that is used to compute the logical error rate {:.2e} of a patch, which replaces the default formula based on the crossing prefactor and the error correction threshold."#, result.logical_patch().logical_error_rate())));
+        }
         groups.push(ReportEntryGroup {
             title: "Logical qubit parameters".into(),
             always_visible: false,
@@ -216,6 +242,48 @@ impl Report {
             entries,
         });
 
+        if matches!(
+            job_params.layout(),
+            LayoutSpecification::LatticeSurgery { .. }
+        ) {
+            let mut entries = vec![];
+            entries.push(ReportEntry::new("jobParams/layout/routingFraction", "Routing fraction", r#"Fraction of all tiles reserved for routing in the lattice surgery layout"#, r#"This is the fraction of logical tiles that do not hold data qubits but are used as routing space to execute lattice surgery operations between data qubits and to bring magic states to them."#));
+            entries.push(ReportEntry::new("jobParams/layout/magicStatePorts", "Magic state ports", r#"Number of magic states consumed in parallel in the lattice surgery layout"#, r#"This is the number of ports through which magic states enter the routing space, i.e., the number of magic states that can be consumed by the algorithm in the same logical cycle."#));
+            groups.push(ReportEntryGroup {
+                title: "Logical layout".into(),
+                always_visible: false,
+                entries,
+            });
+        }
+
+        if !job_params.magic_state_factories().is_default() {
+            let mut entries = vec![];
+            for (index, (kind, part)) in job_params
+                .magic_state_factories()
+                .kinds()
+                .into_iter()
+                .zip(result.factory_parts())
+                .enumerate()
+            {
+                let Some(part) = part else {
+                    continue;
+                };
+                let path = match kind {
+                    MagicStateKind::T => "magicStateFactories/t",
+                    MagicStateKind::Ccz => "magicStateFactories/ccz",
+                    MagicStateKind::Rotation => "magicStateFactories/rotation",
+                };
+                entries.push(ReportEntry::new(&format!("{path}/numFactories"), &format!("Number of {kind} factories"), &format!("Number of factories producing {kind} states"), &format!("The {} copies of the {kind} factory produce the {} {kind} states during the algorithm's runtime in {} runs.", format_thousand_sep(&part.copies()), format_thousand_sep(&result.num_magic_states(index)), format_thousand_sep(&part.runs()))));
+                entries.push(ReportEntry::new(&format!("{path}/physicalQubits"), &format!("Physical {kind} factory qubits"), &format!("Number of physical qubits for the {kind} factories"), &format!("Each {kind} factory requires {} physical qubits and we run {} in parallel.", format_thousand_sep(&part.factory().physical_qubits()), format_thousand_sep(&part.copies()))));
+                entries.push(ReportEntry::new(&format!("{path}/factory/unitNamePerRound"), &format!("Distillation units for {kind} states"), &format!("Distillation units per round of the {kind} factory"), &format!("The {kind} factory uses {} rounds of distillation, the last round produces {kind} states with an error rate of {:.2e}, which is equal or smaller than the required error rate {:.2e}.", part.factory().num_rounds(), part.factory().output_error_rate(), part.required_output_error_rate())));
+            }
+            groups.push(ReportEntryGroup {
+                title: "Magic state factories".into(),
+                always_visible: false,
+                entries,
+            });
+        }
+
         let assumptions = vec![
             String::from("_More details on the following lists of assumptions can be found in the paper [Accessing requirements for scaling quantum computers and their applications](https://aka.ms/AQ/RE/Paper)._"),
             String::from("**Uniform independent physical noise.** We assume that the noise on physical qubits and physical qubit operations is the standard circuit noise model. In particular we assume error events at different space-time locations are independent and that error rates are uniform across the system in time and space."),
@@ -231,18 +299,79 @@ impl Report {
             assumptions,
         }
     }
+
+    /// Formats the report as a table with one line per entry, in which the
+    /// value of each entry is looked up by its path in the serialized
+    /// `result`.  For frontier estimates, values that are not in the result
+    /// are looked up in its first frontier entry, which is the one the report
+    /// was created for.  Entries without value are skipped.
+    #[must_use]
+    pub fn summary(&self, result: &Value) -> String {
+        let width = self
+            .groups
+            .iter()
+            .flat_map(|group| &group.entries)
+            .map(|entry| entry.label.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut output = String::new();
+        for group in &self.groups {
+            let rows: Vec<_> = group
+                .entries
+                .iter()
+                .filter_map(|entry| {
+                    lookup_path(result, &entry.path)
+                        .or_else(|| lookup_path(&result["frontierEntries"][0], &entry.path))
+                        .map(|value| (&entry.label, format_value(value)))
+                })
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            writeln!(output, "{}", group.title).expect("writing to string should succeed");
+            for (label, value) in rows {
+                writeln!(output, "  {label:<width$}  {value}")
+                    .expect("writing to string should succeed");
+            }
+        }
+
+        output
+    }
 }
 
-#[derive(Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
+fn lookup_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('/')
+        .try_fold(value, |value, key| value.get(key))
+        .filter(|value| !value.is_null())
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(format_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => value.to_string(),
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
 struct ReportEntryGroup {
     title: String,
     always_visible: bool,
     entries: Vec<ReportEntry>,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all(serialize = "camelCase"))]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
 struct ReportEntry {
     path: String,
     label: String,
@@ -1,18 +1,18 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use std::rc::Rc;
+use std::{collections::BTreeMap, rc::Rc};
 
 use crate::estimates::{
     ErrorBudget, FactoryPart, LogicalPatch, Overhead, PhysicalResourceEstimationResult,
 };
-use crate::system::modeling::{Protocol, TFactory};
+use crate::system::modeling::{MagicStateKind, Protocol, TFactory};
 
-use super::LayoutReportData;
 use super::{
     super::Error, FormattedPhysicalResourceCounts, JobParams, PhysicalResourceCounts,
     PhysicalResourceCountsBreakdown, Report,
 };
+use super::{LayoutReportData, LogicalResourceBreakdown};
 use miette::Diagnostic;
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
@@ -29,12 +29,18 @@ pub struct Success<L: Serialize> {
     logical_qubit: Option<LogicalPatch<Protocol>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tfactory: Option<TFactory>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    magic_state_factories: BTreeMap<MagicStateKind, MagicStateFactorySizing>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error_budget: Option<ErrorBudget>,
     logical_counts: Rc<L>,
     report_data: Report,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     frontier_entries: Vec<FrontierEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callable_breakdown: Option<LogicalResourceBreakdown>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    callable_flamegraph: Option<String>,
 }
 
 impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
@@ -48,6 +54,7 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
             FormattedPhysicalResourceCounts::new(&result, &job_params);
 
         let report_data = Report::new(&job_params, &result, &formatted_counts);
+        let magic_state_factories = create_magic_state_factories(&job_params, &result);
 
         let logical_counts = result.layout_overhead().clone();
         let (logical_qubit, mut parts, error_budget) = result.take();
@@ -60,10 +67,13 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
             physical_counts_formatted: Some(formatted_counts),
             logical_qubit: Some(logical_qubit),
             tfactory,
+            magic_state_factories,
             error_budget: Some(error_budget),
             logical_counts,
             report_data,
             frontier_entries: Vec::new(),
+            callable_breakdown: None,
+            callable_flamegraph: None,
         }
     }
 
@@ -97,12 +107,106 @@ impl<L: Overhead + LayoutReportData + Serialize> Success<L> {
             physical_counts_formatted: None,
             logical_qubit: None,
             tfactory: None,
+            magic_state_factories: BTreeMap::new(),
             error_budget: None,
             logical_counts,
             report_data: report_data.expect("error should have report"), // Here we assume that at least a single solution was found.
             frontier_entries,
+            callable_breakdown: None,
+            callable_flamegraph: None,
+        }
+    }
+
+    /// Returns the physical qubits and runtime of the result, or of each of
+    /// its frontier entries together with the index of the entry.
+    pub fn qubits_and_runtime(&self) -> Vec<(Option<usize>, u64, u64)> {
+        if let Some(counts) = &self.physical_counts {
+            vec![(None, counts.physical_qubits, counts.runtime)]
+        } else {
+            self.frontier_entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    (
+                        Some(index),
+                        entry.physical_counts.physical_qubits,
+                        entry.physical_counts.runtime,
+                    )
+                })
+                .collect()
         }
     }
+
+    /// Adds the logical resources per callable to the result, as requested by
+    /// the profiling job parameters.
+    #[must_use]
+    pub fn with_breakdown(mut self, breakdown: Option<&LogicalResourceBreakdown>) -> Self {
+        let Some(breakdown) = breakdown else {
+            return self;
+        };
+
+        let profiling = self.job_params.profiling();
+        let breakdown = match profiling.call_stack_depth {
+            Some(depth) => breakdown.truncated(depth),
+            None => breakdown.clone(),
+        };
+        self.callable_flamegraph = profiling
+            .flamegraph
+            .map(|metric| breakdown.flamegraph(metric));
+        if profiling.call_stack_depth.is_some() {
+            self.callable_breakdown = Some(breakdown);
+        }
+
+        self
+    }
+}
+
+/// Sizing of the factories that produce one kind of magic states
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MagicStateFactorySizing {
+    /// The number of magic states of this kind required by the algorithm
+    num_states: u64,
+    num_factories: u64,
+    num_factory_runs: u64,
+    /// The number of physical qubits for all factories of this kind
+    physical_qubits: u64,
+    required_logical_error_rate: f64,
+    factory: TFactory,
+}
+
+/// Sizes the factories per kind of magic states, if kinds other than T states
+/// are enabled in the job parameters.
+fn create_magic_state_factories(
+    job_params: &JobParams,
+    result: &PhysicalResourceEstimationResult<Protocol, TFactory, impl Overhead + LayoutReportData>,
+) -> BTreeMap<MagicStateKind, MagicStateFactorySizing> {
+    if job_params.magic_state_factories().is_default() {
+        return BTreeMap::new();
+    }
+
+    job_params
+        .magic_state_factories()
+        .kinds()
+        .into_iter()
+        .zip(result.factory_parts())
+        .enumerate()
+        .filter_map(|(index, (kind, part))| {
+            part.as_ref().map(|part| {
+                (
+                    kind,
+                    MagicStateFactorySizing {
+                        num_states: result.num_magic_states(index),
+                        num_factories: part.copies(),
+                        num_factory_runs: part.runs(),
+                        physical_qubits: part.physical_qubits(),
+                        required_logical_error_rate: part.required_output_error_rate(),
+                        factory: part.factory().clone(),
+                    },
+                )
+            })
+        })
+        .collect()
 }
 
 #[derive(Serialize)]
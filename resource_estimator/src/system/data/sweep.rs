@@ -0,0 +1,150 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Job parameter variations that are estimated in a single batch
+///
+/// Every entry in `items` is combined with every element of the cartesian
+/// product in `product`, and the resulting variation is merged into `base`.
+/// Top-level fields of a variation replace those of `base`, except for
+/// `maxTFactories` in the product, which is set in the `constraints` of the
+/// merged job parameters.
+#[derive(Default, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"), deny_unknown_fields)]
+pub struct SweepSpecification {
+    #[serde(default)]
+    base: Map<String, Value>,
+    #[serde(default)]
+    items: Vec<Map<String, Value>>,
+    #[serde(default)]
+    product: SweepProduct,
+}
+
+/// Values for each job parameter that is swept in the cartesian product; an
+/// empty list keeps the value from the base parameters.
+#[derive(Default, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"), deny_unknown_fields)]
+pub struct SweepProduct {
+    #[serde(default)]
+    qubit_params: Vec<Value>,
+    #[serde(default)]
+    qec_scheme: Vec<Value>,
+    #[serde(default)]
+    error_budget: Vec<Value>,
+    #[serde(default)]
+    max_t_factories: Vec<u64>,
+}
+
+impl SweepSpecification {
+    /// Returns the JSON job parameters of all variations in the sweep, in
+    /// the order of `items` and then in row-major order of the product.
+    pub fn job_params(&self) -> Vec<Value> {
+        let items = if self.items.is_empty() {
+            vec![Map::new()]
+        } else {
+            self.items.clone()
+        };
+
+        let mut variations: Vec<Map<String, Value>> = items
+            .into_iter()
+            .map(|item| {
+                let mut job_params = self.base.clone();
+                job_params.extend(item);
+                job_params
+            })
+            .collect();
+
+        variations = product(variations, "qubitParams", &self.product.qubit_params);
+        variations = product(variations, "qecScheme", &self.product.qec_scheme);
+        variations = product(variations, "errorBudget", &self.product.error_budget);
+
+        if !self.product.max_t_factories.is_empty() {
+            variations = variations
+                .into_iter()
+                .flat_map(|job_params| {
+                    self.product
+                        .max_t_factories
+                        .iter()
+                        .map(move |&max_t_factories| {
+                            let mut job_params = job_params.clone();
+                            let constraints = job_params
+                                .entry("constraints")
+                                .or_insert_with(|| Value::Object(Map::new()));
+                            if let Value::Object(constraints) = constraints {
+                                constraints
+                                    .insert("maxTFactories".into(), Value::from(max_t_factories));
+                            }
+                            job_params
+                        })
+                })
+                .collect();
+        }
+
+        variations.into_iter().map(Value::Object).collect()
+    }
+}
+
+fn product(
+    variations: Vec<Map<String, Value>>,
+    key: &str,
+    values: &[Value],
+) -> Vec<Map<String, Value>> {
+    if values.is_empty() {
+        return variations;
+    }
+
+    variations
+        .into_iter()
+        .flat_map(|job_params| {
+            values.iter().map(move |value| {
+                let mut job_params = job_params.clone();
+                job_params.insert(key.into(), value.clone());
+                job_params
+            })
+        })
+        .collect()
+}
+
+/// A solution in the combined qubits-vs-runtime Pareto frontier of a sweep
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct ParetoPoint {
+    /// Index of the job parameters in the sweep
+    pub result_index: usize,
+    /// Index into the frontier entries of the result, if the job parameters
+    /// requested a frontier estimation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontier_index: Option<usize>,
+    pub physical_qubits: u64,
+    pub runtime: u64,
+}
+
+/// Returns the points that are not dominated in both physical qubits and
+/// runtime, sorted by increasing number of physical qubits.  Of several
+/// points with the same counts, only the first one is kept.
+pub fn pareto_frontier(mut points: Vec<ParetoPoint>) -> Vec<ParetoPoint> {
+    // stable sort, such that the first point is kept for equal counts
+    points.sort_by_key(|point| (point.physical_qubits, point.runtime));
+
+    let mut frontier: Vec<ParetoPoint> = Vec::new();
+    let mut min_runtime = u64::MAX;
+    for point in points {
+        if point.runtime < min_runtime {
+            min_runtime = point.runtime;
+            frontier.push(point);
+        }
+    }
+
+    frontier
+}
+
+/// Results of all job parameters in a sweep together with their combined
+/// Pareto frontier
+#[derive(Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct SweepResult {
+    pub results: Vec<Value>,
+    pub pareto_frontier: Vec<ParetoPoint>,
+}
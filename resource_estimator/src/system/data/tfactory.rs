@@ -8,7 +8,7 @@ use super::super::{
     compiled_expression::CompiledExpression,
     error::IO::{self, CannotParseJSON},
     modeling::{
-        TFactoryDistillationUnitResources, TFactoryDistillationUnitTemplate,
+        MagicStateKind, TFactoryDistillationUnitResources, TFactoryDistillationUnitTemplate,
         TFactoryDistillationUnitType, TFactoryFormula,
     },
     Error, Result,
@@ -18,14 +18,64 @@ use super::super::{
 pub struct TFactoryDistillationUnitSpecifications(Vec<TFactoryDistillationUnitSpecification>);
 
 impl TFactoryDistillationUnitSpecifications {
-    pub fn as_templates(&self) -> Result<Vec<TFactoryDistillationUnitTemplate>> {
-        if self.is_empty() {
-            Ok(TFactoryDistillationUnitTemplate::default_distillation_unit_templates())
-        } else {
-            self.iter()
-                .map(TFactoryDistillationUnitTemplate::try_from)
-                .collect()
+    /// Returns the templates for all specified distillation units, and adds
+    /// the default templates for each kind in `output_states` that is not
+    /// produced by any specified distillation unit.
+    pub fn as_templates(
+        &self,
+        output_states: &[MagicStateKind],
+    ) -> Result<Vec<TFactoryDistillationUnitTemplate>> {
+        let mut templates = self
+            .iter()
+            .map(TFactoryDistillationUnitTemplate::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        for &output_state in output_states {
+            if !templates
+                .iter()
+                .any(|template| template.output_state == output_state)
+            {
+                templates.extend(
+                    TFactoryDistillationUnitTemplate::default_distillation_unit_templates_for(
+                        output_state,
+                    ),
+                );
+            }
+        }
+
+        Ok(templates)
+    }
+}
+
+/// Dedicated magic state factories in addition to T factories
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
+pub struct MagicStateFactories {
+    /// Implements CCZ and `CCiX` gates with CCZ states instead of 4 T states
+    #[serde(default)]
+    pub ccz: bool,
+    /// Implements rotations with directly prepared rotation states instead of
+    /// synthesizing them from T states
+    #[serde(default)]
+    pub rotation: bool,
+}
+
+impl MagicStateFactories {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The kinds of magic states, in the order of their magic state index in
+    /// the estimation; T states are always the first kind.
+    pub fn kinds(&self) -> Vec<MagicStateKind> {
+        let mut kinds = vec![MagicStateKind::T];
+        if self.ccz {
+            kinds.push(MagicStateKind::Ccz);
+        }
+        if self.rotation {
+            kinds.push(MagicStateKind::Rotation);
         }
+        kinds
     }
 }
 
@@ -51,6 +101,9 @@ pub enum TFactoryDistillationUnitSpecification {
         num_input_ts: u64,
         /// The number of output t states generated by the distillation unit.
         num_output_ts: u64,
+        /// The kind of the output states, input states are always T states.
+        #[serde(default, skip_serializing_if = "MagicStateKind::is_default")]
+        output_state: MagicStateKind,
         /// The failure probability formula.
         failure_probability_formula: String,
         /// The output error rate formula.
@@ -80,6 +133,7 @@ impl TryFrom<&TFactoryDistillationUnitSpecification> for TFactoryDistillationUni
                 display_name,
                 num_input_ts,
                 num_output_ts,
+                output_state,
                 failure_probability_formula,
                 output_error_rate_formula,
                 physical_qubit_specification,
@@ -159,6 +213,7 @@ impl TryFrom<&TFactoryDistillationUnitSpecification> for TFactoryDistillationUni
                     name: display_name.clone(),
                     num_input_ts: *num_input_ts,
                     num_output_ts: *num_output_ts,
+                    output_state: *output_state,
                     failure_probability_function,
                     output_error_rate_function,
                     unit_type,
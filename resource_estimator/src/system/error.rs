@@ -63,6 +63,14 @@ pub enum InvalidInput {
         "Qsc.Estimates.InvalidInputError.NonPositivePhysicalQubitsPerLogicalQubit"
     ))]
     NonPositivePhysicalQubitsPerLogicalQubit(u64),
+    /// Number of logical qubits per patch is non-positive for some code
+    /// distance value
+    ///
+    /// ✅ This does not contain user data and can be logged
+    /// 🧑‍💻 This indicates a user error
+    #[error("logicalQubitsPerPatch formula yields non-positive value for code distance = {0}")]
+    #[diagnostic(code("Qsc.Estimates.InvalidInputError.NonPositiveLogicalQubitsPerPatch"))]
+    NonPositiveLogicalQubitsPerPatch(u64),
     /// Invalid error budget (<= 0.0 or >= 1.0)
     ///
     /// ✅ This does not contain user data and can be logged
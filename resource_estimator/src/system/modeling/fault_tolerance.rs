@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 use crate::estimates::{
-    Error::{LogicalCycleTimeComputationFailed, PhysicalQubitComputationFailed},
+    Error::{
+        LogicalCycleTimeComputationFailed, LogicalQubitComputationFailed,
+        PhysicalQubitComputationFailed,
+    },
     ErrorCorrection,
 };
 
@@ -24,7 +27,7 @@ use super::{
         error::{
             InvalidInput::{
                 InvalidFaultToleranceProtocol, NonPositiveLogicalCycleTime,
-                NonPositivePhysicalQubitsPerLogicalQubit,
+                NonPositiveLogicalQubitsPerPatch, NonPositivePhysicalQubitsPerLogicalQubit,
             },
             IO::CannotParseJSON,
         },
@@ -46,6 +49,10 @@ pub struct ProtocolSpecification {
     pub(crate) logical_cycle_time: Option<String>,
     #[serde(default)]
     pub(crate) physical_qubits_per_logical_qubit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) logical_qubits_per_patch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) logical_error_rate: Option<String>,
     #[serde(default = "Protocol::default_max_code_distance")]
     pub(crate) max_code_distance: u64,
 }
@@ -58,6 +65,8 @@ impl Default for ProtocolSpecification {
             error_correction_threshold: None,
             logical_cycle_time: None,
             physical_qubits_per_logical_qubit: None,
+            logical_qubits_per_patch: None,
+            logical_error_rate: None,
             max_code_distance: Protocol::default_max_code_distance(),
         }
     }
@@ -79,6 +88,14 @@ impl Default for ProtocolSpecification {
 ///
 /// Note that all physical qubit related variables are not available as variable
 /// in formulas for `physical_qubits_per_logical_qubit`.
+///
+/// Codes that encode several logical qubits in one patch, such as qLDPC codes,
+/// can specify the number of logical qubits per patch in terms of
+/// `code_distance` in `logical_qubits_per_patch`; in that case
+/// `physical_qubits_per_logical_qubit` is the number of physical qubits of the
+/// whole patch.  The optional `logical_error_rate` formula replaces the default
+/// logical error rate model of the patch and can additionally refer to
+/// `physicalErrorRate`, `errorCorrectionThreshold`, and `crossingPrefactor`.
 #[derive(Debug)]
 pub struct Protocol {
     error_correction_threshold: f64,
@@ -87,6 +104,10 @@ pub struct Protocol {
     logical_cycle_time: CompiledExpression,
     physical_qubits_per_logical_qubit_expr: String,
     physical_qubits_per_logical_qubit: CompiledExpression,
+    logical_qubits_per_patch_expr: Option<String>,
+    logical_qubits_per_patch: Option<CompiledExpression>,
+    logical_error_rate_expr: Option<String>,
+    logical_error_rate: Option<CompiledExpression>,
     max_code_distance: u64,
 }
 
@@ -147,6 +168,8 @@ impl Protocol {
                 .map_err(LogicalCycleTimeComputationFailed)?;
             ftp.physical_qubits(&code_distance)
                 .map_err(PhysicalQubitComputationFailed)?;
+            ftp.logical_qubits(&code_distance)
+                .map_err(LogicalQubitComputationFailed)?;
         }
 
         Ok(ftp)
@@ -174,6 +197,22 @@ impl Protocol {
                 PhysicalInstructionSet::GateBased => Err(InvalidFaultToleranceProtocol.into()),
                 PhysicalInstructionSet::Majorana => Ok((Self::floquet_code(), true)),
             }
+        } else if model.name == "color_code"
+            || model.name == "colorCode"
+            || model.name == "color-code"
+        {
+            match qubit.instruction_set() {
+                PhysicalInstructionSet::GateBased => Ok((Self::color_code(), true)),
+                PhysicalInstructionSet::Majorana => Err(InvalidFaultToleranceProtocol.into()),
+            }
+        } else if model.name == "qldpc_code"
+            || model.name == "qldpcCode"
+            || model.name == "qldpc-code"
+        {
+            match qubit.instruction_set() {
+                PhysicalInstructionSet::GateBased => Ok((Self::qldpc_code(), true)),
+                PhysicalInstructionSet::Majorana => Err(InvalidFaultToleranceProtocol.into()),
+            }
         } else {
             let error_correction_threshold = model.error_correction_threshold.ok_or_else(|| {
                 CannotParseJSON(serde::de::Error::missing_field("errorCorrectionThreshold"))
@@ -206,6 +245,18 @@ impl Protocol {
                     &physical_qubits_per_logical_qubit_expr,
                 )?;
 
+            let logical_qubits_per_patch_expr = model.logical_qubits_per_patch.clone();
+            let logical_qubits_per_patch = logical_qubits_per_patch_expr
+                .as_deref()
+                .map(|expr| CompiledExpression::from_string(expr, "logicalQubitsPerPatch"))
+                .transpose()?;
+
+            let logical_error_rate_expr = model.logical_error_rate.clone();
+            let logical_error_rate = logical_error_rate_expr
+                .as_deref()
+                .map(|expr| CompiledExpression::from_string(expr, "logicalErrorRate"))
+                .transpose()?;
+
             let max_code_distance = model.max_code_distance;
 
             Ok((
@@ -216,6 +267,10 @@ impl Protocol {
                     logical_cycle_time,
                     physical_qubits_per_logical_qubit_expr,
                     physical_qubits_per_logical_qubit,
+                    logical_qubits_per_patch_expr,
+                    logical_qubits_per_patch,
+                    logical_error_rate_expr,
+                    logical_error_rate,
                     max_code_distance,
                 },
                 false,
@@ -262,6 +317,28 @@ impl Protocol {
                 Some(self.physical_qubits_per_logical_qubit_expr.clone());
         }
 
+        if let Some(logical_qubits_per_patch) = model.logical_qubits_per_patch.as_ref() {
+            self.logical_qubits_per_patch = Some(CompiledExpression::from_string(
+                logical_qubits_per_patch,
+                "logicalQubitsPerPatch",
+            )?);
+        } else {
+            model
+                .logical_qubits_per_patch
+                .clone_from(&self.logical_qubits_per_patch_expr);
+        }
+
+        if let Some(logical_error_rate) = model.logical_error_rate.as_ref() {
+            self.logical_error_rate = Some(CompiledExpression::from_string(
+                logical_error_rate,
+                "logicalErrorRate",
+            )?);
+        } else {
+            model
+                .logical_error_rate
+                .clone_from(&self.logical_error_rate_expr);
+        }
+
         self.max_code_distance = model.max_code_distance;
 
         Ok(())
@@ -308,6 +385,10 @@ impl Protocol {
             logical_cycle_time,
             physical_qubits_per_logical_qubit_expr,
             physical_qubits_per_logical_qubit,
+            logical_qubits_per_patch_expr: None,
+            logical_qubits_per_patch: None,
+            logical_error_rate_expr: None,
+            logical_error_rate: None,
             max_code_distance: Self::default_max_code_distance(),
         }
     }
@@ -347,6 +428,10 @@ impl Protocol {
             logical_cycle_time,
             physical_qubits_per_logical_qubit_expr,
             physical_qubits_per_logical_qubit,
+            logical_qubits_per_patch_expr: None,
+            logical_qubits_per_patch: None,
+            logical_error_rate_expr: None,
+            logical_error_rate: None,
             max_code_distance: Self::default_max_code_distance(),
         }
     }
@@ -385,6 +470,120 @@ impl Protocol {
             logical_cycle_time,
             physical_qubits_per_logical_qubit_expr,
             physical_qubits_per_logical_qubit,
+            logical_qubits_per_patch_expr: None,
+            logical_qubits_per_patch: None,
+            logical_error_rate_expr: None,
+            logical_error_rate: None,
+            max_code_distance: Self::default_max_code_distance(),
+        }
+    }
+
+    /// Default color code FTP for gate based qubits
+    ///
+    /// ```yaml
+    /// name: "color_code"
+    /// instruction_set: "gate_based"
+    /// error_correction_threshold: 0.005
+    /// crossing_prefactor: 0.05
+    /// logical_cycle_time: "(12 * twoQubitGateTime + 2 * oneQubitMeasurementTime) * codeDistance"
+    /// # triangular 6.6.6 code with one X and one Z syndrome qubit per plaquette
+    /// physical_qubits_per_logical_qubit: "(3 * codeDistance * codeDistance - 1) / 2"
+    /// ```
+    #[must_use]
+    pub fn color_code() -> Self {
+        let error_correction_threshold = 0.005;
+        let crossing_prefactor = 0.05;
+
+        // X and Z stabilizers of weight 6 are measured one after the other
+        let logical_cycle_time_expr = format!(
+            "(12 * {TWO_QUBIT_GATE_TIME} + 2 * {ONE_QUBIT_MEASUREMENT_TIME}) * codeDistance"
+        );
+        let physical_qubits_per_logical_qubit_expr =
+            String::from("(3 * codeDistance * codeDistance - 1) / 2");
+
+        let (logical_cycle_time, physical_qubits_per_logical_qubit) =
+            Protocol::parse_compiled_expressions(
+                &logical_cycle_time_expr,
+                &physical_qubits_per_logical_qubit_expr,
+            )
+            .expect("could not parse expressions");
+
+        Self {
+            error_correction_threshold,
+            crossing_prefactor,
+            logical_cycle_time_expr,
+            logical_cycle_time,
+            physical_qubits_per_logical_qubit_expr,
+            physical_qubits_per_logical_qubit,
+            logical_qubits_per_patch_expr: None,
+            logical_qubits_per_patch: None,
+            logical_error_rate_expr: None,
+            logical_error_rate: None,
+            max_code_distance: Self::default_max_code_distance(),
+        }
+    }
+
+    /// Default qLDPC code FTP for gate based qubits
+    ///
+    /// The code is modeled after bivariate bicycle codes, in which a patch of
+    /// `2 * codeDistance * codeDistance` physical qubits (data and syndrome
+    /// qubits) encodes 12 logical qubits.
+    ///
+    /// ```yaml
+    /// name: "qldpc_code"
+    /// instruction_set: "gate_based"
+    /// # [arXiv:2308.07915]
+    /// error_correction_threshold: 0.007
+    /// crossing_prefactor: 0.1
+    /// logical_cycle_time: "(8 * twoQubitGateTime + 2 * oneQubitMeasurementTime) * codeDistance"
+    /// physical_qubits_per_logical_qubit: "2 * codeDistance * codeDistance"
+    /// logical_qubits_per_patch: "12"
+    /// logical_error_rate: "crossingPrefactor * (physicalErrorRate / errorCorrectionThreshold) ^ (codeDistance / 2)"
+    /// ```
+    #[must_use]
+    pub fn qldpc_code() -> Self {
+        // [arXiv:2308.07915]
+        let error_correction_threshold = 0.007;
+        let crossing_prefactor = 0.1;
+
+        let logical_cycle_time_expr = format!(
+            "(8 * {TWO_QUBIT_GATE_TIME} + 2 * {ONE_QUBIT_MEASUREMENT_TIME}) * codeDistance"
+        );
+        let physical_qubits_per_logical_qubit_expr =
+            String::from("2 * codeDistance * codeDistance");
+        let logical_qubits_per_patch_expr = String::from("12");
+        // The circuit-level distance of these codes is smaller than their code
+        // distance, which is accounted for in the exponent.
+        let logical_error_rate_expr = String::from(
+            "crossingPrefactor * (physicalErrorRate / errorCorrectionThreshold) ^ (codeDistance / 2)",
+        );
+
+        let (logical_cycle_time, physical_qubits_per_logical_qubit) =
+            Protocol::parse_compiled_expressions(
+                &logical_cycle_time_expr,
+                &physical_qubits_per_logical_qubit_expr,
+            )
+            .expect("could not parse expressions");
+        let logical_qubits_per_patch = CompiledExpression::from_string(
+            &logical_qubits_per_patch_expr,
+            "logicalQubitsPerPatch",
+        )
+        .expect("could not parse expressions");
+        let logical_error_rate =
+            CompiledExpression::from_string(&logical_error_rate_expr, "logicalErrorRate")
+                .expect("could not parse expressions");
+
+        Self {
+            error_correction_threshold,
+            crossing_prefactor,
+            logical_cycle_time_expr,
+            logical_cycle_time,
+            physical_qubits_per_logical_qubit_expr,
+            physical_qubits_per_logical_qubit,
+            logical_qubits_per_patch_expr: Some(logical_qubits_per_patch_expr),
+            logical_qubits_per_patch: Some(logical_qubits_per_patch),
+            logical_error_rate_expr: Some(logical_error_rate_expr),
+            logical_error_rate: Some(logical_error_rate),
             max_code_distance: Self::default_max_code_distance(),
         }
     }
@@ -486,9 +685,25 @@ impl ErrorCorrection for Protocol {
         }
     }
 
-    /// The planar codes considered in this system encode 1 logical qubit
-    fn logical_qubits(&self, _code_parameter: &Self::Parameter) -> Result<u64, String> {
-        Ok(1)
+    /// Computes the number of logical qubits encoded in one patch
+    ///
+    /// The planar codes considered in this system encode 1 logical qubit,
+    /// unless a formula is provided.
+    fn logical_qubits(&self, code_distance: &u64) -> Result<u64, String> {
+        let Some(logical_qubits_per_patch) = &self.logical_qubits_per_patch else {
+            return Ok(1);
+        };
+
+        let mut context = Self::create_evaluation_context(None, *code_distance);
+        let value = logical_qubits_per_patch
+            .evaluate(&mut context)
+            .map_err(|err| err.to_string())?;
+
+        if value < 1.0 {
+            Err(NonPositiveLogicalQubitsPerPatch(*code_distance).to_string())
+        } else {
+            Ok(value as u64)
+        }
     }
 
     /// Returns the time of one logical cycle.
@@ -535,6 +750,18 @@ impl ErrorCorrection for Protocol {
                 self.error_correction_threshold,
             )
             .to_string())
+        } else if let Some(logical_error_rate) = &self.logical_error_rate {
+            let mut context = Self::create_evaluation_context(None, *code_distance);
+            context.insert("physicalErrorRate".to_string(), physical_error_rate);
+            context.insert(
+                "errorCorrectionThreshold".to_string(),
+                self.error_correction_threshold,
+            );
+            context.insert("crossingPrefactor".to_string(), self.crossing_prefactor);
+
+            logical_error_rate
+                .evaluate(&mut context)
+                .map_err(|err| err.to_string())
         } else {
             Ok(self.crossing_prefactor()
                 * ((physical_error_rate / self.error_correction_threshold())
@@ -548,6 +775,24 @@ impl ErrorCorrection for Protocol {
         qubit: &PhysicalQubit,
         required_logical_qubit_error_rate: f64,
     ) -> Result<u64, String> {
+        if self.logical_error_rate.is_some() || self.logical_qubits_per_patch.is_some() {
+            // The logical error rate cannot be inverted in closed form, search
+            // for the smallest code distance that is sufficient
+            return self
+                .code_parameter_range(None)
+                .find(|code_distance| {
+                    matches!(
+                        (
+                            self.logical_error_rate(qubit, code_distance),
+                            self.logical_qubits(code_distance)
+                        ),
+                        (Ok(probability), Ok(logical_qubits))
+                            if probability / logical_qubits as f64 <= required_logical_qubit_error_rate
+                    )
+                })
+                .ok_or_else(|| format!("No code distance up to the maximum allowed code distance {} achieves the required logical error rate {required_logical_qubit_error_rate:.3e}; try increasing the total logical error budget", self.max_code_distance));
+        }
+
         let physical_error_rate = qubit.clifford_error_rate().max(qubit.readout_error_rate());
         let numerator = 2.0 * (self.crossing_prefactor() / required_logical_qubit_error_rate).ln();
         let denominator = (self.error_correction_threshold() / physical_error_rate).ln();
@@ -17,3 +17,46 @@ fn compute_code_distance() -> Result<(), String> {
 
     Ok(())
 }
+
+#[test]
+fn color_code() -> Result<(), String> {
+    let qubit = PhysicalQubit::default();
+    let ftp = Protocol::color_code();
+    assert_eq!(ftp.physical_qubits(&3)?, 13);
+    assert_eq!(ftp.logical_qubits(&3)?, 1);
+    assert_eq!(ftp.logical_cycle_time(&qubit, &1)?, 800);
+
+    Ok(())
+}
+
+#[test]
+fn qldpc_code() -> Result<(), String> {
+    let qubit = PhysicalQubit::default();
+    let ftp = Protocol::qldpc_code();
+    assert_eq!(ftp.physical_qubits(&11)?, 242);
+    assert_eq!(ftp.logical_qubits(&11)?, 12);
+    assert!(
+        (ftp.logical_error_rate(&qubit, &4)? - 0.1 * (1e-3_f64 / 0.007).powi(2)).abs()
+            <= f64::EPSILON
+    );
+
+    Ok(())
+}
+
+#[test]
+fn qldpc_code_distance_is_smallest_sufficient() -> Result<(), String> {
+    let qubit = PhysicalQubit::default();
+    let ftp = Protocol::qldpc_code();
+    let required = 1e-10;
+
+    let code_distance = ftp.compute_code_parameter(&qubit, required)?;
+    let per_qubit = |d: u64| -> Result<f64, String> {
+        Ok(ftp.logical_error_rate(&qubit, &d)? / ftp.logical_qubits(&d)? as f64)
+    };
+
+    assert_eq!(code_distance % 2, 1);
+    assert!(per_qubit(code_distance)? <= required);
+    assert!(per_qubit(code_distance - 2)? > required);
+
+    Ok(())
+}
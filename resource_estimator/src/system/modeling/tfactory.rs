@@ -7,7 +7,7 @@ mod tests;
 use core::fmt;
 use std::{collections::BTreeMap, fmt::Display, vec};
 
-use serde::{ser::SerializeMap, Serialize};
+use serde::{ser::SerializeMap, Deserialize, Serialize};
 
 use crate::estimates::{
     DistillationRound, DistillationUnit, Factory, LogicalPatch, RoundBasedFactory,
@@ -115,6 +115,38 @@ impl From<CompiledExpression> for TFactoryFormula {
     }
 }
 
+/// The kind of magic state that is produced by a distillation unit or factory
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "camelCase"))]
+pub enum MagicStateKind {
+    /// T states, which are also used to implement CCZ gates and rotations if
+    /// there are no dedicated factories for them
+    #[default]
+    T,
+    /// CCZ states that implement a CCZ or `CCiX` gate
+    Ccz,
+    /// States to directly implement single-qubit rotations with arbitrary
+    /// angles
+    Rotation,
+}
+
+impl MagicStateKind {
+    #[allow(clippy::trivially_copy_pass_by_ref)] // forced by serde interface
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for MagicStateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MagicStateKind::T => f.write_str("T"),
+            MagicStateKind::Ccz => f.write_str("CCZ"),
+            MagicStateKind::Rotation => f.write_str("rotation"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum TFactoryDistillationUnitType {
     Logical,
@@ -136,6 +168,9 @@ pub struct TFactoryDistillationUnitTemplate {
     pub(crate) num_input_ts: u64,
     /// The number of output t states generated by the distillation unit.
     pub(crate) num_output_ts: u64,
+    /// The kind of magic states generated by the distillation unit; all input
+    /// states are T states.
+    pub(crate) output_state: MagicStateKind,
     /// The failure probability formula expression.
     pub(crate) failure_probability_function: TFactoryFormula,
     /// The output error rate formula expression.
@@ -163,6 +198,8 @@ impl TFactoryDistillationUnitTemplate {
             | "15-to-1 space efficient" => {
                 Ok(Self::create_distillation_unit_15_to_1_rm_space_efficient_template())
             }
+            "8-to-CCZ" | "8T-to-CCZ" => Ok(Self::create_distillation_unit_8_to_ccz_template()),
+            "rotation state preparation" => Ok(Self::create_rotation_state_preparation_template()),
             _ => Err(CannotParseJSON(serde::de::Error::custom(format!(
                 "Invalid distillation unit specification name: {name}."
             )))),
@@ -174,6 +211,7 @@ impl TFactoryDistillationUnitTemplate {
             name: String::from("15-to-1 RM prep"),
             num_input_ts: 15,
             num_output_ts: 1,
+            output_state: MagicStateKind::T,
             failure_probability_function: Box::new(Self::failure_probability),
             output_error_rate_function: Box::new(Self::output_error_rate),
             unit_type: TFactoryDistillationUnitType::Combined,
@@ -195,6 +233,7 @@ impl TFactoryDistillationUnitTemplate {
             name: String::from("15-to-1 space efficient"),
             num_input_ts: 15,
             num_output_ts: 1,
+            output_state: MagicStateKind::T,
             failure_probability_function: Box::new(Self::failure_probability),
             output_error_rate_function: Box::new(Self::output_error_rate),
             unit_type: TFactoryDistillationUnitType::Combined,
@@ -215,6 +254,7 @@ impl TFactoryDistillationUnitTemplate {
             name: String::from("trivial 1-to-1"),
             num_input_ts: 1,
             num_output_ts: 1,
+            output_state: MagicStateKind::T,
             failure_probability_function: Box::new(Self::trivial_failure_probability),
             output_error_rate_function: Box::new(Self::trivial_error_rate),
             unit_type: TFactoryDistillationUnitType::Logical,
@@ -227,6 +267,48 @@ impl TFactoryDistillationUnitTemplate {
         }
     }
 
+    /// Distills one CCZ state from 8 T states, and detects any single fault
+    /// in the input T states [[1812.01238](https://arxiv.org/abs/1812.01238)]
+    pub fn create_distillation_unit_8_to_ccz_template() -> Self {
+        Self {
+            name: String::from("8-to-CCZ"),
+            num_input_ts: 8,
+            num_output_ts: 1,
+            output_state: MagicStateKind::Ccz,
+            failure_probability_function: Box::new(Self::ccz_failure_probability),
+            output_error_rate_function: Box::new(Self::ccz_output_error_rate),
+            unit_type: TFactoryDistillationUnitType::Logical,
+            physical_qubit_specification: None,
+            logical_qubit_specification: Some(TFactoryDistillationUnitResources {
+                num_unit_qubits: 12,
+                duration_in_qubit_cycle_time: 6,
+            }),
+            logical_qubit_specification_first_round_override: None,
+        }
+    }
+
+    /// Prepares a rotation state by injecting a physical rotation into a
+    /// logical qubit and post-selecting on the syndrome measurements.  Such
+    /// states cannot be distilled further, therefore this unit is always the
+    /// only round of a rotation state factory.
+    pub fn create_rotation_state_preparation_template() -> Self {
+        Self {
+            name: String::from("rotation state preparation"),
+            num_input_ts: 1,
+            num_output_ts: 1,
+            output_state: MagicStateKind::Rotation,
+            failure_probability_function: Box::new(Self::rotation_failure_probability),
+            output_error_rate_function: Box::new(Self::rotation_output_error_rate),
+            unit_type: TFactoryDistillationUnitType::Logical,
+            physical_qubit_specification: None,
+            logical_qubit_specification: Some(TFactoryDistillationUnitResources {
+                num_unit_qubits: 2,
+                duration_in_qubit_cycle_time: 2,
+            }),
+            logical_qubit_specification_first_round_override: None,
+        }
+    }
+
     fn failure_probability(
         input_error_rate: f64,
         clifford_error_rate: f64,
@@ -243,6 +325,38 @@ impl TFactoryDistillationUnitTemplate {
         35.0 * input_error_rate.powi(3) + 7.1 * clifford_error_rate
     }
 
+    fn ccz_failure_probability(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        8.0 * input_error_rate + 200.0 * clifford_error_rate
+    }
+
+    fn ccz_output_error_rate(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        28.0 * input_error_rate.powi(2) + 7.1 * clifford_error_rate
+    }
+
+    fn rotation_failure_probability(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        2.0 * input_error_rate + 10.0 * clifford_error_rate
+    }
+
+    fn rotation_output_error_rate(
+        input_error_rate: f64,
+        clifford_error_rate: f64,
+        _readout_error_rate: f64,
+    ) -> f64 {
+        2.0 / 15.0 * input_error_rate + clifford_error_rate
+    }
+
     fn trivial_failure_probability(
         _input_error_rate: f64,
         _clifford_error_rate: f64,
@@ -265,12 +379,23 @@ impl TFactoryDistillationUnitTemplate {
             Self::create_distillation_unit_15_to_1_rm_space_efficient_template(),
         ]
     }
+
+    /// The distillation unit templates that are used for a kind of magic
+    /// state, if none is specified in the job parameters
+    pub fn default_distillation_unit_templates_for(output_state: MagicStateKind) -> Vec<Self> {
+        match output_state {
+            MagicStateKind::T => Self::default_distillation_unit_templates(),
+            MagicStateKind::Ccz => vec![Self::create_distillation_unit_8_to_ccz_template()],
+            MagicStateKind::Rotation => vec![Self::create_rotation_state_preparation_template()],
+        }
+    }
 }
 
 pub struct TFactoryDistillationUnit<'a> {
     unit_type: TFactoryDistillationUnitType,
     num_input_ts: u64,
     num_output_ts: u64,
+    pub(crate) output_state: MagicStateKind,
     physical_qubits_at_first_round: u64,
     physical_qubits_at_subsequent_rounds: u64,
     duration_at_first_round: u64,
@@ -292,6 +417,7 @@ impl<'a> fmt::Debug for TFactoryDistillationUnit<'a> {
             .field("unit_type", &self.unit_type.to_string())
             .field("num_input_ts", &self.num_input_ts)
             .field("num_output_ts", &self.num_output_ts)
+            .field("output_state", &self.output_state)
             .field(
                 "physical_qubits_at_first_round",
                 &self.physical_qubits_at_first_round,
@@ -383,6 +509,7 @@ impl<'a> TFactoryDistillationUnit<'a> {
             unit_type: template.unit_type,
             num_input_ts,
             num_output_ts,
+            output_state: template.output_state,
             physical_qubits_at_first_round,
             physical_qubits_at_subsequent_rounds,
             duration_at_first_round,
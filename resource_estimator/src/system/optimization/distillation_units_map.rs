@@ -10,8 +10,8 @@ use std::rc::Rc;
 
 use crate::estimates::LogicalPatch;
 use crate::system::modeling::{
-    PhysicalQubit, Protocol, TFactoryDistillationUnit, TFactoryDistillationUnitTemplate,
-    TFactoryDistillationUnitType, TFactoryQubit,
+    MagicStateKind, PhysicalQubit, Protocol, TFactoryDistillationUnit,
+    TFactoryDistillationUnitTemplate, TFactoryDistillationUnitType, TFactoryQubit,
 };
 
 pub struct DistillationUnitsMap<'a> {
@@ -24,6 +24,9 @@ pub struct DistillationUnitsMap<'a> {
     min_valid_code_distance_indexes: Vec<usize>,
     num_code_distances: usize,
     distances: Vec<u64>,
+    /// The kind of output states per unit index (combined, purely logical,
+    /// purely physical)
+    output_states: Vec<MagicStateKind>,
 }
 
 impl<'a> DistillationUnitsMap<'a> {
@@ -76,6 +79,16 @@ impl<'a> DistillationUnitsMap<'a> {
         let mut logical_distillation_unit_templates = combined_distillation_unit_templates;
         logical_distillation_unit_templates.append(&mut purely_logical_distillation_unit_templates);
 
+        let output_states = logical_distillation_unit_templates
+            .iter()
+            .map(|template| template.output_state)
+            .chain(
+                physical_distillation_units[num_combined_distillation_units..]
+                    .iter()
+                    .map(|unit| unit.output_state),
+            )
+            .collect();
+
         let mut logical_distillation_units: Vec<Vec<TFactoryDistillationUnit>> = Vec::new();
 
         for qubit in qubits {
@@ -123,9 +136,27 @@ impl<'a> DistillationUnitsMap<'a> {
             min_valid_code_distance_indexes,
             num_code_distances,
             distances,
+            output_states,
         }
     }
 
+    /// Checks whether the units in `indexes` produce states of kind
+    /// `output_state` in the last round, and T states in all previous rounds.
+    /// Rotation states are prepared from physical states and can therefore
+    /// only be produced in a single round.
+    pub fn produces(&self, indexes: &[usize], output_state: MagicStateKind) -> bool {
+        indexes.split_last().is_some_and(|(&last, previous)| {
+            self.output_states[last] == output_state
+                && if output_state == MagicStateKind::Rotation {
+                    previous.is_empty()
+                } else {
+                    previous
+                        .iter()
+                        .all(|&idx| self.output_states[idx] == MagicStateKind::T)
+                }
+        })
+    }
+
     fn get_templates_for_unit_type(
         distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
         unit_type: TFactoryDistillationUnitType,
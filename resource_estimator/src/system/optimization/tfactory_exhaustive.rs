@@ -6,7 +6,8 @@ use std::fmt::Display;
 use std::rc::Rc;
 
 use crate::system::modeling::{
-    PhysicalQubit, Protocol, TFactory, TFactoryDistillationUnit, TFactoryDistillationUnitTemplate,
+    MagicStateKind, PhysicalQubit, Protocol, TFactory, TFactoryDistillationUnit,
+    TFactoryDistillationUnitTemplate,
 };
 use crate::{
     estimates::{
@@ -209,18 +210,22 @@ impl Display for Point4D<TFactory> {
     }
 }
 
-pub(crate) fn find_nondominated_tfactories<'a>(
+/// Finds factories for magic states of kind `output_state`, whose last round
+/// uses a distillation unit that produces this kind of states
+pub(crate) fn find_nondominated_factories<'a>(
     ftp: &Protocol,
     qubit: &Rc<PhysicalQubit>,
     distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
-    output_t_error_rate: f64,
+    output_state: MagicStateKind,
+    output_error_rate: f64,
     max_code_distance: u64,
 ) -> Vec<Cow<'a, TFactory>> {
     let points = find_nondominated_population::<Point2D<TFactory>>(
         ftp,
         qubit,
         distillation_unit_templates,
-        output_t_error_rate,
+        output_state,
+        output_error_rate,
         max_code_distance,
     );
 
@@ -235,6 +240,7 @@ fn find_nondominated_population<P>(
     ftp: &Protocol,
     qubit: &Rc<PhysicalQubit>,
     distillation_unit_templates: &[TFactoryDistillationUnitTemplate],
+    output_state: MagicStateKind,
     output_t_error_rate: f64,
     max_code_distance: u64,
 ) -> Population<P>
@@ -244,7 +250,9 @@ where
     let min_code_distance = 1;
     let distances: Vec<_> = (min_code_distance..=max_code_distance).step_by(2).collect();
 
-    if output_t_error_rate > qubit.t_gate_error_rate() {
+    // Physical T states can be used directly, but other kinds of magic states
+    // always require a distillation unit that produces them.
+    if output_state == MagicStateKind::T && output_t_error_rate > qubit.t_gate_error_rate() {
         let mut population = Population::<P>::new();
 
         if let Ok(logical_qubit) = LogicalPatch::new(ftp, max_code_distance, qubit.clone()) {
@@ -275,12 +283,22 @@ where
     let mut searcher = TFactoryExhaustiveSearch::<P>::new(output_t_error_rate);
 
     for num_rounds in 1..=MAX_DISTILLATION_ROUNDS {
-        process_for_num_rounds(&mut searcher, &distillation_units_map, num_rounds);
+        process_for_num_rounds(
+            &mut searcher,
+            &distillation_units_map,
+            output_state,
+            num_rounds,
+        );
     }
 
     if searcher.frontier_factories.items().is_empty() || P::ITERATE_MAX_NUM_ROUNDS {
         for num_rounds in MAX_DISTILLATION_ROUNDS + 1..=MAX_EXTRA_DISTILLATION_ROUNDS {
-            process_for_num_rounds(&mut searcher, &distillation_units_map, num_rounds);
+            process_for_num_rounds(
+                &mut searcher,
+                &distillation_units_map,
+                output_state,
+                num_rounds,
+            );
         }
     }
 
@@ -293,12 +311,15 @@ where
 fn process_for_num_rounds<P>(
     searcher: &mut TFactoryExhaustiveSearch<P>,
     distillation_units_map: &DistillationUnitsMap,
+    output_state: MagicStateKind,
     num_rounds: usize,
 ) where
     P: Point + Ord + From<TFactory> + TFactoryExhaustiveSearchOptions,
 {
     distillation_units_map.iterate_for_all_distillation_units(num_rounds, &mut |unit_indexes| {
-        process_for_specifications_combination(searcher, distillation_units_map, unit_indexes);
+        if distillation_units_map.produces(unit_indexes, output_state) {
+            process_for_specifications_combination(searcher, distillation_units_map, unit_indexes);
+        }
     });
 }
 
@@ -341,6 +362,8 @@ fn process_for_specifications_combination<P>(
 
 pub struct TFactoryBuilder {
     distillation_unit_templates: Vec<TFactoryDistillationUnitTemplate>,
+    /// The kind of magic states per magic state index
+    magic_state_kinds: Vec<MagicStateKind>,
 }
 
 impl TFactoryBuilder {
@@ -350,6 +373,12 @@ impl TFactoryBuilder {
     ) {
         self.distillation_unit_templates = distillation_unit_templates;
     }
+
+    /// Sets the kinds of magic states for which factories are built, the
+    /// distillation unit templates must contain units for each of them.
+    pub fn set_magic_state_kinds(&mut self, magic_state_kinds: Vec<MagicStateKind>) {
+        self.magic_state_kinds = magic_state_kinds;
+    }
 }
 
 impl Default for TFactoryBuilder {
@@ -357,6 +386,7 @@ impl Default for TFactoryBuilder {
         Self {
             distillation_unit_templates:
                 TFactoryDistillationUnitTemplate::default_distillation_unit_templates(),
+            magic_state_kinds: vec![MagicStateKind::T],
         }
     }
 }
@@ -368,18 +398,41 @@ impl FactoryBuilder<Protocol> for TFactoryBuilder {
         &self,
         ftp: &Protocol,
         qubit: &Rc<PhysicalQubit>,
-        _magic_state_type: usize,
-        output_t_error_rate: f64,
+        magic_state_type: usize,
+        output_error_rate: f64,
         max_code_distance: &u64,
     ) -> Option<Vec<Cow<Self::Factory>>> {
-        Some(find_nondominated_tfactories(
+        let output_state = self.magic_state_kinds[magic_state_type];
+
+        // Rotation states cannot be distilled, therefore no code distance can
+        // achieve an output error rate below the one of a preparation with
+        // perfect Clifford operations.
+        if output_state == MagicStateKind::Rotation
+            && self
+                .distillation_unit_templates
+                .iter()
+                .filter(|template| template.output_state == MagicStateKind::Rotation)
+                .all(|template| {
+                    (template.output_error_rate_function)(qubit.t_gate_error_rate(), 0.0, 0.0)
+                        > output_error_rate
+                })
+        {
+            return None;
+        }
+
+        Some(find_nondominated_factories(
             ftp,
             qubit,
             &self.distillation_unit_templates,
-            output_t_error_rate,
+            output_state,
+            output_error_rate,
             *max_code_distance,
         ))
     }
+
+    fn num_magic_state_types(&self) -> usize {
+        self.magic_state_kinds.len()
+    }
 }
 
 #[cfg(test)]
@@ -9,19 +9,20 @@ use crate::{
 use super::{
     super::super::{
         data,
-        modeling::{PhysicalQubit, Protocol, TFactoryDistillationUnitTemplate},
+        modeling::{MagicStateKind, PhysicalQubit, Protocol, TFactoryDistillationUnitTemplate},
     },
-    find_nondominated_population, find_nondominated_tfactories,
+    find_nondominated_factories, find_nondominated_population,
 };
 use std::{borrow::Cow, rc::Rc, time::Instant};
 
 #[test]
 fn test_one_t_error_rate() {
     let start = Instant::now();
-    let factories = find_nondominated_tfactories(
+    let factories = find_nondominated_factories(
         &Protocol::default(),
         &Rc::new(PhysicalQubit::default()),
         &TFactoryDistillationUnitTemplate::default_distillation_unit_templates(),
+        MagicStateKind::T,
         1e-18,
         35,
     );
@@ -141,6 +142,7 @@ fn required_logical_tstate_error_too_high() {
         &ftp,
         &qubit,
         &distillation_unit_templates,
+        MagicStateKind::T,
         output_t_error_rate,
         max_code_distance,
     );
@@ -154,15 +156,81 @@ fn required_logical_tstate_error_too_high() {
     assert_eq!(tfactory.unit_names(), vec!["trivial 1-to-1"]);
 }
 
+#[test]
+fn ccz_factories_end_with_ccz_distillation() {
+    let mut distillation_unit_templates =
+        TFactoryDistillationUnitTemplate::default_distillation_unit_templates();
+    distillation_unit_templates
+        .push(TFactoryDistillationUnitTemplate::create_distillation_unit_8_to_ccz_template());
+    let output_error_rate = 1e-12;
+
+    let factories = find_nondominated_factories(
+        &Protocol::default(),
+        &Rc::new(PhysicalQubit::default()),
+        &distillation_unit_templates,
+        MagicStateKind::Ccz,
+        output_error_rate,
+        35,
+    );
+
+    assert!(!factories.is_empty());
+    for factory in factories {
+        let unit_names = factory.unit_names();
+        let (last, previous) = unit_names.split_last().expect("factory has rounds");
+        assert_eq!(last, "8-to-CCZ");
+        assert!(previous.iter().all(|name| name.starts_with("15-to-1")));
+        assert!(factory.output_error_rate() <= output_error_rate);
+    }
+}
+
+#[test]
+fn rotation_factories_have_single_preparation_round() {
+    let mut distillation_unit_templates =
+        TFactoryDistillationUnitTemplate::default_distillation_unit_templates();
+    distillation_unit_templates
+        .push(TFactoryDistillationUnitTemplate::create_rotation_state_preparation_template());
+    let output_error_rate = 5e-4;
+
+    let factories = find_nondominated_factories(
+        &Protocol::default(),
+        &Rc::new(PhysicalQubit::default()),
+        &distillation_unit_templates,
+        MagicStateKind::Rotation,
+        output_error_rate,
+        35,
+    );
+
+    assert!(!factories.is_empty());
+    for factory in factories {
+        assert_eq!(factory.unit_names(), vec!["rotation state preparation"]);
+        assert!(factory.output_error_rate() <= output_error_rate);
+    }
+}
+
+#[test]
+fn rotation_factories_cannot_reach_error_rate_below_injection() {
+    let factories = find_nondominated_factories(
+        &Protocol::default(),
+        &Rc::new(PhysicalQubit::default()),
+        &[TFactoryDistillationUnitTemplate::create_rotation_state_preparation_template()],
+        MagicStateKind::Rotation,
+        1e-6,
+        35,
+    );
+
+    assert!(factories.is_empty());
+}
+
 fn find_tfactories<'a>(ftp: &Protocol, qubit_name: &str) -> Vec<Cow<'a, TFactory>> {
     let qubit: Rc<PhysicalQubit> = serde_json::from_str(&format!(r#"{{"name": "{qubit_name}"}}"#))
         .expect("json should be valid");
 
     let output_t_error_rate = 6.123_826_261_916_663E-16;
-    find_nondominated_tfactories(
+    find_nondominated_factories(
         ftp,
         &qubit,
         &create_test_templates(),
+        MagicStateKind::T,
         output_t_error_rate,
         ftp.max_code_distance(),
     )
@@ -206,6 +274,7 @@ fn create_custom_distillation_unit_1_to_15_template(
         display_name: name.to_string(),
         num_input_ts: 15,
         num_output_ts: 1,
+        output_state: MagicStateKind::T,
         failure_probability_formula: String::from(
             "15.0 * inputErrorRate + 356.0 * cliffordErrorRate",
         ),
@@ -8,10 +8,16 @@ use crate::estimates::{
     PhysicalResourceEstimation, PhysicalResourceEstimationResult,
 };
 
-use super::estimate_physical_resources;
+use super::{
+    estimate_physical_resources, estimate_physical_resources_sweep,
+    estimate_physical_resources_with_breakdown, format_summary,
+};
 
 use crate::system::{
-    data::{ErrorBudgetSpecification, JobParams, LogicalResourceCounts},
+    data::{
+        pareto_frontier, ErrorBudgetSpecification, JobParams, LayoutSpecification,
+        LogicalResourceBreakdown, LogicalResourceCounts, ParetoPoint,
+    },
     error::IO,
     modeling::GateBasedPhysicalQubit,
     modeling::{PhysicalQubit, Protocol, TFactory},
@@ -98,6 +104,628 @@ fn physical_estimates_crash() {
         .contains(r#""status":"success"#));
 }
 
+#[test]
+fn estimate_lattice_surgery_layout() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 100,
+        t_count: 400,
+        rotation_count: 0,
+        rotation_depth: 0,
+        ccz_count: 20,
+        ccix_count: 0,
+        measurement_count: 10,
+    };
+
+    let params: &str = r#"[{}, {
+        "layout": {"name": "latticeSurgery", "routingFraction": 0.25, "magicStatePorts": 4}
+    }]"#;
+    let result = estimate_physical_resources(logical_resources, params);
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(json_value.len(), 2);
+
+    let breakdown = |index: usize, key: &str| {
+        json_value[index]["physicalCounts"]["breakdown"][key]
+            .as_u64()
+            .expect("breakdown entry is a number")
+    };
+
+    // PSSPC: 2 * 100 + ceil(sqrt(8 * 100)) + 1
+    assert_eq!(breakdown(0, "algorithmicLogicalQubits"), 230);
+    // 10 + 400 + 3 * 20
+    assert_eq!(breakdown(0, "algorithmicLogicalDepth"), 470);
+
+    // Lattice surgery: ceil(100 / (1 - 0.25))
+    assert_eq!(breakdown(1, "algorithmicLogicalQubits"), 134);
+    // 10 + 400 / 4 + 3 * 20 / 4
+    assert_eq!(breakdown(1, "algorithmicLogicalDepth"), 125);
+    assert_eq!(
+        breakdown(0, "numTstates"),
+        breakdown(1, "numTstates"),
+        "number of T states does not depend on layout"
+    );
+
+    assert!(json_value[0]["jobParams"].get("layout").is_none());
+    assert_eq!(
+        json_value[1]["jobParams"]["layout"],
+        json!({"name": "latticeSurgery", "routingFraction": 0.25, "magicStatePorts": 4})
+    );
+    assert_eq!(
+        json_value[1]["logicalCounts"],
+        json_value[0]["logicalCounts"]
+    );
+}
+
+#[test]
+fn lattice_surgery_layout_defaults() {
+    let job_params: JobParams = serde_json::from_str(r#"{"layout": {"name": "latticeSurgery"}}"#)
+        .expect("Failed to parse JSON");
+
+    assert_eq!(
+        *job_params.layout(),
+        LayoutSpecification::LatticeSurgery {
+            routing_fraction: 0.5,
+            magic_state_ports: 1
+        }
+    );
+}
+
+#[test]
+fn lattice_surgery_layout_invalid_routing_fraction() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            t_count: 400,
+            ..Default::default()
+        },
+        r#"[{"layout": {"name": "latticeSurgery", "routingFraction": 1.0}}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(
+        json_value[0]["code"],
+        json!("Qsc.Estimates.InvalidValueError.InvalidValue")
+    );
+    assert_eq!(
+        json_value[0]["message"],
+        json!("invalid value for 'layout.routingFraction', expected value between 0 and 1")
+    );
+}
+
+#[test]
+fn layout_unknown_field() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            ..Default::default()
+        },
+        r#"[{"layout": {"name": "latticeSurgery", "routingFracton": 0.25}}]"#,
+    );
+
+    assert!(matches!(
+        result,
+        Err(super::Error::IO(IO::CannotParseJSON(_)))
+    ));
+}
+
+#[test]
+fn estimate_with_ccz_and_rotation_factories() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 100,
+        t_count: 400,
+        rotation_count: 50,
+        rotation_depth: 10,
+        ccz_count: 20,
+        ccix_count: 5,
+        measurement_count: 10,
+    };
+
+    let params: &str = r#"[{
+        "qubitParams": {"name": "qubit_gate_ns_e4"},
+        "errorBudget": 0.3
+    }, {
+        "qubitParams": {"name": "qubit_gate_ns_e4"},
+        "errorBudget": 0.3,
+        "magicStateFactories": {"ccz": true, "rotation": true}
+    }]"#;
+    let result = estimate_physical_resources(logical_resources, params);
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(json_value.len(), 2);
+
+    let breakdown = |index: usize, key: &str| {
+        json_value[index]["physicalCounts"]["breakdown"][key]
+            .as_u64()
+            .expect("breakdown entry is a number")
+    };
+
+    // T states are only used for T gates
+    assert_eq!(breakdown(1, "numTstates"), 400);
+    assert!(breakdown(0, "numTstates") > 400 + 4 * 25);
+    // 10 + 50 + 400 + 3 * 25 + 2 * 10
+    assert_eq!(breakdown(1, "algorithmicLogicalDepth"), 555);
+
+    assert!(json_value[0].get("magicStateFactories").is_none());
+    let factories = json_value[1]["magicStateFactories"]
+        .as_object()
+        .expect("magic state factories are reported");
+    assert_eq!(
+        factories.keys().collect::<Vec<_>>(),
+        vec!["ccz", "rotation", "t"]
+    );
+    assert_eq!(factories["ccz"]["numStates"], json!(25));
+    assert_eq!(factories["rotation"]["numStates"], json!(100));
+    assert_eq!(
+        factories["ccz"]["factory"]["unitNamePerRound"]
+            .as_array()
+            .and_then(|names| names.last()),
+        Some(&json!("8-to-CCZ"))
+    );
+    assert_eq!(
+        factories["rotation"]["factory"]["unitNamePerRound"],
+        json!(["rotation state preparation"])
+    );
+
+    let physical_qubits_for_factories: u64 = factories
+        .values()
+        .map(|factory| factory["physicalQubits"].as_u64().expect("is a number"))
+        .sum();
+    assert_eq!(
+        breakdown(1, "physicalQubitsForTfactories"),
+        physical_qubits_for_factories
+    );
+
+    assert!(
+        report_explanation(&json_value[1], "magicStateFactories/ccz/numFactories")
+            .contains("25 CCZ states")
+    );
+    assert!(report_explanation(&json_value[0], "magicStateFactories/ccz/numFactories").is_empty());
+}
+
+#[test]
+fn rotation_factories_cannot_reach_required_error_rate() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            t_count: 400,
+            rotation_count: 50,
+            rotation_depth: 10,
+            ..Default::default()
+        },
+        r#"[{"magicStateFactories": {"rotation": true}}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(
+        json_value[0]["code"],
+        json!("Qsc.Estimates.CannotComputeMagicStates")
+    );
+}
+
+#[test]
+fn magic_state_factories_not_supported_for_frontier() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            t_count: 400,
+            ccz_count: 20,
+            ..Default::default()
+        },
+        r#"[{"estimateType": "frontier", "magicStateFactories": {"ccz": true}}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(
+        json_value[0]["code"],
+        json!("Qsc.Estimates.MultipleMagicStatesNotSupported")
+    );
+}
+
+#[test]
+fn estimate_color_code() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            t_count: 400,
+            ..Default::default()
+        },
+        r#"[{"qecScheme": {"name": "color_code"}},
+            {"qubitParams": {"name": "qubit_maj_ns_e4"}, "qecScheme": {"name": "color_code"}}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(json_value[0]["status"], json!("success"));
+    assert_eq!(
+        json_value[0]["jobParams"]["qecScheme"]["physicalQubitsPerLogicalQubit"],
+        json!("(3 * codeDistance * codeDistance - 1) / 2")
+    );
+    assert!(
+        report_explanation(&json_value[0], "logicalQubit/codeDistance")
+            .starts_with("The code distance is the smallest odd integer greater or equal to")
+    );
+    assert!(report_explanation(&json_value[0], "jobParams/qecScheme/logicalErrorRate").is_empty());
+
+    // color codes are not supported for Majorana qubits
+    assert_eq!(
+        json_value[1]["code"],
+        json!("Qsc.Estimates.InvalidInputError.InvalidFaultToleranceProtocol")
+    );
+}
+
+#[test]
+fn estimate_qldpc_code() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 100,
+        t_count: 400,
+        ..Default::default()
+    };
+
+    let result = estimate_physical_resources(
+        logical_resources,
+        r#"[{"qecScheme": {"name": "qldpc_code"}}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    let result = &json_value[0];
+    assert_eq!(result["status"], json!("success"));
+    assert_eq!(
+        result["jobParams"]["qecScheme"]["logicalQubitsPerPatch"],
+        json!("12")
+    );
+
+    let code_distance = result["logicalQubit"]["codeDistance"]
+        .as_u64()
+        .expect("code distance is a number");
+    let patch_qubits = result["logicalQubit"]["physicalQubits"]
+        .as_u64()
+        .expect("physical qubits is a number");
+    assert_eq!(patch_qubits, 2 * code_distance * code_distance);
+
+    // 230 logical qubits after layout are stored in 20 patches of 12 logical qubits
+    assert_eq!(
+        result["physicalCounts"]["breakdown"]["physicalQubitsForAlgorithm"],
+        json!(20 * patch_qubits)
+    );
+
+    assert!(report_explanation(result, "logicalQubit/codeDistance")
+        .contains("divided by the 12 logical qubits"));
+    assert!(report_explanation(result, "physicalCountsFormatted/logicalErrorRate").contains(
+        "crossingPrefactor * (physicalErrorRate / errorCorrectionThreshold) ^ (codeDistance / 2)"
+    ));
+    assert!(!report_explanation(result, "jobParams/qecScheme/logicalQubitsPerPatch").is_empty());
+    assert!(!report_explanation(result, "jobParams/qecScheme/logicalErrorRate").is_empty());
+}
+
+#[test]
+fn estimate_custom_qldpc_code() {
+    let result = estimate_physical_resources(
+        LogicalResourceCounts {
+            num_qubits: 100,
+            t_count: 400,
+            ..Default::default()
+        },
+        r#"[{"qecScheme": {
+            "name": "gross_code",
+            "errorCorrectionThreshold": 0.007,
+            "crossingPrefactor": 0.1,
+            "logicalCycleTime": "(8 * twoQubitGateTime + 2 * oneQubitMeasurementTime) * codeDistance",
+            "physicalQubitsPerLogicalQubit": "288",
+            "logicalQubitsPerPatch": "12",
+            "logicalErrorRate": "crossingPrefactor * (physicalErrorRate / errorCorrectionThreshold) ^ (codeDistance / 2)"
+        }},
+        {"qecScheme": {
+            "name": "gross_code",
+            "errorCorrectionThreshold": 0.007,
+            "crossingPrefactor": 0.1,
+            "logicalCycleTime": "codeDistance",
+            "physicalQubitsPerLogicalQubit": "288",
+            "logicalQubitsPerPatch": "12 - codeDistance"
+        }}]"#,
+    );
+
+    let json_value: Vec<Value> =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    assert_eq!(json_value[0]["status"], json!("success"));
+    assert_eq!(json_value[0]["logicalQubit"]["physicalQubits"], json!(288));
+    assert_eq!(
+        json_value[1]["code"],
+        json!("Qsc.Estimates.LogicalQubitComputationFailed")
+    );
+}
+
+#[test]
+fn callable_breakdown_is_added_with_profiling() {
+    let logical_resources = LogicalResourceCounts {
+        num_qubits: 10,
+        t_count: 10,
+        rotation_count: 0,
+        rotation_depth: 0,
+        ccz_count: 0,
+        ccix_count: 0,
+        measurement_count: 0,
+    };
+
+    let leaf = |name: &str, t_count| LogicalResourceBreakdown {
+        name: name.into(),
+        t_count,
+        max_qubits: 10,
+        ..Default::default()
+    };
+    let breakdown = LogicalResourceBreakdown {
+        children: vec![LogicalResourceBreakdown {
+            children: vec![leaf("Inner", 6)],
+            ..leaf("Main", 10)
+        }],
+        ..leaf("program", 10)
+    };
+
+    let params = r#"[{}, {"profiling": {"callStackDepth": 1, "flamegraph": "tCount"}}]"#;
+    let result =
+        estimate_physical_resources_with_breakdown(logical_resources, Some(&breakdown), params)
+            .expect("estimation should succeed");
+    let results: Vec<Value> = serde_json::from_str(&result).expect("Failed to parse JSON");
+
+    assert!(results[0].get("callableBreakdown").is_none());
+    assert!(results[0].get("callableFlamegraph").is_none());
+
+    assert_eq!(
+        results[1]["callableBreakdown"],
+        json!({
+            "name": "program",
+            "tCount": 10,
+            "rotationCount": 0,
+            "cczCount": 0,
+            "measurementCount": 0,
+            "maxQubits": 10,
+            "children": [{
+                "name": "Main",
+                "tCount": 10,
+                "rotationCount": 0,
+                "cczCount": 0,
+                "measurementCount": 0,
+                "maxQubits": 10,
+            }],
+        })
+    );
+    assert_eq!(results[1]["callableFlamegraph"], json!("program;Main 10\n"));
+}
+
+fn sweep_logical_resources() -> LogicalResourceCounts {
+    LogicalResourceCounts {
+        num_qubits: 100,
+        t_count: 400,
+        rotation_count: 50,
+        rotation_depth: 10,
+        ccz_count: 20,
+        ccix_count: 5,
+        measurement_count: 10,
+    }
+}
+
+fn physical_counts(result: &Value) -> (u64, u64) {
+    (
+        result["physicalQubits"]
+            .as_u64()
+            .expect("physical qubits is a number"),
+        result["runtime"].as_u64().expect("runtime is a number"),
+    )
+}
+
+#[test]
+fn estimate_sweep_cartesian_product() {
+    let result = estimate_physical_resources_sweep(
+        &sweep_logical_resources(),
+        r#"{
+            "base": {"errorBudget": 0.01},
+            "product": {
+                "qubitParams": [{"name": "qubit_gate_ns_e3"}, {"name": "qubit_gate_ns_e4"}],
+                "maxTFactories": [1, 10]
+            }
+        }"#,
+    );
+
+    let json_value: Value =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    let results = json_value["results"]
+        .as_array()
+        .expect("results is an array");
+    assert_eq!(results.len(), 4);
+
+    let variations: Vec<_> = results
+        .iter()
+        .map(|result| {
+            (
+                result["jobParams"]["qubitParams"]["name"].clone(),
+                result["jobParams"]["constraints"]["maxTFactories"].clone(),
+                result["jobParams"]["errorBudget"] == json!(0.01),
+            )
+        })
+        .collect();
+    assert_eq!(
+        variations,
+        vec![
+            (json!("qubit_gate_ns_e3"), json!(1), true),
+            (json!("qubit_gate_ns_e3"), json!(10), true),
+            (json!("qubit_gate_ns_e4"), json!(1), true),
+            (json!("qubit_gate_ns_e4"), json!(10), true),
+        ]
+    );
+
+    let frontier = json_value["paretoFrontier"]
+        .as_array()
+        .expect("pareto frontier is an array");
+    assert!(!frontier.is_empty());
+
+    // points on the frontier are sorted by qubits and strictly decreasing in runtime
+    let points: Vec<_> = frontier.iter().map(physical_counts).collect();
+    assert!(points
+        .windows(2)
+        .all(|pair| pair[0].0 < pair[1].0 && pair[0].1 > pair[1].1));
+
+    for point in frontier {
+        assert!(point.get("frontierIndex").is_none());
+        let index = point["resultIndex"]
+            .as_u64()
+            .expect("result index is a number") as usize;
+        assert_eq!(
+            physical_counts(point),
+            physical_counts(&results[index]["physicalCounts"])
+        );
+    }
+
+    // every result is dominated by or on the frontier
+    for result in results {
+        let (qubits, runtime) = physical_counts(&result["physicalCounts"]);
+        assert!(points.iter().any(|&(q, r)| q <= qubits && r <= runtime));
+    }
+}
+
+#[test]
+fn estimate_sweep_items_with_frontier_and_failure() {
+    let result = estimate_physical_resources_sweep(
+        &sweep_logical_resources(),
+        r#"{
+            "base": {"qubitParams": {"name": "qubit_maj_ns_e6"}, "qecScheme": {"name": "floquet_code"}},
+            "items": [
+                {"estimateType": "frontier"},
+                {"errorBudget": 0.01},
+                {"errorBudget": 2.0}
+            ]
+        }"#,
+    );
+
+    let json_value: Value =
+        serde_json::from_str(&result.expect("result is err")).expect("Failed to parse JSON");
+    let results = json_value["results"]
+        .as_array()
+        .expect("results is an array");
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["status"], json!("success"));
+    assert_eq!(results[1]["status"], json!("success"));
+    assert_eq!(
+        results[2]["code"],
+        json!("Qsc.Estimates.InvalidInputError.InvalidErrorBudget")
+    );
+
+    let frontier = json_value["paretoFrontier"]
+        .as_array()
+        .expect("pareto frontier is an array");
+    assert!(frontier
+        .iter()
+        .all(|point| point["resultIndex"] != json!(2)));
+    for point in frontier {
+        if point["resultIndex"] == json!(0) {
+            let index = point["frontierIndex"]
+                .as_u64()
+                .expect("frontier index is a number") as usize;
+            assert_eq!(
+                physical_counts(point),
+                physical_counts(&results[0]["frontierEntries"][index]["physicalCounts"])
+            );
+        } else {
+            assert!(point.get("frontierIndex").is_none());
+        }
+    }
+}
+
+#[test]
+fn estimate_sweep_unknown_field() {
+    let result = estimate_physical_resources_sweep(
+        &sweep_logical_resources(),
+        r#"{"product": {"maxDuration": ["1 s"]}}"#,
+    );
+
+    assert!(matches!(
+        result,
+        Err(crate::system::Error::IO(IO::CannotParseJSON(_)))
+    ));
+}
+
+#[test]
+fn pareto_frontier_removes_dominated_points() {
+    let point = |result_index, physical_qubits, runtime| ParetoPoint {
+        result_index,
+        frontier_index: None,
+        physical_qubits,
+        runtime,
+    };
+
+    let frontier = pareto_frontier(vec![
+        point(0, 100, 50),
+        point(1, 200, 40),
+        point(2, 150, 60),
+        point(3, 50, 100),
+        point(4, 100, 50),
+        point(5, 300, 40),
+    ]);
+
+    assert_eq!(
+        frontier,
+        vec![point(3, 50, 100), point(0, 100, 50), point(1, 200, 40)]
+    );
+}
+
+#[test]
+fn summary_lists_report_entries_with_values() {
+    let results = estimate_physical_resources(
+        sweep_logical_resources(),
+        r#"[{"errorBudget": 0.01}, {"errorBudget": 0.01, "estimateType": "frontier"}, {"errorBudget": 2.0}]"#,
+    )
+    .expect("estimation should succeed");
+    let json_value: Vec<Value> = serde_json::from_str(&results).expect("Failed to parse JSON");
+
+    let summary = format_summary(&results).expect("summary should be formatted");
+    let sections: Vec<_> = summary.split("Result ").collect();
+    assert_eq!(sections.len(), 4);
+    assert!(sections[0].is_empty());
+
+    for (index, section) in sections[1..3].iter().enumerate() {
+        let mut lines = section.lines();
+        assert_eq!(lines.next(), Some(index.to_string().as_str()));
+        assert_eq!(lines.next(), Some(""));
+        assert_eq!(lines.next(), Some("Physical resource estimates"));
+
+        let runtime = if index == 0 {
+            &json_value[0]["physicalCountsFormatted"]["runtime"]
+        } else {
+            &json_value[1]["frontierEntries"][0]["physicalCountsFormatted"]["runtime"]
+        };
+        let runtime_line = lines.next().expect("runtime is reported");
+        assert!(runtime_line.starts_with("  Runtime  "));
+        assert!(runtime_line.ends_with(&format!(
+            "  {}",
+            runtime.as_str().expect("runtime is a string")
+        )));
+    }
+
+    assert!(summary.contains("  QEC scheme"));
+    assert!(sections[3].ends_with(
+        "Error [Qsc.Estimates.InvalidInputError.InvalidErrorBudget]: The error budget must be between 0.0 and 1.0, provided input was `2`\n"
+    ));
+}
+
+fn report_explanation(result: &Value, path: &str) -> String {
+    result["reportData"]["groups"]
+        .as_array()
+        .expect("report has groups")
+        .iter()
+        .flat_map(|group| group["entries"].as_array().expect("group has entries"))
+        .find(|entry| entry["path"] == json!(path))
+        .map_or_else(String::new, |entry| {
+            entry["explanation"]
+                .as_str()
+                .expect("explanation is a string")
+                .to_string()
+        })
+}
+
 #[derive(Clone)]
 struct TestLayoutOverhead {
     num_qubits: u64,
@@ -94,6 +94,9 @@ export function toVsCodeDiagnostic(d: VSDiagnostic): vscode.Diagnostic {
       );
     });
   }
+  if (d.tags?.includes("deprecated")) {
+    vscodeDiagnostic.tags = [vscode.DiagnosticTag.Deprecated];
+  }
   return vscodeDiagnostic;
 }
 
@@ -32,9 +32,12 @@ class QSharpFormattingProvider
     );
     const start = performance.now();
 
-    const lsEdits = await this.languageService.getFormatChanges(
-      document.uri.toString(),
-    );
+    const lsEdits = range
+      ? await this.languageService.getRangeFormatChanges(
+          document.uri.toString(),
+          range,
+        )
+      : await this.languageService.getFormatChanges(document.uri.toString());
 
     if (!lsEdits) {
       // telemetry end format
@@ -49,17 +52,10 @@ class QSharpFormattingProvider
       return [];
     }
 
-    let edits = lsEdits.map(
+    const edits = lsEdits.map(
       (edit) => new vscode.TextEdit(toVscodeRange(edit.range), edit.newText),
     );
 
-    if (range) {
-      edits = edits.filter(
-        (e) =>
-          range.start.isBefore(e.range.end) && range.end.isAfter(e.range.start),
-      );
-    }
-
     // telemetry end format
     sendTelemetryEvent(
       EventType.FormatEnd,
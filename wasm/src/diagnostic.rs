@@ -6,7 +6,11 @@ use crate::{
     serializable_type,
 };
 use miette::{Diagnostic, LabeledSpan, Severity};
-use qsc::{self, error::WithSource, interpret, SourceName, Span};
+use qsc::{
+    self,
+    error::{docs_url, WithSource},
+    interpret, SourceName, Span,
+};
 use serde::{Deserialize, Serialize};
 use std::{fmt::Write, iter};
 use wasm_bindgen::prelude::*;
@@ -22,7 +26,9 @@ serializable_type! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub uri: Option<String>,
         #[serde(skip_serializing_if = "Vec::is_empty")]
-        pub related: Vec<Related>
+        pub related: Vec<Related>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<String>
     },
     r#"export interface VSDiagnostic {
         range: IRange,
@@ -31,6 +37,7 @@ serializable_type! {
         code?: string;
         uri?: string;
         related?: IRelatedInformation[];
+        tags?: "deprecated"[];
     }"#
 }
 
@@ -77,7 +84,13 @@ impl VSDiagnostic {
     pub(crate) fn from_compile_error(source_name: &str, err: &qsc::compile::Error) -> Self {
         let labels = error_labels(err);
 
-        Self::new(labels, source_name, err)
+        let mut diagnostic = Self::new(labels, source_name, err);
+        if let qsc::compile::ErrorKind::Lint(lint) = err.error() {
+            if lint.kind == qsc::linter::LintKind::Ast(qsc::linter::AstLint::Deprecated) {
+                diagnostic.tags.push("deprecated".to_string());
+            }
+        }
+        diagnostic
     }
 
     /// Creates a [`VSDiagnostic`] using the information from a [`miette::Diagnostic`].
@@ -145,7 +158,7 @@ impl VSDiagnostic {
         let code = err.code().map(|c| c.to_string());
 
         // e.g. https://aka.ms/qdk.qir
-        let uri = err.url().map(|u| u.to_string());
+        let uri = docs_url(err);
 
         Self {
             range: range.into(),
@@ -159,6 +172,39 @@ impl VSDiagnostic {
             code,
             uri,
             related,
+            tags: Vec::new(),
+        }
+    }
+}
+
+impl From<qsls::protocol::Diagnostic> for VSDiagnostic {
+    fn from(diagnostic: qsls::protocol::Diagnostic) -> Self {
+        Self {
+            range: diagnostic.range.into(),
+            message: diagnostic.message,
+            severity: (match diagnostic.severity {
+                qsls::protocol::DiagnosticSeverity::Error => "error",
+                qsls::protocol::DiagnosticSeverity::Warning => "warning",
+                qsls::protocol::DiagnosticSeverity::Information => "info",
+            })
+            .to_string(),
+            code: diagnostic.code,
+            uri: diagnostic.code_description,
+            related: diagnostic
+                .related_information
+                .into_iter()
+                .map(|related| Related {
+                    location: related.location.into(),
+                    message: related.message,
+                })
+                .collect(),
+            tags: diagnostic
+                .tags
+                .into_iter()
+                .map(|tag| match tag {
+                    qsls::protocol::DiagnosticTag::Deprecated => "deprecated".to_string(),
+                })
+                .collect(),
         }
     }
 }
@@ -243,6 +289,8 @@ fn interpret_error_labels(err: &interpret::Error) -> Vec<Label> {
         interpret::Error::NoEntryPoint
         | interpret::Error::UnsupportedRuntimeCapabilities
         | interpret::Error::Circuit(_)
+        | interpret::Error::CircuitGeneration(_)
+        | interpret::Error::UnsupportedCircuitGroups
         | interpret::Error::NotAnOperation => Vec::new(),
     }
 }
@@ -207,6 +207,18 @@ impl LanguageService {
         definition.map(|definition| Location::from(definition).into())
     }
 
+    pub fn get_type_definition(&self, uri: &str, position: IPosition) -> Option<ILocation> {
+        let position: Position = position.into();
+        let definition = self.0.get_type_definition(uri, position.into());
+        definition.map(|definition| Location::from(definition).into())
+    }
+
+    pub fn get_implementation(&self, uri: &str, position: IPosition) -> Option<ILocation> {
+        let position: Position = position.into();
+        let implementation = self.0.get_implementation(uri, position.into());
+        implementation.map(|implementation| Location::from(implementation).into())
+    }
+
     pub fn get_references(
         &self,
         uri: &str,
@@ -223,6 +235,41 @@ impl LanguageService {
             .collect()
     }
 
+    pub fn get_document_highlights(
+        &self,
+        uri: &str,
+        position: IPosition,
+    ) -> Vec<IDocumentHighlight> {
+        let position: Position = position.into();
+        let highlights = self.0.get_document_highlights(uri, position.into());
+        highlights
+            .into_iter()
+            .map(|highlight| {
+                DocumentHighlight {
+                    range: highlight.range.into(),
+                    kind: (match highlight.kind {
+                        qsls::protocol::DocumentHighlightKind::Read => "read",
+                        qsls::protocol::DocumentHighlightKind::Write => "write",
+                    })
+                    .to_string(),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    pub fn get_diagnostics(&self, uri: &str) -> IDiagnostics {
+        let diags = self
+            .0
+            .get_diagnostics(uri)
+            .into_iter()
+            .map(VSDiagnostic::from)
+            .collect::<Vec<_>>();
+        serde_wasm_bindgen::to_value(&diags)
+            .expect("conversion to VSDiagnostic should succeed")
+            .into()
+    }
+
     pub fn get_format_changes(&self, uri: &str) -> Vec<ITextEdit> {
         let edits = self.0.get_format_changes(uri);
         edits
@@ -237,6 +284,21 @@ impl LanguageService {
             .collect()
     }
 
+    pub fn get_range_format_changes(&self, uri: &str, range: IRange) -> Vec<ITextEdit> {
+        let range: Range = range.into();
+        let edits = self.0.get_range_format_changes(uri, range.into());
+        edits
+            .into_iter()
+            .map(|edit| {
+                TextEdit {
+                    range: edit.range.into(),
+                    newText: edit.new_text,
+                }
+                .into()
+            })
+            .collect()
+    }
+
     pub fn get_hover(&self, uri: &str, position: IPosition) -> Option<IHover> {
         let position: Position = position.into();
         let hover = self.0.get_hover(uri, position.into());
@@ -311,6 +373,43 @@ impl LanguageService {
         })
     }
 
+    pub fn get_folding_ranges(&self, uri: &str) -> Vec<IFoldingRange> {
+        let folding_ranges = self.0.get_folding_ranges(uri);
+        folding_ranges
+            .into_iter()
+            .map(|folding_range| {
+                FoldingRange {
+                    range: folding_range.range.into(),
+                    kind: folding_range.kind.map(|kind| {
+                        (match kind {
+                            qsls::protocol::FoldingRangeKind::Comment => "comment",
+                            qsls::protocol::FoldingRangeKind::Imports => "imports",
+                            qsls::protocol::FoldingRangeKind::Region => "region",
+                        })
+                        .to_string()
+                    }),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    pub fn get_selection_ranges(
+        &self,
+        uri: &str,
+        positions: Vec<IPosition>,
+    ) -> Vec<ISelectionRange> {
+        let positions = positions
+            .into_iter()
+            .map(|position| Position::from(position).into())
+            .collect();
+        let selection_ranges = self.0.get_selection_ranges(uri, positions);
+        selection_ranges
+            .into_iter()
+            .map(|selection_range| SelectionRange::from(selection_range).into())
+            .collect()
+    }
+
     pub fn get_code_lenses(&self, uri: &str) -> Vec<ICodeLens> {
         let code_lenses = self.0.get_code_lenses(uri);
         code_lenses
@@ -542,6 +641,57 @@ serializable_type! {
     IOperationInfo
 }
 
+serializable_type! {
+    DocumentHighlight,
+    {
+        pub range: Range,
+        pub kind: String,
+    },
+    r#"export interface IDocumentHighlight {
+        range: IRange;
+        kind: "read" | "write";
+    }"#,
+    IDocumentHighlight
+}
+
+serializable_type! {
+    FoldingRange,
+    {
+        pub range: Range,
+        pub kind: Option<String>,
+    },
+    r#"export interface IFoldingRange {
+        range: IRange;
+        kind?: "comment" | "imports" | "region";
+    }"#,
+    IFoldingRange
+}
+
+serializable_type! {
+    SelectionRange,
+    {
+        pub range: Range,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub parent: Option<Box<SelectionRange>>,
+    },
+    r#"export interface ISelectionRange {
+        range: IRange;
+        parent?: ISelectionRange;
+    }"#,
+    ISelectionRange
+}
+
+impl From<qsls::protocol::SelectionRange> for SelectionRange {
+    fn from(selection_range: qsls::protocol::SelectionRange) -> Self {
+        Self {
+            range: selection_range.range.into(),
+            parent: selection_range
+                .parent
+                .map(|parent| Box::new((*parent).into())),
+        }
+    }
+}
+
 serializable_type! {
     WorkspaceEdit,
     {
@@ -601,4 +751,7 @@ extern "C" {
         typescript_type = "(uri: string, version: number | undefined, diagnostics: VSDiagnostic[]) => void"
     )]
     pub type DiagnosticsCallback;
+
+    #[wasm_bindgen(typescript_type = "VSDiagnostic[]")]
+    pub type IDiagnostics;
 }
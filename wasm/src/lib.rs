@@ -122,6 +122,7 @@ pub fn get_estimates(
     estimate_entry(&mut interpreter, params).map_err(|e| match &e[0] {
         re::Error::Interpreter(interpret::Error::Eval(e)) => e.to_string(),
         re::Error::Interpreter(_) => unreachable!("interpreter errors should be eval errors"),
+        re::Error::Program(e) => e.to_string(),
         re::Error::Estimation(e) => e.to_string(),
     })
 }
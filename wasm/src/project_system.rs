@@ -3,7 +3,7 @@
 
 use async_trait::async_trait;
 use js_sys::JsString;
-use qsc::linter::LintConfig;
+use qsc::{formatter::FormatOptions, linter::LintConfig};
 use qsc_project::{EntryType, JSFileEntry, Manifest, ManifestDescriptor, ProjectSystemCallbacks};
 
 use std::iter::FromIterator;
@@ -199,6 +199,13 @@ pub(crate) fn get_manifest_transformer(js_val: JsValue, _: String) -> Option<Man
         _ => Vec::new(),
     };
 
+    let format = match js_sys::Reflect::get(&js_val, &JsValue::from_str("format")) {
+        Ok(v) if !v.is_undefined() => {
+            serde_wasm_bindgen::from_value::<FormatOptions>(v).unwrap_or_default()
+        }
+        _ => FormatOptions::default(),
+    };
+
     log::trace!("found manifest at {manifest_dir:?}");
 
     let manifest_dir = PathBuf::from(manifest_dir);
@@ -207,6 +214,7 @@ pub(crate) fn get_manifest_transformer(js_val: JsValue, _: String) -> Option<Man
         manifest: Manifest {
             language_features,
             lints,
+            format,
             author: Option::default(),
             license: Option::default(),
         },